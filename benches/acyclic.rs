@@ -3,7 +3,7 @@
 extern crate petgraph;
 extern crate test;
 
-use petgraph::algo::{toposort, DfsSpace};
+use petgraph::algo::{has_path_connecting, toposort, DfsSpace};
 use petgraph::prelude::*;
 use petgraph::{acyclic::Acyclic, data::Build};
 use std::cmp::max;
@@ -32,6 +32,28 @@ fn acyclic_bench(bench: &mut Bencher) {
     });
 }
 
+/// Dynamic toposort using Acyclic<G>, adding all edges in one batch
+#[bench]
+#[allow(clippy::needless_range_loop)]
+fn acyclic_batch_bench(bench: &mut Bencher) {
+    static NODE_COUNT: usize = 100;
+    let mut g = Acyclic::<DiGraph<usize, ()>>::new();
+    let nodes: Vec<NodeIndex<_>> = (0..NODE_COUNT).map(|i| g.add_node(i)).collect();
+    let mut edges = Vec::new();
+    for i in 0..NODE_COUNT {
+        let neighbour_count = i % 8 + 3;
+        let j_from = max(0, i as i32 - neighbour_count as i32) as usize;
+        for j in j_from..i {
+            edges.push((nodes[i], nodes[j], ()));
+        }
+    }
+
+    bench.iter(|| {
+        let mut g = g.clone();
+        g.try_extend_edges(edges.iter().copied()).unwrap();
+    });
+}
+
 /// As a baseline: build the graph and toposort it every time a new edge is added
 #[bench]
 #[allow(clippy::needless_range_loop)]
@@ -56,3 +78,56 @@ fn toposort_baseline_bench(bench: &mut Bencher) {
         }
     });
 }
+
+/// Reachability queries between all pairs of nodes, pruned by the maintained
+/// topological order
+#[bench]
+fn acyclic_has_path_bench(bench: &mut Bencher) {
+    static NODE_COUNT: usize = 100;
+    let mut g = Acyclic::<DiGraph<usize, ()>>::new();
+    let nodes: Vec<NodeIndex<_>> = (0..NODE_COUNT).map(|i| g.add_node(i)).collect();
+    for i in 0..NODE_COUNT {
+        let neighbour_count = i % 8 + 3;
+        let j_from = max(0, i as i32 - neighbour_count as i32) as usize;
+        for j in j_from..i {
+            g.try_add_edge(nodes[i], nodes[j], ()).unwrap();
+        }
+    }
+
+    bench.iter(|| {
+        let mut count = 0;
+        for &a in &nodes {
+            for &b in &nodes {
+                count += g.has_path_connecting(a, b) as usize;
+            }
+        }
+        count
+    });
+}
+
+/// As a baseline: reachability queries between all pairs of nodes without
+/// using the order
+#[bench]
+fn has_path_baseline_bench(bench: &mut Bencher) {
+    static NODE_COUNT: usize = 100;
+    let mut g = DiGraph::<usize, ()>::new();
+    let nodes: Vec<NodeIndex<_>> = (0..NODE_COUNT).map(|i| g.add_node(i)).collect();
+    for i in 0..NODE_COUNT {
+        let neighbour_count = i % 8 + 3;
+        let j_from = max(0, i as i32 - neighbour_count as i32) as usize;
+        for j in j_from..i {
+            g.add_edge(nodes[i], nodes[j], ());
+        }
+    }
+
+    bench.iter(|| {
+        let mut space = DfsSpace::new(&g);
+        let mut count = 0;
+        for &a in &nodes {
+            for &b in &nodes {
+                count += has_path_connecting(&g, a, b, Some(&mut space)) as usize;
+            }
+        }
+        count
+    });
+}
@@ -0,0 +1,49 @@
+#![feature(test)]
+
+extern crate petgraph;
+extern crate test;
+
+use petgraph::prelude::*;
+use petgraph::visit::Bfs;
+use std::cmp::{max, min};
+use test::Bencher;
+
+#[allow(clippy::needless_range_loop)]
+fn graph() -> (UnGraph<usize, ()>, Vec<NodeIndex>) {
+    static NODE_COUNT: usize = 100_000;
+    let mut g = Graph::new_undirected();
+    let nodes: Vec<NodeIndex<_>> = (0..NODE_COUNT).map(|i| g.add_node(i)).collect();
+    for i in 0..NODE_COUNT {
+        let neighbour_count = i % 8 + 3;
+        let j_from = max(0, i as i32 - neighbour_count as i32 / 2) as usize;
+        let j_to = min(NODE_COUNT, j_from + neighbour_count);
+        for j in j_from..j_to {
+            g.add_edge(nodes[i], nodes[j], ());
+        }
+        // A few long edges, so that the graph has a small diameter.
+        g.add_edge(nodes[i], nodes[i * 7919 % NODE_COUNT], ());
+    }
+    (g, nodes)
+}
+
+#[bench]
+fn bfs_bench(bench: &mut Bencher) {
+    let (g, nodes) = graph();
+    bench.iter(|| {
+        let mut bfs = Bfs::new(&g, nodes[0]);
+        let mut count = 0;
+        while bfs.next(&g).is_some() {
+            count += 1;
+        }
+        count
+    });
+}
+
+#[cfg(feature = "rayon")]
+#[bench]
+fn parallel_bfs_bench(bench: &mut Bencher) {
+    let (g, nodes) = graph();
+    bench.iter(|| {
+        let _depths = petgraph::algo::bfs::parallel_bfs(&g, nodes[0]);
+    });
+}
@@ -0,0 +1,52 @@
+#![feature(test)]
+
+extern crate petgraph;
+extern crate test;
+
+use petgraph::prelude::*;
+use test::Bencher;
+
+use petgraph::algo::{bidirectional_dijkstra, contraction_hierarchy};
+
+// A grid with costs that vary between the rows and the columns.
+fn grid(size: usize) -> (UnGraph<(), usize>, Vec<NodeIndex>) {
+    let mut g = Graph::new_undirected();
+    let nodes: Vec<NodeIndex<_>> = (0..size * size).map(|_| g.add_node(())).collect();
+    for i in 0..size * size {
+        if i % size + 1 < size {
+            g.add_edge(nodes[i], nodes[i + 1], 1 + (i * 7) % 10);
+        }
+        if i + size < size * size {
+            g.add_edge(nodes[i], nodes[i + size], 1 + (i * 3) % 10);
+        }
+    }
+    (g, nodes)
+}
+
+#[bench]
+fn contraction_hierarchy_build_bench(bench: &mut Bencher) {
+    let (g, _) = grid(50);
+    bench.iter(|| contraction_hierarchy(&g, |e| *e.weight()));
+}
+
+#[bench]
+fn contraction_hierarchy_query_bench(bench: &mut Bencher) {
+    let (g, nodes) = grid(100);
+    let hierarchy = contraction_hierarchy(&g, |e| *e.weight());
+    bench.iter(|| {
+        for i in (0..nodes.len() / 2).step_by(500) {
+            let _path = hierarchy.shortest_path(&g, nodes[i], nodes[nodes.len() - 1 - i]);
+        }
+    });
+}
+
+#[bench]
+fn contraction_hierarchy_bidirectional_dijkstra_bench(bench: &mut Bencher) {
+    let (g, nodes) = grid(100);
+    bench.iter(|| {
+        for i in (0..nodes.len() / 2).step_by(500) {
+            let _path =
+                bidirectional_dijkstra(&g, nodes[i], nodes[nodes.len() - 1 - i], |e| *e.weight());
+        }
+    });
+}
@@ -7,11 +7,12 @@ use petgraph::prelude::*;
 use std::cmp::{max, min};
 use test::Bencher;
 
-use petgraph::algo::dijkstra;
+use petgraph::algo::{
+    bidirectional_dijkstra, dijkstra, dijkstra_with_workspace, ShortestPathWorkspace,
+};
 
-#[bench]
 #[allow(clippy::needless_range_loop)]
-fn dijkstra_bench(bench: &mut Bencher) {
+fn graph() -> (UnGraph<usize, usize>, Vec<NodeIndex>) {
     static NODE_COUNT: usize = 10_000;
     let mut g = Graph::new_undirected();
     let nodes: Vec<NodeIndex<_>> = (0..NODE_COUNT).map(|i| g.add_node(i)).collect();
@@ -26,8 +27,63 @@ fn dijkstra_bench(bench: &mut Bencher) {
             g.add_edge(n1, n2, distance);
         }
     }
+    (g, nodes)
+}
 
+#[bench]
+fn dijkstra_bench(bench: &mut Bencher) {
+    let (g, nodes) = graph();
     bench.iter(|| {
         let _scores = dijkstra(&g, nodes[0], None, |e| *e.weight());
     });
 }
+
+#[cfg(feature = "rayon")]
+#[bench]
+fn parallel_delta_stepping_bench(bench: &mut Bencher) {
+    let (g, nodes) = graph();
+    bench.iter(|| {
+        let _scores =
+            petgraph::algo::delta_stepping::parallel_delta_stepping(&g, nodes[0], 3, |e| {
+                *e.weight()
+            });
+    });
+}
+
+// Short point-to-point queries, which explore a small part of the graph.
+#[bench]
+fn dijkstra_goal_bench(bench: &mut Bencher) {
+    let (g, nodes) = graph();
+    bench.iter(|| {
+        for i in (0..nodes.len() - 100).step_by(100) {
+            let _scores = dijkstra(&g, nodes[i], Some(nodes[i + 100]), |e| *e.weight());
+        }
+    });
+}
+
+#[bench]
+fn dijkstra_goal_workspace_bench(bench: &mut Bencher) {
+    let (g, nodes) = graph();
+    let mut workspace = ShortestPathWorkspace::new(&g);
+    bench.iter(|| {
+        for i in (0..nodes.len() - 100).step_by(100) {
+            let _cost = dijkstra_with_workspace(
+                &g,
+                nodes[i],
+                Some(nodes[i + 100]),
+                |e| *e.weight(),
+                &mut workspace,
+            );
+        }
+    });
+}
+
+#[bench]
+fn bidirectional_dijkstra_bench(bench: &mut Bencher) {
+    let (g, nodes) = graph();
+    bench.iter(|| {
+        for i in (0..nodes.len() - 100).step_by(100) {
+            let _path = bidirectional_dijkstra(&g, nodes[i], nodes[i + 100], |e| *e.weight());
+        }
+    });
+}
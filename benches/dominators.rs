@@ -0,0 +1,35 @@
+#![feature(test)]
+extern crate petgraph;
+extern crate test;
+
+use petgraph::algo::dominators::{lengauer_tarjan, simple_fast};
+use petgraph::prelude::{DiGraph, NodeIndex};
+use test::Bencher;
+
+// A chain of nested loops, every node branching back to all its ancestors
+// at a power of two distance.
+fn nested_loops(node_count: usize) -> DiGraph<(), ()> {
+    let mut g = DiGraph::new();
+    let nodes: Vec<_> = (0..node_count).map(|_| g.add_node(())).collect();
+    for i in 1..node_count {
+        g.add_edge(nodes[i - 1], nodes[i], ());
+        let mut back = 2;
+        while back <= i {
+            g.add_edge(nodes[i], nodes[i - back], ());
+            back *= 2;
+        }
+    }
+    g
+}
+
+#[bench]
+fn simple_fast_bench(bench: &mut Bencher) {
+    let g = nested_loops(5_000);
+    bench.iter(|| simple_fast(&g, NodeIndex::new(0)));
+}
+
+#[bench]
+fn lengauer_tarjan_bench(bench: &mut Bencher) {
+    let g = nested_loops(5_000);
+    bench.iter(|| lengauer_tarjan(&g, NodeIndex::new(0)));
+}
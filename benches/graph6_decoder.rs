@@ -3,7 +3,7 @@
 extern crate petgraph;
 extern crate test;
 
-use petgraph::graph6::from_graph6_representation;
+use petgraph::graph6::try_from_graph6_representation;
 use test::Bencher;
 
 #[bench]
@@ -33,5 +33,5 @@ fn from_graph6_str_63(bench: &mut Bencher) {
 }
 
 fn from_graph6_bench(bench: &mut Bencher, graph6_str: &str) {
-    bench.iter(|| (from_graph6_representation::<u16>(graph6_str.to_string())));
+    bench.iter(|| try_from_graph6_representation::<u16>(graph6_str).unwrap());
 }
@@ -55,3 +55,44 @@ fn praust_undir_no_iso_bench(bench: &mut Bencher) {
     bench.iter(|| is_isomorphic(&a, &b));
     assert!(!is_isomorphic(&a, &b));
 }
+
+// A grid of 30 x 30 nodes.
+fn grid() -> petgraph::graph::UnGraph<(), ()> {
+    let mut edges = Vec::new();
+    for i in 0..30u32 {
+        for j in 0..30u32 {
+            if i + 1 < 30 {
+                edges.push((i * 30 + j, (i + 1) * 30 + j));
+            }
+            if j + 1 < 30 {
+                edges.push((i * 30 + j, i * 30 + j + 1));
+            }
+        }
+    }
+    petgraph::graph::UnGraph::from_edges(edges)
+}
+
+#[bench]
+fn grid_subgraph_cycle_bench(bench: &mut Bencher) {
+    use petgraph::algo::subgraph_isomorphisms_iter;
+
+    let g = grid();
+    // A cycle of eight nodes, around a square of four cells.
+    let cycle = petgraph::graph::UnGraph::<(), ()>::from_edges([
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+        (5, 6),
+        (6, 7),
+        (7, 0),
+    ]);
+    let mut any = |_: &(), _: &()| true;
+    let mut any_edge = |_: &(), _: &()| true;
+    bench.iter(|| {
+        subgraph_isomorphisms_iter(&&cycle, &&g, &mut any, &mut any_edge)
+            .unwrap()
+            .count()
+    });
+}
@@ -0,0 +1,63 @@
+#![feature(test)]
+
+extern crate petgraph;
+extern crate test;
+
+use petgraph::prelude::*;
+use std::cmp::{max, min};
+use test::Bencher;
+
+use petgraph::algo::{floyd_warshall, johnson};
+
+#[allow(clippy::needless_range_loop)]
+fn graph(node_count: usize) -> DiGraph<usize, f64> {
+    let mut g = Graph::new();
+    let nodes: Vec<NodeIndex<_>> = (0..node_count).map(|i| g.add_node(i)).collect();
+    for i in 0..node_count {
+        let n1 = nodes[i];
+        let neighbour_count = i % 8 + 3;
+        let j_from = max(0, i as i32 - neighbour_count as i32 / 2) as usize;
+        let j_to = min(node_count, j_from + neighbour_count);
+        for j in j_from..j_to {
+            let n2 = nodes[j];
+            // Negative costs on edges to higher nodes, balanced by the
+            // positive costs back.
+            let distance = ((i + 3) % 10) as f64 + i as f64 - j as f64;
+            g.add_edge(n1, n2, distance);
+        }
+    }
+    g
+}
+
+#[bench]
+fn johnson_bench(bench: &mut Bencher) {
+    let g = graph(100);
+    bench.iter(|| {
+        let _paths = johnson(&g, |e| *e.weight());
+    });
+}
+
+#[bench]
+fn johnson_floyd_warshall_bench(bench: &mut Bencher) {
+    let g = graph(100);
+    bench.iter(|| {
+        let _scores = floyd_warshall(&g, |e| *e.weight());
+    });
+}
+
+#[cfg(feature = "rayon")]
+#[bench]
+fn parallel_johnson_bench(bench: &mut Bencher) {
+    let g = graph(1000);
+    bench.iter(|| {
+        let _paths = petgraph::algo::johnson::parallel_johnson(&g, |e| *e.weight());
+    });
+}
+
+#[bench]
+fn johnson_large_bench(bench: &mut Bencher) {
+    let g = graph(1000);
+    bench.iter(|| {
+        let _paths = johnson(&g, |e| *e.weight());
+    });
+}
@@ -7,11 +7,10 @@ use petgraph::prelude::*;
 use std::cmp::{max, min};
 use test::Bencher;
 
-use petgraph::algo::k_shortest_path;
+use petgraph::algo::{k_shortest_path, k_shortest_simple_paths};
 
-#[bench]
 #[allow(clippy::needless_range_loop)]
-fn k_shortest_path_bench(bench: &mut Bencher) {
+fn graph() -> (UnGraph<usize, usize>, Vec<NodeIndex>) {
     static NODE_COUNT: usize = 10_000;
     let mut g = Graph::new_undirected();
     let nodes: Vec<NodeIndex<_>> = (0..NODE_COUNT).map(|i| g.add_node(i)).collect();
@@ -26,6 +25,21 @@ fn k_shortest_path_bench(bench: &mut Bencher) {
             g.add_edge(n1, n2, distance);
         }
     }
+    (g, nodes)
+}
 
+#[bench]
+fn k_shortest_path_bench(bench: &mut Bencher) {
+    let (g, nodes) = graph();
     bench.iter(|| k_shortest_path(&g, nodes[0], None, 2, |e| *e.weight()));
 }
+
+#[bench]
+fn k_shortest_simple_paths_bench(bench: &mut Bencher) {
+    let (g, nodes) = graph();
+    bench.iter(|| {
+        k_shortest_simple_paths(&g, nodes[0], nodes[100], |e| *e.weight())
+            .take(10)
+            .count()
+    });
+}
@@ -19,6 +19,22 @@ fn connected_components_praust_undir_bench(bench: &mut Bencher) {
     bench.iter(|| (connected_components(&a), connected_components(&b)));
 }
 
+#[cfg(feature = "rayon")]
+#[bench]
+fn parallel_connected_components_praust_undir_bench(bench: &mut Bencher) {
+    use petgraph::algo::parallel_connected_components;
+
+    let a = ungraph().praust_a();
+    let b = ungraph().praust_b();
+
+    bench.iter(|| {
+        (
+            parallel_connected_components(&a),
+            parallel_connected_components(&b),
+        )
+    });
+}
+
 #[bench]
 fn connected_components_praust_dir_bench(bench: &mut Bencher) {
     let a = digraph().praust_a();
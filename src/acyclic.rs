@@ -2,8 +2,8 @@
 
 use std::{
     cell::RefCell,
-    cmp::Ordering,
-    collections::{BTreeMap, BTreeSet},
+    cmp::{Ordering, Reverse},
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap},
     convert::TryFrom,
     ops::{Deref, RangeBounds},
 };
@@ -12,24 +12,30 @@ use crate::{
     adj::IndexType,
     algo::Cycle,
     data::{Build, Create, DataMap, DataMapMut},
-    graph::NodeIndex,
+    graph::{EdgeIndex, NodeIndex},
     prelude::DiGraph,
     visit::{
-        dfs_visitor, Control, Data, DfsEvent, EdgeCount, EdgeIndexable, GetAdjacencyMatrix,
-        GraphBase, GraphProp, IntoEdgeReferences, IntoEdges, IntoEdgesDirected, IntoNeighbors,
-        IntoNeighborsDirected, IntoNodeIdentifiers, IntoNodeReferences, NodeCompactIndexable,
-        NodeCount, NodeIndexable, Reversed, Time, Visitable,
+        Data, EdgeCount, EdgeIndexable, GetAdjacencyMatrix, GraphBase, GraphProp,
+        IntoEdgeReferences, IntoEdges, IntoEdgesDirected, IntoNeighbors, IntoNeighborsDirected,
+        IntoNodeIdentifiers, IntoNodeReferences, NodeCompactIndexable, NodeCount, NodeIndexable,
+        Reversed, Visitable,
     },
-    Direction,
+    Directed, Direction,
 };
 
+#[cfg(feature = "graphmap")]
+use crate::graphmap::{DiGraphMap, NodeTrait};
+#[cfg(feature = "matrix_graph")]
+use crate::matrix_graph::{MatrixGraph, Nullable};
 #[cfg(feature = "stable_graph")]
 use crate::stable_graph::StableDiGraph;
 
 mod order_map;
+mod schedule;
 use fixedbitset::FixedBitSet;
 use order_map::OrderMap;
 pub use order_map::TopologicalPosition;
+pub use schedule::Schedule;
 
 /// A directed acyclic graph.
 ///
@@ -41,7 +47,9 @@ pub use order_map::TopologicalPosition;
 ///
 /// To be modifiable (and hence to be useful), the graphs of generic type `G`
 /// should implement the [`Build`] trait. Good candidates for `G` are thus
-/// [`crate::graph::DiGraph`] and [`crate::stable_graph::StableDiGraph`].
+/// [`crate::graph::DiGraph`], [`crate::stable_graph::StableDiGraph`],
+/// [`crate::graphmap::DiGraphMap`] and [`crate::matrix_graph::MatrixGraph`]
+/// with directed edges.
 ///
 /// ## Algorithm
 /// This implements the PK algorithm for dynamic topological sort described in
@@ -64,19 +72,38 @@ pub use order_map::TopologicalPosition;
 /// that would create a cycle. The [`Build::add_edge`] on the other hand method
 /// will return `None` if the edge cannot be added (either it already exists on
 /// a graph type that does not support it or would create a cycle).
+///
+/// ## Batches and rollback
+/// Many edges can be added at once with [`Acyclic::try_extend_edges`], which
+/// checks the whole batch and updates the topological order only once. To
+/// undo a sequence of changes, take a [`Checkpoint`] with
+/// [`Acyclic::checkpoint`] and pass it to `rollback` (available for the graph
+/// types listed above) or to [`Acyclic::commit`] to keep the changes.
+///
+/// ## Removals
+/// Nodes and edges are removed with `remove_node` and `remove_edge`, also
+/// available for the graph types listed above. Removing a node updates the
+/// order in O(|V|) time.
+///
+/// ## Path queries
+/// The maintained order is used to answer path queries without sorting the
+/// graph again: [`Acyclic::has_path_connecting`] and [`Acyclic::find_path`]
+/// never search past the target's position, and [`Acyclic::longest_path`]
+/// and [`Acyclic::schedule`] compute longest paths and critical-path
+/// schedules in a single pass over the order.
 #[derive(Clone, Debug)]
 pub struct Acyclic<G: Visitable> {
     /// The underlying graph, accessible through the `inner` method.
     graph: G,
     /// The current topological order of the nodes.
     order_map: OrderMap<G::NodeId>,
+    /// The changes to undo when rolling back to an open checkpoint.
+    log: ChangeLog<G::NodeId, G::EdgeId>,
 
     // We fix the internal DFS maps to FixedBitSet instead of G::VisitMap to do
     // faster resets (by just setting bits to false)
     /// Helper map for DFS tracking discovered nodes.
     discovered: RefCell<FixedBitSet>,
-    /// Helper map for DFS tracking finished nodes.
-    finished: RefCell<FixedBitSet>,
 }
 
 /// An error that can occur during edge addition for acyclic graphs.
@@ -96,6 +123,101 @@ impl<N> From<Cycle<N>> for AcyclicEdgeError<N> {
     }
 }
 
+/// A state of an acyclic graph to return to, created by
+/// [`Acyclic::checkpoint`].
+///
+/// While a checkpoint is open, the graph records the nodes and edges that are
+/// added and the changes to the topological order, so that `rollback` can
+/// undo them. A checkpoint should be closed by passing it to either `rollback`
+/// or [`Acyclic::commit`].
+///
+/// Rolling back removes the nodes and edges added since the checkpoint was
+/// opened and restores the topological order. The weights of edges updated
+/// with [`Acyclic::try_update_edge`] are not restored. Removing a node or an
+/// edge cannot be undone, so it invalidates all open checkpoints, and rolling
+/// back to an invalidated checkpoint panics.
+///
+/// Nested checkpoints must be closed in LIFO order, the last one opened
+/// first: closing a checkpoint while one opened after it is still open
+/// panics.
+#[must_use = "a checkpoint should be passed to `rollback` or `commit`"]
+#[derive(Debug)]
+pub struct Checkpoint {
+    generation: usize,
+    /// The length of the change log when the checkpoint was opened.
+    position: usize,
+    /// The number of open checkpoints, including this one.
+    depth: usize,
+}
+
+/// A change to an acyclic graph that can be undone.
+#[derive(Clone, Debug)]
+enum Change<N, E> {
+    AddNode(N),
+    AddEdge(E),
+    /// A node was moved from the given position.
+    Reorder(N, TopologicalPosition),
+}
+
+/// The changes since the oldest open checkpoint.
+#[derive(Clone, Debug)]
+struct ChangeLog<N, E> {
+    changes: Vec<Change<N, E>>,
+    /// The number of open checkpoints. Changes are only recorded while there
+    /// are some.
+    open: usize,
+    /// Incremented whenever the open checkpoints are invalidated.
+    generation: usize,
+}
+
+impl<N, E> Default for ChangeLog<N, E> {
+    fn default() -> Self {
+        Self {
+            changes: Vec::new(),
+            open: 0,
+            generation: 0,
+        }
+    }
+}
+
+impl<N, E> ChangeLog<N, E> {
+    fn is_recording(&self) -> bool {
+        self.open > 0
+    }
+
+    fn record(&mut self, change: Change<N, E>) {
+        if self.is_recording() {
+            self.changes.push(change);
+        }
+    }
+
+    /// Panics if a checkpoint opened after `checkpoint` is still open.
+    fn assert_innermost(&self, checkpoint: &Checkpoint) {
+        assert_eq!(
+            checkpoint.depth, self.open,
+            "checkpoints must be closed in LIFO order"
+        );
+        debug_assert!(checkpoint.position <= self.changes.len());
+    }
+
+    /// Close a checkpoint, and forget the changes once none are open.
+    fn close(&mut self) {
+        self.open -= 1;
+        if self.open == 0 {
+            self.changes.clear();
+        }
+    }
+
+    /// Invalidate all open checkpoints, after a change that cannot be undone.
+    fn invalidate(&mut self) {
+        if self.is_recording() {
+            self.generation += 1;
+            self.open = 0;
+            self.changes.clear();
+        }
+    }
+}
+
 impl<G: Visitable> Acyclic<G> {
     /// Create a new empty acyclic graph.
     pub fn new() -> Self
@@ -136,6 +258,28 @@ impl<G: Visitable> Acyclic<G> {
     pub fn into_inner(self) -> G {
         self.graph
     }
+
+    /// Open a checkpoint to return to with `rollback`.
+    ///
+    /// Checkpoints can be nested: rolling back to a checkpoint undoes all
+    /// changes made since it was opened, including those of checkpoints
+    /// opened after it.
+    pub fn checkpoint(&mut self) -> Checkpoint {
+        self.log.open += 1;
+        Checkpoint {
+            generation: self.log.generation,
+            position: self.log.changes.len(),
+            depth: self.log.open,
+        }
+    }
+
+    /// Close a checkpoint and keep the changes made since it was opened.
+    pub fn commit(&mut self, checkpoint: Checkpoint) {
+        if checkpoint.generation == self.log.generation {
+            self.log.assert_innermost(&checkpoint);
+            self.log.close();
+        }
+    }
 }
 
 impl<G: Visitable + NodeIndexable> Acyclic<G>
@@ -144,18 +288,17 @@ where
 {
     /// Wrap a graph into an acyclic graph.
     ///
-    /// The graph types [`DiGraph`] and [`StableDiGraph`] also implement
-    /// [`TryFrom`], which can be used instead of this method and have looser
-    /// type bounds.
+    /// The graph types [`DiGraph`], [`StableDiGraph`], `DiGraphMap` and
+    /// `MatrixGraph` also implement [`TryFrom`], which can be used instead of
+    /// this method and have looser type bounds.
     pub fn try_from_graph(graph: G) -> Result<Self, Cycle<G::NodeId>> {
         let order_map = OrderMap::try_from_graph(&graph)?;
         let discovered = RefCell::new(FixedBitSet::with_capacity(graph.node_bound()));
-        let finished = RefCell::new(FixedBitSet::with_capacity(graph.node_bound()));
         Ok(Self {
             graph,
             order_map,
+            log: ChangeLog::default(),
             discovered,
-            finished,
         })
     }
 
@@ -177,16 +320,18 @@ where
     ) -> Result<G::EdgeId, AcyclicEdgeError<G::NodeId>>
     where
         G: Build,
-        G::NodeId: IndexType,
     {
         if a == b {
             // No self-loops allowed
             return Err(AcyclicEdgeError::SelfLoop);
         }
         self.update_ordering(a, b)?;
-        self.graph
+        let edge = self
+            .graph
             .add_edge(a, b, weight)
-            .ok_or(AcyclicEdgeError::InvalidEdge)
+            .ok_or(AcyclicEdgeError::InvalidEdge)?;
+        self.log.record(Change::AddEdge(edge));
+        Ok(edge)
     }
 
     /// Update an edge in a graph using [`Build::update_edge`].
@@ -197,6 +342,9 @@ where
     ///
     /// This will return an error if and only if [`Self::is_valid_edge`] returns
     /// `false`.
+    ///
+    /// Rolling back to a [`Checkpoint`] removes the edge if it was created,
+    /// but does not restore the weight of an updated edge.
     pub fn try_update_edge(
         &mut self,
         a: G::NodeId,
@@ -205,21 +353,84 @@ where
     ) -> Result<G::EdgeId, AcyclicEdgeError<G::NodeId>>
     where
         G: Build,
-        G::NodeId: IndexType,
     {
         if a == b {
             // No self-loops allowed
             return Err(AcyclicEdgeError::SelfLoop);
         }
         self.update_ordering(a, b)?;
-        Ok(self.graph.update_edge(a, b, weight))
+        let is_new = self.log.is_recording()
+            && !self
+                .graph
+                .neighbors_directed(a, Direction::Outgoing)
+                .any(|n| n == b);
+        let edge = self.graph.update_edge(a, b, weight);
+        if is_new {
+            self.log.record(Change::AddEdge(edge));
+        }
+        Ok(edge)
     }
 
-    /// Check if an edge would be valid, i.e. adding it would not create a cycle.
-    pub fn is_valid_edge(&self, a: G::NodeId, b: G::NodeId) -> bool
+    /// Add a batch of edges to the graph using [`Build::add_edge`].
+    ///
+    /// The whole batch is checked before any edge is added: if one of the
+    /// edges is a self-loop or the edges would create a cycle together with
+    /// the graph, an [`AcyclicEdgeError`] is returned and `self` remains
+    /// unchanged. Otherwise the topological order is updated once for the
+    /// whole batch, which is much faster than adding the edges one at a time
+    /// with [`Self::try_add_edge`] when many of them go against the current
+    /// order.
+    ///
+    /// Returns the ids of the added edges, in the order of the batch. If the
+    /// underlying graph rejects an edge, [`AcyclicEdgeError::InvalidEdge`] is
+    /// returned and the edges before it remain added; use a [`Checkpoint`] to
+    /// undo them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::acyclic::{Acyclic, AcyclicEdgeError};
+    /// use petgraph::data::Build;
+    /// use petgraph::prelude::*;
+    ///
+    /// let mut dag = Acyclic::<DiGraph<(), ()>>::new();
+    /// let [a, b, c] = [(); 3].map(|_| dag.add_node(()));
+    /// assert!(dag.try_extend_edges([(c, b, ()), (b, a, ())]).is_ok());
+    /// assert_eq!(dag.nodes_iter().collect::<Vec<_>>(), [c, b, a]);
+    ///
+    /// let cycle = dag.try_extend_edges([(a, c, ())]);
+    /// assert!(matches!(cycle, Err(AcyclicEdgeError::Cycle(_))));
+    /// assert_eq!(dag.edge_count(), 2);
+    /// ```
+    pub fn try_extend_edges<I>(
+        &mut self,
+        edges: I,
+    ) -> Result<Vec<G::EdgeId>, AcyclicEdgeError<G::NodeId>>
     where
-        G::NodeId: IndexType,
+        G: Build,
+        I: IntoIterator<Item = (G::NodeId, G::NodeId, G::EdgeWeight)>,
     {
+        let edges: Vec<_> = edges.into_iter().collect();
+        if edges.iter().any(|&(a, b, _)| a == b) {
+            // No self-loops allowed
+            return Err(AcyclicEdgeError::SelfLoop);
+        }
+        let endpoints: Vec<_> = edges.iter().map(|&(a, b, _)| (a, b)).collect();
+        self.reorder_for_batch(&endpoints)?;
+
+        let mut ids = Vec::with_capacity(edges.len());
+        for (a, b, weight) in edges {
+            let edge = self
+                .graph
+                .add_edge(a, b, weight)
+                .ok_or(AcyclicEdgeError::InvalidEdge)?;
+            self.log.record(Change::AddEdge(edge));
+            ids.push(edge);
+        }
+        Ok(ids)
+    }
+
+    /// Check if an edge would be valid, i.e. adding it would not create a cycle.
+    pub fn is_valid_edge(&self, a: G::NodeId, b: G::NodeId) -> bool {
         if a == b {
             false // No self-loops
         } else if self.get_position(a) < self.get_position(b) {
@@ -231,16 +442,112 @@ where
         }
     }
 
+    /// Return `true` if there is a path from `a` to `b`, or if `a` and `b` are
+    /// the same node.
+    ///
+    /// Only the nodes between `a` and `b` in the topological order can be on
+    /// such a path, so the search never visits nodes past `b`, and returns
+    /// immediately if `a` comes after `b`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::acyclic::Acyclic;
+    /// use petgraph::data::Build;
+    /// use petgraph::prelude::*;
+    ///
+    /// let mut dag = Acyclic::<DiGraph<(), ()>>::new();
+    /// let a = dag.add_node(());
+    /// let b = dag.add_node(());
+    /// let c = dag.add_node(());
+    /// dag.try_add_edge(a, b, ()).unwrap();
+    /// dag.try_add_edge(b, c, ()).unwrap();
+    ///
+    /// assert!(dag.has_path_connecting(a, c));
+    /// assert!(!dag.has_path_connecting(c, a));
+    /// assert_eq!(dag.find_path(a, c), Some(vec![a, b, c]));
+    /// ```
+    pub fn has_path_connecting(&self, a: G::NodeId, b: G::NodeId) -> bool {
+        self.search_path(a, b, |_, _| ())
+    }
+
+    /// Return the nodes of a path from `a` to `b`, if there is one.
+    ///
+    /// The path is not necessarily the shortest one. As with
+    /// [`Acyclic::has_path_connecting`], the search never visits nodes past
+    /// `b` in the topological order.
+    pub fn find_path(&self, a: G::NodeId, b: G::NodeId) -> Option<Vec<G::NodeId>> {
+        let mut predecessors = HashMap::new();
+        let found = self.search_path(a, b, |u, v| {
+            predecessors.insert(self.graph.to_index(v), u);
+        });
+        if !found {
+            return None;
+        }
+        let mut path = vec![b];
+        let mut node = b;
+        while node != a {
+            node = predecessors[&self.graph.to_index(node)];
+            path.push(node);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Search for `b` depth-first from `a`, among the nodes before `b` in the
+    /// topological order, and call `visit(u, v)` for every node `v` that is
+    /// discovered from `u`.
+    fn search_path(
+        &self,
+        a: G::NodeId,
+        b: G::NodeId,
+        mut visit: impl FnMut(G::NodeId, G::NodeId),
+    ) -> bool {
+        if a == b {
+            return true;
+        }
+        let max_position = self.get_position(b);
+        if self.get_position(a) > max_position {
+            return false;
+        }
+
+        let mut discovered = self.discovered.borrow_mut();
+        debug_assert!(discovered.is_clear());
+        discovered.grow(self.graph.node_bound());
+        let ix = |n| self.graph.to_index(n);
+
+        discovered.insert(ix(a));
+        let mut visited = vec![a];
+        let mut stack = vec![a];
+        let mut found = false;
+        'search: while let Some(u) = stack.pop() {
+            for v in self.graph.neighbors(u) {
+                if v == b {
+                    visit(u, v);
+                    found = true;
+                    break 'search;
+                }
+                if self.get_position(v) < max_position && !discovered.put(ix(v)) {
+                    visit(u, v);
+                    visited.push(v);
+                    stack.push(v);
+                }
+            }
+        }
+
+        // Reset only the discovered nodes, as in `causal_cones`.
+        for v in visited {
+            discovered.set(ix(v), false);
+        }
+        found
+    }
+
     /// Update the ordering of the nodes in the order map resulting from adding an
     /// edge a -> b.
     ///
     /// If a cycle is detected, an error is returned and `self` remains unchanged.
     ///
     /// Implements the core update logic of the PK algorithm.
-    fn update_ordering(&mut self, a: G::NodeId, b: G::NodeId) -> Result<(), Cycle<G::NodeId>>
-    where
-        G::NodeId: IndexType,
-    {
+    fn update_ordering(&mut self, a: G::NodeId, b: G::NodeId) -> Result<(), Cycle<G::NodeId>> {
         let min_order = self.get_position(b);
         let max_order = self.get_position(a);
         if min_order >= max_order {
@@ -261,11 +568,119 @@ where
         debug_assert_eq!(all_positions.len(), b_fut.len() + a_past.len());
 
         for (pos, node) in all_positions.into_iter().zip(all_nodes) {
-            self.order_map.set_position(node, pos, &self.graph);
+            self.set_position(node, pos);
+        }
+        Ok(())
+    }
+
+    /// Update the ordering of the nodes in the order map for adding all the
+    /// edges `a -> b` of a batch at once.
+    ///
+    /// If the edges would create a cycle, an error is returned and `self`
+    /// remains unchanged.
+    ///
+    /// The nodes between the first target and the last source of the edges
+    /// that go against the current order are sorted again with Kahn's
+    /// algorithm, preferring the current order. Nodes outside of this window
+    /// keep their positions, and every cycle would lie within it.
+    fn reorder_for_batch(
+        &mut self,
+        edges: &[(G::NodeId, G::NodeId)],
+    ) -> Result<(), Cycle<G::NodeId>> {
+        let backward = edges
+            .iter()
+            .filter(|&&(a, b)| self.get_position(a) > self.get_position(b));
+        let (min_order, max_order) = match backward
+            .map(|&(a, b)| (self.get_position(b), self.get_position(a)))
+            .reduce(|(min1, max1), (min2, max2)| (min1.min(min2), max1.max(max2)))
+        {
+            Some(window) => window,
+            // Order is already correct
+            None => return Ok(()),
+        };
+        let this = &*self;
+        let in_window = |n| (min_order..=max_order).contains(&this.get_position(n));
+
+        // The batch edges within the window, in both directions, by the
+        // index of their source and target.
+        let ix = |n| this.graph.to_index(n);
+        let mut successors = HashMap::<_, Vec<_>>::new();
+        let mut predecessors = HashMap::<_, Vec<_>>::new();
+        for &(a, b) in edges {
+            if in_window(a) && in_window(b) {
+                successors.entry(ix(a)).or_default().push(b);
+                predecessors.entry(ix(b)).or_default().push(a);
+            }
+        }
+        let graph_predecessors = |n| {
+            this.graph
+                .neighbors_directed(n, Direction::Incoming)
+                .filter(move |&m| in_window(m))
+        };
+        let batch_predecessors = |n| predecessors.get(&ix(n)).into_iter().flatten().copied();
+
+        // Count the predecessors of every node within the window.
+        let window: Vec<_> = this.order_map.range(min_order..=max_order).collect();
+        let mut in_degree = vec![0; this.graph.node_bound()];
+        for &n in &window {
+            in_degree[ix(n)] = graph_predecessors(n).count() + batch_predecessors(n).count();
+        }
+
+        // Kahn's algorithm, taking the ready node with the smallest position.
+        let mut ready: BinaryHeap<_> = window
+            .iter()
+            .filter(|&&n| in_degree[ix(n)] == 0)
+            .map(|&n| Reverse(this.get_position(n)))
+            .collect();
+        let mut order = Vec::with_capacity(window.len());
+        while let Some(Reverse(pos)) = ready.pop() {
+            let n = this.order_map.at_position(pos).unwrap();
+            order.push(n);
+            let graph_successors = this
+                .graph
+                .neighbors_directed(n, Direction::Outgoing)
+                .filter(|&m| in_window(m));
+            let batch_successors = successors.get(&ix(n)).into_iter().flatten().copied();
+            for m in graph_successors.chain(batch_successors) {
+                in_degree[ix(m)] -= 1;
+                if in_degree[ix(m)] == 0 {
+                    ready.push(Reverse(this.get_position(m)));
+                }
+            }
+        }
+
+        if order.len() < window.len() {
+            // Every node left has a predecessor that is left, so walking
+            // backwards from one of them leads into a cycle.
+            let mut node = *window.iter().find(|&&n| in_degree[ix(n)] > 0).unwrap();
+            let mut seen = FixedBitSet::with_capacity(this.graph.node_bound());
+            while !seen.put(ix(node)) {
+                node = graph_predecessors(node)
+                    .chain(batch_predecessors(node))
+                    .find(|&m| in_degree[ix(m)] > 0)
+                    .unwrap();
+            }
+            return Err(Cycle(node));
+        }
+
+        let positions: Vec<_> = window.iter().map(|&n| self.get_position(n)).collect();
+        for (pos, node) in positions.into_iter().zip(order) {
+            if self.get_position(node) != pos {
+                self.set_position(node, pos);
+            }
         }
         Ok(())
     }
 
+    /// Move a node to a new position, recording the change.
+    fn set_position(&mut self, node: G::NodeId, pos: TopologicalPosition) {
+        if self.log.is_recording() {
+            let old = self.get_position(node);
+            self.log.record(Change::Reorder(node, old));
+        }
+        self.order_map.set_position(node, pos, &self.graph);
+    }
+
     /// Use DFS to find the future causal cone of `min_node` and the past causal
     /// cone of `max_node`.
     ///
@@ -285,12 +700,8 @@ where
             BTreeMap<TopologicalPosition, G::NodeId>,
         ),
         Cycle<G::NodeId>,
-    >
-    where
-        G::NodeId: IndexType,
-    {
+    > {
         debug_assert!(self.discovered.borrow().is_clear());
-        debug_assert!(self.finished.borrow().is_clear());
 
         let min_order = self.get_position(min_node);
         let max_order = self.get_position(max_node);
@@ -298,7 +709,6 @@ where
         // Prepare DFS scratch space: make sure the maps have enough capacity
         if self.discovered.borrow().len() < self.graph.node_bound() {
             self.discovered.borrow_mut().grow(self.graph.node_bound());
-            self.finished.borrow_mut().grow(self.graph.node_bound());
         }
 
         // Get all nodes reachable from b with min_order <= order < max_order
@@ -314,8 +724,12 @@ where
             // Get all nodes that can reach a with min_order < order <= max_order
             // These are disjoint from the nodes in the forward cone, otherwise
             // we would have a cycle.
-            self.past_cone(max_node, min_order, max_order, &mut backward_cone)
-                .expect("cycles already detected in future_cone");
+            if self
+                .past_cone(max_node, min_order, max_order, &mut backward_cone)
+                .is_err()
+            {
+                unreachable!("cycles already detected in future_cone");
+            }
 
             Ok(())
         };
@@ -325,11 +739,11 @@ where
         // Cleanup: reset map to 0. This is faster than a full reset, especially
         // on large sparse graphs.
         for &v in forward_cone.values().chain(backward_cone.values()) {
-            self.discovered.borrow_mut().set(v.index(), false);
-            self.finished.borrow_mut().set(v.index(), false);
+            self.discovered
+                .borrow_mut()
+                .set(self.graph.to_index(v), false);
         }
         debug_assert!(self.discovered.borrow().is_clear());
-        debug_assert!(self.finished.borrow().is_clear());
 
         match success {
             Ok(()) => Ok((forward_cone, backward_cone)),
@@ -343,10 +757,7 @@ where
         min_position: TopologicalPosition,
         max_position: TopologicalPosition,
         res: &mut BTreeMap<TopologicalPosition, G::NodeId>,
-    ) -> Result<(), Cycle<G::NodeId>>
-    where
-        G::NodeId: IndexType,
-    {
+    ) -> Result<(), Cycle<G::NodeId>> {
         dfs(
             &self.graph,
             start,
@@ -361,7 +772,6 @@ where
             },
             res,
             &mut self.discovered.borrow_mut(),
-            &mut self.finished.borrow_mut(),
         )
     }
 
@@ -371,10 +781,7 @@ where
         min_position: TopologicalPosition,
         max_position: TopologicalPosition,
         res: &mut BTreeMap<TopologicalPosition, G::NodeId>,
-    ) -> Result<(), Cycle<G::NodeId>>
-    where
-        G::NodeId: IndexType,
-    {
+    ) -> Result<(), Cycle<G::NodeId>> {
         dfs(
             Reversed(&self.graph),
             start,
@@ -389,7 +796,6 @@ where
             },
             res,
             &mut self.discovered.borrow_mut(),
-            &mut self.finished.borrow_mut(),
         )
     }
 }
@@ -404,12 +810,11 @@ impl<G: Default + Visitable> Default for Acyclic<G> {
         let graph: G = Default::default();
         let order_map = Default::default();
         let discovered = RefCell::new(FixedBitSet::default());
-        let finished = RefCell::new(FixedBitSet::default());
         Self {
             graph,
             order_map,
+            log: ChangeLog::default(),
             discovered,
-            finished,
         }
     }
 }
@@ -420,11 +825,11 @@ where
         + IntoNodeIdentifiers
         + Visitable<Map = G::Map>
         + GraphBase<NodeId = G::NodeId>,
-    G::NodeId: IndexType,
 {
     fn add_node(&mut self, weight: Self::NodeWeight) -> Self::NodeId {
         let n = self.graph.add_node(weight);
         self.order_map.add_node(n, &self.graph);
+        self.log.record(Change::AddNode(n));
         n
     }
 
@@ -443,7 +848,10 @@ where
         b: Self::NodeId,
         weight: Self::EdgeWeight,
     ) -> Self::EdgeId {
-        self.try_update_edge(a, b, weight).unwrap()
+        match self.try_update_edge(a, b, weight) {
+            Ok(edge) => edge,
+            Err(_) => panic!("the edge would create a cycle or a self-loop"),
+        }
     }
 }
 
@@ -453,18 +861,16 @@ where
         + IntoNodeIdentifiers
         + Visitable<Map = G::Map>
         + GraphBase<NodeId = G::NodeId>,
-    G::NodeId: IndexType,
 {
     fn with_capacity(nodes: usize, edges: usize) -> Self {
         let graph = G::with_capacity(nodes, edges);
         let order_map = OrderMap::with_capacity(nodes);
         let discovered = FixedBitSet::with_capacity(nodes);
-        let finished = FixedBitSet::with_capacity(nodes);
         Self {
             graph,
             order_map,
+            log: ChangeLog::default(),
             discovered: RefCell::new(discovered),
-            finished: RefCell::new(finished),
         }
     }
 }
@@ -479,7 +885,9 @@ impl<G: Visitable> Deref for Acyclic<G> {
 
 /// Traverse nodes in `graph` in DFS order, starting from `start`, for as long
 /// as the predicate `valid_order` returns `true` on the current node's order.
-fn dfs<G: NodeIndexable + IntoNeighborsDirected + IntoNodeIdentifiers + Visitable>(
+///
+/// The discovered nodes are marked in `discovered` by their index.
+fn dfs<G: NodeIndexable + IntoNeighbors>(
     graph: G,
     start: G::NodeId,
     order_map: &OrderMap<G::NodeId>,
@@ -488,39 +896,24 @@ fn dfs<G: NodeIndexable + IntoNeighborsDirected + IntoNodeIdentifiers + Visitabl
     mut valid_order: impl FnMut(TopologicalPosition) -> Result<bool, Cycle<G::NodeId>>,
     res: &mut BTreeMap<TopologicalPosition, G::NodeId>,
     discovered: &mut FixedBitSet,
-    finished: &mut FixedBitSet,
-) -> Result<(), Cycle<G::NodeId>>
-where
-    G::NodeId: IndexType,
-{
-    dfs_visitor(
-        graph,
-        start,
-        &mut |ev| -> Result<Control<()>, Cycle<G::NodeId>> {
-            match ev {
-                DfsEvent::Discover(u, _) => {
-                    // We are visiting u
-                    let order = order_map.get_position(u, &graph);
-                    res.insert(order, u);
-                    Ok(Control::Continue)
-                }
-                DfsEvent::TreeEdge(_, u) => {
-                    // Should we visit u?
-                    let order = order_map.get_position(u, &graph);
-                    match valid_order(order) {
-                        Ok(true) => Ok(Control::Continue),
-                        Ok(false) => Ok(Control::Prune),
-                        Err(cycle) => Err(cycle),
-                    }
-                }
-                _ => Ok(Control::Continue),
+) -> Result<(), Cycle<G::NodeId>> {
+    discovered.insert(graph.to_index(start));
+    res.insert(order_map.get_position(start, &graph), start);
+    let mut stack = vec![start];
+    while let Some(u) = stack.pop() {
+        for v in graph.neighbors(u) {
+            if discovered.contains(graph.to_index(v)) {
+                continue;
             }
-        },
-        discovered,
-        finished,
-        &mut Time::default(),
-    )?;
-
+            // Should we visit v?
+            let order = order_map.get_position(v, &graph);
+            if valid_order(order)? {
+                discovered.insert(graph.to_index(v));
+                res.insert(order, v);
+                stack.push(v);
+            }
+        }
+    }
     Ok(())
 }
 
@@ -538,9 +931,10 @@ where
 // - NodeIndexable
 // - Visitable
 //
-// Furthermore, we also implement the `remove_node` and `remove_edge` methods,
-// as well as the following traits for `DiGraph` and `StableDiGraph` (these
-// are hard/impossible to implement generically):
+// Furthermore, we also implement the `remove_node`, `remove_edge` and
+// `rollback` methods, as well as the following traits for `DiGraph`,
+// `StableDiGraph`, `DiGraphMap` and `MatrixGraph` (these are
+// hard/impossible to implement generically):
 // - TryFrom
 // - IntoEdgeReferences
 // - IntoEdges
@@ -645,106 +1039,129 @@ impl<G: Visitable> Visitable for Acyclic<G> {
     }
 }
 
-macro_rules! impl_graph_traits {
-    ($graph_type:ident) => {
-        // Remove edge and node methods (not available through traits)
-        impl<N, E, Ix: IndexType> Acyclic<$graph_type<N, E, Ix>> {
-            /// Remove an edge and return its edge weight, or None if it didn't exist.
-            ///
-            /// Pass through to underlying graph.
-            pub fn remove_edge(
-                &mut self,
-                e: <$graph_type<N, E, Ix> as GraphBase>::EdgeId,
-            ) -> Option<E> {
-                self.graph.remove_edge(e)
-            }
+impl<G: Visitable + NodeIndexable> Acyclic<G> {
+    /// Remove the node `n` from the order, then from the graph using `remove`.
+    ///
+    /// If `swaps` is `true`, `remove` moves the node with the last index into
+    /// the index of the removed node, as [`DiGraph`] and
+    /// [`GraphMap`](crate::graphmap::GraphMap) do, and
+    /// the order is updated to match.
+    fn remove_ordered_node<R>(
+        &mut self,
+        n: G::NodeId,
+        swaps: bool,
+        remove: impl FnOnce(&mut G, G::NodeId) -> R,
+    ) -> R {
+        let index = self.graph.to_index(n);
+        let last = self.graph.node_bound() - 1;
+        let moved = (swaps && index != last).then(|| {
+            self.order_map
+                .get_position(self.graph.from_index(last), &self.graph)
+        });
+        self.order_map.remove_node(n, &self.graph);
+        let removed = remove(&mut self.graph, n);
+        if let Some(pos) = moved {
+            let node = self.graph.from_index(index);
+            self.order_map.set_position(node, pos, &self.graph);
+        }
+        removed
+    }
 
-            /// Remove a node from the graph if it exists, and return its
-            /// weight. If it doesn't exist in the graph, return None.
-            ///
-            /// This updates the order in O(v) runtime and removes the node in
-            /// the underlying graph.
-            pub fn remove_node(
-                &mut self,
-                n: <$graph_type<N, E, Ix> as GraphBase>::NodeId,
-            ) -> Option<N> {
-                self.order_map.remove_node(n, &self.graph);
-                self.graph.remove_node(n)
+    /// Undo the changes made since `checkpoint` was opened, as described in
+    /// [`Checkpoint`], removing nodes and edges with `remove_node` and
+    /// `remove_edge`, and close it.
+    fn rollback_with(
+        &mut self,
+        checkpoint: Checkpoint,
+        mut remove_node: impl FnMut(&mut Self, G::NodeId),
+        mut remove_edge: impl FnMut(&mut G, G::EdgeId),
+    ) {
+        assert_eq!(
+            checkpoint.generation, self.log.generation,
+            "the checkpoint was invalidated by a removal"
+        );
+        self.log.assert_innermost(&checkpoint);
+        let changes = self.log.changes.split_off(checkpoint.position);
+        for change in changes.into_iter().rev() {
+            match change {
+                Change::AddNode(n) => remove_node(self, n),
+                Change::AddEdge(e) => remove_edge(&mut self.graph, e),
+                Change::Reorder(n, pos) => self.order_map.set_position(n, pos, &self.graph),
             }
         }
+        self.log.close();
+    }
+}
 
-        impl<N, E, Ix: IndexType> TryFrom<$graph_type<N, E, Ix>>
-            for Acyclic<$graph_type<N, E, Ix>>
-        {
-            type Error = Cycle<NodeIndex<Ix>>;
+macro_rules! impl_graph_traits {
+    ([$($generics:tt)*] $graph:ty) => {
+        impl<$($generics)*> TryFrom<$graph> for Acyclic<$graph> {
+            type Error = Cycle<<$graph as GraphBase>::NodeId>;
 
-            fn try_from(graph: $graph_type<N, E, Ix>) -> Result<Self, Self::Error> {
+            fn try_from(graph: $graph) -> Result<Self, Self::Error> {
                 let order_map = OrderMap::try_from_graph(&graph)?;
                 let discovered = RefCell::new(FixedBitSet::with_capacity(graph.node_bound()));
-                let finished = RefCell::new(FixedBitSet::with_capacity(graph.node_bound()));
                 Ok(Self {
                     graph,
                     order_map,
+                    log: ChangeLog::default(),
                     discovered,
-                    finished,
                 })
             }
         }
 
-        impl<'a, N, E, Ix: IndexType> IntoEdgeReferences for &'a Acyclic<$graph_type<N, E, Ix>> {
-            type EdgeRef = <&'a $graph_type<N, E, Ix> as IntoEdgeReferences>::EdgeRef;
-            type EdgeReferences = <&'a $graph_type<N, E, Ix> as IntoEdgeReferences>::EdgeReferences;
+        impl<'a, $($generics)*> IntoEdgeReferences for &'a Acyclic<$graph> {
+            type EdgeRef = <&'a $graph as IntoEdgeReferences>::EdgeRef;
+            type EdgeReferences = <&'a $graph as IntoEdgeReferences>::EdgeReferences;
 
             fn edge_references(self) -> Self::EdgeReferences {
                 self.inner().edge_references()
             }
         }
 
-        impl<'a, N, E, Ix: IndexType> IntoEdges for &'a Acyclic<$graph_type<N, E, Ix>> {
-            type Edges = <&'a $graph_type<N, E, Ix> as IntoEdges>::Edges;
+        impl<'a, $($generics)*> IntoEdges for &'a Acyclic<$graph> {
+            type Edges = <&'a $graph as IntoEdges>::Edges;
 
             fn edges(self, a: Self::NodeId) -> Self::Edges {
                 self.inner().edges(a)
             }
         }
 
-        impl<'a, N, E, Ix: IndexType> IntoEdgesDirected for &'a Acyclic<$graph_type<N, E, Ix>> {
-            type EdgesDirected = <&'a $graph_type<N, E, Ix> as IntoEdgesDirected>::EdgesDirected;
+        impl<'a, $($generics)*> IntoEdgesDirected for &'a Acyclic<$graph> {
+            type EdgesDirected = <&'a $graph as IntoEdgesDirected>::EdgesDirected;
 
             fn edges_directed(self, a: Self::NodeId, dir: Direction) -> Self::EdgesDirected {
                 self.inner().edges_directed(a, dir)
             }
         }
 
-        impl<'a, N, E, Ix: IndexType> IntoNeighbors for &'a Acyclic<$graph_type<N, E, Ix>> {
-            type Neighbors = <&'a $graph_type<N, E, Ix> as IntoNeighbors>::Neighbors;
+        impl<'a, $($generics)*> IntoNeighbors for &'a Acyclic<$graph> {
+            type Neighbors = <&'a $graph as IntoNeighbors>::Neighbors;
 
             fn neighbors(self, a: Self::NodeId) -> Self::Neighbors {
                 self.inner().neighbors(a)
             }
         }
 
-        impl<'a, N, E, Ix: IndexType> IntoNeighborsDirected for &'a Acyclic<$graph_type<N, E, Ix>> {
-            type NeighborsDirected =
-                <&'a $graph_type<N, E, Ix> as IntoNeighborsDirected>::NeighborsDirected;
+        impl<'a, $($generics)*> IntoNeighborsDirected for &'a Acyclic<$graph> {
+            type NeighborsDirected = <&'a $graph as IntoNeighborsDirected>::NeighborsDirected;
 
             fn neighbors_directed(self, n: Self::NodeId, d: Direction) -> Self::NeighborsDirected {
                 self.inner().neighbors_directed(n, d)
             }
         }
 
-        impl<'a, N, E, Ix: IndexType> IntoNodeIdentifiers for &'a Acyclic<$graph_type<N, E, Ix>> {
-            type NodeIdentifiers =
-                <&'a $graph_type<N, E, Ix> as IntoNodeIdentifiers>::NodeIdentifiers;
+        impl<'a, $($generics)*> IntoNodeIdentifiers for &'a Acyclic<$graph> {
+            type NodeIdentifiers = <&'a $graph as IntoNodeIdentifiers>::NodeIdentifiers;
 
             fn node_identifiers(self) -> Self::NodeIdentifiers {
                 self.inner().node_identifiers()
             }
         }
 
-        impl<'a, N, E, Ix: IndexType> IntoNodeReferences for &'a Acyclic<$graph_type<N, E, Ix>> {
-            type NodeRef = <&'a $graph_type<N, E, Ix> as IntoNodeReferences>::NodeRef;
-            type NodeReferences = <&'a $graph_type<N, E, Ix> as IntoNodeReferences>::NodeReferences;
+        impl<'a, $($generics)*> IntoNodeReferences for &'a Acyclic<$graph> {
+            type NodeRef = <&'a $graph as IntoNodeReferences>::NodeRef;
+            type NodeReferences = <&'a $graph as IntoNodeReferences>::NodeReferences;
 
             fn node_references(self) -> Self::NodeReferences {
                 self.inner().node_references()
@@ -753,9 +1170,167 @@ macro_rules! impl_graph_traits {
     };
 }
 
-impl_graph_traits!(DiGraph);
+impl_graph_traits!([N, E, Ix: IndexType] DiGraph<N, E, Ix>);
 #[cfg(feature = "stable_graph")]
-impl_graph_traits!(StableDiGraph);
+impl_graph_traits!([N, E, Ix: IndexType] StableDiGraph<N, E, Ix>);
+#[cfg(feature = "graphmap")]
+impl_graph_traits!([N: NodeTrait, E] DiGraphMap<N, E>);
+#[cfg(feature = "matrix_graph")]
+impl_graph_traits!(
+    [N, E, Null: Nullable<Wrapped = E>, Ix: IndexType] MatrixGraph<N, E, Directed, Null, Ix>
+);
+
+// Remove edge and node methods (not available through traits)
+impl<N, E, Ix: IndexType> Acyclic<DiGraph<N, E, Ix>> {
+    /// Remove an edge and return its edge weight, or `None` if it didn't exist.
+    pub fn remove_edge(&mut self, e: EdgeIndex<Ix>) -> Option<E> {
+        self.log.invalidate();
+        self.graph.remove_edge(e)
+    }
+
+    /// Remove a node and return its weight, or `None` if it didn't exist.
+    ///
+    /// As in [`DiGraph::remove_node`], the node with the last index takes the
+    /// index of the removed node.
+    pub fn remove_node(&mut self, n: NodeIndex<Ix>) -> Option<N> {
+        self.log.invalidate();
+        if n.index() >= self.graph.node_count() {
+            return None;
+        }
+        self.remove_ordered_node(n, true, |graph, n| graph.remove_node(n))
+    }
+
+    /// Undo all changes made since `checkpoint` was opened, and close it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::acyclic::Acyclic;
+    /// use petgraph::data::Build;
+    /// use petgraph::prelude::*;
+    ///
+    /// let mut dag = Acyclic::<DiGraph<(), ()>>::new();
+    /// let a = dag.add_node(());
+    /// let b = dag.add_node(());
+    ///
+    /// let checkpoint = dag.checkpoint();
+    /// let c = dag.add_node(());
+    /// dag.try_extend_edges([(b, a, ()), (c, b, ())]).unwrap();
+    /// assert!(dag.try_add_edge(a, c, ()).is_err());
+    /// dag.rollback(checkpoint);
+    ///
+    /// assert_eq!(dag.node_count(), 2);
+    /// assert_eq!(dag.edge_count(), 0);
+    /// assert_eq!(dag.nodes_iter().collect::<Vec<_>>(), [a, b]);
+    /// ```
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.rollback_with(
+            checkpoint,
+            |this, n| {
+                this.remove_ordered_node(n, true, |graph, n| graph.remove_node(n));
+            },
+            |graph, e| {
+                graph.remove_edge(e);
+            },
+        );
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+impl<N, E, Ix: IndexType> Acyclic<StableDiGraph<N, E, Ix>> {
+    /// Remove an edge and return its edge weight, or `None` if it didn't exist.
+    pub fn remove_edge(&mut self, e: EdgeIndex<Ix>) -> Option<E> {
+        self.log.invalidate();
+        self.graph.remove_edge(e)
+    }
+
+    /// Remove a node and return its weight, or `None` if it didn't exist.
+    pub fn remove_node(&mut self, n: NodeIndex<Ix>) -> Option<N> {
+        self.log.invalidate();
+        if !self.graph.contains_node(n) {
+            return None;
+        }
+        self.remove_ordered_node(n, false, |graph, n| graph.remove_node(n))
+    }
+
+    /// Undo all changes made since `checkpoint` was opened, and close it.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.rollback_with(
+            checkpoint,
+            |this, n| {
+                this.remove_ordered_node(n, false, |graph, n| graph.remove_node(n));
+            },
+            |graph, e| {
+                graph.remove_edge(e);
+            },
+        );
+    }
+}
+
+#[cfg(feature = "graphmap")]
+impl<N: NodeTrait, E> Acyclic<DiGraphMap<N, E>> {
+    /// Remove the edge from `a` to `b` and return its edge weight, or `None`
+    /// if it didn't exist.
+    pub fn remove_edge(&mut self, a: N, b: N) -> Option<E> {
+        self.log.invalidate();
+        self.graph.remove_edge(a, b)
+    }
+
+    /// Remove a node, and return `true` if it existed.
+    pub fn remove_node(&mut self, n: N) -> bool {
+        self.log.invalidate();
+        if !self.graph.contains_node(n) {
+            return false;
+        }
+        self.remove_ordered_node(n, true, |graph, n| graph.remove_node(n))
+    }
+
+    /// Undo all changes made since `checkpoint` was opened, and close it.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.rollback_with(
+            checkpoint,
+            |this, n| {
+                this.remove_ordered_node(n, true, |graph, n| graph.remove_node(n));
+            },
+            |graph, (a, b)| {
+                graph.remove_edge(a, b);
+            },
+        );
+    }
+}
+
+#[cfg(feature = "matrix_graph")]
+impl<N, E, Null: Nullable<Wrapped = E>, Ix: IndexType>
+    Acyclic<MatrixGraph<N, E, Directed, Null, Ix>>
+{
+    /// Remove the edge from `a` to `b` and return its edge weight.
+    ///
+    /// **Panics** if no edge exists between `a` and `b`.
+    pub fn remove_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> E {
+        self.log.invalidate();
+        self.graph.remove_edge(a, b)
+    }
+
+    /// Remove a node and return its weight.
+    ///
+    /// **Panics** if the node does not exist.
+    pub fn remove_node(&mut self, n: NodeIndex<Ix>) -> N {
+        self.log.invalidate();
+        self.remove_ordered_node(n, false, |graph, n| graph.remove_node(n))
+    }
+
+    /// Undo all changes made since `checkpoint` was opened, and close it.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.rollback_with(
+            checkpoint,
+            |this, n| {
+                this.remove_ordered_node(n, false, |graph, n| graph.remove_node(n));
+            },
+            |graph, (a, b)| {
+                graph.remove_edge(a, b);
+            },
+        );
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -763,7 +1338,8 @@ mod tests {
     use crate::prelude::DiGraph;
     #[cfg(feature = "stable_graph")]
     use crate::prelude::StableDiGraph;
-    use crate::visit::IntoNodeReferences;
+    use crate::visit::{EdgeRef, IntoNodeReferences};
+    use rand::{ChaChaRng, Rng, SeedableRng};
 
     #[test]
     fn test_acyclic_graph() {
@@ -834,6 +1410,456 @@ mod tests {
         assert!(!acyclic.inner().contains_edge(a, c));
     }
 
+    // A random DAG on `nodes` nodes, with its nodes in a random order.
+    fn random_dag(rng: &mut ChaChaRng, nodes: usize) -> Acyclic<DiGraph<(), ()>> {
+        let mut acyclic = Acyclic::<DiGraph<(), ()>>::new();
+        let ids: Vec<_> = (0..nodes).map(|_| acyclic.add_node(())).collect();
+        let mut order = ids.clone();
+        rng.shuffle(&mut order);
+        for _ in 0..nodes {
+            let i = rng.gen_range(0, nodes);
+            let j = rng.gen_range(0, nodes);
+            if i < j {
+                acyclic.try_add_edge(order[i], order[j], ()).unwrap();
+            }
+        }
+        acyclic
+    }
+
+    fn random_batch(rng: &mut ChaChaRng, nodes: usize) -> Vec<(NodeIndex, NodeIndex, ())> {
+        (0..rng.gen_range(0, nodes))
+            .map(|_| {
+                let a = NodeIndex::new(rng.gen_range(0, nodes));
+                let b = NodeIndex::new(rng.gen_range(0, nodes));
+                (a, b, ())
+            })
+            .collect()
+    }
+
+    fn snapshot(
+        acyclic: &Acyclic<DiGraph<(), ()>>,
+    ) -> (Vec<NodeIndex>, Vec<(NodeIndex, NodeIndex)>) {
+        let edges = acyclic
+            .inner()
+            .edge_references()
+            .map(|e| (e.source(), e.target()))
+            .collect();
+        (acyclic.nodes_iter().collect(), edges)
+    }
+
+    #[test]
+    fn test_try_extend_edges() {
+        let mut rng = ChaChaRng::from_seed([80; 32]);
+        for _ in 0..500 {
+            let nodes = rng.gen_range(1, 20);
+            let mut acyclic = random_dag(&mut rng, nodes);
+            let batch = random_batch(&mut rng, nodes);
+
+            let mut combined = acyclic.inner().clone();
+            combined.extend_with_edges(batch.iter().copied());
+            let before = snapshot(&acyclic);
+            match acyclic.try_extend_edges(batch.iter().copied()) {
+                Ok(edges) => {
+                    assert!(!crate::algo::is_cyclic_directed(&combined));
+                    assert_eq!(edges.len(), batch.len());
+                    for (&edge, &(a, b, _)) in edges.iter().zip(&batch) {
+                        assert_eq!(acyclic.inner().edge_endpoints(edge), Some((a, b)));
+                    }
+                    assert_eq!(acyclic.edge_count(), combined.edge_count());
+                    assert_valid_topological_order(&acyclic);
+                }
+                Err(AcyclicEdgeError::SelfLoop) => {
+                    assert!(batch.iter().any(|&(a, b, _)| a == b));
+                    assert_eq!(snapshot(&acyclic), before);
+                }
+                Err(AcyclicEdgeError::Cycle(cycle)) => {
+                    // The node is on a cycle of the graph with the batch.
+                    let node = cycle.node_id();
+                    let on_cycle = combined
+                        .neighbors(node)
+                        .any(|n| crate::algo::has_path_connecting(&combined, n, node, None));
+                    assert!(on_cycle);
+                    assert_eq!(snapshot(&acyclic), before);
+                }
+                Err(AcyclicEdgeError::InvalidEdge) => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_extend_edges_in_order() {
+        let mut acyclic = Acyclic::<DiGraph<(), ()>>::new();
+        let nodes: Vec<_> = (0..100).map(|_| acyclic.add_node(())).collect();
+        let order: Vec<_> = acyclic.nodes_iter().collect();
+        let edges = nodes.windows(2).map(|pair| (pair[0], pair[1], ()));
+        assert_eq!(acyclic.try_extend_edges(edges).unwrap().len(), 99);
+        assert_eq!(acyclic.nodes_iter().collect::<Vec<_>>(), order);
+
+        // A single batch that reverses the whole order.
+        let mut acyclic = Acyclic::<DiGraph<(), ()>>::new();
+        let nodes: Vec<_> = (0..100).map(|_| acyclic.add_node(())).collect();
+        let edges = nodes.windows(2).map(|pair| (pair[1], pair[0], ()));
+        assert!(acyclic.try_extend_edges(edges).is_ok());
+        let reversed: Vec<_> = nodes.iter().rev().copied().collect();
+        assert_eq!(acyclic.nodes_iter().collect::<Vec<_>>(), reversed);
+        assert_valid_topological_order(&acyclic);
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut rng = ChaChaRng::from_seed([81; 32]);
+        for _ in 0..200 {
+            let nodes = rng.gen_range(1, 20);
+            let mut acyclic = random_dag(&mut rng, nodes);
+            let before = snapshot(&acyclic);
+
+            let checkpoint = acyclic.checkpoint();
+            for _ in 0..rng.gen_range(0, 5) {
+                let new_node = acyclic.add_node(());
+                let nodes = acyclic.node_count();
+                let _ = acyclic.try_extend_edges(random_batch(&mut rng, nodes));
+                let a = NodeIndex::new(rng.gen_range(0, nodes));
+                let _ = acyclic.try_add_edge(a, new_node, ());
+                let _ = acyclic.try_update_edge(new_node, a, ());
+                assert_valid_topological_order(&acyclic);
+            }
+            acyclic.rollback(checkpoint);
+            assert_eq!(snapshot(&acyclic), before);
+            assert_valid_topological_order(&acyclic);
+        }
+    }
+
+    #[test]
+    fn test_nested_checkpoints() {
+        let mut acyclic = Acyclic::<DiGraph<(), ()>>::new();
+        let a = acyclic.add_node(());
+        let b = acyclic.add_node(());
+
+        let outer = acyclic.checkpoint();
+        acyclic.try_add_edge(b, a, ()).unwrap();
+        let inner = acyclic.checkpoint();
+        let c = acyclic.add_node(());
+        acyclic.try_add_edge(a, c, ()).unwrap();
+        acyclic.rollback(inner);
+        assert_eq!(acyclic.node_count(), 2);
+        assert_eq!(acyclic.edge_count(), 1);
+
+        let inner = acyclic.checkpoint();
+        acyclic.add_node(());
+        acyclic.commit(inner);
+        assert_eq!(acyclic.node_count(), 3);
+        acyclic.rollback(outer);
+        assert_eq!(acyclic.node_count(), 2);
+        assert_eq!(acyclic.edge_count(), 0);
+        assert_eq!(acyclic.nodes_iter().collect::<Vec<_>>(), [a, b]);
+
+        // Without an open checkpoint, nothing is recorded.
+        acyclic.try_add_edge(b, a, ()).unwrap();
+        assert!(acyclic.log.changes.is_empty());
+        let checkpoint = acyclic.checkpoint();
+        acyclic.commit(checkpoint);
+        assert!(acyclic.log.changes.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "LIFO")]
+    fn test_rollback_out_of_order() {
+        let mut acyclic = Acyclic::<DiGraph<(), ()>>::new();
+        let outer = acyclic.checkpoint();
+        acyclic.add_node(());
+        let inner = acyclic.checkpoint();
+        acyclic.add_node(());
+        acyclic.rollback(outer);
+        acyclic.rollback(inner);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalidated")]
+    fn test_rollback_after_removal() {
+        let mut acyclic = Acyclic::<DiGraph<(), ()>>::new();
+        let a = acyclic.add_node(());
+        let b = acyclic.add_node(());
+        let edge = acyclic.try_add_edge(a, b, ()).unwrap();
+        let checkpoint = acyclic.checkpoint();
+        acyclic.add_node(());
+        acyclic.remove_edge(edge);
+        acyclic.rollback(checkpoint);
+    }
+
+    #[test]
+    fn test_remove_node_moves_last_node() {
+        let mut rng = ChaChaRng::from_seed([82; 32]);
+        for _ in 0..100 {
+            let nodes = rng.gen_range(1, 20);
+            let mut acyclic = random_dag(&mut rng, nodes);
+            while acyclic.node_count() > 0 {
+                let n = NodeIndex::new(rng.gen_range(0, acyclic.node_count()));
+                assert!(acyclic.remove_node(n).is_some());
+                assert_valid_topological_order(&acyclic);
+            }
+            assert_eq!(acyclic.remove_node(NodeIndex::new(0)), None);
+        }
+    }
+
+    #[cfg(feature = "graphmap")]
+    #[test]
+    fn test_acyclic_graphmap() {
+        use crate::graphmap::DiGraphMap;
+
+        let mut acyclic = Acyclic::<DiGraphMap<&str, u32>>::new();
+        for n in ["shirt", "tie", "jacket", "belt", "trousers"] {
+            acyclic.add_node(n);
+        }
+        assert!(acyclic.try_add_edge("shirt", "tie", 1).is_ok());
+        assert!(acyclic.try_add_edge("tie", "jacket", 2).is_ok());
+        assert!(acyclic.try_add_edge("trousers", "belt", 3).is_ok());
+        assert!(acyclic.try_add_edge("belt", "jacket", 4).is_ok());
+        assert_valid_topological_order(&acyclic);
+
+        assert!(matches!(
+            acyclic.try_add_edge("jacket", "shirt", 5),
+            Err(AcyclicEdgeError::Cycle(_))
+        ));
+        assert_eq!(
+            acyclic.try_add_edge("tie", "tie", 5),
+            Err(AcyclicEdgeError::SelfLoop)
+        );
+        // A second edge between the same nodes is rejected by the graph.
+        assert_eq!(
+            acyclic.try_add_edge("shirt", "tie", 5),
+            Err(AcyclicEdgeError::InvalidEdge)
+        );
+        assert_eq!(
+            acyclic.try_update_edge("shirt", "tie", 5),
+            Ok(("shirt", "tie"))
+        );
+        assert_eq!(acyclic.edge_weight("shirt", "tie"), Some(&5));
+
+        // The nodes before the jacket in the order.
+        let jacket = acyclic.get_position("jacket");
+        let before: Vec<_> = acyclic.range(..jacket).collect();
+        assert_eq!(before.len(), 4);
+        assert_eq!(acyclic.range(jacket..).collect::<Vec<_>>(), ["jacket"]);
+
+        let checkpoint = acyclic.checkpoint();
+        acyclic.add_node("socks");
+        acyclic
+            .try_extend_edges([("socks", "shirt", 6), ("trousers", "socks", 7)])
+            .unwrap();
+        assert!(acyclic.get_position("trousers") < acyclic.get_position("shirt"));
+        assert_valid_topological_order(&acyclic);
+        acyclic.rollback(checkpoint);
+        assert!(!acyclic.contains_node("socks"));
+        assert_eq!(acyclic.edge_count(), 4);
+        assert_valid_topological_order(&acyclic);
+
+        // Removing a node moves the last one in the underlying graph.
+        assert!(acyclic.remove_node("shirt"));
+        assert!(!acyclic.remove_node("shirt"));
+        assert_valid_topological_order(&acyclic);
+        assert_eq!(acyclic.remove_edge("belt", "jacket"), Some(4));
+        assert_eq!(acyclic.remove_edge("belt", "jacket"), None);
+        assert!(acyclic.try_add_edge("jacket", "belt", 8).is_ok());
+        assert_valid_topological_order(&acyclic);
+
+        let mut graph = DiGraphMap::<u32, ()>::new();
+        graph.add_edge(1, 2, ());
+        graph.add_edge(2, 3, ());
+        let acyclic = Acyclic::try_from(graph.clone()).unwrap();
+        assert_eq!(acyclic.nodes_iter().collect::<Vec<_>>(), [1, 2, 3]);
+        graph.add_edge(3, 1, ());
+        assert!(Acyclic::try_from(graph).is_err());
+    }
+
+    #[cfg(feature = "graphmap")]
+    #[test]
+    fn test_graphmap_rollback() {
+        use crate::graphmap::DiGraphMap;
+
+        let mut rng = ChaChaRng::from_seed([83; 32]);
+        for _ in 0..200 {
+            let nodes = rng.gen_range(1, 20);
+            let mut acyclic = Acyclic::<DiGraphMap<u32, ()>>::new();
+            for n in 0..nodes {
+                acyclic.add_node(n);
+            }
+            let edges: Vec<_> = (0..nodes)
+                .map(|_| (rng.gen_range(0, nodes), rng.gen_range(0, nodes), ()))
+                .collect();
+            let _ = acyclic.try_extend_edges(edges);
+            let before: Vec<_> = acyclic.nodes_iter().collect();
+            let before_edges: Vec<_> = acyclic.all_edges().map(|(a, b, _)| (a, b)).collect();
+
+            let checkpoint = acyclic.checkpoint();
+            for new_node in nodes..nodes + rng.gen_range(0, 5) {
+                acyclic.add_node(new_node);
+                let a = rng.gen_range(0, new_node);
+                let _ = acyclic.try_add_edge(a, new_node, ());
+                let _ = acyclic.try_update_edge(new_node, a, ());
+                let b = rng.gen_range(0, new_node);
+                let _ = acyclic.try_extend_edges([(b, a, ())]);
+                assert_valid_topological_order(&acyclic);
+            }
+            acyclic.rollback(checkpoint);
+            assert_eq!(acyclic.nodes_iter().collect::<Vec<_>>(), before);
+            let edges: Vec<_> = acyclic.all_edges().map(|(a, b, _)| (a, b)).collect();
+            assert_eq!(edges, before_edges);
+            assert_valid_topological_order(&acyclic);
+
+            while let Some(n) = acyclic.nodes().next() {
+                assert!(acyclic.remove_node(n));
+                assert_valid_topological_order(&acyclic);
+            }
+        }
+    }
+
+    #[cfg(feature = "matrix_graph")]
+    #[test]
+    fn test_acyclic_matrix_graph() {
+        use crate::matrix_graph::MatrixGraph;
+
+        let mut acyclic = Acyclic::<MatrixGraph<(), u32>>::new();
+        let a = acyclic.add_node(());
+        let b = acyclic.add_node(());
+        let c = acyclic.add_node(());
+        assert!(acyclic.try_add_edge(c, b, 1).is_ok());
+        assert!(acyclic.try_add_edge(b, a, 2).is_ok());
+        assert_eq!(acyclic.nodes_iter().collect::<Vec<_>>(), [c, b, a]);
+        assert!(acyclic.try_add_edge(a, c, 3).is_err());
+        assert_eq!(
+            acyclic.try_add_edge(c, b, 3),
+            Err(AcyclicEdgeError::InvalidEdge)
+        );
+        assert!(acyclic.add_edge(a, c, 3).is_none());
+
+        let b_position = acyclic.get_position(b);
+        assert_eq!(acyclic.range(b_position..).collect::<Vec<_>>(), [b, a]);
+
+        let checkpoint = acyclic.checkpoint();
+        let d = acyclic.add_node(());
+        acyclic
+            .try_extend_edges([(a, d, 4), (d, c, 5)])
+            .unwrap_err();
+        acyclic
+            .try_extend_edges([(d, c, 5), (a, c, 6)])
+            .unwrap_err();
+        acyclic.try_extend_edges([(d, c, 5)]).unwrap();
+        assert_valid_topological_order(&acyclic);
+        acyclic.rollback(checkpoint);
+        assert_eq!(acyclic.node_count(), 3);
+        assert_eq!(acyclic.edge_count(), 2);
+        assert_valid_topological_order(&acyclic);
+
+        // Node indices are stable, so removing a node moves no other node.
+        acyclic.remove_node(b);
+        assert_valid_topological_order(&acyclic);
+        assert!(acyclic.try_add_edge(a, c, 7).is_ok());
+        assert_eq!(acyclic.remove_edge(a, c), 7);
+        assert_eq!(acyclic.nodes_iter().collect::<Vec<_>>(), [a, c]);
+
+        let mut graph = MatrixGraph::<(), ()>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        graph.add_edge(b, a, ());
+        let acyclic = Acyclic::try_from(graph.clone()).unwrap();
+        assert_eq!(acyclic.nodes_iter().collect::<Vec<_>>(), [b, a]);
+        graph.add_edge(a, b, ());
+        assert!(Acyclic::try_from(graph).is_err());
+    }
+
+    #[test]
+    fn test_has_path_connecting() {
+        let mut rng = ChaChaRng::from_seed([84; 32]);
+        for _ in 0..100 {
+            let nodes = rng.gen_range(1, 20);
+            let acyclic = random_dag(&mut rng, nodes);
+            for a in acyclic.node_identifiers() {
+                for b in acyclic.node_identifiers() {
+                    let expected = crate::algo::has_path_connecting(acyclic.inner(), a, b, None);
+                    assert_eq!(acyclic.has_path_connecting(a, b), expected);
+                    match acyclic.find_path(a, b) {
+                        Some(path) => {
+                            assert!(expected);
+                            assert_eq!((path[0], path[path.len() - 1]), (a, b));
+                            for pair in path.windows(2) {
+                                assert!(acyclic.contains_edge(pair[0], pair[1]));
+                            }
+                        }
+                        None => assert!(!expected),
+                    }
+                }
+            }
+            assert!(acyclic.discovered.borrow().is_clear());
+        }
+    }
+
+    // The cost of the longest path ending at `node`, or 0 if it is larger.
+    fn longest_path_to(graph: &DiGraph<(), i32>, node: NodeIndex) -> i32 {
+        graph
+            .edges_directed(node, Direction::Incoming)
+            .map(|e| longest_path_to(graph, e.source()) + e.weight())
+            .fold(0, i32::max)
+    }
+
+    #[test]
+    fn test_longest_path_and_schedule() {
+        let mut rng = ChaChaRng::from_seed([85; 32]);
+        for _ in 0..200 {
+            let nodes = rng.gen_range(0, 12);
+            let mut acyclic = Acyclic::<DiGraph<(), i32>>::new();
+            for _ in 0..nodes {
+                acyclic.add_node(());
+            }
+            for _ in 0..2 * nodes {
+                let a = NodeIndex::new(rng.gen_range(0, nodes));
+                let b = NodeIndex::new(rng.gen_range(0, nodes));
+                let _ = acyclic.try_add_edge(a, b, rng.gen_range(-5, 10));
+            }
+            let graph = acyclic.inner();
+
+            let (length, path) = acyclic.longest_path(|e| *e.weight());
+            let expected = graph
+                .node_indices()
+                .map(|n| longest_path_to(graph, n))
+                .max()
+                .unwrap_or(0);
+            assert_eq!(length, expected);
+            assert_eq!(path.is_empty(), nodes == 0);
+            let cost: i32 = path
+                .windows(2)
+                .map(|pair| {
+                    let edges = graph.edges_connecting(pair[0], pair[1]);
+                    edges.map(|e| *e.weight()).max().unwrap()
+                })
+                .sum();
+            assert_eq!(cost, length);
+
+            let schedule = acyclic.schedule(|e| *e.weight());
+            assert_eq!(schedule.length(), length);
+            assert_eq!(schedule.critical_path(), path);
+            for n in graph.node_indices() {
+                let earliest = schedule.earliest_start(n).unwrap();
+                let latest = schedule.latest_start(n).unwrap();
+                assert_eq!(earliest, longest_path_to(graph, n));
+                assert!(earliest <= latest && latest <= length);
+                assert_eq!(schedule.slack(n), Some(latest - earliest));
+            }
+            for e in graph.edge_references() {
+                let (a, b) = (e.source(), e.target());
+                let earliest = |n| schedule.earliest_start(n).unwrap();
+                let latest = |n| schedule.latest_start(n).unwrap();
+                assert!(earliest(a) + e.weight() <= earliest(b));
+                assert!(latest(a) + e.weight() <= latest(b));
+            }
+            // Every node without successors can start at the latest at the
+            // end of the schedule.
+            for n in graph.externals(Direction::Outgoing) {
+                assert_eq!(schedule.latest_start(n), Some(length));
+            }
+            assert!(path.iter().all(|&n| schedule.is_critical(n)));
+        }
+    }
+
     fn assert_valid_topological_order<'a, G>(acyclic: &'a Acyclic<G>)
     where
         G: Visitable + NodeCount + NodeIndexable,
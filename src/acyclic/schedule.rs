@@ -0,0 +1,247 @@
+//! Longest paths and critical-path scheduling over the maintained
+//! topological order.
+
+use std::{collections::HashMap, hash::Hash, ops::Sub};
+
+use crate::{
+    algo::Measure,
+    visit::{EdgeRef, GraphBase, IntoEdgeReferences, IntoEdges, Visitable},
+};
+
+/// The earliest and latest start of every node of an acyclic graph, returned
+/// by [`Acyclic::schedule`](super::Acyclic::schedule).
+///
+/// Nodes are tasks, and the cost of an edge `a -> b` is the minimum time
+/// between the start of `a` and the start of `b`. A node starts at the
+/// earliest at the cost of the longest path ending at it, and at the latest
+/// as late as it can without delaying the end of the schedule, which is the
+/// cost of the longest path of the graph. Its slack is the difference
+/// between the two: the nodes with no slack are critical.
+///
+/// # Example
+/// ```rust
+/// use petgraph::acyclic::Acyclic;
+/// use petgraph::data::Build;
+/// use petgraph::prelude::*;
+///
+/// // Tasks and their durations. The cost of an edge is the duration of its
+/// // source, and the end of the schedule is the start of `done`.
+/// let mut dag = Acyclic::<DiGraph<u32, ()>>::new();
+/// let foundations = dag.add_node(4);
+/// let walls = dag.add_node(6);
+/// let plumbing = dag.add_node(2);
+/// let roof = dag.add_node(3);
+/// let done = dag.add_node(0);
+/// dag.try_add_edge(foundations, walls, ()).unwrap();
+/// dag.try_add_edge(foundations, plumbing, ()).unwrap();
+/// dag.try_add_edge(walls, roof, ()).unwrap();
+/// dag.try_add_edge(plumbing, done, ()).unwrap();
+/// dag.try_add_edge(roof, done, ()).unwrap();
+///
+/// let schedule = dag.schedule(|e| dag[e.source()]);
+/// assert_eq!(schedule.length(), 13);
+/// assert_eq!(schedule.critical_path(), [foundations, walls, roof, done]);
+/// assert_eq!(schedule.earliest_start(plumbing), Some(4));
+/// assert_eq!(schedule.latest_start(plumbing), Some(11));
+/// assert_eq!(schedule.slack(plumbing), Some(7));
+/// assert!(!schedule.is_critical(plumbing));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Schedule<N, K>
+where
+    N: Eq + Hash,
+{
+    length: K,
+    earliest: HashMap<N, K>,
+    latest: HashMap<N, K>,
+    critical_path: Vec<N>,
+}
+
+impl<N, K> Schedule<N, K>
+where
+    N: Copy + Eq + Hash,
+    K: Measure + Copy + Sub<Output = K>,
+{
+    /// Return the cost of the longest path of the graph.
+    pub fn length(&self) -> K {
+        self.length
+    }
+
+    /// Return the earliest start of `node`, or `None` if it is not in the
+    /// graph.
+    pub fn earliest_start(&self, node: N) -> Option<K> {
+        self.earliest.get(&node).copied()
+    }
+
+    /// Return the latest start of `node`, or `None` if it is not in the
+    /// graph.
+    pub fn latest_start(&self, node: N) -> Option<K> {
+        self.latest.get(&node).copied()
+    }
+
+    /// Return the difference between the latest and the earliest start of
+    /// `node`, or `None` if it is not in the graph.
+    pub fn slack(&self, node: N) -> Option<K> {
+        Some(self.latest_start(node)? - self.earliest_start(node)?)
+    }
+
+    /// Return `true` if `node` has no slack.
+    ///
+    /// With floating-point costs, rounding errors may leave a small slack on
+    /// nodes that should have none.
+    pub fn is_critical(&self, node: N) -> bool {
+        self.slack(node) == Some(K::default())
+    }
+
+    /// Return the nodes of a longest path of the graph, whose nodes are all
+    /// critical.
+    pub fn critical_path(&self) -> &[N] {
+        &self.critical_path
+    }
+}
+
+impl<G: Visitable> super::Acyclic<G> {
+    /// Return the cost of a longest path of the graph and its nodes.
+    ///
+    /// The cost of an edge is computed by `edge_cost`, and the cost of a path
+    /// is the sum of the costs of its edges. With negative costs, the longest
+    /// path may be a single node, of cost `K::default()`. The path is empty
+    /// only if the graph is.
+    ///
+    /// Paths are found in a single pass over the maintained topological
+    /// order, without sorting the graph again.
+    ///
+    /// # Complexity
+    /// * Time complexity: **O(|V| + |E|)**.
+    /// * Auxiliary space: **O(|V|)**.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::acyclic::Acyclic;
+    /// use petgraph::data::Build;
+    /// use petgraph::prelude::*;
+    ///
+    /// let mut dag = Acyclic::<DiGraph<(), i32>>::new();
+    /// let a = dag.add_node(());
+    /// let b = dag.add_node(());
+    /// let c = dag.add_node(());
+    /// dag.try_add_edge(a, b, 2).unwrap();
+    /// dag.try_add_edge(b, c, 3).unwrap();
+    /// dag.try_add_edge(a, c, 4).unwrap();
+    ///
+    /// assert_eq!(dag.longest_path(|e| *e.weight()), (5, vec![a, b, c]));
+    /// ```
+    pub fn longest_path<'a, F, K>(&'a self, mut edge_cost: F) -> (K, Vec<G::NodeId>)
+    where
+        &'a G: IntoEdges + GraphBase<NodeId = G::NodeId>,
+        G::NodeId: Eq + Hash,
+        F: FnMut(<&'a G as IntoEdgeReferences>::EdgeRef) -> K,
+        K: Measure + Copy,
+    {
+        let (earliest, predecessors) = self.earliest_starts(&mut edge_cost);
+        longest_path_to_end(&earliest, &predecessors, self.nodes_iter())
+    }
+
+    /// Compute the earliest and latest start and the slack of every node, and
+    /// a critical path.
+    ///
+    /// The cost of an edge is computed by `edge_cost`, see [`Schedule`] for
+    /// its meaning.
+    ///
+    /// # Complexity
+    /// * Time complexity: **O(|V| + |E|)**.
+    /// * Auxiliary space: **O(|V|)**.
+    pub fn schedule<'a, F, K>(&'a self, mut edge_cost: F) -> Schedule<G::NodeId, K>
+    where
+        &'a G: IntoEdges + GraphBase<NodeId = G::NodeId>,
+        G::NodeId: Eq + Hash,
+        F: FnMut(<&'a G as IntoEdgeReferences>::EdgeRef) -> K,
+        K: Measure + Copy + Sub<Output = K>,
+    {
+        let (earliest, predecessors) = self.earliest_starts(&mut edge_cost);
+        let (length, critical_path) =
+            longest_path_to_end(&earliest, &predecessors, self.nodes_iter());
+
+        // Backward pass: a node starts at the latest early enough for each of
+        // its successors to start at their latest.
+        let order: Vec<_> = self.nodes_iter().collect();
+        let mut latest = HashMap::with_capacity(order.len());
+        for &node in order.iter().rev() {
+            let mut start = length;
+            for edge in self.graph.edges(node) {
+                let candidate = latest[&edge.target()] - edge_cost(edge);
+                if candidate < start {
+                    start = candidate;
+                }
+            }
+            latest.insert(node, start);
+        }
+
+        Schedule {
+            length,
+            earliest,
+            latest,
+            critical_path,
+        }
+    }
+
+    /// Compute the cost of the longest path ending at every node, and the
+    /// node before it on that path, if any.
+    #[allow(clippy::type_complexity)]
+    fn earliest_starts<'a, F, K>(
+        &'a self,
+        edge_cost: &mut F,
+    ) -> (HashMap<G::NodeId, K>, HashMap<G::NodeId, G::NodeId>)
+    where
+        &'a G: IntoEdges + GraphBase<NodeId = G::NodeId>,
+        G::NodeId: Eq + Hash,
+        F: FnMut(<&'a G as IntoEdgeReferences>::EdgeRef) -> K,
+        K: Measure + Copy,
+    {
+        let mut earliest = HashMap::new();
+        let mut predecessors = HashMap::new();
+        // The predecessors of a node come before it, so its earliest start
+        // is final when it is reached.
+        for node in self.nodes_iter() {
+            let start = *earliest.entry(node).or_insert_with(K::default);
+            for edge in self.graph.edges(node) {
+                let candidate = start + edge_cost(edge);
+                let next = earliest.entry(edge.target()).or_insert_with(K::default);
+                if candidate > *next {
+                    *next = candidate;
+                    predecessors.insert(edge.target(), node);
+                }
+            }
+        }
+        (earliest, predecessors)
+    }
+}
+
+/// Return the cost of the longest path and its nodes, ending at the first
+/// node of `nodes` with the largest earliest start.
+fn longest_path_to_end<N, K>(
+    earliest: &HashMap<N, K>,
+    predecessors: &HashMap<N, N>,
+    nodes: impl Iterator<Item = N>,
+) -> (K, Vec<N>)
+where
+    N: Copy + Eq + Hash,
+    K: Measure + Copy,
+{
+    let mut end = None;
+    let mut length = K::default();
+    for node in nodes {
+        let start = earliest[&node];
+        if end.is_none() || start > length {
+            end = Some(node);
+            length = start;
+        }
+    }
+
+    let mut path: Vec<_> = end.into_iter().collect();
+    while let Some(&previous) = path.last().and_then(|node| predecessors.get(node)) {
+        path.push(previous);
+    }
+    path.reverse();
+    (length, path)
+}
@@ -1,8 +1,9 @@
+use crate::graph::{NodeIndex, UnGraph};
 use crate::visit;
-use crate::visit::{EdgeRef, IntoEdges, IntoNodeReferences, NodeIndexable, NodeRef};
+use crate::visit::{EdgeRef, IntoEdges, IntoNodeIdentifiers, IntoNodeReferences, NodeIndexable};
 use fixedbitset::FixedBitSet;
 use std::cmp::min;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::hash::Hash;
 
 /// \[Generic\] Find articulation points in a graph using [Tarjan's algorithm](https://en.wikipedia.org/wiki/Tarjan%27s_strongly_connected_components_algorithm).
@@ -10,7 +11,7 @@ use std::hash::Hash;
 /// Compute the articulation points of a graph (Nodes, which would increase the number of connected components in the graph.
 ///
 /// # Arguments
-/// * `graph`: A directed graph
+/// * `graph`: An undirected graph
 ///
 /// # Returns
 /// * `HashSet`: HashSet of the node ids which correspond to the articulation points of the graph.
@@ -46,121 +47,292 @@ where
     G::EdgeWeight: Clone + PartialOrd,
     G::NodeId: Eq + Hash,
 {
-    let graph_size = g.node_references().size_hint().0;
-    let mut auxiliary_const = ArticulationPointTracker::new(graph_size);
-
-    for node in g.node_references() {
-        let node_id = g.to_index(node.id());
-        if !auxiliary_const.visited[node_id] {
-            _dfs(&g, node_id, &mut auxiliary_const);
-        }
-    }
-
-    auxiliary_const
+    Biconnected::new(g)
         .articulation_points
         .into_iter()
-        .map(|id| g.from_index(id))
         .collect()
 }
 
-/// Small helper enum that defines the various splitup recursion steps of Tarjan's algorithm.
-enum RecursionStep {
-    BaseStep(usize),
-    ProcessChildStep(usize, usize),
-    NoBackEdgeConditionCheck(usize, usize),
-    RootMoreThanTwoChildrenCheck(usize),
+/// \[Generic\] Find the biconnected components of an undirected graph.
+///
+/// A biconnected component, or block, is a maximal set of edges in which
+/// every two edges lie on a common simple cycle, or a single edge that lies on
+/// no cycle at all: a bridge. Removing any single node leaves the edges of a
+/// block connected. Every edge belongs to exactly one block, and two blocks
+/// share at most one node, which is an [articulation point](articulation_points).
+///
+/// Returns the edges of every block. Self loops lie on no path between two
+/// nodes and belong to no block.
+///
+/// The components are found with the same depth-first search as the
+/// [articulation points](articulation_points) and the [bridges], by
+/// Hopcroft and Tarjan.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::articulation_points::biconnected_components;
+/// use petgraph::graph::UnGraph;
+/// use petgraph::visit::EdgeRef;
+///
+/// // A triangle 0, 1, 2 with a tail 2 - 3.
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+///
+/// let mut blocks: Vec<Vec<usize>> = biconnected_components(&graph)
+///     .iter()
+///     .map(|block| {
+///         let mut edges: Vec<_> = block.iter().map(|e| e.id().index()).collect();
+///         edges.sort();
+///         edges
+///     })
+///     .collect();
+/// blocks.sort();
+/// assert_eq!(blocks, vec![vec![0, 1, 2], vec![3]]);
+/// ```
+pub fn biconnected_components<G>(g: G) -> Vec<Vec<G::EdgeRef>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    Biconnected::new(g).components
+}
+
+/// \[Generic\] Find the bridges of an undirected graph.
+///
+/// A bridge is an edge whose removal increases the number of connected
+/// components of the graph, that is an edge that lies on no cycle. Parallel
+/// edges are never bridges.
+///
+/// The bridges are found with the same depth-first search as the
+/// [biconnected components](biconnected_components), and every bridge is a
+/// block of its own.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::articulation_points::bridges;
+/// use petgraph::graph::UnGraph;
+/// use petgraph::visit::EdgeRef;
+///
+/// // Two triangles joined by the edge 2 - 3.
+/// let graph = UnGraph::<(), ()>::from_edges(&[
+///     (0, 1), (1, 2), (2, 0),
+///     (3, 4), (4, 5), (5, 3),
+///     (2, 3),
+/// ]);
+///
+/// let bridges: Vec<_> = bridges(&graph)
+///     .iter()
+///     .map(|e| (e.source().index(), e.target().index()))
+///     .collect();
+/// assert_eq!(bridges, vec![(2, 3)]);
+/// ```
+pub fn bridges<G>(g: G) -> Vec<G::EdgeRef>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    Biconnected::new(g).bridges
 }
 
-/// Internal auxiliary helper struct for global variables.
-struct ArticulationPointTracker {
-    visited: FixedBitSet,
-    low: Vec<usize>,
-    disc: Vec<usize>,
-    parent: Vec<usize>,
-    time: usize,
-    articulation_points: HashSet<usize>,
+/// A node of a [block-cut tree](block_cut_tree).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockCutNode<N, E> {
+    /// A biconnected component, with its edges.
+    Block(Vec<E>),
+    /// An articulation point.
+    Cut(N),
 }
 
-impl ArticulationPointTracker {
-    fn new(graph_size: usize) -> Self {
-        Self {
-            visited: FixedBitSet::with_capacity(graph_size),
-            low: vec![usize::MAX; graph_size],
-            disc: vec![usize::MAX; graph_size],
-            parent: vec![usize::MAX; graph_size],
-            articulation_points: HashSet::with_capacity(graph_size),
-            time: 0,
+/// \[Generic\] Build the block-cut tree of an undirected graph.
+///
+/// The block-cut tree has a node for every
+/// [biconnected component](biconnected_components) and every
+/// [articulation point](articulation_points) of the graph, and an edge
+/// between every articulation point and every block that contains it. The
+/// result is a tree for every connected component of the graph that has at
+/// least one edge, and nodes without edges do not appear in it.
+///
+/// Blocks that are leaves of the tree are attached to the rest of the graph
+/// by a single articulation point. Every path between two blocks passes
+/// through the articulation points on their path in the tree.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::articulation_points::{block_cut_tree, BlockCutNode};
+/// use petgraph::graph::{NodeIndex, UnGraph};
+///
+/// // Two triangles sharing the node 2.
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2)]);
+///
+/// let tree = block_cut_tree(&graph);
+/// assert_eq!(tree.node_count(), 3);
+/// assert_eq!(tree.edge_count(), 2);
+/// let cut = tree
+///     .node_indices()
+///     .find(|&n| matches!(tree[n], BlockCutNode::Cut(_)))
+///     .unwrap();
+/// assert_eq!(tree[cut], BlockCutNode::Cut(NodeIndex::new(2)));
+/// assert_eq!(tree.neighbors(cut).count(), 2);
+/// ```
+pub fn block_cut_tree<G>(g: G) -> UnGraph<BlockCutNode<G::NodeId, G::EdgeRef>, ()>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    let biconnected = Biconnected::new(g);
+    let mut tree = UnGraph::with_capacity(
+        biconnected.components.len() + biconnected.articulation_points.len(),
+        0,
+    );
+    let mut cuts = vec![NodeIndex::end(); g.node_bound()];
+    for &node in &biconnected.articulation_points {
+        cuts[g.to_index(node)] = tree.add_node(BlockCutNode::Cut(node));
+    }
+    // The last block that was joined to every articulation point.
+    let mut joined = vec![NodeIndex::end(); g.node_bound()];
+    for component in biconnected.components {
+        let mut nodes = Vec::new();
+        for edge in &component {
+            nodes.push(g.to_index(edge.source()));
+            nodes.push(g.to_index(edge.target()));
+        }
+        let block = tree.add_node(BlockCutNode::Block(component));
+        for node in nodes {
+            if cuts[node] != NodeIndex::end() && joined[node] != block {
+                joined[node] = block;
+                tree.add_edge(cuts[node], block, ());
+            }
         }
     }
+    tree
 }
 
-/// Helper that performs the required DFS in an iterative manner.
-fn _dfs<G>(g: &G, target_node: usize, articulation_point_tracker: &mut ArticulationPointTracker)
+// The results of a single depth-first search for the blocks of a graph.
+struct Biconnected<G>
 where
-    G: IntoEdges + NodeIndexable,
+    G: IntoEdges,
 {
-    let mut stack: Vec<RecursionStep> = vec![RecursionStep::BaseStep(target_node)];
-    let mut children_count: HashMap<usize, usize> = HashMap::new();
+    articulation_points: Vec<G::NodeId>,
+    components: Vec<Vec<G::EdgeRef>>,
+    bridges: Vec<G::EdgeRef>,
+}
 
-    while let Some(recursion_step) = stack.pop() {
-        match recursion_step {
-            RecursionStep::BaseStep(current_node) => {
-                articulation_point_tracker.visited.insert(current_node);
-                articulation_point_tracker.disc[current_node] = articulation_point_tracker.time;
-                articulation_point_tracker.low[current_node] = articulation_point_tracker.time;
-                articulation_point_tracker.time += 1;
+// A node on the depth-first search stack.
+struct Frame<G>
+where
+    G: IntoEdges,
+{
+    node: G::NodeId,
+    // The tree edge to the node.
+    parent: Option<G::EdgeRef>,
+    edges: G::Edges,
+    children: usize,
+}
 
-                stack.push(RecursionStep::RootMoreThanTwoChildrenCheck(current_node));
-                for edge in g.edges(g.from_index(current_node)) {
-                    let child_node = g.to_index(edge.target());
-                    stack.push(RecursionStep::ProcessChildStep(current_node, child_node));
-                }
-            }
-            RecursionStep::ProcessChildStep(current_node, child_node) => {
-                if !articulation_point_tracker.visited.contains(child_node) {
-                    articulation_point_tracker.parent[child_node] = current_node;
-                    children_count
-                        .entry(current_node)
-                        .and_modify(|c| *c += 1)
-                        .or_insert(1);
+impl<G> Biconnected<G>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    fn new(g: G) -> Self {
+        let mut result = Biconnected {
+            articulation_points: Vec::new(),
+            components: Vec::new(),
+            bridges: Vec::new(),
+        };
+        let mut disc = vec![usize::MAX; g.node_bound()];
+        let mut low = vec![usize::MAX; g.node_bound()];
+        let mut time = 0;
+        let mut is_cut = FixedBitSet::with_capacity(g.node_bound());
+        // The tree and back edges of the blocks that are not complete yet.
+        let mut edge_stack: Vec<G::EdgeRef> = Vec::new();
+        let mut stack: Vec<Frame<G>> = Vec::new();
 
-                    stack.push(RecursionStep::NoBackEdgeConditionCheck(
-                        current_node,
-                        child_node,
-                    ));
-                    stack.push(RecursionStep::BaseStep(child_node));
-                } else if child_node != articulation_point_tracker.parent[current_node] {
-                    articulation_point_tracker.low[current_node] = min(
-                        articulation_point_tracker.low[current_node],
-                        articulation_point_tracker.disc[child_node],
-                    );
-                }
+        for root in g.node_identifiers() {
+            if disc[g.to_index(root)] != usize::MAX {
+                continue;
             }
-            RecursionStep::NoBackEdgeConditionCheck(current_node, child_node) => {
-                articulation_point_tracker.low[current_node] = min(
-                    articulation_point_tracker.low[current_node],
-                    articulation_point_tracker.low[child_node],
-                );
+            disc[g.to_index(root)] = time;
+            low[g.to_index(root)] = time;
+            time += 1;
+            stack.push(Frame {
+                node: root,
+                parent: None,
+                edges: g.edges(root),
+                children: 0,
+            });
 
-                if articulation_point_tracker.parent[current_node] != usize::MAX
-                    && articulation_point_tracker.low[child_node]
-                        >= articulation_point_tracker.disc[current_node]
-                {
-                    articulation_point_tracker
-                        .articulation_points
-                        .insert(current_node);
+            while let Some(frame) = stack.last_mut() {
+                let v = g.to_index(frame.node);
+                if let Some(edge) = frame.edges.next() {
+                    let other = if edge.source() == frame.node {
+                        edge.target()
+                    } else {
+                        edge.source()
+                    };
+                    let u = g.to_index(other);
+                    if u == v || frame.parent.map_or(false, |p| p.id() == edge.id()) {
+                        continue;
+                    }
+                    if disc[u] == usize::MAX {
+                        edge_stack.push(edge);
+                        frame.children += 1;
+                        disc[u] = time;
+                        low[u] = time;
+                        time += 1;
+                        stack.push(Frame {
+                            node: other,
+                            parent: Some(edge),
+                            edges: g.edges(other),
+                            children: 0,
+                        });
+                    } else if disc[u] < disc[v] {
+                        // A back edge, which is seen again from `u` later.
+                        low[v] = min(low[v], disc[u]);
+                        edge_stack.push(edge);
+                    }
+                    continue;
                 }
-            }
 
-            RecursionStep::RootMoreThanTwoChildrenCheck(current_node) => {
-                let child_count = children_count.get(&current_node).cloned().unwrap_or(0);
-                if articulation_point_tracker.parent[current_node] == usize::MAX && child_count > 1
-                {
-                    articulation_point_tracker
-                        .articulation_points
-                        .insert(current_node);
+                let done = stack.pop().unwrap();
+                let parent = match (done.parent, stack.last()) {
+                    (Some(edge), Some(parent)) => (edge, parent),
+                    _ => {
+                        if done.children > 1 {
+                            result.articulation_points.push(done.node);
+                        }
+                        continue;
+                    }
+                };
+                let (tree_edge, parent) = parent;
+                let w = g.to_index(parent.node);
+                low[w] = min(low[w], low[v]);
+                if low[v] >= disc[w] {
+                    // `w` separates the subtree of `v` from the rest.
+                    if parent.parent.is_some() && !is_cut.put(w) {
+                        result.articulation_points.push(parent.node);
+                    }
+                    let mut component = Vec::new();
+                    while let Some(edge) = edge_stack.pop() {
+                        component.push(edge);
+                        if edge.id() == tree_edge.id() {
+                            break;
+                        }
+                    }
+                    result.components.push(component);
+                    if low[v] > disc[w] {
+                        result.bridges.push(tree_edge);
+                    }
                 }
             }
         }
+        result
     }
 }
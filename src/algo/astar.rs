@@ -4,8 +4,9 @@ use std::collections::{BinaryHeap, HashMap};
 use std::hash::Hash;
 
 use crate::scored::MinScored;
-use crate::visit::{EdgeRef, GraphBase, IntoEdges, Visitable};
+use crate::visit::{EdgeRef, GraphBase, IntoEdges, NodeIndexable, Visitable};
 
+use crate::algo::dijkstra::ShortestPathWorkspace;
 use crate::algo::Measure;
 
 /// \[Generic\] A* shortest path algorithm.
@@ -139,6 +140,104 @@ where
     None
 }
 
+/// \[Generic\] A* shortest path algorithm, with a reusable workspace.
+///
+/// Computes the shortest path from `start` to a node for which `is_goal`
+/// returns `true`, like [`astar`], but keeps the costs and predecessors of the
+/// nodes in `workspace` instead of allocating maps for them. See
+/// [`ShortestPathWorkspace`].
+///
+/// The graph should implement `IntoEdges` and `NodeIndexable`. Edge costs and
+/// estimates must be non-negative, and the estimates admissible.
+///
+/// Returns the total cost and the path from `start` to the goal, if one was
+/// found.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::astar::astar_with_workspace;
+/// use petgraph::algo::dijkstra::ShortestPathWorkspace;
+/// use petgraph::prelude::*;
+///
+/// // Points on a line, with edges between neighbors.
+/// let graph = UnGraph::<i32, i32>::from_edges(&[(0, 1, 1), (1, 2, 1), (2, 3, 1), (0, 3, 5)]);
+/// let mut workspace = ShortestPathWorkspace::new(&graph);
+///
+/// for goal in 0..4 {
+///     let goal = NodeIndex::new(goal);
+///     let path = astar_with_workspace(
+///         &graph,
+///         0.into(),
+///         |n| n == goal,
+///         |e| *e.weight(),
+///         |n| (goal.index() as i32 - n.index() as i32).abs(),
+///         &mut workspace,
+///     );
+///     let (cost, path) = path.unwrap();
+///     assert_eq!(cost, goal.index() as i32);
+///     assert_eq!(path.len(), goal.index() + 1);
+/// }
+/// ```
+pub fn astar_with_workspace<G, F, H, K, IsGoal>(
+    graph: G,
+    start: G::NodeId,
+    mut is_goal: IsGoal,
+    mut edge_cost: F,
+    mut estimate_cost: H,
+    workspace: &mut ShortestPathWorkspace<K>,
+) -> Option<(K, Vec<G::NodeId>)>
+where
+    G: IntoEdges + NodeIndexable,
+    IsGoal: FnMut(G::NodeId) -> bool,
+    F: FnMut(G::EdgeRef) -> K,
+    H: FnMut(G::NodeId) -> K,
+    K: Measure + Copy,
+{
+    workspace.start(graph.node_bound());
+    let zero_score = K::default();
+    let start_index = graph.to_index(start);
+    workspace.reach(start_index, zero_score, usize::MAX);
+    workspace
+        .visit_next
+        .push(MinScored(estimate_cost(start), (start_index, zero_score)));
+
+    while let Some(MinScored(_, (node, node_score))) = workspace.visit_next.pop() {
+        // The node was queued again with a lower cost since.
+        if workspace
+            .score(node)
+            .map_or(false, |score| score < node_score)
+        {
+            continue;
+        }
+        workspace.settle(node);
+        let node_id = graph.from_index(node);
+        if is_goal(node_id) {
+            return Some((node_score, workspace.path_to_index(graph, node)));
+        }
+
+        for edge in graph.edges(node_id) {
+            let next_id = edge.target();
+            let next = graph.to_index(next_id);
+            let next_score = node_score + edge_cost(edge);
+            // No need to add neighbors that we have already reached through a
+            // shorter path than now.
+            if workspace
+                .score(next)
+                .map_or(false, |score| score <= next_score)
+            {
+                continue;
+            }
+            workspace.reach(next, next_score, node);
+            let next_estimate_score = next_score + estimate_cost(next_id);
+            workspace
+                .visit_next
+                .push(MinScored(next_estimate_score, (next, next_score)));
+        }
+    }
+
+    None
+}
+
 struct PathTracker<G>
 where
     G: GraphBase,
@@ -1,10 +1,13 @@
 //! Bellman-Ford algorithms.
 
+use std::collections::HashMap;
+use std::hash::Hash;
+
 use crate::prelude::*;
 
 use crate::visit::{IntoEdges, IntoNodeIdentifiers, NodeCount, NodeIndexable, VisitMap, Visitable};
 
-use super::{FloatMeasure, NegativeCycle};
+use super::{FloatMeasure, NegativeCycle, ShortestPathWorkspace, ShortestPaths};
 
 #[derive(Debug, Clone)]
 pub struct Paths<NodeId, EdgeWeight> {
@@ -22,6 +25,8 @@ pub struct Paths<NodeId, EdgeWeight> {
 /// out the predecessor of a node along a shortest path. The vectors
 /// are indexed by the graph's node indices.
 ///
+/// To reconstruct the paths, use [`bellman_ford_paths`] instead.
+///
 /// [bf]: https://en.wikipedia.org/wiki/Bellman%E2%80%93Ford_algorithm
 ///
 /// # Example
@@ -86,28 +91,156 @@ where
     G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable,
     G::EdgeWeight: FloatMeasure,
 {
-    let ix = |i| g.to_index(i);
-
     // Step 1 and Step 2: initialize and relax
     let (distances, predecessors) = bellman_ford_initialize_relax(g, source);
 
     // Step 3: check for negative weight cycle
-    for i in g.node_identifiers() {
-        for edge in g.edges(i) {
-            let j = edge.target();
-            let w = *edge.weight();
-            if distances[ix(i)] + w < distances[ix(j)] {
-                return Err(NegativeCycle(()));
-            }
-        }
+    if has_negative_cycle(g, &distances) {
+        return Err(NegativeCycle(()));
     }
 
     Ok(Paths {
         distances,
-        predecessors,
+        predecessors: predecessors
+            .into_iter()
+            .map(|predecessor| predecessor.map(|(node, _)| node))
+            .collect(),
     })
 }
 
+/// \[Generic\] Compute shortest paths from node `source` to all other, with
+/// the edges of the paths.
+///
+/// Like [`bellman_ford`], but returns the paths as [`ShortestPaths`], which
+/// gives the nodes and edges of the shortest path to every node reachable
+/// from `source` with [`ShortestPaths::path_to`].
+///
+/// # Complexity
+/// * Time complexity: **O(|V|·|E|)**.
+/// * Auxiliary space: **O(|V|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::bellman_ford_paths;
+/// use petgraph::prelude::*;
+///
+/// let graph = UnGraph::<(), f32>::from_edges(&[(0, 1, 2.), (1, 2, 1.), (0, 2, 4.), (2, 3, 1.)]);
+/// let paths = bellman_ford_paths(&graph, 0.into()).unwrap();
+/// assert_eq!(paths.distance(3.into()), Some(4.));
+///
+/// let (nodes, edges) = paths.path_to(3.into()).unwrap();
+/// assert_eq!(nodes, [0.into(), 1.into(), 2.into(), 3.into()]);
+/// assert_eq!(edges, [EdgeIndex::new(0), EdgeIndex::new(1), EdgeIndex::new(3)]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn bellman_ford_paths<G>(
+    g: G,
+    source: G::NodeId,
+) -> Result<ShortestPaths<G::NodeId, G::EdgeId, G::EdgeWeight>, NegativeCycle>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    G::EdgeWeight: FloatMeasure,
+{
+    let (distance, predecessor) = bellman_ford_initialize_relax(g, source);
+    if has_negative_cycle(g, &distance) {
+        return Err(NegativeCycle(()));
+    }
+
+    let mut distances = HashMap::new();
+    let mut predecessors = HashMap::new();
+    for node in g.node_identifiers() {
+        let index = g.to_index(node);
+        if distance[index] != <_>::infinite() {
+            distances.insert(node, distance[index]);
+        }
+        if let Some(previous) = predecessor[index] {
+            predecessors.insert(node, previous);
+        }
+    }
+    Ok(ShortestPaths::new(source, distances, predecessors))
+}
+
+/// \[Generic\] Compute shortest paths from node `source` to all other, with a
+/// reusable workspace.
+///
+/// Like [`bellman_ford`], but keeps the costs and paths in `workspace`
+/// instead of allocating vectors for them. See [`ShortestPathWorkspace`] for
+/// the results, which give every node reachable from `source`.
+///
+/// Returns `Err(NegativeCycle)` if a negative cycle is reachable from
+/// `source`, in which case the workspace holds no results.
+///
+/// # Complexity
+/// * Time complexity: **O(|V|·|E|)**.
+/// * Auxiliary space: **O(|V|)**, reused between searches.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::{bellman_ford_with_workspace, ShortestPathWorkspace};
+/// use petgraph::prelude::*;
+///
+/// let mut graph = DiGraph::<(), f32>::from_edges(&[(0, 1, 2.), (1, 2, -1.), (0, 2, 3.)]);
+/// let mut workspace = ShortestPathWorkspace::new(&graph);
+///
+/// bellman_ford_with_workspace(&graph, 0.into(), &mut workspace).unwrap();
+/// assert_eq!(workspace.distance(&graph, 2.into()), Some(1.));
+/// assert_eq!(workspace.path_to(&graph, 2.into()), Some(vec![0.into(), 1.into(), 2.into()]));
+///
+/// graph.add_edge(2.into(), 1.into(), -1.);
+/// assert!(bellman_ford_with_workspace(&graph, 0.into(), &mut workspace).is_err());
+/// assert_eq!(workspace.distance(&graph, 0.into()), None);
+/// ```
+pub fn bellman_ford_with_workspace<G>(
+    g: G,
+    source: G::NodeId,
+    workspace: &mut ShortestPathWorkspace<G::EdgeWeight>,
+) -> Result<(), NegativeCycle>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable,
+    G::EdgeWeight: FloatMeasure,
+{
+    let ix = |i| g.to_index(i);
+    workspace.start(g.node_bound());
+    workspace.reach(ix(source), <_>::zero(), usize::MAX);
+
+    // Nodes that were not reached yet are at an infinite distance.
+    let relaxed = |workspace: &ShortestPathWorkspace<_>, i, edge: G::EdgeRef| {
+        let distance = workspace.score(ix(i))? + *edge.weight();
+        match workspace.score(ix(edge.target())) {
+            Some(current) if current <= distance => None,
+            _ => Some(distance),
+        }
+    };
+    for _ in 1..g.node_count() {
+        let mut did_update = false;
+        for i in g.node_identifiers() {
+            for edge in g.edges(i) {
+                if let Some(distance) = relaxed(workspace, i, edge) {
+                    workspace.reach(ix(edge.target()), distance, ix(i));
+                    did_update = true;
+                }
+            }
+        }
+        if !did_update {
+            break;
+        }
+    }
+
+    let has_negative_cycle = g
+        .node_identifiers()
+        .any(|i| g.edges(i).any(|edge| relaxed(workspace, i, edge).is_some()));
+    if has_negative_cycle {
+        return Err(NegativeCycle(()));
+    }
+    for i in g.node_identifiers() {
+        if workspace.score(ix(i)).is_some() {
+            workspace.settle(ix(i));
+        }
+    }
+    Ok(())
+}
+
 /// \[Generic\] Find the path of a negative cycle reachable from node `source`.
 ///
 /// Using the [find_negative_cycle][nc]; will search the Graph for negative cycles using
@@ -165,7 +298,7 @@ where
                 // Go backward in the predecessor chain
                 loop {
                     let ancestor = match predecessor[ix(node)] {
-                        Some(predecessor_node) => predecessor_node,
+                        Some((predecessor_node, _)) => predecessor_node,
                         None => node, // no predecessor, self cycle
                     };
                     // We have only 2 ways to find the cycle and break the loop:
@@ -206,12 +339,14 @@ where
     }
 }
 
-// Perform Step 1 and Step 2 of the Bellman-Ford algorithm.
+// Perform Step 1 and Step 2 of the Bellman-Ford algorithm, recording the node
+// and edge before every node on its path.
+#[allow(clippy::type_complexity)]
 #[inline(always)]
 fn bellman_ford_initialize_relax<G>(
     g: G,
     source: G::NodeId,
-) -> (Vec<G::EdgeWeight>, Vec<Option<G::NodeId>>)
+) -> (Vec<G::EdgeWeight>, Vec<Option<(G::NodeId, G::EdgeId)>>)
 where
     G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable,
     G::EdgeWeight: FloatMeasure,
@@ -231,7 +366,7 @@ where
                 let w = *edge.weight();
                 if distance[ix(i)] + w < distance[ix(j)] {
                     distance[ix(j)] = distance[ix(i)] + w;
-                    predecessor[ix(j)] = Some(i);
+                    predecessor[ix(j)] = Some((i, edge.id()));
                     did_update = true;
                 }
             }
@@ -242,3 +377,17 @@ where
     }
     (distance, predecessor)
 }
+
+// Whether an edge can still be relaxed, which means that a negative cycle is
+// reachable from the source.
+fn has_negative_cycle<G>(g: G, distance: &[G::EdgeWeight]) -> bool
+where
+    G: IntoNodeIdentifiers + IntoEdges + NodeIndexable,
+    G::EdgeWeight: FloatMeasure,
+{
+    let ix = |i| g.to_index(i);
+    g.node_identifiers().any(|i| {
+        g.edges(i)
+            .any(|edge| distance[ix(i)] + *edge.weight() < distance[ix(edge.target())])
+    })
+}
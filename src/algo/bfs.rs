@@ -0,0 +1,258 @@
+//! Parallel breadth-first search.
+
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use fixedbitset::FixedBitSet;
+use rayon::prelude::*;
+
+use crate::visit::{GraphProp, IntoNeighbors, NodeCompactIndexable};
+
+/// The depth of the nodes that have not been reached yet.
+const UNVISITED: usize = usize::MAX;
+
+/// Switch to bottom-up steps when the edges to check from the frontier are
+/// more than the edges left to check divided by this.
+const ALPHA: usize = 15;
+
+/// Switch back to top-down steps when the frontier shrinks below the number
+/// of nodes divided by this.
+const BETA: usize = 18;
+
+/// \[Generic\] Parallel direction-optimizing breadth-first search.
+///
+/// Compute the depth of every node reachable from `start`, that is the number
+/// of edges of a shortest path from `start` to it. The search visits the
+/// graph level by level, and switches between two kinds of steps, as
+/// described by Beamer, Asanović and Patterson in *Direction-Optimizing
+/// Breadth-First Search* (2012):
+///
+/// * top-down steps visit the neighbors of the nodes of the frontier, and
+///   are faster while the frontier is small,
+/// * bottom-up steps check, for every node not visited yet, whether one of
+///   its predecessors is in the frontier, and are faster when the frontier
+///   holds a large part of the graph.
+///
+/// Both kinds of steps process the nodes in parallel, using [rayon].
+///
+/// The graph must have compact node indices, as [`Graph`](crate::graph::Graph)
+/// and [`Csr`](crate::csr::Csr) do. The predecessors of a node in a directed
+/// graph are not available through [`IntoNeighbors`], so they are collected
+/// once before the first bottom-up step.
+///
+/// Returns the depth of every node, indexed by its
+/// [`NodeIndexable::to_index`](crate::visit::NodeIndexable::to_index), or
+/// `None` if it is not reachable from `start`.
+///
+/// [rayon]: https://docs.rs/rayon
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)** for each bottom-up step, and in total
+///   for all the top-down steps.
+/// * Auxiliary space: **O(|V|)** for undirected graphs, **O(|V| + |E|)** for
+///   directed graphs.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::bfs::parallel_bfs;
+/// use petgraph::csr::Csr;
+///
+/// let graph = Csr::<(), ()>::from_sorted_edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)]).unwrap();
+///
+/// let depths = parallel_bfs(&graph, 0);
+/// assert_eq!(depths, [Some(0), Some(1), Some(1), Some(2), Some(3)]);
+/// assert_eq!(parallel_bfs(&graph, 3), [None, None, None, Some(0), Some(1)]);
+/// ```
+pub fn parallel_bfs<G>(graph: G, start: G::NodeId) -> Vec<Option<usize>>
+where
+    G: IntoNeighbors + NodeCompactIndexable + GraphProp + Sync,
+{
+    let start = graph.to_index(start);
+    // Run the search on a thread of the pool: it starts a few short parallel
+    // operations at every level, which are much slower to start from outside
+    // of the pool.
+    let graph = &graph;
+    rayon::scope(move |_| bfs(*graph, start))
+}
+
+fn bfs<G>(graph: G, start: usize) -> Vec<Option<usize>>
+where
+    G: IntoNeighbors + NodeCompactIndexable + GraphProp + Sync,
+{
+    let node_count = graph.node_bound();
+    let depths: Vec<_> = (0..node_count)
+        .into_par_iter()
+        .map(|_| AtomicUsize::new(UNVISITED))
+        .collect();
+    let degrees: Vec<usize> = (0..node_count)
+        .into_par_iter()
+        .map(|i| graph.neighbors(graph.from_index(i)).count())
+        .collect();
+    let mut predecessors = None;
+
+    depths[start].store(0, Relaxed);
+    let mut frontier = vec![start];
+    let mut edges_to_check: usize = degrees.par_iter().sum::<usize>() - degrees[start];
+    let mut scout_count = degrees[start];
+    let mut level = 0;
+    while !frontier.is_empty() {
+        if scout_count > edges_to_check / ALPHA {
+            let predecessors =
+                predecessors.get_or_insert_with(|| Predecessors::new(graph, node_count));
+            // Bottom-up steps, while the frontier grows or stays large.
+            let mut previous_len;
+            loop {
+                previous_len = frontier.len();
+                frontier = bottom_up_step(graph, predecessors, &depths, &frontier, level);
+                level += 1;
+                let visited_edges: usize = frontier.par_iter().map(|&v| degrees[v]).sum();
+                edges_to_check = edges_to_check.saturating_sub(visited_edges);
+                if frontier.is_empty()
+                    || (frontier.len() < previous_len && frontier.len() <= node_count / BETA)
+                {
+                    break;
+                }
+            }
+            // Force a top-down step, which recomputes the scout count.
+            scout_count = 1;
+        } else {
+            frontier = top_down_step(graph, &depths, &frontier, level);
+            level += 1;
+            scout_count = frontier.par_iter().map(|&v| degrees[v]).sum();
+            edges_to_check = edges_to_check.saturating_sub(scout_count);
+        }
+    }
+
+    depths
+        .into_iter()
+        .map(|depth| match depth.into_inner() {
+            UNVISITED => None,
+            depth => Some(depth),
+        })
+        .collect()
+}
+
+/// Visit the neighbors of the frontier, and return the nodes reached for the
+/// first time.
+fn top_down_step<G>(
+    graph: G,
+    depths: &[AtomicUsize],
+    frontier: &[usize],
+    level: usize,
+) -> Vec<usize>
+where
+    G: IntoNeighbors + NodeCompactIndexable + Sync,
+{
+    frontier
+        .par_iter()
+        .flat_map_iter(|&u| {
+            graph.neighbors(graph.from_index(u)).filter_map(move |v| {
+                let v = graph.to_index(v);
+                // Only the first search to reach a node claims it.
+                depths[v]
+                    .compare_exchange(UNVISITED, level + 1, Relaxed, Relaxed)
+                    .ok()
+                    .map(|_| v)
+            })
+        })
+        .collect()
+}
+
+/// Visit the nodes not visited yet that have a predecessor in the frontier,
+/// and return them.
+fn bottom_up_step<G>(
+    graph: G,
+    predecessors: &Predecessors,
+    depths: &[AtomicUsize],
+    frontier: &[usize],
+    level: usize,
+) -> Vec<usize>
+where
+    G: IntoNeighbors + NodeCompactIndexable + Sync,
+{
+    let mut in_frontier = FixedBitSet::with_capacity(depths.len());
+    for &u in frontier {
+        in_frontier.insert(u);
+    }
+    (0..depths.len())
+        .into_par_iter()
+        .filter(|&v| {
+            // Every node is checked by a single task, so no other task
+            // writes its depth.
+            if depths[v].load(Relaxed) != UNVISITED {
+                return false;
+            }
+            let found = match predecessors {
+                Predecessors::Neighbors => graph
+                    .neighbors(graph.from_index(v))
+                    .any(|u| in_frontier.contains(graph.to_index(u))),
+                Predecessors::Incoming { offsets, sources } => sources[offsets[v]..offsets[v + 1]]
+                    .iter()
+                    .any(|&u| in_frontier.contains(u)),
+            };
+            if found {
+                depths[v].store(level + 1, Relaxed);
+            }
+            found
+        })
+        .collect()
+}
+
+/// The predecessors of every node, for bottom-up steps.
+enum Predecessors {
+    /// The neighbors of a node of an undirected graph are its predecessors.
+    Neighbors,
+    /// The sources of the incoming edges of every node of a directed graph,
+    /// those of the node with index `i` being
+    /// `sources[offsets[i]..offsets[i + 1]]`.
+    Incoming {
+        offsets: Vec<usize>,
+        sources: Vec<usize>,
+    },
+}
+
+impl Predecessors {
+    fn new<G>(graph: G, node_count: usize) -> Self
+    where
+        G: IntoNeighbors + NodeCompactIndexable + GraphProp + Sync,
+    {
+        if !graph.is_directed() {
+            return Predecessors::Neighbors;
+        }
+        let in_degrees: Vec<_> = (0..node_count)
+            .into_par_iter()
+            .map(|_| AtomicUsize::new(0))
+            .collect();
+        (0..node_count).into_par_iter().for_each(|u| {
+            for v in graph.neighbors(graph.from_index(u)) {
+                in_degrees[graph.to_index(v)].fetch_add(1, Relaxed);
+            }
+        });
+
+        let mut offsets = Vec::with_capacity(node_count + 1);
+        offsets.push(0);
+        for in_degree in in_degrees {
+            offsets.push(offsets[offsets.len() - 1] + in_degree.into_inner());
+        }
+
+        // Every edge takes the next free slot among those of its target.
+        let cursors: Vec<_> = offsets[..node_count]
+            .par_iter()
+            .map(|&offset| AtomicUsize::new(offset))
+            .collect();
+        let sources: Vec<_> = (0..offsets[node_count])
+            .into_par_iter()
+            .map(|_| AtomicUsize::new(0))
+            .collect();
+        (0..node_count).into_par_iter().for_each(|u| {
+            for v in graph.neighbors(graph.from_index(u)) {
+                let slot = cursors[graph.to_index(v)].fetch_add(1, Relaxed);
+                sources[slot].store(u, Relaxed);
+            }
+        });
+
+        Predecessors::Incoming {
+            offsets,
+            sources: sources.into_iter().map(AtomicUsize::into_inner).collect(),
+        }
+    }
+}
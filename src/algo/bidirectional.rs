@@ -0,0 +1,280 @@
+//! Bidirectional shortest path searches, from the start and from the goal at
+//! once.
+
+use std::collections::hash_map::Entry::{Occupied, Vacant};
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+use crate::algo::Measure;
+use crate::scored::MinScored;
+use crate::visit::{EdgeRef, IntoEdgesDirected, VisitMap, Visitable};
+use crate::{Direction, Incoming, Outgoing};
+
+/// \[Generic\] Bidirectional Dijkstra's shortest path algorithm.
+///
+/// Computes the shortest path from `start` to `goal`, including the total
+/// path cost, with two searches: one forward from `start`, and one backward
+/// from `goal` along incoming edges. The searches take turns with the node of
+/// lowest cost, and stop once the shortest path that connects them cannot get
+/// any shorter. On graphs like road networks, they settle about half the
+/// nodes a single search would.
+///
+/// The function `edge_cost` should return the cost for a particular edge.
+/// Edge costs must be non-negative.
+///
+/// The graph should be `Visitable` and implement `IntoEdgesDirected`.
+///
+/// Returns the total cost and the path of subsequent `NodeId` from `start` to
+/// `goal`, if `goal` is reachable.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::bidirectional_dijkstra;
+/// use petgraph::prelude::*;
+///
+/// let mut g = DiGraph::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// let c = g.add_node(());
+/// let d = g.add_node(());
+/// g.extend_with_edges(&[(a, b, 2), (b, c, 2), (a, c, 5), (c, d, 1), (d, a, 1)]);
+///
+/// assert_eq!(bidirectional_dijkstra(&g, a, d, |e| *e.weight()), Some((5, vec![a, b, c, d])));
+/// assert_eq!(bidirectional_dijkstra(&g, d, c, |e| *e.weight()), Some((5, vec![d, a, b, c])));
+/// ```
+pub fn bidirectional_dijkstra<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: G::NodeId,
+    edge_cost: F,
+) -> Option<(K, Vec<G::NodeId>)>
+where
+    G: IntoEdgesDirected + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let zero = |_| K::default();
+    // Every path through a node in neither search is at least as long as the
+    // sum of the lowest costs in the two queues.
+    bidirectional_search(graph, start, goal, edge_cost, zero, zero, |f, b, best| {
+        f + b >= best
+    })
+}
+
+/// \[Generic\] Bidirectional A* shortest path algorithm.
+///
+/// Computes the shortest path from `start` to `goal`, including the total
+/// path cost, with two A* searches: one forward from `start` guided by
+/// `estimate_to_goal`, and one backward from `goal` along incoming edges
+/// guided by `estimate_from_start`. The searches take turns with the node of
+/// lowest estimate, and stop once the estimates show that no path shorter than
+/// the best connection of the two can be found.
+///
+/// The function `edge_cost` should return the cost for a particular edge.
+/// Edge costs must be non-negative.
+///
+/// The estimates need to be *consistent*: `estimate_to_goal` must be 0 at
+/// `goal`, and for every edge from `u` to `v` with cost `c`,
+/// `estimate_to_goal(u) <= c + estimate_to_goal(v)` and
+/// `estimate_from_start(v) <= estimate_from_start(u) + c`. Distances to the
+/// goal and from the start as the crow flies are consistent, for example.
+///
+/// The graph should be `Visitable` and implement `IntoEdgesDirected`.
+///
+/// Returns the total cost and the path of subsequent `NodeId` from `start` to
+/// `goal`, if `goal` is reachable.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::bidirectional_astar;
+/// use petgraph::prelude::*;
+///
+/// // A 10 x 10 grid with unit edges.
+/// let mut g = UnGraph::<(i32, i32), i32>::default();
+/// let nodes: Vec<_> = (0..100).map(|i| g.add_node((i % 10, i / 10))).collect();
+/// for i in 0..100 {
+///     if i % 10 < 9 {
+///         g.add_edge(nodes[i], nodes[i + 1], 1);
+///     }
+///     if i / 10 < 9 {
+///         g.add_edge(nodes[i], nodes[i + 10], 1);
+///     }
+/// }
+/// let distance = |a: (i32, i32), b: (i32, i32)| (a.0 - b.0).abs() + (a.1 - b.1).abs();
+///
+/// let (start, goal) = (nodes[12], nodes[87]);
+/// let (cost, path) = bidirectional_astar(
+///     &g,
+///     start,
+///     goal,
+///     |e| *e.weight(),
+///     |n| distance(g[n], g[goal]),
+///     |n| distance(g[start], g[n]),
+/// )
+/// .unwrap();
+/// assert_eq!(cost, 12);
+/// assert_eq!(path.len(), 13);
+/// ```
+pub fn bidirectional_astar<G, F, H, R, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: G::NodeId,
+    edge_cost: F,
+    estimate_to_goal: H,
+    estimate_from_start: R,
+) -> Option<(K, Vec<G::NodeId>)>
+where
+    G: IntoEdgesDirected + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    H: FnMut(G::NodeId) -> K,
+    R: FnMut(G::NodeId) -> K,
+    K: Measure + Copy,
+{
+    // Every path through a node that neither search has settled is at least
+    // as long as the lowest estimate in either queue.
+    bidirectional_search(
+        graph,
+        start,
+        goal,
+        edge_cost,
+        estimate_to_goal,
+        estimate_from_start,
+        |f, b, best| f >= best || b >= best,
+    )
+}
+
+/// One of the two searches.
+struct Side<N, K, VM> {
+    direction: Direction,
+    visited: VM,
+    scores: HashMap<N, K>,
+    /// The node before every node on its path from the origin of the search.
+    predecessors: HashMap<N, N>,
+    visit_next: BinaryHeap<MinScored<K, N>>,
+}
+
+impl<N, K, VM> Side<N, K, VM>
+where
+    N: Copy + Eq + Hash,
+    K: Measure + Copy,
+    VM: VisitMap<N>,
+{
+    fn new(direction: Direction, visited: VM, origin: N, estimate: K) -> Self {
+        let mut scores = HashMap::new();
+        scores.insert(origin, K::default());
+        let mut visit_next = BinaryHeap::new();
+        visit_next.push(MinScored(estimate, origin));
+        Side {
+            direction,
+            visited,
+            scores,
+            predecessors: HashMap::new(),
+            visit_next,
+        }
+    }
+
+    /// The lowest estimate of a node that is not settled yet.
+    fn peek(&mut self) -> Option<K> {
+        while let Some(&MinScored(estimate, node)) = self.visit_next.peek() {
+            if !self.visited.is_visited(&node) {
+                return Some(estimate);
+            }
+            self.visit_next.pop();
+        }
+        None
+    }
+
+    /// The nodes from `node` back to the origin.
+    fn path_from(&self, mut node: N) -> Vec<N> {
+        let mut path = vec![node];
+        while let Some(&previous) = self.predecessors.get(&node) {
+            path.push(previous);
+            node = previous;
+        }
+        path
+    }
+}
+
+fn bidirectional_search<G, F, H, R, K, D>(
+    graph: G,
+    start: G::NodeId,
+    goal: G::NodeId,
+    mut edge_cost: F,
+    mut estimate_to_goal: H,
+    mut estimate_from_start: R,
+    mut done: D,
+) -> Option<(K, Vec<G::NodeId>)>
+where
+    G: IntoEdgesDirected + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    H: FnMut(G::NodeId) -> K,
+    R: FnMut(G::NodeId) -> K,
+    K: Measure + Copy,
+    D: FnMut(K, K, K) -> bool,
+{
+    if start == goal {
+        return Some((K::default(), vec![start]));
+    }
+    let mut forward = Side::new(Outgoing, graph.visit_map(), start, estimate_to_goal(start));
+    let mut backward = Side::new(Incoming, graph.visit_map(), goal, estimate_from_start(goal));
+    // The cost of the shortest connection so far, and the node where the
+    // forward and the backward paths meet.
+    let mut best: Option<(K, G::NodeId)> = None;
+
+    while let (Some(f), Some(b)) = (forward.peek(), backward.peek()) {
+        if let Some((cost, _)) = best {
+            if done(f, b, cost) {
+                break;
+            }
+        }
+        let (side, other, estimate): (_, _, &mut dyn FnMut(G::NodeId) -> K) = if f <= b {
+            (&mut forward, &backward, &mut estimate_to_goal)
+        } else {
+            (&mut backward, &forward, &mut estimate_from_start)
+        };
+
+        let MinScored(_, node) = side.visit_next.pop().unwrap();
+        side.visited.visit(node);
+        let node_score = side.scores[&node];
+        for edge in graph.edges_directed(node, side.direction) {
+            let next = match side.direction {
+                Outgoing => edge.target(),
+                Incoming => edge.source(),
+            };
+            if side.visited.is_visited(&next) {
+                continue;
+            }
+            let next_score = node_score + edge_cost(edge);
+            match side.scores.entry(next) {
+                Occupied(entry) if *entry.get() <= next_score => continue,
+                Occupied(mut entry) => {
+                    entry.insert(next_score);
+                }
+                Vacant(entry) => {
+                    entry.insert(next_score);
+                }
+            }
+            side.predecessors.insert(next, node);
+            // Connections only get shorter when either side improves the score
+            // of a node the other side has reached.
+            if let Some(&other_score) = other.scores.get(&next) {
+                let cost = next_score + other_score;
+                if best.map_or(true, |(best, _)| cost < best) {
+                    best = Some((cost, next));
+                }
+            }
+            side.visit_next
+                .push(MinScored(next_score + estimate(next), next));
+        }
+    }
+
+    best.map(|(cost, meet)| {
+        let mut path = forward.path_from(meet);
+        path.reverse();
+        path.extend(backward.path_from(meet).into_iter().skip(1));
+        (cost, path)
+    })
+}
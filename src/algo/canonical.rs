@@ -0,0 +1,880 @@
+//! Canonical labelling and hashing of graphs, to sort graphs into
+//! isomorphism classes without comparing them pairwise.
+//!
+//! A canonical labelling numbers the nodes of a graph such that two graphs
+//! are isomorphic if and only if they are equal once their nodes are
+//! renumbered by their canonical labellings. It is found by partition
+//! refinement, in the spirit of nauty and bliss: the nodes are split into
+//! cells by their degrees into the other cells, and the search individualizes
+//! the nodes of a cell in turn until every cell holds a single node. Symmetries
+//! that are found on the way prune the search.
+//!
+//! The same search finds the [automorphism group](automorphism_group) of a
+//! graph, the symmetries that map the graph to itself.
+//!
+//! The [Weisfeiler–Lehman hash](weisfeiler_lehman_hash) is much cheaper. It
+//! is equal for isomorphic graphs, but may also be equal for some graphs that
+//! are not isomorphic, so it is suited to sort graphs into buckets before
+//! comparing them.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+
+use crate::data::DataMap;
+use crate::graph6::{encode_digraph6, encode_graph6};
+use crate::unionfind::UnionFind;
+use crate::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeCompactIndexable,
+    NodeIndexable,
+};
+
+/// \[Generic\] Find a canonical labelling of the nodes of a graph.
+///
+/// Returns the nodes of the graph in canonical order: two graphs are
+/// isomorphic if and only if they have the same edges once their nodes are
+/// renumbered by their position in the result. Directed graphs are labelled
+/// as directed graphs. Parallel edges count as a single edge, and loops are
+/// taken into account.
+///
+/// The labelling depends only on the structure of the graph, and not on the
+/// order of its nodes or edges. It may change between versions of petgraph.
+///
+/// See [`canonical_labeling_by_key`] to take node labels into account, and
+/// [`canonical_graph6`] for a canonical string.
+///
+/// # Complexity
+/// Exponential in the worst case, but fast for the graphs met in practice,
+/// like molecules and circuits, and for graphs with many symmetries.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::canonical::canonical_labeling;
+/// use petgraph::graph::{NodeIndex, UnGraph};
+///
+/// // A path 0 - 1 - 2, and the same path numbered differently.
+/// let a = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+/// let b = UnGraph::<(), ()>::from_edges([(1, 0), (0, 2)]);
+///
+/// let order_a = canonical_labeling(&a);
+/// let order_b = canonical_labeling(&b);
+/// // The middle of the path gets the same label in both graphs.
+/// let middle_a = order_a.iter().position(|&n| n == NodeIndex::new(1));
+/// let middle_b = order_b.iter().position(|&n| n == NodeIndex::new(0));
+/// assert_eq!(middle_a, middle_b);
+/// ```
+pub fn canonical_labeling<G>(graph: G) -> Vec<G::NodeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    canonical_labeling_by_key(graph, |_| ())
+}
+
+/// \[Generic\] Find a canonical labelling of the nodes of a graph with
+/// labelled nodes.
+///
+/// Like [`canonical_labeling`], but the isomorphisms need to map every node
+/// to a node with the same key, as given by `node_key`. Nodes with smaller
+/// keys come first in the result.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::canonical::canonical_labeling_by_key;
+/// use petgraph::graph::UnGraph;
+///
+/// let mut graph = UnGraph::<&str, ()>::new_undirected();
+/// let o = graph.add_node("O");
+/// let c = graph.add_node("C");
+/// let h = graph.add_node("H");
+/// graph.extend_with_edges([(c, o), (c, h)]);
+///
+/// let order = canonical_labeling_by_key(&graph, |n| graph[n]);
+/// assert_eq!(order, vec![c, h, o]);
+/// ```
+pub fn canonical_labeling_by_key<G, F, K>(graph: G, mut node_key: F) -> Vec<G::NodeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::NodeId) -> K,
+    K: Ord,
+{
+    let nodes: Vec<_> = graph.node_identifiers().collect();
+    let keys: Vec<_> = nodes.iter().map(|&n| node_key(n)).collect();
+    let cells = key_cells(&keys);
+    let canonizer = Canonizer::new(graph, &nodes, |_| 0);
+    let search = canonizer.search(cells);
+    search.best.iter().map(|&v| nodes[v]).collect()
+}
+
+/// \[Generic\] Encode a graph in canonical form, as a graph6 string for an
+/// undirected graph or a digraph6 string for a directed graph.
+///
+/// Two graphs have the same canonical string if and only if they are
+/// isomorphic, so the strings can be used as keys to deduplicate graphs. The
+/// graph is encoded with its nodes in the order of [`canonical_labeling`].
+///
+/// Graph6 has no room for loops: an undirected graph with loops gets the same
+/// string as the graph without them.
+///
+/// **Panics** if the graph has 2<sup>36</sup> nodes or more.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::canonical::canonical_graph6;
+/// use petgraph::graph::UnGraph;
+///
+/// let a = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3)]);
+/// let b = UnGraph::<(), ()>::from_edges([(2, 0), (0, 3), (3, 1)]);
+/// let star = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3)]);
+///
+/// assert_eq!(canonical_graph6(&a), canonical_graph6(&b));
+/// assert_ne!(canonical_graph6(&a), canonical_graph6(&star));
+/// ```
+pub fn canonical_graph6<G>(graph: G) -> String
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    let order = canonical_labeling(graph);
+    let mut label = vec![usize::MAX; graph.node_bound()];
+    for (i, &node) in order.iter().enumerate() {
+        label[graph.to_index(node)] = i;
+    }
+    let edges = graph.edge_references().map(|edge| {
+        (
+            label[graph.to_index(edge.source())],
+            label[graph.to_index(edge.target())],
+        )
+    });
+    if graph.is_directed() {
+        encode_digraph6(order.len(), edges)
+    } else {
+        encode_graph6(order.len(), edges)
+    }
+}
+
+/// \[Generic\] Compute the Weisfeiler–Lehman hash of a graph.
+///
+/// Every node starts with the hash of its label, as given by `node_label`.
+/// In each of the `iterations`, the label of every node is replaced by the
+/// hash of its label and the multiset of the labels of its neighbors, with
+/// the labels of the edges to them, as given by `edge_label`. For directed
+/// graphs successors and predecessors are told apart. The hash of the graph
+/// combines the multisets of node labels of all iterations.
+///
+/// Isomorphic graphs with the same labels get the same hash. Graphs that are
+/// not isomorphic usually get different hashes, but not always: regular
+/// graphs with the same number of nodes and degree, for example, cannot be
+/// told apart. Three iterations are a common choice.
+///
+/// The hash is computed with the [`DefaultHasher`] of the standard library.
+/// It is the same on every run of a program, but may change between versions
+/// of Rust and petgraph.
+///
+/// # Complexity
+/// * Time complexity: **O(k (|V| + |E| log |E|))**, where **k** is the number
+///   of iterations.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::canonical::weisfeiler_lehman_hash;
+/// use petgraph::graph::UnGraph;
+///
+/// let mut a = UnGraph::<&str, u8>::new_undirected();
+/// let c = a.add_node("C");
+/// let o = a.add_node("O");
+/// let n = a.add_node("N");
+/// a.extend_with_edges([(c, o, 2), (c, n, 1)]);
+///
+/// // The same molecule, with a single instead of a double bond.
+/// let mut b = a.clone();
+/// let bond = b.find_edge(c, o).unwrap();
+/// b[bond] = 1;
+///
+/// let hash = |g: &UnGraph<&str, u8>| weisfeiler_lehman_hash(g, |n| g[n], |e| *e.weight(), 3);
+/// assert_eq!(hash(&a), hash(&a.clone()));
+/// assert_ne!(hash(&a), hash(&b));
+/// ```
+pub fn weisfeiler_lehman_hash<G, F, H, L, M>(
+    graph: G,
+    mut node_label: F,
+    mut edge_label: H,
+    iterations: usize,
+) -> u64
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::NodeId) -> L,
+    H: FnMut(G::EdgeRef) -> M,
+    L: Hash,
+    M: Hash,
+{
+    fn hash_one<T: Hash>(value: T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let nodes: Vec<usize> = graph
+        .node_identifiers()
+        .map(|n| graph.to_index(n))
+        .collect();
+    let mut labels = vec![0; graph.node_bound()];
+    for node in graph.node_identifiers() {
+        labels[graph.to_index(node)] = hash_one(node_label(node));
+    }
+    let edges: Vec<_> = graph
+        .edge_references()
+        .map(|edge| {
+            (
+                graph.to_index(edge.source()),
+                graph.to_index(edge.target()),
+                hash_one(edge_label(edge)),
+            )
+        })
+        .collect();
+    // Predecessors are told apart from successors in directed graphs.
+    let incoming = u8::from(graph.is_directed());
+
+    let mut hasher = DefaultHasher::new();
+    let mut sorted: Vec<u64> = nodes.iter().map(|&v| labels[v]).collect();
+    sorted.sort_unstable();
+    sorted.hash(&mut hasher);
+    let mut neighborhoods = vec![Vec::new(); graph.node_bound()];
+    let mut next = labels.clone();
+    for _ in 0..iterations {
+        for &(a, b, label) in &edges {
+            neighborhoods[a].push((0, label, labels[b]));
+            neighborhoods[b].push((incoming, label, labels[a]));
+        }
+        for &v in &nodes {
+            let neighborhood: &mut Vec<(u8, u64, u64)> = &mut neighborhoods[v];
+            neighborhood.sort_unstable();
+            next[v] = hash_one((labels[v], &*neighborhood));
+            neighborhood.clear();
+        }
+        std::mem::swap(&mut labels, &mut next);
+        sorted.clear();
+        sorted.extend(nodes.iter().map(|&v| labels[v]));
+        sorted.sort_unstable();
+        sorted.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// The automorphism group of a graph, as found by [`automorphism_group`].
+///
+/// An automorphism is an isomorphism from a graph to itself, a permutation of
+/// its nodes that maps edges to edges and non-edges to non-edges.
+/// Permutations are given as vectors that map every node index to the index
+/// of its image, like the mappings of
+/// [`subgraph_isomorphisms_iter`](super::subgraph_isomorphisms_iter).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AutomorphismGroup {
+    generators: Vec<Vec<usize>>,
+    orbits: Vec<usize>,
+    order: Option<u128>,
+}
+
+impl AutomorphismGroup {
+    /// A set of automorphisms that generates the group. The identity is left
+    /// out, so the set is empty if the group is trivial.
+    pub fn generators(&self) -> &[Vec<usize>] {
+        &self.generators
+    }
+
+    /// The orbits of the group: two nodes are in the same orbit if an
+    /// automorphism maps one to the other. Maps every node index to the
+    /// smallest node index of its orbit.
+    pub fn orbits(&self) -> &[usize] {
+        &self.orbits
+    }
+
+    /// The number of automorphisms, or `None` if it does not fit in a
+    /// `u128`, like the order of the symmetric group of more than 34 nodes.
+    pub fn order(&self) -> Option<u128> {
+        self.order
+    }
+}
+
+/// \[Generic\] Find the automorphism group of a graph.
+///
+/// Returns generators, orbits and order of the group. The automorphisms are
+/// found by the same search as the [`canonical_labeling`], where every pair of
+/// leaves of the search tree with the same graph gives an automorphism. The
+/// generators form a strong generating set for the nodes individualized on
+/// the way to the first leaf, which gives the order of the group.
+///
+/// Parallel edges count as a single edge, and loops are taken into account.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::canonical::automorphism_group;
+/// use petgraph::graph::UnGraph;
+///
+/// // A square, with the symmetries of the dihedral group of order 8.
+/// let square = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 0)]);
+/// let group = automorphism_group(&square);
+/// assert_eq!(group.order(), Some(8));
+/// assert_eq!(group.orbits(), [0, 0, 0, 0]);
+///
+/// // A path 0 - 1 - 2 can only be reversed.
+/// let path = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+/// let group = automorphism_group(&path);
+/// assert_eq!(group.generators(), [vec![2, 1, 0]]);
+/// assert_eq!(group.orbits(), [0, 1, 0]);
+/// ```
+pub fn automorphism_group<G>(graph: G) -> AutomorphismGroup
+where
+    G: IntoEdgeReferences + NodeCompactIndexable + GraphProp,
+{
+    let nodes: Vec<_> = (0..graph.node_count())
+        .map(|i| graph.from_index(i))
+        .collect();
+    let canonizer = Canonizer::new(graph, &nodes, |_| 0);
+    let cells = if nodes.is_empty() {
+        Vec::new()
+    } else {
+        vec![(0..nodes.len()).collect()]
+    };
+    group_from_search(canonizer.search(cells))
+}
+
+/// \[Generic\] Find the automorphism group of a graph with node and edge
+/// weights.
+///
+/// Like [`automorphism_group`], but an automorphism needs to map every node
+/// to a node with an equivalent weight, as given by `node_match`, and every
+/// edge to an edge with an equivalent weight, as given by `edge_match`, like
+/// the isomorphisms of
+/// [`is_isomorphic_matching`](super::is_isomorphic_matching). Both need to be
+/// equivalence relations. Of parallel edges, only the weight of the first
+/// counts.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::canonical::automorphism_group_matching;
+/// use petgraph::graph::UnGraph;
+///
+/// // A square with one diagonal pair of nodes colored.
+/// let mut square = UnGraph::<bool, ()>::new_undirected();
+/// let nodes: Vec<_> = [true, false, true, false]
+///     .iter()
+///     .map(|&c| square.add_node(c))
+///     .collect();
+/// for i in 0..4 {
+///     square.add_edge(nodes[i], nodes[(i + 1) % 4], ());
+/// }
+///
+/// let group = automorphism_group_matching(&square, |a, b| a == b, |_, _| true);
+/// assert_eq!(group.order(), Some(4));
+/// assert_eq!(group.orbits(), [0, 1, 0, 1]);
+/// ```
+pub fn automorphism_group_matching<G, NM, EM>(
+    graph: G,
+    mut node_match: NM,
+    mut edge_match: EM,
+) -> AutomorphismGroup
+where
+    G: IntoEdgeReferences + NodeCompactIndexable + GraphProp + DataMap,
+    NM: FnMut(&G::NodeWeight, &G::NodeWeight) -> bool,
+    EM: FnMut(&G::EdgeWeight, &G::EdgeWeight) -> bool,
+{
+    let nodes: Vec<_> = (0..graph.node_count())
+        .map(|i| graph.from_index(i))
+        .collect();
+    // Split the nodes and edges into classes of equivalent weights, by
+    // comparing them with a representative of every class.
+    let mut cells: Vec<Vec<usize>> = Vec::new();
+    for (i, &node) in nodes.iter().enumerate() {
+        let weight = graph.node_weight(node).unwrap();
+        let class = cells.iter().position(|cell| {
+            let representative = graph.node_weight(nodes[cell[0]]).unwrap();
+            node_match(representative, weight)
+        });
+        match class {
+            Some(class) => cells[class].push(i),
+            None => cells.push(vec![i]),
+        }
+    }
+    let mut representatives: Vec<G::EdgeRef> = Vec::new();
+    let canonizer = Canonizer::new(graph, &nodes, |edge| {
+        match representatives
+            .iter()
+            .position(|r| edge_match(r.weight(), edge.weight()))
+        {
+            Some(class) => class,
+            None => {
+                representatives.push(edge);
+                representatives.len() - 1
+            }
+        }
+    });
+    group_from_search(canonizer.search(cells))
+}
+
+fn group_from_search(search: Search) -> AutomorphismGroup {
+    let n = search.best.len();
+    let mut generators = search.generators;
+    generators.sort_unstable();
+    generators.dedup();
+    let orbits_of = |fixed: &[usize]| {
+        let mut orbits = UnionFind::new(n);
+        for generator in &generators {
+            if fixed.iter().all(|&v| generator[v] == v) {
+                for (v, &w) in generator.iter().enumerate() {
+                    orbits.union(v, w);
+                }
+            }
+        }
+        orbits
+    };
+    // The order is the product of the sizes of the orbits of the base nodes
+    // in the stabilizers of the nodes before them.
+    let mut order = Some(1u128);
+    for (i, &b) in search.base.iter().enumerate() {
+        let orbits = orbits_of(&search.base[..i]);
+        let size = (0..n).filter(|&v| orbits.equiv(v, b)).count();
+        order = order.and_then(|order| order.checked_mul(size as u128));
+    }
+    let orbits = orbits_of(&[]);
+    // Label every orbit with its smallest node.
+    let mut smallest = vec![usize::MAX; n];
+    let orbits = (0..n)
+        .map(|v| {
+            let root = orbits.find(v);
+            if smallest[root] == usize::MAX {
+                smallest[root] = v;
+            }
+            smallest[root]
+        })
+        .collect();
+    AutomorphismGroup {
+        generators,
+        orbits,
+        order,
+    }
+}
+
+// Sort the nodes `0..keys.len()` by key, and split them into the cells of
+// equal keys, in order.
+pub(crate) fn key_cells<K: Ord>(keys: &[K]) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    order.sort_by(|&a, &b| keys[a].cmp(&keys[b]));
+    let mut cells: Vec<Vec<usize>> = Vec::new();
+    for (i, &v) in order.iter().enumerate() {
+        if i == 0 || keys[order[i - 1]] != keys[v] {
+            cells.push(Vec::new());
+        }
+        cells.last_mut().unwrap().push(v);
+    }
+    cells
+}
+
+/// An ordered partition of the nodes into cells.
+#[derive(Clone, Debug)]
+pub(crate) struct Partition {
+    /// The nodes, cell after cell.
+    elements: Vec<usize>,
+    /// The position of every node in `elements`.
+    position: Vec<usize>,
+    /// The start of the cell of every node.
+    cell: Vec<usize>,
+    /// The end of every cell, by its start.
+    end: Vec<usize>,
+}
+
+impl Partition {
+    fn new(n: usize, cells: &[Vec<usize>]) -> Self {
+        let mut partition = Partition {
+            elements: Vec::with_capacity(n),
+            position: vec![0; n],
+            cell: vec![0; n],
+            end: vec![0; n],
+        };
+        for members in cells {
+            let start = partition.elements.len();
+            for &v in members {
+                partition.position[v] = partition.elements.len();
+                partition.cell[v] = start;
+                partition.elements.push(v);
+            }
+            partition.end[start] = partition.elements.len();
+        }
+        partition
+    }
+
+    fn starts(&self) -> impl Iterator<Item = usize> + '_ {
+        let mut start = 0;
+        std::iter::from_fn(move || {
+            if start < self.elements.len() {
+                let cell = start;
+                start = self.end[start];
+                Some(cell)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// The first cell with more than one node.
+    fn target_cell(&self) -> Option<&[usize]> {
+        self.starts()
+            .find(|&start| self.end[start] - start > 1)
+            .map(|start| &self.elements[start..self.end[start]])
+    }
+
+    /// Split `v` from its cell, in front of it, and refine.
+    fn individualize(&mut self, v: usize, adjacency: &Adjacency) {
+        let start = self.cell[v];
+        let end = self.end[start];
+        let other = self.elements[start];
+        let pos = self.position[v];
+        self.elements.swap(start, pos);
+        self.position[other] = pos;
+        self.position[v] = start;
+        self.end[start] = start + 1;
+        self.end[start + 1] = end;
+        for &u in &self.elements[start + 1..end] {
+            self.cell[u] = start + 1;
+        }
+        self.refine(adjacency, vec![start]);
+    }
+
+    /// Refine the partition until it is equitable: until the nodes of every
+    /// cell have the same number of successors and predecessors in every
+    /// cell. The cells in `splitters` are those that may split other cells.
+    fn refine(&mut self, adjacency: &Adjacency, splitters: Vec<usize>) {
+        let n = self.elements.len();
+        let mut queued = vec![false; n];
+        for &start in &splitters {
+            queued[start] = true;
+        }
+        let mut queue = VecDeque::from(splitters);
+        let mut counts = vec![(0, 0); n];
+        let mut touched = Vec::new();
+        let mut touched_cells = Vec::new();
+        while let Some(splitter) = queue.pop_front() {
+            queued[splitter] = false;
+            for i in splitter..self.end[splitter] {
+                let v = self.elements[i];
+                for &u in &adjacency.predecessors[v] {
+                    if counts[u] == (0, 0) {
+                        touched.push(u);
+                    }
+                    counts[u].0 += 1;
+                }
+                if adjacency.directed {
+                    for &u in &adjacency.successors[v] {
+                        if counts[u] == (0, 0) {
+                            touched.push(u);
+                        }
+                        counts[u].1 += 1;
+                    }
+                }
+            }
+            touched_cells.extend(touched.iter().map(|&u| self.cell[u]));
+            touched_cells.sort_unstable();
+            touched_cells.dedup();
+            for &start in &touched_cells {
+                let end = self.end[start];
+                if end - start == 1 {
+                    continue;
+                }
+                let members = &mut self.elements[start..end];
+                members.sort_unstable_by_key(|&u| counts[u]);
+                if counts[members[0]] == counts[members[end - start - 1]] {
+                    continue;
+                }
+                // Split into runs of equal counts, the first keeps the start.
+                let mut parts = vec![start];
+                for i in start + 1..end {
+                    if counts[self.elements[i]] != counts[self.elements[i - 1]] {
+                        self.end[*parts.last().unwrap()] = i;
+                        parts.push(i);
+                    }
+                }
+                self.end[*parts.last().unwrap()] = end;
+                for &part in &parts {
+                    for i in part..self.end[part] {
+                        let u = self.elements[i];
+                        self.position[u] = i;
+                        self.cell[u] = part;
+                    }
+                }
+                // Every part needs to split the others, except that a part
+                // can be left out if the whole cell is still to do.
+                let largest = if queued[start] {
+                    start
+                } else {
+                    *parts
+                        .iter()
+                        .max_by_key(|&&part| (self.end[part] - part, std::cmp::Reverse(part)))
+                        .unwrap()
+                };
+                for &part in &parts {
+                    if part != largest && !queued[part] {
+                        queued[part] = true;
+                        queue.push_back(part);
+                    }
+                }
+            }
+            for &u in &touched {
+                counts[u] = (0, 0);
+            }
+            touched.clear();
+            touched_cells.clear();
+        }
+    }
+}
+
+/// The adjacency lists of a graph, with nodes numbered from zero.
+#[derive(Debug)]
+pub(crate) struct Adjacency {
+    directed: bool,
+    successors: Vec<Vec<usize>>,
+    /// The same as `successors` if the graph is undirected.
+    predecessors: Vec<Vec<usize>>,
+}
+
+/// The result of a search for a canonical labelling.
+#[derive(Debug)]
+pub(crate) struct Search {
+    /// The nodes in canonical order.
+    pub best: Vec<usize>,
+    /// The nodes individualized on the path to the first leaf.
+    pub base: Vec<usize>,
+    /// Automorphisms that generate the automorphism group, as maps from node
+    /// to node.
+    pub generators: Vec<Vec<usize>>,
+}
+
+/// The search tree of partitions for a canonical labelling.
+pub(crate) struct Canonizer {
+    adjacency: Adjacency,
+    /// The edges with their classes, without parallel edges.
+    edges: Vec<(usize, usize, usize)>,
+}
+
+/// A node of the search tree.
+struct Level {
+    partition: Partition,
+    /// The nodes of the target cell, the children of this level.
+    target: Vec<usize>,
+    /// The position in `target` of the next child.
+    next: usize,
+    /// The children tried so far, the last one is on the current path.
+    tried: Vec<usize>,
+}
+
+/// A leaf of the search tree.
+#[derive(Clone)]
+struct Leaf {
+    path: Vec<usize>,
+    order: Vec<usize>,
+    certificate: Vec<(usize, usize, usize)>,
+}
+
+impl Canonizer {
+    /// Number the nodes of `graph` by their position in `nodes`. The edges
+    /// are split into classes by `edge_class`, and automorphisms need to map
+    /// every edge to an edge of the same class. Of parallel edges, only the
+    /// first counts.
+    pub fn new<G, F>(graph: G, nodes: &[G::NodeId], mut edge_class: F) -> Self
+    where
+        G: IntoEdgeReferences + NodeIndexable + GraphProp,
+        F: FnMut(G::EdgeRef) -> usize,
+    {
+        let directed = graph.is_directed();
+        let mut index = vec![usize::MAX; graph.node_bound()];
+        for (i, &node) in nodes.iter().enumerate() {
+            index[graph.to_index(node)] = i;
+        }
+        let mut edges: Vec<(usize, usize, usize)> = graph
+            .edge_references()
+            .map(|edge| {
+                let a = index[graph.to_index(edge.source())];
+                let b = index[graph.to_index(edge.target())];
+                let class = edge_class(edge);
+                if directed {
+                    (a, b, class)
+                } else {
+                    (a.min(b), a.max(b), class)
+                }
+            })
+            .collect();
+        // A stable sort keeps the first of parallel edges first.
+        edges.sort_by_key(|&(a, b, _)| (a, b));
+        edges.dedup_by_key(|&mut (a, b, _)| (a, b));
+        let mut successors = vec![Vec::new(); nodes.len()];
+        let mut predecessors = vec![Vec::new(); nodes.len()];
+        for &(a, b, _) in &edges {
+            successors[a].push(b);
+            if directed {
+                predecessors[b].push(a);
+            } else if a != b {
+                successors[b].push(a);
+            }
+        }
+        if !directed {
+            predecessors = successors.clone();
+        }
+        Canonizer {
+            adjacency: Adjacency {
+                directed,
+                successors,
+                predecessors,
+            },
+            edges,
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.adjacency.successors.len()
+    }
+
+    /// The edges with the nodes renumbered by their position in `order`.
+    fn certificate(&self, order: &[usize]) -> Vec<(usize, usize, usize)> {
+        let mut label = vec![0; order.len()];
+        for (i, &v) in order.iter().enumerate() {
+            label[v] = i;
+        }
+        let mut certificate: Vec<_> = self
+            .edges
+            .iter()
+            .map(|&(a, b, class)| {
+                let (a, b) = (label[a], label[b]);
+                if self.adjacency.directed {
+                    (a, b, class)
+                } else {
+                    (a.min(b), a.max(b), class)
+                }
+            })
+            .collect();
+        certificate.sort_unstable();
+        certificate
+    }
+
+    /// Search the labellings that keep the nodes in the given `cells`, in
+    /// order, for the canonical one. Cells must not be empty.
+    pub fn search(&self, cells: Vec<Vec<usize>>) -> Search {
+        let n = self.node_count();
+        let mut root = Partition::new(n, &cells);
+        let splitters = root.starts().collect();
+        root.refine(&self.adjacency, splitters);
+
+        let mut generators: Vec<Vec<usize>> = Vec::new();
+        let mut first: Option<Leaf> = None;
+        let mut best: Option<Leaf> = None;
+        let mut stack = Vec::new();
+        let mut pending = Some(root);
+        loop {
+            if let Some(partition) = pending.take() {
+                match partition.target_cell() {
+                    Some(target) => {
+                        let target = target.to_vec();
+                        stack.push(Level {
+                            partition,
+                            target,
+                            next: 0,
+                            tried: Vec::new(),
+                        });
+                    }
+                    None => {
+                        let path: Vec<usize> = stack
+                            .iter()
+                            .map(|level| *level.tried.last().unwrap())
+                            .collect();
+                        let order = partition.elements;
+                        let leaf = Leaf {
+                            certificate: self.certificate(&order),
+                            path,
+                            order,
+                        };
+                        let mut jump = None;
+                        for known in [&first, &best].into_iter().flatten() {
+                            if known.certificate == leaf.certificate {
+                                let mut automorphism = vec![0; n];
+                                for (&a, &b) in known.order.iter().zip(&leaf.order) {
+                                    automorphism[a] = b;
+                                }
+                                generators.push(automorphism);
+                                // The rest of the subtree where the paths part
+                                // is the image of the subtree of the known leaf.
+                                let common = known
+                                    .path
+                                    .iter()
+                                    .zip(&leaf.path)
+                                    .take_while(|(a, b)| a == b)
+                                    .count();
+                                jump = Some(common + 1);
+                                break;
+                            }
+                        }
+                        if let Some(depth) = jump {
+                            stack.truncate(depth);
+                        } else if first.is_none() {
+                            first = Some(leaf.clone());
+                            best = Some(leaf);
+                        } else if best
+                            .as_ref()
+                            .map_or(true, |b| leaf.certificate > b.certificate)
+                        {
+                            best = Some(leaf);
+                        }
+                    }
+                }
+            }
+
+            let (level, ancestors) = match stack.split_last_mut() {
+                Some(split) => split,
+                None => break,
+            };
+            // Children in the same orbit of the automorphisms that fix the
+            // path to this level have isomorphic subtrees.
+            let prefix: Vec<usize> = ancestors
+                .iter()
+                .map(|level| *level.tried.last().unwrap())
+                .collect();
+            let mut orbits: Option<UnionFind<usize>> = None;
+            let mut next = None;
+            while let Some(&candidate) = level.target.get(level.next) {
+                level.next += 1;
+                if !level.tried.is_empty() && !generators.is_empty() {
+                    let orbits = orbits.get_or_insert_with(|| {
+                        let mut orbits = UnionFind::new(n);
+                        for generator in &generators {
+                            if prefix.iter().all(|&v| generator[v] == v) {
+                                for (v, &w) in generator.iter().enumerate() {
+                                    orbits.union(v, w);
+                                }
+                            }
+                        }
+                        orbits
+                    });
+                    if level.tried.iter().any(|&t| orbits.equiv(t, candidate)) {
+                        continue;
+                    }
+                }
+                next = Some(candidate);
+                break;
+            }
+            match next {
+                Some(child) => {
+                    level.tried.push(child);
+                    let mut partition = level.partition.clone();
+                    partition.individualize(child, &self.adjacency);
+                    pending = Some(partition);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+
+        let first = first.unwrap();
+        Search {
+            best: best.map_or_else(Vec::new, |leaf| leaf.order),
+            base: first.path,
+            generators,
+        }
+    }
+}
@@ -0,0 +1,702 @@
+//! Centrality measures: betweenness, closeness, harmonic, eigenvector and
+//! Katz centrality.
+
+use std::collections::{BinaryHeap, VecDeque};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use super::{Measure, UnitMeasure};
+use crate::scored::MinScored;
+use crate::visit::{EdgeRef, GraphProp, IntoEdges, IntoNodeIdentifiers, NodeCount, NodeIndexable};
+
+// Buffers for the shortest paths from one source, reused between sources.
+struct SingleSource<K, D> {
+    // The nodes reached, by non-decreasing distance.
+    order: Vec<usize>,
+    distance: Vec<Option<K>>,
+    // The number of shortest paths to every node.
+    sigma: Vec<D>,
+    // The predecessors of every node on its shortest paths.
+    preds: Vec<Vec<usize>>,
+    delta: Vec<D>,
+    settled: Vec<bool>,
+    heap: BinaryHeap<MinScored<K, usize>>,
+    queue: VecDeque<usize>,
+}
+
+impl<K, D> SingleSource<K, D>
+where
+    K: Measure + Copy,
+    D: UnitMeasure + Copy,
+{
+    fn new(node_bound: usize) -> Self {
+        SingleSource {
+            order: Vec::new(),
+            distance: vec![None; node_bound],
+            sigma: vec![D::zero(); node_bound],
+            preds: vec![Vec::new(); node_bound],
+            delta: vec![D::zero(); node_bound],
+            settled: vec![false; node_bound],
+            heap: BinaryHeap::new(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    fn reset(&mut self, source: usize) {
+        for &v in &self.order {
+            self.distance[v] = None;
+            self.sigma[v] = D::zero();
+            self.preds[v].clear();
+            self.delta[v] = D::zero();
+            self.settled[v] = false;
+        }
+        self.order.clear();
+        self.distance[source] = Some(K::default());
+        self.sigma[source] = D::one();
+    }
+
+    // Shortest paths by Dijkstra's algorithm, for non-negative edge costs.
+    fn dijkstra<G, F>(&mut self, graph: G, source: G::NodeId, edge_cost: &mut F)
+    where
+        G: IntoEdges + NodeIndexable,
+        F: FnMut(G::EdgeRef) -> K,
+    {
+        let s = graph.to_index(source);
+        self.reset(s);
+        self.heap.push(MinScored(K::default(), s));
+        while let Some(MinScored(d, v)) = self.heap.pop() {
+            if self.settled[v] {
+                continue;
+            }
+            self.settled[v] = true;
+            self.order.push(v);
+            for edge in graph.edges(graph.from_index(v)) {
+                let w = graph.to_index(edge.target());
+                if self.settled[w] {
+                    continue;
+                }
+                let next = d + edge_cost(edge);
+                match self.distance[w] {
+                    Some(old) if next > old => {}
+                    Some(old) if next == old => {
+                        self.sigma[w] = self.sigma[w] + self.sigma[v];
+                        self.preds[w].push(v);
+                    }
+                    _ => {
+                        self.distance[w] = Some(next);
+                        self.sigma[w] = self.sigma[v];
+                        self.preds[w].clear();
+                        self.preds[w].push(v);
+                        self.heap.push(MinScored(next, w));
+                    }
+                }
+            }
+        }
+    }
+
+    // Add the dependencies of `source` on every node to `betweenness`, by
+    // Brandes' accumulation in order of non-increasing distance.
+    fn accumulate(&mut self, source: usize, endpoints: bool, betweenness: &mut [D]) {
+        if endpoints {
+            betweenness[source] = betweenness[source] + D::from_usize(self.order.len() - 1);
+        }
+        for &w in self.order.iter().rev() {
+            let coefficient = (D::one() + self.delta[w]) / self.sigma[w];
+            for &v in &self.preds[w] {
+                self.delta[v] = self.delta[v] + self.sigma[v] * coefficient;
+            }
+            if w != source {
+                betweenness[w] = betweenness[w] + self.delta[w];
+                if endpoints {
+                    betweenness[w] = betweenness[w] + D::one();
+                }
+            }
+        }
+    }
+
+    // The closeness of the source from its distances, with the improvement
+    // of Wasserman and Faust for graphs that are not connected.
+    fn closeness(&self, node_count: usize) -> D
+    where
+        K: Into<D>,
+    {
+        let reachable = self.order.len() - 1;
+        let total = self
+            .order
+            .iter()
+            .map(|&v| self.distance[v].unwrap().into())
+            .fold(D::zero(), |total, d| total + d);
+        if total > D::zero() && node_count > 1 {
+            let reachable = D::from_usize(reachable);
+            reachable / total * reachable / D::from_usize(node_count - 1)
+        } else {
+            D::zero()
+        }
+    }
+
+    // The sum of the inverse distances from the source.
+    fn harmonic(&self) -> D
+    where
+        K: Into<D>,
+    {
+        self.order
+            .iter()
+            .map(|&v| self.distance[v].unwrap().into())
+            .filter(|&d| d > D::zero())
+            .fold(D::zero(), |total, d| total + D::one() / d)
+    }
+}
+
+impl<D> SingleSource<usize, D>
+where
+    D: UnitMeasure + Copy,
+{
+    // Shortest paths by breadth first search, counting every edge once.
+    fn bfs<G>(&mut self, graph: G, source: G::NodeId)
+    where
+        G: IntoEdges + NodeIndexable,
+    {
+        let s = graph.to_index(source);
+        self.reset(s);
+        self.queue.push_back(s);
+        while let Some(v) = self.queue.pop_front() {
+            self.order.push(v);
+            let next = self.distance[v].unwrap() + 1;
+            for edge in graph.edges(graph.from_index(v)) {
+                let w = graph.to_index(edge.target());
+                match self.distance[w] {
+                    None => {
+                        self.distance[w] = Some(next);
+                        self.sigma[w] = self.sigma[v];
+                        self.preds[w].push(v);
+                        self.queue.push_back(w);
+                    }
+                    Some(d) if d == next => {
+                        self.sigma[w] = self.sigma[v] + self.sigma[w];
+                        self.preds[w].push(v);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+}
+
+// Scale betweenness values like NetworkX does.
+fn rescale<D: UnitMeasure + Copy>(
+    betweenness: &mut [D],
+    node_count: usize,
+    directed: bool,
+    endpoints: bool,
+    normalized: bool,
+) {
+    let scale = if normalized {
+        // The number of pairs of other nodes.
+        let pairs = if endpoints {
+            node_count * node_count.saturating_sub(1)
+        } else {
+            node_count.saturating_sub(1) * node_count.saturating_sub(2)
+        };
+        if pairs == 0 {
+            return;
+        }
+        D::one() / D::from_usize(pairs)
+    } else if !directed {
+        // Every pair of nodes was counted in both directions.
+        D::one() / D::from_usize(2)
+    } else {
+        return;
+    };
+    for b in betweenness {
+        *b = *b * scale;
+    }
+}
+
+/// \[Generic\] Betweenness centrality of an unweighted graph.
+///
+/// Computes for every node the sum over all pairs of other nodes of the
+/// fraction of shortest paths between them that pass through the node, using
+/// [Brandes' algorithm][brandes].
+///
+/// If `endpoints` is `true`, the endpoints of the paths are counted as well.
+/// If `normalized` is `true`, the values are divided by the number of pairs of
+/// nodes, so that they lie between 0 and 1. Otherwise, every pair is counted
+/// once in undirected graphs, and once in each direction in directed graphs.
+///
+/// Returns a `Vec` mapping each node index to its centrality, see
+/// [`NodeIndexable`].
+///
+/// # Complexity
+/// Time complexity is **O(|V||E|)**.
+/// Space complexity is **O(|V| + |E|)**.
+///
+/// [brandes]: https://doi.org/10.1080/0022250X.2001.9990249
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::betweenness_centrality;
+/// use petgraph::graph::UnGraph;
+///
+/// // A path a - b - c: every path between a and c passes through b.
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+///
+/// let centrality: Vec<f64> = betweenness_centrality(&graph, false, false);
+/// assert_eq!(centrality, vec![0., 1., 0.]);
+///
+/// let centrality: Vec<f64> = betweenness_centrality(&graph, false, true);
+/// assert_eq!(centrality, vec![0., 1., 0.]);
+/// ```
+pub fn betweenness_centrality<G, D>(graph: G, endpoints: bool, normalized: bool) -> Vec<D>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + NodeCount + GraphProp,
+    D: UnitMeasure + Copy,
+{
+    let mut betweenness = vec![D::zero(); graph.node_bound()];
+    let mut paths = SingleSource::new(graph.node_bound());
+    for s in graph.node_identifiers() {
+        paths.bfs(graph, s);
+        paths.accumulate(graph.to_index(s), endpoints, &mut betweenness);
+    }
+    rescale(
+        &mut betweenness,
+        graph.node_count(),
+        graph.is_directed(),
+        endpoints,
+        normalized,
+    );
+    betweenness
+}
+
+/// \[Generic\] Betweenness centrality of a weighted graph.
+///
+/// Like [`betweenness_centrality`], but the length of a path is the sum of
+/// the costs of its edges, given by `edge_cost`. Edge costs must be
+/// non-negative.
+///
+/// # Complexity
+/// Time complexity is **O(|V||E| + |V|² log |V|)**.
+/// Space complexity is **O(|V| + |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::weighted_betweenness_centrality;
+/// use petgraph::graph::UnGraph;
+///
+/// // The direct edge a - c is longer than the detour through b.
+/// let graph = UnGraph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1), (0, 2, 3)]);
+///
+/// let centrality: Vec<f64> =
+///     weighted_betweenness_centrality(&graph, |e| *e.weight(), false, false);
+/// assert_eq!(centrality, vec![0., 1., 0.]);
+/// ```
+pub fn weighted_betweenness_centrality<G, F, K, D>(
+    graph: G,
+    mut edge_cost: F,
+    endpoints: bool,
+    normalized: bool,
+) -> Vec<D>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + NodeCount + GraphProp,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+    D: UnitMeasure + Copy,
+{
+    let mut betweenness = vec![D::zero(); graph.node_bound()];
+    let mut paths = SingleSource::new(graph.node_bound());
+    for s in graph.node_identifiers() {
+        paths.dijkstra(graph, s, &mut edge_cost);
+        paths.accumulate(graph.to_index(s), endpoints, &mut betweenness);
+    }
+    rescale(
+        &mut betweenness,
+        graph.node_count(),
+        graph.is_directed(),
+        endpoints,
+        normalized,
+    );
+    betweenness
+}
+
+/// \[Generic\] Closeness centrality.
+///
+/// Computes for every node the inverse of its average distance to the nodes
+/// it can reach, where the length of a path is the sum of the costs of its
+/// edges, given by `edge_cost`. Edge costs must be non-negative. Use
+/// `|_| 1.0` to count the edges of a path.
+///
+/// In graphs that are not connected, the closeness of a node reaching `r`
+/// of the other `n - 1` nodes is scaled by `r / (n - 1)`, as proposed by
+/// Wasserman and Faust. A node that reaches no other node has closeness 0.
+///
+/// Distances are measured along outgoing edges in directed graphs, wrap the
+/// graph in [`Reversed`](crate::visit::Reversed) to use incoming edges.
+///
+/// Returns a `Vec` mapping each node index to its centrality, see
+/// [`NodeIndexable`].
+///
+/// # Complexity
+/// Time complexity is **O(|V||E| + |V|² log |V|)**.
+/// Space complexity is **O(|V| + |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::closeness_centrality;
+/// use petgraph::graph::UnGraph;
+///
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+///
+/// let centrality = closeness_centrality(&graph, |_| 1.0);
+/// assert_eq!(centrality, vec![2. / 3., 1., 2. / 3.]);
+/// ```
+pub fn closeness_centrality<G, F, K>(graph: G, mut edge_cost: F) -> Vec<K>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + NodeCount,
+    F: FnMut(G::EdgeRef) -> K,
+    K: UnitMeasure + Copy,
+{
+    let mut closeness = vec![K::zero(); graph.node_bound()];
+    let mut paths = SingleSource::<K, K>::new(graph.node_bound());
+    for s in graph.node_identifiers() {
+        paths.dijkstra(graph, s, &mut edge_cost);
+        closeness[graph.to_index(s)] = paths.closeness(graph.node_count());
+    }
+    closeness
+}
+
+/// \[Generic\] Harmonic centrality.
+///
+/// Computes for every node the sum of the inverse distances to all other
+/// nodes, where unreachable nodes contribute 0. The length of a path is the
+/// sum of the costs of its edges, given by `edge_cost`. Edge costs must be
+/// non-negative. Use `|_| 1.0` to count the edges of a path.
+///
+/// Distances are measured along outgoing edges in directed graphs, wrap the
+/// graph in [`Reversed`](crate::visit::Reversed) to use incoming edges.
+///
+/// Returns a `Vec` mapping each node index to its centrality, see
+/// [`NodeIndexable`].
+///
+/// # Complexity
+/// Time complexity is **O(|V||E| + |V|² log |V|)**.
+/// Space complexity is **O(|V| + |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::harmonic_centrality;
+/// use petgraph::graph::DiGraph;
+///
+/// let graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+///
+/// let centrality = harmonic_centrality(&graph, |_| 1.0);
+/// assert_eq!(centrality, vec![1.5, 1., 0.]);
+/// ```
+pub fn harmonic_centrality<G, F, K>(graph: G, mut edge_cost: F) -> Vec<K>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: UnitMeasure + Copy,
+{
+    let mut harmonic = vec![K::zero(); graph.node_bound()];
+    let mut paths = SingleSource::<K, K>::new(graph.node_bound());
+    for s in graph.node_identifiers() {
+        paths.dijkstra(graph, s, &mut edge_cost);
+        harmonic[graph.to_index(s)] = paths.harmonic();
+    }
+    harmonic
+}
+
+// The sum of absolute differences between `a` and `b`.
+fn distance<D: UnitMeasure + Copy>(a: &[D], b: &[D]) -> D {
+    a.iter()
+        .zip(b)
+        .map(|(&x, &y)| if x > y { x - y } else { y - x })
+        .fold(D::zero(), |total, d| total + d)
+}
+
+/// \[Generic\] Eigenvector centrality.
+///
+/// Computes the centrality of every node as the sum of the centralities of
+/// the nodes with an edge to it, weighted by `edge_weight`, by power
+/// iteration. Edges are followed in both directions in undirected graphs.
+/// Edge weights should be non-negative.
+///
+/// The iteration stops after `nb_iter` steps, or once the values change by
+/// less than `tol` per node (by default `1e-6`). The centralities are scaled
+/// so that they add up to 1.
+///
+/// Returns a `Vec` mapping each node index to its centrality, see
+/// [`NodeIndexable`].
+///
+/// # Complexity
+/// Time complexity is **O(N(|V| + |E|))**.
+/// Space complexity is **O(|V|)**
+/// where **N** is the number of iterations.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::eigenvector_centrality;
+/// use petgraph::graph::UnGraph;
+///
+/// // A star with center 0.
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (0, 3), (0, 4)]);
+///
+/// let centrality = eigenvector_centrality(&graph, |_| 1.0_f64, 100, None);
+/// assert!((centrality[0] - 1. / 3.).abs() < 1e-6);
+/// assert!((centrality[1] - 1. / 6.).abs() < 1e-6);
+/// ```
+pub fn eigenvector_centrality<G, F, D>(
+    graph: G,
+    mut edge_weight: F,
+    nb_iter: usize,
+    tol: Option<D>,
+) -> Vec<D>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + NodeCount,
+    F: FnMut(G::EdgeRef) -> D,
+    D: UnitMeasure + Copy,
+{
+    let node_count = graph.node_count();
+    let tolerance = tol.unwrap_or_else(D::default_tol) * D::from_usize(node_count);
+    let mut centrality = vec![D::zero(); graph.node_bound()];
+    for node in graph.node_identifiers() {
+        centrality[graph.to_index(node)] = D::one() / D::from_usize(node_count);
+    }
+    for _ in 0..nb_iter {
+        // Iterate with the matrix `A + I`, which has the same eigenvectors,
+        // so that bipartite graphs converge too.
+        let mut next = centrality.clone();
+        for node in graph.node_identifiers() {
+            let value = centrality[graph.to_index(node)];
+            for edge in graph.edges(node) {
+                let target = graph.to_index(edge.target());
+                next[target] = next[target] + edge_weight(edge) * value;
+            }
+        }
+        let sum = next.iter().fold(D::zero(), |sum, &x| sum + x);
+        if sum == D::zero() {
+            return next;
+        }
+        for x in &mut next {
+            *x = *x / sum;
+        }
+        let change = distance(&next, &centrality);
+        centrality = next;
+        if change < tolerance {
+            break;
+        }
+    }
+    centrality
+}
+
+/// \[Generic\] Katz centrality.
+///
+/// Computes the centrality of every node as `beta` plus `alpha` times the sum
+/// of the centralities of the nodes with an edge to it, weighted by
+/// `edge_weight`. Edges are followed in both directions in undirected graphs.
+///
+/// The iteration converges if `alpha` is smaller than the inverse of the
+/// largest eigenvalue of the adjacency matrix. It stops after `nb_iter` steps,
+/// or once the values change by less than `tol` per node (by default `1e-6`).
+/// The centralities are scaled so that they add up to 1.
+///
+/// Returns a `Vec` mapping each node index to its centrality, see
+/// [`NodeIndexable`].
+///
+/// # Complexity
+/// Time complexity is **O(N(|V| + |E|))**.
+/// Space complexity is **O(|V|)**
+/// where **N** is the number of iterations.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::katz_centrality;
+/// use petgraph::graph::DiGraph;
+///
+/// let graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+///
+/// // Node 2 gets 1 + 0.5 (1 + 0.5), node 1 gets 1 + 0.5 and node 0 gets 1.
+/// let centrality = katz_centrality(&graph, |_| 1.0_f64, 0.5, 1.0, 100, None);
+/// assert_eq!(centrality, vec![1. / 4.25, 1.5 / 4.25, 1.75 / 4.25]);
+/// ```
+pub fn katz_centrality<G, F, D>(
+    graph: G,
+    mut edge_weight: F,
+    alpha: D,
+    beta: D,
+    nb_iter: usize,
+    tol: Option<D>,
+) -> Vec<D>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + NodeCount,
+    F: FnMut(G::EdgeRef) -> D,
+    D: UnitMeasure + Copy,
+{
+    let tolerance = tol.unwrap_or_else(D::default_tol) * D::from_usize(graph.node_count());
+    let mut centrality = vec![D::zero(); graph.node_bound()];
+    for _ in 0..nb_iter {
+        let mut next = vec![D::zero(); graph.node_bound()];
+        for node in graph.node_identifiers() {
+            let index = graph.to_index(node);
+            next[index] = next[index] + beta;
+            let value = alpha * centrality[index];
+            for edge in graph.edges(node) {
+                let target = graph.to_index(edge.target());
+                next[target] = next[target] + edge_weight(edge) * value;
+            }
+        }
+        let change = distance(&next, &centrality);
+        centrality = next;
+        if change < tolerance {
+            break;
+        }
+    }
+    let sum = centrality.iter().fold(D::zero(), |sum, &x| sum + x);
+    if sum != D::zero() {
+        for x in &mut centrality {
+            *x = *x / sum;
+        }
+    }
+    centrality
+}
+
+// Brandes' algorithm with the sources spread over threads, `search` finds
+// the shortest paths from one source.
+#[cfg(feature = "rayon")]
+fn parallel_betweenness<G, K, D, S>(
+    graph: G,
+    endpoints: bool,
+    normalized: bool,
+    search: S,
+) -> Vec<D>
+where
+    G: IntoNodeIdentifiers + NodeIndexable + NodeCount + GraphProp + Sync,
+    G::NodeId: Send + Sync,
+    K: Measure + Copy + Send,
+    D: UnitMeasure + Copy + Send + Sync,
+    S: Fn(&mut SingleSource<K, D>, G::NodeId) + Sync,
+{
+    let node_bound = graph.node_bound();
+    let sources: Vec<_> = graph.node_identifiers().collect();
+    let mut betweenness = sources
+        .par_iter()
+        .fold(
+            || (SingleSource::new(node_bound), vec![D::zero(); node_bound]),
+            |(mut paths, mut betweenness), &s| {
+                search(&mut paths, s);
+                paths.accumulate(graph.to_index(s), endpoints, &mut betweenness);
+                (paths, betweenness)
+            },
+        )
+        .map(|(_, betweenness)| betweenness)
+        .reduce(
+            || vec![D::zero(); node_bound],
+            |mut a, b| {
+                for (x, y) in a.iter_mut().zip(b) {
+                    *x = *x + y;
+                }
+                a
+            },
+        );
+    rescale(
+        &mut betweenness,
+        graph.node_count(),
+        graph.is_directed(),
+        endpoints,
+        normalized,
+    );
+    betweenness
+}
+
+/// \[Generic\] Parallel betweenness centrality of an unweighted graph.
+///
+/// See [`betweenness_centrality`].
+#[cfg(feature = "rayon")]
+pub fn parallel_betweenness_centrality<G, D>(graph: G, endpoints: bool, normalized: bool) -> Vec<D>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + NodeCount + GraphProp + Sync,
+    G::NodeId: Send + Sync,
+    D: UnitMeasure + Copy + Send + Sync,
+{
+    parallel_betweenness(graph, endpoints, normalized, |paths, s| paths.bfs(graph, s))
+}
+
+/// \[Generic\] Parallel betweenness centrality of a weighted graph.
+///
+/// See [`weighted_betweenness_centrality`].
+#[cfg(feature = "rayon")]
+pub fn parallel_weighted_betweenness_centrality<G, F, K, D>(
+    graph: G,
+    edge_cost: F,
+    endpoints: bool,
+    normalized: bool,
+) -> Vec<D>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + NodeCount + GraphProp + Sync,
+    G::NodeId: Send + Sync,
+    F: Fn(G::EdgeRef) -> K + Sync,
+    K: Measure + Copy + Send,
+    D: UnitMeasure + Copy + Send + Sync,
+{
+    parallel_betweenness(graph, endpoints, normalized, |paths, s| {
+        paths.dijkstra(graph, s, &mut |edge| edge_cost(edge))
+    })
+}
+
+// Compute `value` from the distances of every source, spread over threads.
+#[cfg(feature = "rayon")]
+fn parallel_per_source<G, F, K, V>(graph: G, edge_cost: F, value: V) -> Vec<K>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + Sync,
+    G::NodeId: Send + Sync,
+    F: Fn(G::EdgeRef) -> K + Sync,
+    K: UnitMeasure + Copy + Send + Sync,
+    V: Fn(&SingleSource<K, K>) -> K + Sync,
+{
+    let node_bound = graph.node_bound();
+    let sources: Vec<_> = graph.node_identifiers().collect();
+    let values: Vec<_> = sources
+        .par_iter()
+        .map_init(
+            || SingleSource::new(node_bound),
+            |paths, &s| {
+                paths.dijkstra(graph, s, &mut |edge| edge_cost(edge));
+                (graph.to_index(s), value(paths))
+            },
+        )
+        .collect();
+    let mut result = vec![K::zero(); node_bound];
+    for (index, value) in values {
+        result[index] = value;
+    }
+    result
+}
+
+/// \[Generic\] Parallel closeness centrality.
+///
+/// See [`closeness_centrality`].
+#[cfg(feature = "rayon")]
+pub fn parallel_closeness_centrality<G, F, K>(graph: G, edge_cost: F) -> Vec<K>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + NodeCount + Sync,
+    G::NodeId: Send + Sync,
+    F: Fn(G::EdgeRef) -> K + Sync,
+    K: UnitMeasure + Copy + Send + Sync,
+{
+    let node_count = graph.node_count();
+    parallel_per_source(graph, edge_cost, |paths| paths.closeness(node_count))
+}
+
+/// \[Generic\] Parallel harmonic centrality.
+///
+/// See [`harmonic_centrality`].
+#[cfg(feature = "rayon")]
+pub fn parallel_harmonic_centrality<G, F, K>(graph: G, edge_cost: F) -> Vec<K>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + Sync,
+    G::NodeId: Send + Sync,
+    F: Fn(G::EdgeRef) -> K + Sync,
+    K: UnitMeasure + Copy + Send + Sync,
+{
+    parallel_per_source(graph, edge_cost, |paths| paths.harmonic())
+}
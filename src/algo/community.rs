@@ -0,0 +1,535 @@
+//! Community detection: label propagation, Louvain and Leiden modularity
+//! optimisation, and the modularity of a partition.
+//!
+//! A partition of the nodes is given as a `Vec` that maps every node index
+//! (see [`NodeIndexable`]) to a label, like
+//! [`UnionFind::into_labeling`](crate::unionfind::UnionFind::into_labeling).
+//! Two nodes are in the same community if they have the same label. The
+//! functions in this module label every community with its smallest node
+//! index.
+
+use std::collections::HashMap;
+
+use super::{Measure, UnitMeasure};
+use crate::visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+/// \[Generic\] Compute the modularity of a partition of the nodes.
+///
+/// The modularity compares the weight of the edges within communities with
+/// the weight expected if edges were placed at random, keeping the weighted
+/// degrees of the nodes:
+///
+/// **Q = Σ<sub>c</sub> ( L<sub>c</sub> / m - γ (d<sub>c</sub> / 2m)² )**
+///
+/// where **m** is the total weight of the edges, **L<sub>c</sub>** the weight
+/// of the edges within community **c**, **d<sub>c</sub>** the sum of the
+/// weighted degrees of its nodes and **γ** the `resolution`. Larger
+/// resolutions favor smaller communities, 1 gives the standard modularity.
+///
+/// The graph is treated as undirected, with the weight of every edge given by
+/// `edge_weight`. `partition` maps every node index to the label of its
+/// community. A graph without edges has modularity 0.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::community::modularity;
+/// use petgraph::graph::UnGraph;
+///
+/// // Two triangles joined by the edge 2 - 3.
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)]);
+///
+/// let q: f64 = modularity(&graph, &[0, 0, 0, 3, 3, 3], |_| 1.0, 1.0);
+/// assert!((q - 5. / 14.).abs() < 1e-9);
+/// ```
+pub fn modularity<G, F, K>(graph: G, partition: &[usize], mut edge_weight: F, resolution: K) -> K
+where
+    G: IntoEdgeReferences + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: UnitMeasure + Copy,
+{
+    let zero = K::zero();
+    let two = K::from_usize(2);
+    // The weight within and the degree of every community.
+    let mut communities: HashMap<usize, (K, K)> = HashMap::new();
+    let mut total = zero;
+    for edge in graph.edge_references() {
+        let w = edge_weight(edge);
+        let a = partition[graph.to_index(edge.source())];
+        let b = partition[graph.to_index(edge.target())];
+        total = total + w;
+        let entry = communities.entry(a).or_insert((zero, zero));
+        entry.1 = entry.1 + w;
+        let entry = communities.entry(b).or_insert((zero, zero));
+        entry.1 = entry.1 + w;
+        if a == b {
+            entry.0 = entry.0 + w;
+        }
+    }
+    if total == zero {
+        return zero;
+    }
+    communities
+        .values()
+        .map(|&(within, degree)| {
+            let fraction = degree / (two * total);
+            within / total - resolution * fraction * fraction
+        })
+        .fold(zero, |q, x| q + x)
+}
+
+/// \[Generic\] Find communities by label propagation.
+///
+/// Every node starts in its own community and repeatedly joins the community
+/// it has the largest total edge weight to, given by `edge_weight`, until no
+/// node changes its community. Nodes are visited in the order of
+/// `node_identifiers` and only move if another community is strictly
+/// better, which makes the result deterministic and guarantees termination.
+/// Ties between other communities are broken in favor of the one whose label
+/// was taken from the latest node, which keeps the label of the first node
+/// from spreading along the visiting order.
+///
+/// The graph is treated as undirected. Returns a `Vec` mapping every node
+/// index to the label of its community, see the [module documentation](self).
+///
+/// # Complexity
+/// Every round takes **O(|V| + |E|)** time, and few rounds are needed in
+/// practice.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::community::label_propagation;
+/// use petgraph::graph::UnGraph;
+///
+/// // Two triangles joined by a light edge 2 - 3.
+/// let graph = UnGraph::<(), f64>::from_edges(&[
+///     (0, 1, 1.), (1, 2, 1.), (2, 0, 1.),
+///     (3, 4, 1.), (4, 5, 1.), (5, 3, 1.),
+///     (2, 3, 0.5),
+/// ]);
+///
+/// let labels = label_propagation(&graph, |e| *e.weight());
+/// assert!(labels[0] == labels[1] && labels[1] == labels[2]);
+/// assert!(labels[3] == labels[4] && labels[4] == labels[5]);
+/// assert_ne!(labels[0], labels[3]);
+/// ```
+pub fn label_propagation<G, F, K>(graph: G, edge_weight: F) -> Vec<usize>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let (network, nodes) = Network::new(graph, edge_weight);
+    let n = network.len();
+    let mut label: Vec<usize> = (0..n).collect();
+    let mut weight = vec![K::default(); n];
+    let mut seen = vec![false; n];
+    let mut candidates = Vec::new();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for v in 0..n {
+            for &(u, w) in &network.adjacency[v] {
+                let l = label[u];
+                if !seen[l] {
+                    seen[l] = true;
+                    weight[l] = K::default();
+                    candidates.push(l);
+                }
+                weight[l] = weight[l] + w;
+            }
+            let own = label[v];
+            let mut best = own;
+            let mut best_weight = if seen[own] { weight[own] } else { K::default() };
+            for &l in &candidates {
+                if weight[l] > best_weight || (weight[l] == best_weight && l > best && best != own)
+                {
+                    best = l;
+                    best_weight = weight[l];
+                }
+                seen[l] = false;
+            }
+            candidates.clear();
+            if best != own {
+                label[v] = best;
+                changed = true;
+            }
+        }
+    }
+    labeling(graph, &nodes, &label)
+}
+
+/// \[Generic\] Find communities with the Louvain method.
+///
+/// Greedily maximizes the [`modularity`] with the given `resolution`: nodes
+/// are moved to the neighboring community that increases the modularity the
+/// most, then every community is merged into a single node, and this repeats
+/// on the merged graph until no node moves. Nodes are visited in the order of
+/// `node_identifiers`, which makes the result deterministic.
+///
+/// The Louvain method may find communities that are badly connected, or even
+/// disconnected. [`leiden`] refines the communities to avoid this.
+///
+/// The graph is treated as undirected, with the weight of every edge given by
+/// `edge_weight`. Returns a `Vec` mapping every node index to the label of its
+/// community, see the [module documentation](self).
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::community::{louvain, modularity};
+/// use petgraph::graph::UnGraph;
+///
+/// let graph = UnGraph::<(), ()>::from_edges(&[
+///     (0, 1), (1, 2), (2, 0),
+///     (3, 4), (4, 5), (5, 3),
+///     (2, 3),
+/// ]);
+///
+/// let labels = louvain(&graph, |_| 1.0, 1.0);
+/// assert_eq!(labels, vec![0, 0, 0, 3, 3, 3]);
+/// let q: f64 = modularity(&graph, &labels, |_| 1.0, 1.0);
+/// assert!((q - 5. / 14.).abs() < 1e-9);
+/// ```
+pub fn louvain<G, F, K>(graph: G, edge_weight: F, resolution: K) -> Vec<usize>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: UnitMeasure + Copy,
+{
+    let (mut network, nodes) = Network::new(graph, edge_weight);
+    // The node of the current network that every original node belongs to.
+    let mut membership: Vec<usize> = (0..network.len()).collect();
+    loop {
+        let mut community: Vec<usize> = (0..network.len()).collect();
+        if !network.move_nodes(&mut community, resolution) {
+            break;
+        }
+        let (aggregate, renumber) = network.aggregate(&community);
+        for m in &mut membership {
+            *m = renumber[community[*m]];
+        }
+        network = aggregate;
+    }
+    labeling(graph, &nodes, &membership)
+}
+
+/// \[Generic\] Find communities with the Leiden algorithm.
+///
+/// Like [`louvain`], but before merging the communities into single nodes,
+/// every community is split into well connected parts, which are merged
+/// instead. The communities found are connected, and are refined further in
+/// the following iterations. Parts are grown greedily, which makes the
+/// result deterministic.
+///
+/// The graph is treated as undirected, with the weight of every edge given by
+/// `edge_weight`. Returns a `Vec` mapping every node index to the label of its
+/// community, see the [module documentation](self).
+///
+/// See V.A. Traag, L. Waltman, N.J. van Eck, *From Louvain to Leiden:
+/// guaranteeing well-connected communities*, 2019.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::community::leiden;
+/// use petgraph::graph::UnGraph;
+///
+/// let graph = UnGraph::<(), ()>::from_edges(&[
+///     (0, 1), (1, 2), (2, 0),
+///     (3, 4), (4, 5), (5, 3),
+///     (2, 3),
+/// ]);
+///
+/// let labels = leiden(&graph, |_| 1.0, 1.0);
+/// assert_eq!(labels, vec![0, 0, 0, 3, 3, 3]);
+/// ```
+pub fn leiden<G, F, K>(graph: G, edge_weight: F, resolution: K) -> Vec<usize>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: UnitMeasure + Copy,
+{
+    let (mut network, nodes) = Network::new(graph, edge_weight);
+    let mut membership: Vec<usize> = (0..network.len()).collect();
+    let mut community: Vec<usize> = (0..network.len()).collect();
+    loop {
+        network.move_nodes(&mut community, resolution);
+        let refined = network.refine(&community, resolution);
+        let (aggregate, renumber) = network.aggregate(&refined);
+        if aggregate.len() == network.len() {
+            break;
+        }
+        for m in &mut membership {
+            *m = renumber[refined[*m]];
+        }
+        // Every merged node starts in the community of its part, numbered
+        // anew since there are at most as many communities as parts.
+        let mut numbers = vec![usize::MAX; network.len()];
+        let mut count = 0;
+        let mut next = vec![0; aggregate.len()];
+        for (v, &part) in refined.iter().enumerate() {
+            let c = community[v];
+            if numbers[c] == usize::MAX {
+                numbers[c] = count;
+                count += 1;
+            }
+            next[renumber[part]] = numbers[c];
+        }
+        community = next;
+        network = aggregate;
+    }
+    let communities: Vec<usize> = membership.iter().map(|&m| community[m]).collect();
+    labeling(graph, &nodes, &communities)
+}
+
+// Label every node of `graph` with the smallest index in its community, given
+// for the nodes in the order of `nodes`. Vacant indices label themselves.
+fn labeling<G>(graph: G, nodes: &[G::NodeId], community: &[usize]) -> Vec<usize>
+where
+    G: NodeIndexable,
+{
+    let mut smallest = HashMap::new();
+    for (&node, &c) in nodes.iter().zip(community) {
+        let index = graph.to_index(node);
+        let entry = smallest.entry(c).or_insert(index);
+        *entry = index.min(*entry);
+    }
+    let mut labels: Vec<usize> = (0..graph.node_bound()).collect();
+    for (&node, c) in nodes.iter().zip(community) {
+        labels[graph.to_index(node)] = smallest[c];
+    }
+    labels
+}
+
+// A weighted undirected graph on the nodes `0..n`.
+struct Network<K> {
+    // The neighbors of every node with the total weight of the edges to
+    // them, without self loops.
+    adjacency: Vec<Vec<(usize, K)>>,
+    // The total weight of the self loops of every node.
+    self_loops: Vec<K>,
+    // The weighted degree of every node, counting self loops twice.
+    degree: Vec<K>,
+    // Twice the total weight of the edges.
+    total: K,
+}
+
+impl<K> Network<K>
+where
+    K: Measure + Copy,
+{
+    // Number the nodes of `graph` in the order of `node_identifiers`.
+    fn new<G, F>(graph: G, mut edge_weight: F) -> (Self, Vec<G::NodeId>)
+    where
+        G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+        F: FnMut(G::EdgeRef) -> K,
+    {
+        let nodes: Vec<_> = graph.node_identifiers().collect();
+        let mut position = vec![usize::MAX; graph.node_bound()];
+        for (i, &node) in nodes.iter().enumerate() {
+            position[graph.to_index(node)] = i;
+        }
+        let n = nodes.len();
+        let mut edges = Vec::new();
+        for edge in graph.edge_references() {
+            let a = position[graph.to_index(edge.source())];
+            let b = position[graph.to_index(edge.target())];
+            edges.push((a, b, edge_weight(edge)));
+        }
+        (Network::from_edges(n, edges), nodes)
+    }
+
+    fn from_edges(n: usize, edges: Vec<(usize, usize, K)>) -> Self {
+        let zero = K::default();
+        let mut network = Network {
+            adjacency: vec![Vec::new(); n],
+            self_loops: vec![zero; n],
+            degree: vec![zero; n],
+            total: zero,
+        };
+        let mut weights: HashMap<(usize, usize), K> = HashMap::new();
+        for (a, b, w) in edges {
+            network.degree[a] = network.degree[a] + w;
+            network.degree[b] = network.degree[b] + w;
+            network.total = network.total + w + w;
+            if a == b {
+                network.self_loops[a] = network.self_loops[a] + w;
+            } else {
+                let entry = weights.entry((a.min(b), a.max(b))).or_insert(zero);
+                *entry = *entry + w;
+            }
+        }
+        let mut pairs: Vec<_> = weights.into_iter().collect();
+        pairs.sort_by_key(|&(pair, _)| pair);
+        for ((a, b), w) in pairs {
+            network.adjacency[a].push((b, w));
+            network.adjacency[b].push((a, w));
+        }
+        network
+    }
+
+    fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+}
+
+impl<K> Network<K>
+where
+    K: UnitMeasure + Copy,
+{
+    // Move single nodes between the communities while that increases the
+    // modularity. Returns whether any node moved.
+    fn move_nodes(&self, community: &mut [usize], resolution: K) -> bool {
+        let n = self.len();
+        let zero = K::zero();
+        // The total degree of every community.
+        let mut total = vec![zero; n];
+        for v in 0..n {
+            total[community[v]] = total[community[v]] + self.degree[v];
+        }
+        let mut weight = vec![zero; n];
+        let mut seen = vec![false; n];
+        let mut candidates = Vec::new();
+        let mut moved = false;
+        loop {
+            let mut improved = false;
+            for v in 0..n {
+                let own = community[v];
+                for &(u, w) in &self.adjacency[v] {
+                    let c = community[u];
+                    if !seen[c] {
+                        seen[c] = true;
+                        weight[c] = zero;
+                        candidates.push(c);
+                    }
+                    weight[c] = weight[c] + w;
+                }
+
+                // The modularity gain of `v` joining a community, times `m`.
+                let degree = self.degree[v];
+                total[own] = total[own] - degree;
+                let gain =
+                    |c: usize, weight: K| weight - resolution * total[c] * degree / self.total;
+                let mut best = own;
+                let mut best_gain = gain(own, if seen[own] { weight[own] } else { zero });
+                for &c in &candidates {
+                    let g = gain(c, weight[c]);
+                    if g > best_gain {
+                        best = c;
+                        best_gain = g;
+                    }
+                    seen[c] = false;
+                }
+                candidates.clear();
+                total[best] = total[best] + degree;
+                if best != own {
+                    community[v] = best;
+                    improved = true;
+                    moved = true;
+                }
+            }
+            if !improved {
+                return moved;
+            }
+        }
+    }
+
+    // Split every community into well connected parts, by greedily merging
+    // single nodes into parts of the same community.
+    fn refine(&self, community: &[usize], resolution: K) -> Vec<usize> {
+        let n = self.len();
+        let zero = K::zero();
+        let mut community_total = vec![zero; n];
+        for v in 0..n {
+            community_total[community[v]] = community_total[community[v]] + self.degree[v];
+        }
+        // The weight from every node to the rest of its community.
+        let mut external: Vec<K> = (0..n)
+            .map(|v| {
+                self.adjacency[v]
+                    .iter()
+                    .filter(|&&(u, _)| community[u] == community[v])
+                    .fold(zero, |sum, &(_, w)| sum + w)
+            })
+            .collect();
+        let mut part: Vec<usize> = (0..n).collect();
+        let mut part_total = self.degree.clone();
+        let mut part_size = vec![1; n];
+        let mut weight = vec![zero; n];
+        let mut seen = vec![false; n];
+        let mut candidates = Vec::new();
+
+        // Whether a set with this degree and weight to the rest of its
+        // community is well connected to it.
+        let well_connected = |external: K, total: K, community: K| {
+            external >= resolution * total * (community - total) / self.total
+        };
+        for v in 0..n {
+            let c = community[v];
+            if part_size[part[v]] > 1
+                || !well_connected(external[v], self.degree[v], community_total[c])
+            {
+                continue;
+            }
+            for &(u, w) in &self.adjacency[v] {
+                let p = part[u];
+                if community[u] == c && p != part[v] {
+                    if !seen[p] {
+                        seen[p] = true;
+                        weight[p] = zero;
+                        candidates.push(p);
+                    }
+                    weight[p] = weight[p] + w;
+                }
+            }
+            let degree = self.degree[v];
+            let mut best = None;
+            let mut best_gain = zero;
+            for &p in &candidates {
+                seen[p] = false;
+                if !well_connected(external[p], part_total[p], community_total[c]) {
+                    continue;
+                }
+                let gain = weight[p] - resolution * part_total[p] * degree / self.total;
+                if gain >= best_gain && best.map_or(true, |_| gain > best_gain) {
+                    best = Some(p);
+                    best_gain = gain;
+                }
+            }
+            if let Some(p) = best {
+                // The edges between `v` and the part become internal.
+                let between = weight[p];
+                external[p] = external[p] + external[v] - between - between;
+                part_total[p] = part_total[p] + degree;
+                part_size[p] += 1;
+                part_size[v] = 0;
+                part[v] = p;
+            }
+            candidates.clear();
+        }
+        part
+    }
+
+    // Merge every community into one node. Returns the merged network and the
+    // new number of every community.
+    fn aggregate(&self, community: &[usize]) -> (Self, Vec<usize>) {
+        let mut renumber = vec![usize::MAX; self.len()];
+        let mut count = 0;
+        for &c in community {
+            if renumber[c] == usize::MAX {
+                renumber[c] = count;
+                count += 1;
+            }
+        }
+        let mut edges = Vec::new();
+        for v in 0..self.len() {
+            let a = renumber[community[v]];
+            edges.push((a, a, self.self_loops[v]));
+            for &(u, w) in &self.adjacency[v] {
+                // Every edge is stored at both ends.
+                if v < u {
+                    edges.push((a, renumber[community[u]], w));
+                }
+            }
+        }
+        (Network::from_edges(count, edges), renumber)
+    }
+}
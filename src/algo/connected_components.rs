@@ -0,0 +1,156 @@
+//! Parallel connected components.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use rayon::prelude::*;
+
+use crate::visit::{GraphProp, IntoNeighbors, NodeCompactIndexable};
+
+/// \[Generic\] Parallel connected components.
+///
+/// Return the number of connected components of the graph, like
+/// [`connected_components`](fn@crate::algo::connected_components), using
+/// [`parallel_connected_component_labels`].
+pub fn parallel_connected_components<G>(g: G) -> usize
+where
+    G: NodeCompactIndexable + IntoNeighbors + GraphProp + Sync,
+{
+    let labels = parallel_connected_component_labels(g);
+    labels
+        .par_iter()
+        .enumerate()
+        .filter(|&(i, &label)| i == label)
+        .count()
+}
+
+/// \[Generic\] Label the connected components of the graph in parallel.
+///
+/// For a directed graph, these are the *weakly* connected components.
+///
+/// Return the label of every node, indexed by its
+/// [`NodeIndexable::to_index`](crate::visit::NodeIndexable::to_index): the smallest index of a node of its
+/// component. Two nodes are in the same component if and only if they have
+/// the same label.
+///
+/// This implements the Afforest algorithm of Sutton, Ben-Nun and Barak,
+/// *Optimizing Parallel Graph Connectivity Computation via Subgraph
+/// Sampling* (2018). Every node points to a node of smaller index of its
+/// component, as in a [`UnionFind`](crate::unionfind::UnionFind), and the nodes are linked concurrently
+/// by compare-and-swap, using [rayon]. The components are first
+/// approximated by linking every node with its first neighbors only. In an
+/// undirected graph, the nodes of the largest of these components then skip
+/// their other edges, which are mostly within it, as each such edge is
+/// also listed by the neighbors of its other end.
+///
+/// The graph must have compact node indices, as [`Graph`](crate::Graph) and
+/// [`Csr`](crate::csr::Csr) do.
+///
+/// [rayon]: https://docs.rs/rayon
+///
+/// # Complexity
+/// * Time complexity: **O(|E| log |V|)** in the worst case, close to
+///   **O(|V| + |E|)** in practice.
+/// * Auxiliary space: **O(|V|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::parallel_connected_component_labels;
+/// use petgraph::prelude::*;
+///
+/// let mut graph = UnGraph::<(), ()>::new_undirected();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// let d = graph.add_node(());
+/// graph.extend_with_edges(&[(a, c), (b, d)]);
+///
+/// assert_eq!(parallel_connected_component_labels(&graph), [0, 1, 0, 1]);
+/// ```
+pub fn parallel_connected_component_labels<G>(g: G) -> Vec<usize>
+where
+    G: NodeCompactIndexable + IntoNeighbors + GraphProp + Sync,
+{
+    /// The number of neighbors of every node linked first.
+    const NEIGHBOR_ROUNDS: usize = 2;
+    /// The number of nodes sampled to find the largest component.
+    const SAMPLES: usize = 1024;
+
+    // Link the components of `u` and `v`, by pointing the larger of their
+    // roots to the smaller one.
+    fn link(labels: &[AtomicUsize], u: usize, v: usize) {
+        let mut p1 = labels[u].load(Relaxed);
+        let mut p2 = labels[v].load(Relaxed);
+        while p1 != p2 {
+            let (high, low) = if p1 > p2 { (p1, p2) } else { (p2, p1) };
+            let p_high = labels[high].load(Relaxed);
+            // Done if `high` already points to `low`, or if `high` is a root
+            // and it is linked to `low` before any other change.
+            if p_high == low
+                || (p_high == high
+                    && labels[high]
+                        .compare_exchange(high, low, Relaxed, Relaxed)
+                        .is_ok())
+            {
+                break;
+            }
+            p1 = labels[labels[high].load(Relaxed)].load(Relaxed);
+            p2 = labels[low].load(Relaxed);
+        }
+    }
+
+    // Point every node to its root.
+    fn compress(labels: &[AtomicUsize]) {
+        labels.par_iter().for_each(|label| loop {
+            let parent = label.load(Relaxed);
+            let grandparent = labels[parent].load(Relaxed);
+            if parent == grandparent {
+                break;
+            }
+            label.store(grandparent, Relaxed);
+        });
+    }
+
+    let node_count = g.node_bound();
+    let labels: Vec<_> = (0..node_count)
+        .into_par_iter()
+        .map(AtomicUsize::new)
+        .collect();
+    let neighbors = |u| g.neighbors(g.from_index(u)).map(|v| g.to_index(v));
+
+    for round in 0..NEIGHBOR_ROUNDS {
+        (0..node_count).into_par_iter().for_each(|u| {
+            if let Some(v) = neighbors(u).nth(round) {
+                link(&labels, u, v);
+            }
+        });
+        compress(&labels);
+    }
+
+    // Skipping edges is only valid if they are also found from their target.
+    let largest = if g.is_directed() || node_count == 0 {
+        None
+    } else {
+        let mut counts = HashMap::new();
+        for i in 0..SAMPLES {
+            let label = labels[i * node_count / SAMPLES].load(Relaxed);
+            *counts.entry(label).or_insert(0) += 1;
+        }
+        counts
+            .into_iter()
+            .max_by_key(|&(_, count)| count)
+            .map(|(label, _)| label)
+    };
+
+    (0..node_count).into_par_iter().for_each(|u| {
+        if Some(labels[u].load(Relaxed)) == largest {
+            return;
+        }
+        for v in neighbors(u).skip(NEIGHBOR_ROUNDS) {
+            link(&labels, u, v);
+        }
+    });
+    compress(&labels);
+
+    labels.into_iter().map(AtomicUsize::into_inner).collect()
+}
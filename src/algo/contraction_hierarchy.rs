@@ -0,0 +1,662 @@
+//! Contraction hierarchies, for fast repeated shortest path queries on a
+//! graph that does not change.
+//!
+//! Preprocessing contracts the nodes one by one, from the least to the most
+//! important. Contracting a node removes it from the graph and adds a
+//! *shortcut* edge between any two of its neighbors whose shortest path went
+//! through it. Every shortest path then has a counterpart in the hierarchy
+//! that first only climbs to more important nodes and then only descends, and
+//! a query only has to search upward from both of its ends.
+
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::algo::Measure;
+use crate::csr::Csr;
+use crate::scored::MinScored;
+use crate::visit::{EdgeRef, GraphProp, IntoEdgeReferences, NodeIndexable};
+
+/// The number of nodes a witness search may settle before it gives up, and
+/// the shortcut it looks for a replacement of is added.
+const WITNESS_SEARCH_LIMIT: usize = 100;
+
+/// An edge of the hierarchy.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde-1",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+struct HierarchyEdge<K> {
+    cost: K,
+    /// The node that a shortcut bypasses, or `None` for an edge of the graph.
+    via: Option<u32>,
+}
+
+/// A contraction hierarchy, built by [`contraction_hierarchy`].
+///
+/// Nodes are referred to by the graph the hierarchy was built from, which is
+/// only used to convert its node identifiers to indices and back. The
+/// hierarchy itself is independent of the graph, and can be serialized with
+/// the crate feature `serde-1`. Deserialization checks that the hierarchy is
+/// well formed, but not that it matches any graph.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde-1",
+    derive(serde_derive::Deserialize),
+    serde(
+        try_from = "SerializedHierarchy<K>",
+        bound(deserialize = "K: Measure + Copy + serde::Deserialize<'de>")
+    )
+)]
+pub struct ContractionHierarchy<K> {
+    /// The position of every node in the contraction order.
+    rank: Vec<usize>,
+    /// Edges to nodes of higher rank, in the direction of the graph.
+    upward: Csr<(), HierarchyEdge<K>>,
+    /// Edges from nodes of higher rank, reversed.
+    downward: Csr<(), HierarchyEdge<K>>,
+}
+
+/// \[Generic\] Build a contraction hierarchy of `graph`, to answer shortest
+/// path queries with [`ContractionHierarchy::shortest_path`].
+///
+/// Nodes are contracted in order of their *edge difference*: the number of
+/// shortcuts their contraction adds minus the number of edges it removes.
+/// Nodes next to contracted ones, and above many levels of them, come later,
+/// so that the contracted nodes spread over the graph. A shortcut is left out
+/// if a local search finds a path between its ends that is not longer.
+///
+/// The function `edge_cost` should return the cost for a particular edge.
+/// Edge costs must be non-negative. Edges of undirected graphs can be
+/// traversed both ways.
+///
+/// **Panics** if the graph has 2<sup>32</sup> nodes or more.
+///
+/// Preprocessing takes much longer than a single [`dijkstra`](fn@crate::algo::dijkstra)
+/// search, but queries on road networks then only search a few hundred
+/// nodes.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::contraction_hierarchy;
+/// use petgraph::prelude::*;
+///
+/// let mut g = UnGraph::<(), u32>::new_undirected();
+/// let nodes: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+/// g.extend_with_edges(&[
+///     (nodes[0], nodes[1], 4),
+///     (nodes[1], nodes[2], 1),
+///     (nodes[2], nodes[3], 1),
+///     (nodes[0], nodes[4], 1),
+///     (nodes[4], nodes[5], 1),
+///     (nodes[5], nodes[3], 1),
+/// ]);
+///
+/// let hierarchy = contraction_hierarchy(&g, |e| *e.weight());
+/// assert_eq!(
+///     hierarchy.shortest_path(&g, nodes[1], nodes[5]),
+///     Some((3, vec![nodes[1], nodes[2], nodes[3], nodes[5]]))
+/// );
+/// assert_eq!(hierarchy.distance(&g, nodes[0], nodes[2]), Some(4));
+/// ```
+pub fn contraction_hierarchy<G, F, K>(graph: G, mut edge_cost: F) -> ContractionHierarchy<K>
+where
+    G: IntoEdgeReferences + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let node_count = graph.node_bound();
+    assert!(
+        node_count <= u32::MAX as usize,
+        "contraction hierarchies have less than 2^32 nodes"
+    );
+    let mut contraction = Contraction::new(node_count);
+    for edge in graph.edge_references() {
+        let a = graph.to_index(edge.source());
+        let b = graph.to_index(edge.target());
+        if a == b {
+            continue;
+        }
+        let cost = edge_cost(edge);
+        contraction.add_edge(a, b, cost, None);
+        if !graph.is_directed() {
+            contraction.add_edge(b, a, cost, None);
+        }
+    }
+
+    let mut queue = BinaryHeap::with_capacity(node_count);
+    for node in 0..node_count {
+        let shortcuts = contraction.shortcuts(node);
+        queue.push(MinScored(contraction.priority(node, &shortcuts), node));
+    }
+
+    let mut rank = vec![0; node_count];
+    let mut upward = Vec::new();
+    let mut downward = Vec::new();
+    let mut next_rank = 0;
+    while let Some(MinScored(_, node)) = queue.pop() {
+        // Priorities only change for the neighbors of contracted nodes, so
+        // they are updated lazily.
+        let shortcuts = contraction.shortcuts(node);
+        let priority = contraction.priority(node, &shortcuts);
+        if let Some(&MinScored(next, _)) = queue.peek() {
+            if priority > next {
+                queue.push(MinScored(priority, node));
+                continue;
+            }
+        }
+
+        rank[node] = next_rank;
+        next_rank += 1;
+        let hierarchy_edge = |&(other, cost, via): &(usize, K, Option<usize>)| {
+            let via = via.map(node_id);
+            (node_id(node), node_id(other), HierarchyEdge { cost, via })
+        };
+        upward.extend(contraction.outgoing[node].iter().map(hierarchy_edge));
+        downward.extend(contraction.incoming[node].iter().map(hierarchy_edge));
+        contraction.contract(node);
+        for (a, b, cost) in shortcuts {
+            contraction.add_edge(a, b, cost, Some(node));
+        }
+    }
+
+    ContractionHierarchy {
+        rank,
+        upward: csr(node_count, upward).unwrap(),
+        downward: csr(node_count, downward).unwrap(),
+    }
+}
+
+/// The index of a node in the `Csr`s of the hierarchy.
+fn node_id(index: usize) -> u32 {
+    u32::try_from(index).expect("contraction hierarchies have less than 2^32 nodes")
+}
+
+/// The edge from `from` to `to` stored in `csr`, if any.
+fn find_edge<K>(
+    csr: &Csr<(), HierarchyEdge<K>>,
+    from: usize,
+    to: usize,
+) -> Option<&HierarchyEdge<K>> {
+    let (from, to) = (node_id(from), node_id(to));
+    let i = csr.neighbors_slice(from).binary_search(&to).ok()?;
+    Some(&csr.edges_slice(from)[i])
+}
+
+/// Build the edges of the hierarchy from the edges `(a, b, edge)` stored at
+/// `a`, or return `None` if an edge is stored twice.
+fn csr<K: Clone>(
+    node_count: usize,
+    mut edges: Vec<(u32, u32, HierarchyEdge<K>)>,
+) -> Option<Csr<(), HierarchyEdge<K>>> {
+    edges.sort_unstable_by_key(|&(a, b, _)| (a, b));
+    let mut csr = Csr::from_sorted_edges(&edges).ok()?;
+    while csr.node_count() < node_count {
+        csr.add_node(());
+    }
+    Some(csr)
+}
+
+/// The remaining graph during preprocessing.
+struct Contraction<K> {
+    outgoing: Vec<Vec<(usize, K, Option<usize>)>>,
+    incoming: Vec<Vec<(usize, K, Option<usize>)>>,
+    contracted_neighbors: Vec<usize>,
+    /// One more than the highest level of the contracted neighbors of every
+    /// node, which bounds the depth of the searches through it.
+    level: Vec<usize>,
+    witness: WitnessSearch<K>,
+}
+
+impl<K> Contraction<K>
+where
+    K: Measure + Copy,
+{
+    fn new(node_count: usize) -> Self {
+        Contraction {
+            outgoing: vec![Vec::new(); node_count],
+            incoming: vec![Vec::new(); node_count],
+            contracted_neighbors: vec![0; node_count],
+            level: vec![0; node_count],
+            witness: WitnessSearch::new(node_count),
+        }
+    }
+
+    /// Add an edge from `a` to `b`, or lower the cost of the existing one.
+    fn add_edge(&mut self, a: usize, b: usize, cost: K, via: Option<usize>) {
+        match self.outgoing[a]
+            .iter()
+            .position(|&(target, ..)| target == b)
+        {
+            Some(i) => {
+                if cost < self.outgoing[a][i].1 {
+                    self.outgoing[a][i] = (b, cost, via);
+                    let j = self.incoming[b]
+                        .iter()
+                        .position(|&(source, ..)| source == a)
+                        .unwrap();
+                    self.incoming[b][j] = (a, cost, via);
+                }
+            }
+            None => {
+                self.outgoing[a].push((b, cost, via));
+                self.incoming[b].push((a, cost, via));
+            }
+        }
+    }
+
+    /// The shortcuts that contracting `node` needs.
+    fn shortcuts(&mut self, node: usize) -> Vec<(usize, usize, K)> {
+        let mut shortcuts = Vec::new();
+        let mut targets = Vec::new();
+        for &(source, to_node, _) in &self.incoming[node] {
+            targets.clear();
+            let mut limit = None;
+            for &(target, from_node, _) in &self.outgoing[node] {
+                if target == source {
+                    continue;
+                }
+                let cost = to_node + from_node;
+                if limit.map_or(true, |limit| cost > limit) {
+                    limit = Some(cost);
+                }
+                targets.push((target, cost));
+            }
+            let limit = match limit {
+                Some(limit) => limit,
+                None => continue,
+            };
+            self.witness
+                .run(&self.outgoing, source, node, &targets, limit);
+            for &(target, cost) in &targets {
+                match self.witness.scores[target] {
+                    Some(witness) if witness <= cost => {}
+                    _ => shortcuts.push((source, target, cost)),
+                }
+            }
+        }
+        shortcuts
+    }
+
+    fn priority(&self, node: usize, shortcuts: &[(usize, usize, K)]) -> isize {
+        let removed = self.outgoing[node].len() + self.incoming[node].len();
+        let edge_difference = shortcuts.len() as isize - removed as isize;
+        2 * edge_difference + (self.contracted_neighbors[node] + self.level[node]) as isize
+    }
+
+    /// Remove `node` from the remaining graph.
+    fn contract(&mut self, node: usize) {
+        for (target, ..) in std::mem::take(&mut self.outgoing[node]) {
+            self.incoming[target].retain(|&(source, ..)| source != node);
+            self.contracted_neighbors[target] += 1;
+            self.level[target] = self.level[target].max(self.level[node] + 1);
+        }
+        for (source, ..) in std::mem::take(&mut self.incoming[node]) {
+            self.outgoing[source].retain(|&(target, ..)| target != node);
+            self.contracted_neighbors[source] += 1;
+            self.level[source] = self.level[source].max(self.level[node] + 1);
+        }
+    }
+}
+
+/// A search for paths that make a shortcut unnecessary.
+struct WitnessSearch<K> {
+    scores: Vec<Option<K>>,
+    /// The nodes with a score, to reset them for the next search.
+    reached: Vec<usize>,
+    is_target: Vec<bool>,
+    visit_next: BinaryHeap<MinScored<K, usize>>,
+}
+
+impl<K> WitnessSearch<K>
+where
+    K: Measure + Copy,
+{
+    fn new(node_count: usize) -> Self {
+        WitnessSearch {
+            scores: vec![None; node_count],
+            reached: Vec::new(),
+            is_target: vec![false; node_count],
+            visit_next: BinaryHeap::new(),
+        }
+    }
+
+    /// Search from `start` around `avoid` until all `targets` are settled, up
+    /// to a cost of `limit`.
+    fn run(
+        &mut self,
+        outgoing: &[Vec<(usize, K, Option<usize>)>],
+        start: usize,
+        avoid: usize,
+        targets: &[(usize, K)],
+        limit: K,
+    ) {
+        for node in self.reached.drain(..) {
+            self.scores[node] = None;
+        }
+        self.visit_next.clear();
+        for &(target, _) in targets {
+            self.is_target[target] = true;
+        }
+        let mut remaining = targets.len();
+
+        self.scores[start] = Some(K::default());
+        self.reached.push(start);
+        self.visit_next.push(MinScored(K::default(), start));
+        let mut settled = 0;
+        while let Some(MinScored(score, node)) = self.visit_next.pop() {
+            if Some(score) > self.scores[node] {
+                continue;
+            }
+            if score > limit || settled == WITNESS_SEARCH_LIMIT {
+                break;
+            }
+            settled += 1;
+            if self.is_target[node] {
+                remaining -= 1;
+                if remaining == 0 {
+                    break;
+                }
+            }
+            for &(next, cost, _) in &outgoing[node] {
+                if next == avoid {
+                    continue;
+                }
+                let next_score = score + cost;
+                match self.scores[next] {
+                    Some(old) if old <= next_score => {}
+                    old => {
+                        if old.is_none() {
+                            self.reached.push(next);
+                        }
+                        self.scores[next] = Some(next_score);
+                        self.visit_next.push(MinScored(next_score, next));
+                    }
+                }
+            }
+        }
+
+        for &(target, _) in targets {
+            self.is_target[target] = false;
+        }
+    }
+}
+
+impl<K> ContractionHierarchy<K>
+where
+    K: Measure + Copy,
+{
+    /// Return the cost of the shortest path from `start` to `goal`, if `goal`
+    /// is reachable.
+    ///
+    /// `graph` must be the graph the hierarchy was built from.
+    pub fn distance<G>(&self, graph: G, start: G::NodeId, goal: G::NodeId) -> Option<K>
+    where
+        G: NodeIndexable,
+    {
+        let start = graph.to_index(start);
+        let goal = graph.to_index(goal);
+        self.search(start, goal).map(|search| search.cost)
+    }
+
+    /// Return the cost and the path of subsequent `NodeId` from `start` to
+    /// `goal` of the shortest path, if `goal` is reachable.
+    ///
+    /// `graph` must be the graph the hierarchy was built from.
+    pub fn shortest_path<G>(
+        &self,
+        graph: G,
+        start: G::NodeId,
+        goal: G::NodeId,
+    ) -> Option<(K, Vec<G::NodeId>)>
+    where
+        G: NodeIndexable,
+    {
+        let search = self.search(graph.to_index(start), graph.to_index(goal))?;
+
+        // The path through the hierarchy climbs to the meeting node and
+        // descends from it again.
+        let mut hierarchy_path = vec![search.meet];
+        let mut node = search.meet;
+        while let Some(&previous) = search.forward.predecessors.get(&node) {
+            hierarchy_path.push(previous);
+            node = previous;
+        }
+        hierarchy_path.reverse();
+        let mut node = search.meet;
+        while let Some(&next) = search.backward.predecessors.get(&node) {
+            hierarchy_path.push(next);
+            node = next;
+        }
+
+        let mut path = vec![graph.from_index(hierarchy_path[0])];
+        for pair in hierarchy_path.windows(2) {
+            self.unpack(pair[0], pair[1], |node| path.push(graph.from_index(node)));
+        }
+        Some((search.cost, path))
+    }
+
+    /// The edge of the hierarchy from `a` to `b`.
+    fn edge(&self, a: usize, b: usize) -> &HierarchyEdge<K> {
+        let (csr, from, to) = if self.rank[a] < self.rank[b] {
+            (&self.upward, a, b)
+        } else {
+            (&self.downward, b, a)
+        };
+        find_edge(csr, from, to).unwrap()
+    }
+
+    /// Replace the edge from `a` to `b` with the edges of the graph it stands
+    /// for, and visit the nodes after `a` on them.
+    fn unpack(&self, a: usize, b: usize, mut visit: impl FnMut(usize)) {
+        let mut stack = vec![(a, b)];
+        while let Some((a, b)) = stack.pop() {
+            match self.edge(a, b).via {
+                Some(via) => {
+                    stack.push((via as usize, b));
+                    stack.push((a, via as usize));
+                }
+                None => visit(b),
+            }
+        }
+    }
+
+    fn search(&self, start: usize, goal: usize) -> Option<Search<K>> {
+        let mut forward = UpwardSearch::new(start);
+        let mut backward = UpwardSearch::new(goal);
+        let mut best: Option<(K, usize)> = None;
+        if start == goal {
+            best = Some((K::default(), start));
+        }
+
+        loop {
+            // A side is done once it cannot find a shorter path.
+            let f = forward
+                .peek()
+                .filter(|&f| best.map_or(true, |(b, _)| f < b));
+            let b = backward
+                .peek()
+                .filter(|&b| best.map_or(true, |(c, _)| b < c));
+            let (side, other, csr) = match (f, b) {
+                (Some(f), Some(b)) if f <= b => (&mut forward, &backward, &self.upward),
+                (Some(_), None) => (&mut forward, &backward, &self.upward),
+                (_, Some(_)) => (&mut backward, &forward, &self.downward),
+                (None, None) => break,
+            };
+
+            let MinScored(score, node) = side.visit_next.pop().unwrap();
+            if let Some(&other_score) = other.scores.get(&node) {
+                let cost = score + other_score;
+                if best.map_or(true, |(best, _)| cost < best) {
+                    best = Some((cost, node));
+                }
+            }
+            let row = node_id(node);
+            for (&next, edge) in csr.neighbors_slice(row).iter().zip(csr.edges_slice(row)) {
+                let next = next as usize;
+                let next_score = score + edge.cost;
+                match side.scores.get(&next) {
+                    Some(&old) if old <= next_score => {}
+                    _ => {
+                        side.scores.insert(next, next_score);
+                        side.predecessors.insert(next, node);
+                        side.visit_next.push(MinScored(next_score, next));
+                    }
+                }
+            }
+        }
+
+        best.map(|(cost, meet)| Search {
+            cost,
+            meet,
+            forward,
+            backward,
+        })
+    }
+}
+
+/// The serialized form of a [`ContractionHierarchy`]: the rank of every node,
+/// and the edges of the hierarchy as `(a, b, edge)` where `a` is the end of
+/// lower rank.
+#[cfg(feature = "serde-1")]
+#[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+struct SerializedHierarchy<K> {
+    rank: Vec<usize>,
+    upward: Vec<(u32, u32, HierarchyEdge<K>)>,
+    downward: Vec<(u32, u32, HierarchyEdge<K>)>,
+}
+
+#[cfg(feature = "serde-1")]
+impl<K> serde::Serialize for ContractionHierarchy<K>
+where
+    K: Clone + serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let edges = |csr: &Csr<(), HierarchyEdge<K>>| {
+            csr.edge_references()
+                .map(|edge| (edge.source(), edge.target(), edge.weight().clone()))
+                .collect()
+        };
+        SerializedHierarchy {
+            rank: self.rank.clone(),
+            upward: edges(&self.upward),
+            downward: edges(&self.downward),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde-1")]
+impl<K> TryFrom<SerializedHierarchy<K>> for ContractionHierarchy<K>
+where
+    K: Measure + Copy,
+{
+    type Error = &'static str;
+
+    fn try_from(serialized: SerializedHierarchy<K>) -> Result<Self, Self::Error> {
+        let SerializedHierarchy {
+            rank,
+            upward,
+            downward,
+        } = serialized;
+        let node_count = rank.len();
+        if node_count > u32::MAX as usize {
+            return Err("invalid size: too many nodes for a contraction hierarchy");
+        }
+        let mut ranked = vec![false; node_count];
+        for &r in &rank {
+            if r >= node_count || std::mem::replace(&mut ranked[r], true) {
+                return Err("invalid value: node ranks are not a permutation");
+            }
+        }
+        // Edges lead to nodes of higher rank, and shortcuts bypass a node of
+        // lower rank than both of their ends.
+        for &(a, b, ref edge) in upward.iter().chain(&downward) {
+            let (a, b) = (a as usize, b as usize);
+            let via = edge.via.map(|via| via as usize);
+            if a >= node_count || b >= node_count || via.map_or(false, |v| v >= node_count) {
+                return Err("invalid value: edge of the hierarchy out of bounds");
+            }
+            if rank[a] >= rank[b] || via.map_or(false, |v| rank[v] >= rank[a]) {
+                return Err("invalid value: edge of the hierarchy out of rank order");
+            }
+            if matches!(
+                edge.cost.partial_cmp(&K::default()),
+                None | Some(std::cmp::Ordering::Less)
+            ) {
+                return Err("invalid value: negative edge cost");
+            }
+        }
+        let duplicate = "invalid value: edge of the hierarchy stored twice";
+        let hierarchy = ContractionHierarchy {
+            rank,
+            upward: csr(node_count, upward).ok_or(duplicate)?,
+            downward: csr(node_count, downward).ok_or(duplicate)?,
+        };
+
+        // The edges a shortcut stands for are stored at the node it bypasses.
+        let shortcuts_unpack = |csr: &Csr<(), HierarchyEdge<K>>, upward: bool| {
+            csr.edge_references().all(|edge| {
+                let (a, b) = (edge.source() as usize, edge.target() as usize);
+                let (from, to) = if upward { (a, b) } else { (b, a) };
+                edge.weight().via.map_or(true, |via| {
+                    let via = via as usize;
+                    find_edge(&hierarchy.downward, via, from).is_some()
+                        && find_edge(&hierarchy.upward, via, to).is_some()
+                })
+            })
+        };
+        if !shortcuts_unpack(&hierarchy.upward, true)
+            || !shortcuts_unpack(&hierarchy.downward, false)
+        {
+            return Err("invalid value: shortcut without the edges it bypasses");
+        }
+        Ok(hierarchy)
+    }
+}
+
+/// The result of a query: the two searches and where their paths meet.
+struct Search<K> {
+    cost: K,
+    meet: usize,
+    forward: UpwardSearch<K>,
+    backward: UpwardSearch<K>,
+}
+
+/// A search from one end of a query, along edges to nodes of higher rank.
+struct UpwardSearch<K> {
+    scores: HashMap<usize, K>,
+    /// The node before every node on its path from the origin of the search.
+    predecessors: HashMap<usize, usize>,
+    visit_next: BinaryHeap<MinScored<K, usize>>,
+}
+
+impl<K> UpwardSearch<K>
+where
+    K: Measure + Copy,
+{
+    fn new(origin: usize) -> Self {
+        let mut scores = HashMap::new();
+        scores.insert(origin, K::default());
+        let mut visit_next = BinaryHeap::new();
+        visit_next.push(MinScored(K::default(), origin));
+        UpwardSearch {
+            scores,
+            predecessors: HashMap::new(),
+            visit_next,
+        }
+    }
+
+    /// The lowest score of a node that is not settled yet.
+    fn peek(&mut self) -> Option<K> {
+        while let Some(&MinScored(score, node)) = self.visit_next.peek() {
+            if score <= self.scores[&node] {
+                return Some(score);
+            }
+            self.visit_next.pop();
+        }
+        None
+    }
+}
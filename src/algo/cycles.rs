@@ -0,0 +1,322 @@
+//! Enumeration of elementary cycles and cycle bases.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use indexmap::IndexSet;
+
+use super::kosaraju_scc;
+use crate::visit::{
+    GraphBase, IntoNeighbors, IntoNeighborsDirected, IntoNodeIdentifiers, NodeFiltered, Visitable,
+};
+use crate::Direction::Outgoing;
+
+/// \[Generic\] Return an iterator over the elementary cycles of a directed
+/// graph, using Johnson's algorithm.
+///
+/// An elementary cycle is a closed path that visits no node twice. Each cycle
+/// is produced once, as the list of its nodes starting at the node that comes
+/// first in `node_identifiers` order, without repeating it at the end. A self
+/// loop is a cycle of one node.
+///
+/// If `max_length` is given, only cycles with at most that many nodes are
+/// produced, using the bounded variant of Gupta and Suzumura.
+///
+/// Cycles are computed lazily. Finding all **c** cycles takes
+/// **O((|V| + |E|)(c + 1))** time, or **O((|V| + |E|) L (c + 1))** with a
+/// length bound **L**. Since a graph can have exponentially many cycles,
+/// consider limiting the output with [`Iterator::take`] or `max_length`.
+///
+/// Parallel edges do not give rise to distinct cycles.
+///
+/// # Example
+/// ```
+/// use petgraph::algo::elementary_cycles;
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (1, 0), (2, 2)]);
+///
+/// let mut cycles: Vec<Vec<usize>> = elementary_cycles(&graph, None)
+///     .map(|cycle| cycle.into_iter().map(|n| n.index()).collect())
+///     .collect();
+/// cycles.sort();
+/// assert_eq!(cycles, vec![vec![0, 1], vec![0, 1, 2], vec![2]]);
+///
+/// // Only cycles of at most two nodes.
+/// assert_eq!(elementary_cycles(&graph, Some(2)).count(), 2);
+/// ```
+pub fn elementary_cycles<G>(graph: G, max_length: Option<usize>) -> ElementaryCycles<G>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers + Visitable,
+    G::NodeId: Hash + Eq,
+{
+    let order = graph
+        .node_identifiers()
+        .enumerate()
+        .map(|(i, n)| (n, i))
+        .collect();
+    let mut cycles = ElementaryCycles {
+        graph,
+        length_bound: max_length.unwrap_or(usize::MAX),
+        bounded: max_length.is_some(),
+        order,
+        components: Vec::new(),
+        successors: HashMap::new(),
+        path: IndexSet::new(),
+        stack: Vec::new(),
+        distance: Vec::new(),
+        lock: HashMap::new(),
+        blocked_by: HashMap::new(),
+    };
+    if max_length != Some(0) {
+        let all: HashSet<_> = graph.node_identifiers().collect();
+        cycles.push_components(all);
+    }
+    cycles
+}
+
+/// An iterator over the elementary cycles of a directed graph.
+///
+/// See [`elementary_cycles`].
+pub struct ElementaryCycles<G>
+where
+    G: GraphBase,
+{
+    graph: G,
+    length_bound: usize,
+    bounded: bool,
+    // Position of every node in `node_identifiers`.
+    order: HashMap<G::NodeId, usize>,
+    // Strongly connected components that may still contain cycles.
+    components: Vec<HashSet<G::NodeId>>,
+    // The distinct successors of every node within the searched component.
+    successors: HashMap<G::NodeId, Vec<G::NodeId>>,
+    path: IndexSet<G::NodeId>,
+    // For each node on the path, the next successor to visit.
+    stack: Vec<usize>,
+    // For each node on the path, the shortest known distance to the start
+    // node, `length_bound` if no cycle was found through it yet.
+    distance: Vec<usize>,
+    // Nodes are only entered from paths shorter than their lock. In
+    // Johnson's algorithm a node is either blocked (lock 0) or free.
+    lock: HashMap<G::NodeId, usize>,
+    // The sets `B` of Johnson's algorithm: nodes to release with a node.
+    blocked_by: HashMap<G::NodeId, HashSet<G::NodeId>>,
+}
+
+impl<G> ElementaryCycles<G>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers + Visitable,
+    G::NodeId: Hash + Eq,
+{
+    // Queue the strongly connected components of `nodes` that contain a cycle.
+    fn push_components(&mut self, nodes: HashSet<G::NodeId>) {
+        let graph = self.graph;
+        let filtered = NodeFiltered(graph, &nodes);
+        for scc in kosaraju_scc(&filtered) {
+            let has_cycle = match scc[..] {
+                [n] => graph.neighbors_directed(n, Outgoing).any(|m| m == n),
+                _ => true,
+            };
+            if has_cycle {
+                self.components.push(scc.into_iter().collect());
+            }
+        }
+    }
+
+    // Start searching the cycles through the first node of the next component.
+    fn next_component(&mut self) -> bool {
+        let component = match self.components.pop() {
+            Some(component) => component,
+            None => return false,
+        };
+        self.successors.clear();
+        for &n in &component {
+            let mut seen = HashSet::new();
+            let successors = self
+                .graph
+                .neighbors_directed(n, Outgoing)
+                .filter(|m| component.contains(m) && seen.insert(*m))
+                .collect();
+            self.successors.insert(n, successors);
+        }
+        let start = *component
+            .iter()
+            .min_by_key(|n| self.order[n])
+            .expect("components are not empty");
+        self.path.clear();
+        self.path.insert(start);
+        self.stack.clear();
+        self.stack.push(0);
+        self.distance.clear();
+        self.distance.push(self.length_bound);
+        self.lock.clear();
+        self.lock.insert(start, 0);
+        self.blocked_by.clear();
+        true
+    }
+
+    // Release `node` after a cycle was found `distance` steps behind it.
+    fn unblock(&mut self, node: G::NodeId, distance: usize) {
+        let mut pending = vec![(distance, node)];
+        while let Some((distance, u)) = pending.pop() {
+            if self.bounded {
+                let lock = self.length_bound - distance + 1;
+                if self.lock.get(&u).map_or(false, |&l| l < lock) {
+                    self.lock.insert(u, lock);
+                    if let Some(b) = self.blocked_by.get(&u) {
+                        pending.extend(
+                            b.iter()
+                                .filter(|w| !self.path.contains(*w))
+                                .map(|&w| (distance + 1, w)),
+                        );
+                    }
+                }
+            } else if self.lock.remove(&u).is_some() {
+                if let Some(b) = self.blocked_by.remove(&u) {
+                    pending.extend(b.into_iter().map(|w| (distance, w)));
+                }
+            }
+        }
+    }
+}
+
+impl<G> Iterator for ElementaryCycles<G>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers + Visitable,
+    G::NodeId: Hash + Eq,
+{
+    type Item = Vec<G::NodeId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let start = match self.path.first().cloned() {
+                Some(start) => start,
+                None if self.next_component() => continue,
+                None => return None,
+            };
+            while let Some(&v) = self.path.last() {
+                let successors = &self.successors[&v];
+                let position = self.stack.last_mut().unwrap();
+                if let Some(&w) = successors.get(*position) {
+                    *position += 1;
+                    if w == start {
+                        *self.distance.last_mut().unwrap() = 1;
+                        return Some(self.path.iter().cloned().collect());
+                    }
+                    let lock = self.lock.get(&w).map_or(self.length_bound, |&lock| lock);
+                    if self.path.len() < lock {
+                        let lock = if self.bounded { self.path.len() + 1 } else { 0 };
+                        self.path.insert(w);
+                        self.lock.insert(w, lock);
+                        self.stack.push(0);
+                        self.distance.push(self.length_bound);
+                    }
+                    continue;
+                }
+
+                // All successors are explored, backtrack.
+                self.stack.pop();
+                self.path.pop();
+                let distance = self.distance.pop().unwrap();
+                if let Some(last) = self.distance.last_mut() {
+                    *last = (*last).min(distance.saturating_add(1));
+                }
+                if distance < self.length_bound {
+                    self.unblock(v, distance);
+                } else {
+                    for &w in &self.successors[&v] {
+                        self.blocked_by.entry(w).or_default().insert(v);
+                    }
+                }
+            }
+
+            // All cycles through `start` are found, continue without it.
+            let mut rest: HashSet<_> = self.successors.keys().cloned().collect();
+            rest.remove(&start);
+            self.push_components(rest);
+        }
+    }
+}
+
+/// \[Generic\] Return a cycle basis of an undirected graph.
+///
+/// A cycle basis is a minimal set of cycles such that every cycle of the
+/// graph can be written as a sum (symmetric difference of edge sets) of cycles
+/// in the basis. There are `|E| - |V| + c` cycles in a basis of a simple
+/// graph with `c` connected components.
+///
+/// Each cycle is the list of its nodes, without repeating the first node at
+/// the end. A self loop is a cycle of one node. Parallel edges are treated as
+/// one edge.
+///
+/// The basis is found with Paton's algorithm in **O(|V| + |E|)** space and
+/// at most **O(|V|³)** time.
+///
+/// # Example
+/// ```
+/// use petgraph::algo::cycle_basis;
+/// use petgraph::prelude::*;
+///
+/// // Two triangles sharing the edge 1 - 2.
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (1, 3), (3, 2)]);
+///
+/// let basis = cycle_basis(&graph);
+/// assert_eq!(basis.len(), 2);
+/// assert!(basis.iter().all(|cycle| cycle.len() == 3));
+/// ```
+pub fn cycle_basis<G>(graph: G) -> Vec<Vec<G::NodeId>>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers,
+    G::NodeId: Hash + Eq,
+{
+    let mut cycles = Vec::new();
+    // The parent of every node in the spanning forest.
+    let mut pred = HashMap::new();
+    // For every node, the neighbors whose edge to it was already used.
+    let mut used: HashMap<G::NodeId, HashSet<G::NodeId>> = HashMap::new();
+
+    for root in graph.node_identifiers() {
+        if pred.contains_key(&root) {
+            continue;
+        }
+        pred.insert(root, root);
+        used.insert(root, HashSet::new());
+        let mut stack = vec![root];
+        while let Some(z) = stack.pop() {
+            let mut seen = HashSet::new();
+            for nbr in graph.neighbors(z) {
+                if !seen.insert(nbr) {
+                    // A parallel edge.
+                    continue;
+                }
+                if let Entry::Vacant(entry) = used.entry(nbr) {
+                    // A tree edge to a new node.
+                    entry.insert(HashSet::from_iter([z]));
+                    pred.insert(nbr, z);
+                    stack.push(nbr);
+                    continue;
+                }
+                if nbr == z {
+                    if used.get_mut(&z).unwrap().insert(z) {
+                        cycles.push(vec![z]);
+                    }
+                } else if !used[&z].contains(&nbr) {
+                    // A non-tree edge closes the cycle through the tree.
+                    let pn = &used[&nbr];
+                    let mut cycle = vec![nbr, z];
+                    let mut p = pred[&z];
+                    while !pn.contains(&p) {
+                        cycle.push(p);
+                        p = pred[&p];
+                    }
+                    cycle.push(p);
+                    cycles.push(cycle);
+                    used.get_mut(&nbr).unwrap().insert(z);
+                }
+            }
+        }
+    }
+    cycles
+}
@@ -0,0 +1,200 @@
+//! Parallel single-source shortest paths by delta-stepping.
+
+use rayon::prelude::*;
+
+use crate::algo::Measure;
+use crate::visit::{EdgeRef, IntoEdges, NodeIndexable};
+
+/// \[Generic\] Parallel delta-stepping single-source shortest paths.
+///
+/// Compute the length of the shortest path from `start` to every reachable
+/// node, using the delta-stepping algorithm of Meyer and Sanders,
+/// *Δ-stepping: a parallelizable shortest path algorithm* (2003).
+///
+/// The nodes are settled in buckets of width `delta`: the nodes at the
+/// smallest tentative distances, up to `delta` more than the smallest one,
+/// are settled together by relaxing their *light* edges, of cost less than
+/// `delta`, until none of their distances changes. The *heavy* edges of the
+/// settled nodes are then relaxed once. The edges of the nodes of a bucket
+/// are relaxed in parallel, using [rayon].
+///
+/// A small `delta` relaxes few edges more than once, like
+/// [`dijkstra`](fn@crate::algo::dijkstra), but settles few nodes at a time.
+/// A large one settles more nodes in parallel, but may relax their edges more
+/// often, like [`bellman_ford`](fn@crate::algo::bellman_ford). The average
+/// edge cost divided by the average degree is a good start.
+///
+/// The function `edge_cost` should return the cost for a particular edge,
+/// which must be non-negative. Edges of undirected graphs can be traversed
+/// both ways.
+///
+/// Returns the cost of the shortest path to every node, indexed by its
+/// [`NodeIndexable::to_index`], or `None` if it is not reachable from `start`.
+///
+/// **Panics** if `delta` is not positive.
+///
+/// [rayon]: https://docs.rs/rayon
+///
+/// # Complexity
+/// * Time complexity: **O(|V|·|E|)** in the worst case, close to
+///   **O(|V| + |E|)** on graphs with random edge costs and a suitable `delta`.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::delta_stepping::parallel_delta_stepping;
+/// use petgraph::prelude::*;
+///
+/// let mut graph = DiGraph::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// let d = graph.add_node(());
+/// graph.extend_with_edges(&[(a, b, 1.), (b, c, 1.), (a, c, 3.), (c, a, 1.)]);
+///
+/// let distances = parallel_delta_stepping(&graph, a, 1.5, |e| *e.weight());
+/// assert_eq!(distances, [Some(0.), Some(1.), Some(2.), None]);
+/// assert_eq!(distances[c.index()], Some(2.));
+/// ```
+pub fn parallel_delta_stepping<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    delta: K,
+    edge_cost: F,
+) -> Vec<Option<K>>
+where
+    G: IntoEdges + NodeIndexable + Sync,
+    F: Fn(G::EdgeRef) -> K + Sync,
+    K: Measure + Copy + Send + Sync,
+{
+    assert!(delta > K::default(), "delta must be positive");
+    let start = graph.to_index(start);
+    // Run the search on a thread of the pool: it starts many short parallel
+    // operations, which are much slower to start from outside of the pool.
+    let (graph, edge_cost) = (&graph, &edge_cost);
+    rayon::scope(move |_| delta_stepping(*graph, start, delta, edge_cost))
+}
+
+fn delta_stepping<G, F, K>(graph: G, start: usize, delta: K, edge_cost: &F) -> Vec<Option<K>>
+where
+    G: IntoEdges + NodeIndexable + Sync,
+    F: Fn(G::EdgeRef) -> K + Sync,
+    K: Measure + Copy + Send + Sync,
+{
+    let mut distances = vec![None; graph.node_bound()];
+    distances[start] = Some(K::default());
+
+    // The nodes whose distance changed since they were last settled, with
+    // repetitions.
+    let mut pending = vec![start];
+    while !pending.is_empty() {
+        // The next bucket holds the nodes up to `delta` away from the
+        // closest pending node.
+        let distance = |v: usize| distances[v].unwrap();
+        let closest = pending
+            .par_iter()
+            .map(|&v| distance(v))
+            .reduce_with(|a, b| if b < a { b } else { a })
+            .unwrap();
+        let bound = closest + delta;
+        let (mut frontier, rest): (Vec<_>, Vec<_>) =
+            pending.into_par_iter().partition(|&v| distance(v) < bound);
+        pending = rest;
+        frontier.par_sort_unstable();
+        frontier.dedup();
+
+        // Relax the light edges until the bucket is settled.
+        let mut settled = Vec::new();
+        while !frontier.is_empty() {
+            let requests =
+                edge_requests(graph, &distances, &frontier, |cost| cost < delta, edge_cost);
+            settled.append(&mut frontier);
+            for v in relax(&mut distances, requests) {
+                if distances[v].unwrap() < bound {
+                    frontier.push(v);
+                } else {
+                    pending.push(v);
+                }
+            }
+        }
+
+        // The heavy edges lead out of the bucket.
+        settled.par_sort_unstable();
+        settled.dedup();
+        let requests = edge_requests(graph, &distances, &settled, |cost| cost >= delta, edge_cost);
+        pending.extend(relax(&mut distances, requests));
+    }
+    distances
+}
+
+/// Return the distances through the edges of the nodes of `frontier` whose
+/// cost is `selected`, with the index of their target, where they are
+/// shorter than the current distance of the target.
+fn edge_requests<G, F, K>(
+    graph: G,
+    distances: &[Option<K>],
+    frontier: &[usize],
+    selected: impl Fn(K) -> bool + Sync,
+    edge_cost: &F,
+) -> Vec<(usize, K)>
+where
+    G: IntoEdges + NodeIndexable + Sync,
+    F: Fn(G::EdgeRef) -> K + Sync,
+    K: Measure + Copy + Send + Sync,
+{
+    frontier
+        .par_iter()
+        .flat_map_iter(|&u| {
+            let distance = distances[u].unwrap();
+            let selected = &selected;
+            graph.edges(graph.from_index(u)).filter_map(move |edge| {
+                let cost = edge_cost(edge);
+                if !selected(cost) {
+                    return None;
+                }
+                let v = graph.to_index(edge.target());
+                let candidate = distance + cost;
+                match distances[v] {
+                    Some(current) if current <= candidate => None,
+                    _ => Some((v, candidate)),
+                }
+            })
+        })
+        .collect()
+}
+
+/// Apply the shortest of the requested distances of every node where it is
+/// shorter than its current distance, and return those nodes.
+fn relax<K>(distances: &mut [Option<K>], mut requests: Vec<(usize, K)>) -> Vec<usize>
+where
+    K: Measure + Copy + Send + Sync,
+{
+    if requests.is_empty() {
+        return Vec::new();
+    }
+    requests.par_sort_unstable_by_key(|&(v, _)| v);
+
+    // Every task updates a range of the distances, from the requests for it.
+    let chunks = 4 * rayon::current_num_threads();
+    let chunk_len = ((distances.len() + chunks - 1) / chunks).max(1);
+    distances
+        .par_chunks_mut(chunk_len)
+        .enumerate()
+        .flat_map_iter(|(i, chunk)| {
+            let first = i * chunk_len;
+            let from = requests.partition_point(|&(v, _)| v < first);
+            let to = requests.partition_point(|&(v, _)| v < first + chunk.len());
+            let mut improved = Vec::new();
+            for &(v, candidate) in &requests[from..to] {
+                let current = &mut chunk[v - first];
+                if current.map_or(true, |current| candidate < current) {
+                    if improved.last() != Some(&v) {
+                        improved.push(v);
+                    }
+                    *current = Some(candidate);
+                }
+            }
+            improved
+        })
+        .collect()
+}
@@ -5,7 +5,7 @@ use std::hash::Hash;
 
 use crate::algo::Measure;
 use crate::scored::MinScored;
-use crate::visit::{EdgeRef, IntoEdges, VisitMap, Visitable};
+use crate::visit::{EdgeRef, IntoEdges, NodeIndexable, VisitMap, Visitable};
 
 /// \[Generic\] Dijkstra's shortest path algorithm.
 ///
@@ -70,20 +70,82 @@ use crate::visit::{EdgeRef, IntoEdges, VisitMap, Visitable};
 /// // z is not inside res because there is not path from b to z.
 /// ```
 pub fn dijkstra<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: Option<G::NodeId>,
+    edge_cost: F,
+) -> HashMap<G::NodeId, K>
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    dijkstra_impl(graph, start, goal, edge_cost, |_, _| {})
+}
+
+/// \[Generic\] Dijkstra's shortest path algorithm, with the predecessor of
+/// every node on its shortest path.
+///
+/// Like [`dijkstra`], but also returns a `HashMap` that maps every node
+/// other than `start` to the node before it on its shortest path, so that the
+/// paths can be reconstructed by following predecessors back to `start`.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::dijkstra_with_predecessors;
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1), (0, 2, 5), (2, 3, 1)]);
+/// let (scores, predecessors) =
+///     dijkstra_with_predecessors(&graph, 0.into(), None, |e| *e.weight());
+/// assert_eq!(scores[&3.into()], 3);
+///
+/// let mut path = vec![NodeIndex::new(3)];
+/// while let Some(&previous) = predecessors.get(path.last().unwrap()) {
+///     path.push(previous);
+/// }
+/// path.reverse();
+/// assert_eq!(path, [0.into(), 1.into(), 2.into(), 3.into()]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn dijkstra_with_predecessors<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: Option<G::NodeId>,
+    edge_cost: F,
+) -> (HashMap<G::NodeId, K>, HashMap<G::NodeId, G::NodeId>)
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let mut predecessors = HashMap::new();
+    let scores = dijkstra_impl(graph, start, goal, edge_cost, |next, node| {
+        predecessors.insert(next, node);
+    });
+    (scores, predecessors)
+}
+
+// Dijkstra's algorithm, calling `improved(next, node)` whenever the path to
+// `next` through `node` is the shortest so far.
+fn dijkstra_impl<G, F, K, P>(
     graph: G,
     start: G::NodeId,
     goal: Option<G::NodeId>,
     mut edge_cost: F,
+    mut improved: P,
 ) -> HashMap<G::NodeId, K>
 where
     G: IntoEdges + Visitable,
     G::NodeId: Eq + Hash,
     F: FnMut(G::EdgeRef) -> K,
     K: Measure + Copy,
+    P: FnMut(G::NodeId, G::NodeId),
 {
     let mut visited = graph.visit_map();
     let mut scores = HashMap::new();
-    //let mut predecessor = HashMap::new();
     let mut visit_next = BinaryHeap::new();
     let zero_score = K::default();
     scores.insert(start, zero_score);
@@ -106,13 +168,13 @@ where
                     if next_score < *ent.get() {
                         *ent.into_mut() = next_score;
                         visit_next.push(MinScored(next_score, next));
-                        //predecessor.insert(next.clone(), node.clone());
+                        improved(next, node);
                     }
                 }
                 Vacant(ent) => {
                     ent.insert(next_score);
                     visit_next.push(MinScored(next_score, next));
-                    //predecessor.insert(next.clone(), node.clone());
+                    improved(next, node);
                 }
             }
         }
@@ -120,3 +182,249 @@ where
     }
     scores
 }
+
+/// Workspace for repeated shortest path searches on the same graph.
+///
+/// [`dijkstra`], [`astar`](fn@super::astar) and
+/// [`bellman_ford`](fn@super::bellman_ford) allocate their maps, vectors or
+/// priority queue on every call. The workspace instead keeps dense arrays
+/// indexed by the node indices of the graph (see [`NodeIndexable`]) and the
+/// priority queue between searches with [`dijkstra_with_workspace`],
+/// [`astar_with_workspace`](super::astar::astar_with_workspace) and
+/// [`bellman_ford_with_workspace`](super::bellman_ford_with_workspace). Once
+/// it has grown to the size of the graph, searches allocate nothing but the paths
+/// they return. Every search starts a new generation instead of clearing the
+/// arrays, so that its cost depends only on the part of the graph that it
+/// explores.
+///
+/// After a search, the workspace gives the cost of and path to every node the
+/// search settled. The results of a search are lost when the next one starts.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::dijkstra::{dijkstra_with_workspace, ShortestPathWorkspace};
+/// use petgraph::prelude::*;
+///
+/// let graph = UnGraph::<(), u32>::from_edges(&[(0, 1, 2), (1, 2, 2), (0, 2, 5), (2, 3, 1)]);
+/// let mut workspace = ShortestPathWorkspace::new(&graph);
+///
+/// for (from, to, cost) in [(0, 3, 5), (3, 1, 3), (1, 1, 0)] {
+///     let found = dijkstra_with_workspace(&graph, from.into(), Some(to.into()), |e| *e.weight(), &mut workspace);
+///     assert_eq!(found, Some(cost));
+/// }
+/// assert_eq!(workspace.path_to(&graph, 1.into()), Some(vec![1.into()]));
+///
+/// dijkstra_with_workspace(&graph, 0.into(), None, |e| *e.weight(), &mut workspace);
+/// assert_eq!(workspace.distance(&graph, 3.into()), Some(5));
+/// assert_eq!(
+///     workspace.path_to(&graph, 3.into()),
+///     Some(vec![0.into(), 1.into(), 2.into(), 3.into()])
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct ShortestPathWorkspace<K> {
+    /// The number of the current search.
+    generation: usize,
+    /// The generation in which every node was last reached.
+    reached: Vec<usize>,
+    /// The generation in which every node was last settled.
+    settled: Vec<usize>,
+    scores: Vec<K>,
+    /// The index of the node before every node on its path, `usize::MAX` for
+    /// the start.
+    predecessors: Vec<usize>,
+    /// Nodes by estimated cost, with their cost when they were queued.
+    pub(crate) visit_next: BinaryHeap<MinScored<K, (usize, K)>>,
+}
+
+impl<K> Default for ShortestPathWorkspace<K> {
+    fn default() -> Self {
+        ShortestPathWorkspace {
+            generation: 0,
+            reached: Vec::new(),
+            settled: Vec::new(),
+            scores: Vec::new(),
+            predecessors: Vec::new(),
+            visit_next: BinaryHeap::new(),
+        }
+    }
+}
+
+impl<K> ShortestPathWorkspace<K>
+where
+    K: Copy + Default,
+{
+    /// Create a workspace for searches in `graph`.
+    pub fn new<G>(graph: G) -> Self
+    where
+        G: NodeIndexable,
+    {
+        let mut workspace = Self::default();
+        workspace.grow(graph.node_bound());
+        workspace
+    }
+
+    fn grow(&mut self, node_bound: usize) {
+        if self.reached.len() < node_bound {
+            self.reached.resize(node_bound, 0);
+            self.settled.resize(node_bound, 0);
+            self.scores.resize(node_bound, K::default());
+            self.predecessors.resize(node_bound, usize::MAX);
+        }
+    }
+
+    /// Start a new search in a graph with the given node bound.
+    pub(crate) fn start(&mut self, node_bound: usize) {
+        self.grow(node_bound);
+        self.generation += 1;
+        self.visit_next.clear();
+    }
+
+    /// The cost of `index` in the current search, if it was reached.
+    #[inline]
+    pub(crate) fn score(&self, index: usize) -> Option<K> {
+        if self.reached[index] == self.generation {
+            Some(self.scores[index])
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub(crate) fn reach(&mut self, index: usize, score: K, predecessor: usize) {
+        self.reached[index] = self.generation;
+        self.scores[index] = score;
+        self.predecessors[index] = predecessor;
+    }
+
+    #[inline]
+    pub(crate) fn is_settled(&self, index: usize) -> bool {
+        self.settled[index] == self.generation
+    }
+
+    #[inline]
+    pub(crate) fn settle(&mut self, index: usize) {
+        self.settled[index] = self.generation;
+    }
+
+    /// Return the cost of the path to `node` found by the last search, if it
+    /// settled `node`. After [`dijkstra_with_workspace`], this is the cost of
+    /// a shortest path from its start.
+    pub fn distance<G>(&self, graph: G, node: G::NodeId) -> Option<K>
+    where
+        G: NodeIndexable,
+    {
+        self.settled_score(graph.to_index(node))
+    }
+
+    fn settled_score(&self, index: usize) -> Option<K> {
+        if index < self.settled.len() && self.is_settled(index) {
+            Some(self.scores[index])
+        } else {
+            None
+        }
+    }
+
+    /// Return the node before `node` on its path in the last search, if the
+    /// search settled `node` and it is not the start.
+    pub fn predecessor<G>(&self, graph: G, node: G::NodeId) -> Option<G::NodeId>
+    where
+        G: NodeIndexable,
+    {
+        let index = graph.to_index(node);
+        self.settled_score(index)?;
+        match self.predecessors[index] {
+            usize::MAX => None,
+            index => Some(graph.from_index(index)),
+        }
+    }
+
+    /// Return the path from the start of the last search to `node`, if the
+    /// search settled `node`.
+    pub fn path_to<G>(&self, graph: G, node: G::NodeId) -> Option<Vec<G::NodeId>>
+    where
+        G: NodeIndexable,
+    {
+        let index = graph.to_index(node);
+        self.settled_score(index)?;
+        Some(self.path_to_index(graph, index))
+    }
+
+    pub(crate) fn path_to_index<G>(&self, graph: G, mut index: usize) -> Vec<G::NodeId>
+    where
+        G: NodeIndexable,
+    {
+        let mut path = vec![graph.from_index(index)];
+        while self.predecessors[index] != usize::MAX {
+            index = self.predecessors[index];
+            path.push(graph.from_index(index));
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// \[Generic\] Dijkstra's shortest path algorithm, with a reusable
+/// workspace.
+///
+/// Compute the shortest paths from `start`, like [`dijkstra`], but keep the
+/// costs and paths in `workspace` instead of allocating maps for them. See
+/// [`ShortestPathWorkspace`] for the results.
+///
+/// If `goal` is not `None`, the search stops once the `goal` node is settled,
+/// and returns its cost if it is reachable. Otherwise it settles every node
+/// reachable from `start` and returns `None`.
+///
+/// The graph should implement `IntoEdges` and `NodeIndexable`. Edge costs
+/// must be non-negative.
+///
+/// # Complexity
+/// * Time complexity: **O((|V| + |E|) log |V|)**.
+/// * Auxiliary space: **O(|V| + |E|)**, reused between searches.
+pub fn dijkstra_with_workspace<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: Option<G::NodeId>,
+    mut edge_cost: F,
+    workspace: &mut ShortestPathWorkspace<K>,
+) -> Option<K>
+where
+    G: IntoEdges + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    workspace.start(graph.node_bound());
+    let start = graph.to_index(start);
+    let goal = goal.map(|goal| graph.to_index(goal));
+    let zero_score = K::default();
+    workspace.reach(start, zero_score, usize::MAX);
+    workspace
+        .visit_next
+        .push(MinScored(zero_score, (start, zero_score)));
+    while let Some(MinScored(node_score, (node, _))) = workspace.visit_next.pop() {
+        if workspace.is_settled(node) {
+            continue;
+        }
+        workspace.settle(node);
+        if goal == Some(node) {
+            return Some(node_score);
+        }
+        for edge in graph.edges(graph.from_index(node)) {
+            let next = graph.to_index(edge.target());
+            if workspace.is_settled(next) {
+                continue;
+            }
+            let next_score = node_score + edge_cost(edge);
+            if workspace
+                .score(next)
+                .map_or(true, |score| next_score < score)
+            {
+                workspace.reach(next, next_score, node);
+                workspace
+                    .visit_next
+                    .push(MinScored(next_score, (next, next_score)));
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,162 @@
+use std::{collections::VecDeque, ops::Sub};
+
+use crate::{
+    data::DataMap,
+    visit::{EdgeCount, EdgeIndexable, IntoEdgesDirected, NodeCount, NodeIndexable, Visitable},
+};
+
+use super::ford_fulkerson::ResidualNetwork;
+use super::PositiveMeasure;
+
+/// \[Generic\] Dinic's algorithm.
+///
+/// Computes the [maximum flow][dinic] of a weighted directed graph, by
+/// augmenting a blocking flow in the level graph of the shortest residual
+/// paths in every phase.
+///
+/// It takes the same arguments and returns the same result as
+/// [`ford_fulkerson`](fn@super::ford_fulkerson): the maximum flow and the
+/// computed edge flows, indexed by `EdgeIndexable::to_index`.
+///
+/// Runs in **O(|V|² |E|)** time, and in **O(|E| √|V|)** time on unit
+/// capacity networks.
+///
+/// [dinic]: https://en.wikipedia.org/wiki/Dinic%27s_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::dinic;
+/// // Example from CLRS book
+/// let mut graph = Graph::<u8, u8>::new();
+/// let source = graph.add_node(0);
+/// let _ = graph.add_node(1);
+/// let _ = graph.add_node(2);
+/// let _ = graph.add_node(3);
+/// let _ = graph.add_node(4);
+/// let destination = graph.add_node(5);
+/// graph.extend_with_edges(&[
+///    (0, 1, 16),
+///    (0, 2, 13),
+///    (1, 2, 10),
+///    (1, 3, 12),
+///    (2, 1, 4),
+///    (2, 4, 14),
+///    (3, 2, 9),
+///    (3, 5, 20),
+///    (4, 3, 7),
+///    (4, 5, 4),
+/// ]);
+/// let (max_flow, _) = dinic(&graph, source, destination);
+/// assert_eq!(23, max_flow);
+/// ```
+pub fn dinic<N>(
+    network: N,
+    source: N::NodeId,
+    destination: N::NodeId,
+) -> (N::EdgeWeight, Vec<N::EdgeWeight>)
+where
+    N: NodeCount
+        + EdgeCount
+        + IntoEdgesDirected
+        + EdgeIndexable
+        + NodeIndexable
+        + DataMap
+        + Visitable,
+    N::EdgeWeight: Sub<Output = N::EdgeWeight> + PositiveMeasure,
+{
+    let zero = N::EdgeWeight::zero();
+    let mut residual_network = ResidualNetwork::new(network);
+    let s = NodeIndexable::to_index(&network, source);
+    let t = NodeIndexable::to_index(&network, destination);
+    let mut max_flow = zero;
+    if s == t {
+        return (max_flow, residual_network.edge_flows());
+    }
+
+    let ResidualNetwork {
+        ref target,
+        ref mut residual,
+        ref adjacency,
+        ..
+    } = residual_network;
+    let node_bound = adjacency.len();
+    let mut level = vec![usize::MAX; node_bound];
+    // The next arc to try at every node, arcs before it are blocked.
+    let mut next = vec![0; node_bound];
+    let mut queue = VecDeque::new();
+    // The arcs of the current path from the source.
+    let mut path: Vec<usize> = Vec::new();
+    loop {
+        // Build the level graph with a breadth first search from the source.
+        level.iter_mut().for_each(|l| *l = usize::MAX);
+        level[s] = 0;
+        queue.push_back(s);
+        while let Some(u) = queue.pop_front() {
+            for &arc in &adjacency[u] {
+                let v = target[arc];
+                if level[v] == usize::MAX && residual[arc] > zero {
+                    level[v] = level[u] + 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+        if level[t] == usize::MAX {
+            break;
+        }
+
+        // Find a blocking flow with depth first searches along the levels.
+        next.iter_mut().for_each(|n| *n = 0);
+        path.clear();
+        let mut u = s;
+        loop {
+            if u == t {
+                let mut bottleneck = N::EdgeWeight::max();
+                for &arc in &path {
+                    if residual[arc] < bottleneck {
+                        bottleneck = residual[arc];
+                    }
+                }
+                // Retreat to the tail of the first saturated arc.
+                let mut retreat = path.len();
+                for (i, &arc) in path.iter().enumerate() {
+                    residual[arc] = residual[arc] - bottleneck;
+                    residual[arc ^ 1] = residual[arc ^ 1] + bottleneck;
+                    if retreat == path.len() && residual[arc] <= zero {
+                        retreat = i;
+                    }
+                }
+                max_flow = max_flow + bottleneck;
+                path.truncate(retreat);
+                u = path.last().map_or(s, |&arc| target[arc]);
+                continue;
+            }
+
+            let arcs = &adjacency[u];
+            while let Some(&arc) = arcs.get(next[u]) {
+                if residual[arc] > zero && level[target[arc]] == level[u] + 1 {
+                    break;
+                }
+                next[u] += 1;
+            }
+            match arcs.get(next[u]) {
+                Some(&arc) => {
+                    path.push(arc);
+                    u = target[arc];
+                }
+                None => {
+                    // A dead end, remove it from the level graph.
+                    level[u] = usize::MAX;
+                    match path.pop() {
+                        Some(arc) => {
+                            u = target[arc ^ 1];
+                            next[u] += 1;
+                        }
+                        None => break,
+                    }
+                }
+            }
+        }
+    }
+    (max_flow, residual_network.edge_flows())
+}
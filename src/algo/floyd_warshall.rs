@@ -2,10 +2,11 @@ use std::collections::HashMap;
 
 use std::hash::Hash;
 
-use crate::algo::{BoundedMeasure, NegativeCycle};
+use crate::algo::{AllPairsPaths, BoundedMeasure, NegativeCycle};
 use crate::visit::{
     EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeCompactIndexable,
 };
+use crate::Direction::Outgoing;
 
 #[allow(clippy::type_complexity, clippy::needless_range_loop)]
 /// \[Generic\] [Floyd–Warshall algorithm](https://en.wikipedia.org/wiki/Floyd%E2%80%93Warshall_algorithm) is an algorithm for all pairs shortest path problem
@@ -20,6 +21,8 @@ use crate::visit::{
 /// * `Ok`: (if graph contains no negative cycle) a hashmap containing all pairs shortest paths
 /// * `Err`: if graph contains negative cycle.
 ///
+/// To reconstruct the paths, use [`floyd_warshall_paths`] instead.
+///
 /// # Examples
 /// ```rust
 /// use petgraph::{prelude::*, Graph, Directed};
@@ -80,7 +83,7 @@ use crate::visit::{
 /// ```
 pub fn floyd_warshall<G, F, K>(
     graph: G,
-    mut edge_cost: F,
+    edge_cost: F,
 ) -> Result<HashMap<(G::NodeId, G::NodeId), K>, NegativeCycle>
 where
     G: NodeCompactIndexable + IntoEdgeReferences + IntoNodeIdentifiers + GraphProp,
@@ -89,9 +92,99 @@ where
     K: BoundedMeasure + Copy,
 {
     let num_of_nodes = graph.node_count();
+    let (dist, _) = floyd_warshall_matrices(graph, edge_cost, false)?;
+
+    let mut distance_map: HashMap<(G::NodeId, G::NodeId), K> =
+        HashMap::with_capacity(num_of_nodes * num_of_nodes);
+
+    for i in 0..num_of_nodes {
+        for j in 0..num_of_nodes {
+            distance_map.insert((graph.from_index(i), graph.from_index(j)), dist[i][j]);
+        }
+    }
+
+    Ok(distance_map)
+}
+
+/// \[Generic\] [Floyd–Warshall algorithm](https://en.wikipedia.org/wiki/Floyd%E2%80%93Warshall_algorithm)
+/// for all pairs shortest paths, with the paths.
+///
+/// Like [`floyd_warshall`], but also records the next node and edge on the
+/// shortest path between every pair of nodes, and returns the distances and
+/// paths as [`AllPairsPaths`]. The path between two nodes is given by
+/// [`AllPairsPaths::path`].
+///
+/// # Complexity
+/// * Time complexity: **O(|V|³)**.
+/// * Auxiliary space: **O(|V|²)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::floyd_warshall_paths;
+/// use petgraph::prelude::*;
+///
+/// let mut graph = DiGraph::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// let ab = graph.add_edge(a, b, 1);
+/// let bc = graph.add_edge(b, c, 2);
+/// graph.add_edge(a, c, 4);
+///
+/// let paths = floyd_warshall_paths(&graph, |e| *e.weight()).unwrap();
+/// assert_eq!(paths.distance(a, c), Some(3));
+/// assert_eq!(paths.path(a, c), Some((vec![a, b, c], vec![ab, bc])));
+/// assert_eq!(paths.path(b, b), Some((vec![b], vec![])));
+/// assert_eq!(paths.path(c, a), None);
+///
+/// graph.add_edge(c, a, -4);
+/// assert!(floyd_warshall_paths(&graph, |e| *e.weight()).is_err());
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn floyd_warshall_paths<G, F, K>(
+    graph: G,
+    edge_cost: F,
+) -> Result<AllPairsPaths<G::NodeId, G::EdgeId, K>, NegativeCycle>
+where
+    G: NodeCompactIndexable + IntoEdgeReferences + IntoNodeIdentifiers + GraphProp,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: BoundedMeasure + Copy,
+{
+    let num_of_nodes = graph.node_count();
+    let (dist, next) = floyd_warshall_matrices(graph, edge_cost, true)?;
+    let next = next.unwrap();
+
+    let mut paths = AllPairsPaths::new(graph, Outgoing);
+    for i in 0..num_of_nodes {
+        for j in 0..num_of_nodes {
+            if i == j || next[i][j].is_some() {
+                paths.distances[i * num_of_nodes + j] = Some(dist[i][j]);
+            }
+        }
+        paths.hops[i * num_of_nodes..(i + 1) * num_of_nodes].copy_from_slice(&next[i]);
+    }
+    Ok(paths)
+}
+
+/// The distance matrix, with `K::max()` for no path, and if `paths` is `true`,
+/// the index of the next node and the edge to it on every shortest path.
+#[allow(clippy::type_complexity, clippy::needless_range_loop)]
+fn floyd_warshall_matrices<G, F, K>(
+    graph: G,
+    mut edge_cost: F,
+    paths: bool,
+) -> Result<(Vec<Vec<K>>, Option<Vec<Vec<Option<(usize, G::EdgeId)>>>>), NegativeCycle>
+where
+    G: NodeCompactIndexable + IntoEdgeReferences + IntoNodeIdentifiers + GraphProp,
+    F: FnMut(G::EdgeRef) -> K,
+    K: BoundedMeasure + Copy,
+{
+    let num_of_nodes = graph.node_count();
 
-    // |V|x|V| matrix
+    // |V|x|V| matrices
     let mut dist = vec![vec![K::max(); num_of_nodes]; num_of_nodes];
+    let mut next = paths.then(|| vec![vec![None; num_of_nodes]; num_of_nodes]);
 
     // init distances of paths with no intermediate nodes
     for edge in graph.edge_references() {
@@ -101,15 +194,25 @@ where
 
         if dist[i][j] > cost {
             dist[i][j] = cost;
+            if let Some(next) = &mut next {
+                next[i][j] = Some((j, edge.id()));
+            }
             if !graph.is_directed() {
                 dist[j][i] = cost;
+                if let Some(next) = &mut next {
+                    next[j][i] = Some((i, edge.id()));
+                }
             }
         }
     }
 
     // distance of each node to itself is 0(default value)
     for node in graph.node_identifiers() {
-        dist[graph.to_index(node)][graph.to_index(node)] = K::default();
+        let i = graph.to_index(node);
+        dist[i][i] = K::default();
+        if let Some(next) = &mut next {
+            next[i][i] = None;
+        }
     }
 
     for k in 0..num_of_nodes {
@@ -118,6 +221,9 @@ where
                 let (result, overflow) = dist[i][k].overflowing_add(dist[k][j]);
                 if !overflow && dist[i][j] > result {
                     dist[i][j] = result;
+                    if let Some(next) = &mut next {
+                        next[i][j] = next[i][k];
+                    }
                 }
             }
         }
@@ -130,14 +236,5 @@ where
         }
     }
 
-    let mut distance_map: HashMap<(G::NodeId, G::NodeId), K> =
-        HashMap::with_capacity(num_of_nodes * num_of_nodes);
-
-    for i in 0..num_of_nodes {
-        for j in 0..num_of_nodes {
-            distance_map.insert((graph.from_index(i), graph.from_index(j)), dist[i][j]);
-        }
-    }
-
-    Ok(distance_map)
+    Ok((dist, next))
 }
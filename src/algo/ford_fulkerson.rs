@@ -3,12 +3,12 @@ use std::{collections::VecDeque, ops::Sub};
 use crate::{
     data::DataMap,
     visit::{
-        EdgeCount, EdgeIndexable, IntoEdges, IntoEdgesDirected, NodeCount, NodeIndexable, VisitMap,
-        Visitable,
+        EdgeCount, EdgeIndexable, IntoEdgeReferences, IntoEdges, IntoEdgesDirected,
+        IntoNodeIdentifiers, NodeCount, NodeIndexable, VisitMap, Visitable,
     },
 };
 
-use super::{EdgeRef, PositiveMeasure};
+use super::{EdgeRef, Measure, PositiveMeasure};
 use crate::prelude::Direction;
 
 fn residual_capacity<N>(
@@ -194,3 +194,246 @@ where
     }
     (max_flow, flows)
 }
+
+/// The residual network of a flow network, shared by the max flow algorithms
+/// working on node and edge indices.
+///
+/// Arc `2 * i` runs along the `i`-th edge with positive capacity, arc
+/// `2 * i + 1` is its reverse. Self loops are left out.
+pub(super) struct ResidualNetwork<K> {
+    pub(super) target: Vec<usize>,
+    pub(super) residual: Vec<K>,
+    pub(super) adjacency: Vec<Vec<usize>>,
+    // The edge index of every pair of arcs.
+    edges: Vec<usize>,
+    edge_bound: usize,
+}
+
+impl<K: PositiveMeasure> ResidualNetwork<K> {
+    pub(super) fn new<N>(network: N) -> Self
+    where
+        N: IntoEdgeReferences<EdgeWeight = K> + NodeIndexable + EdgeIndexable,
+    {
+        let mut residual_network = ResidualNetwork {
+            target: Vec::new(),
+            residual: Vec::new(),
+            adjacency: vec![Vec::new(); network.node_bound()],
+            edges: Vec::new(),
+            edge_bound: network.edge_bound(),
+        };
+        for edge in network.edge_references() {
+            let a = NodeIndexable::to_index(&network, edge.source());
+            let b = NodeIndexable::to_index(&network, edge.target());
+            if a == b || *edge.weight() <= K::zero() {
+                continue;
+            }
+            residual_network.adjacency[a].push(residual_network.target.len());
+            residual_network.target.push(b);
+            residual_network.residual.push(*edge.weight());
+            residual_network.adjacency[b].push(residual_network.target.len());
+            residual_network.target.push(a);
+            residual_network.residual.push(K::zero());
+            residual_network
+                .edges
+                .push(EdgeIndexable::to_index(&network, edge.id()));
+        }
+        residual_network
+    }
+
+    /// The flow on every edge, indexed by `EdgeIndexable::to_index`.
+    pub(super) fn edge_flows(&self) -> Vec<K> {
+        let mut flows = vec![K::zero(); self.edge_bound];
+        for (pair, &index) in self.edges.iter().enumerate() {
+            // The residual capacity of the reverse arc is the flow on the edge.
+            flows[index] = self.residual[2 * pair + 1];
+        }
+        flows
+    }
+}
+
+// The outgoing edges of every node, with the edge index and target index.
+fn outgoing_edges<N>(network: N) -> Vec<Vec<(N::EdgeRef, usize, usize)>>
+where
+    N: IntoEdgeReferences + NodeIndexable + EdgeIndexable,
+{
+    let mut outgoing = vec![Vec::new(); network.node_bound()];
+    for edge in network.edge_references() {
+        let index = EdgeIndexable::to_index(&network, edge.id());
+        let target = NodeIndexable::to_index(&network, edge.target());
+        outgoing[NodeIndexable::to_index(&network, edge.source())].push((edge, index, target));
+    }
+    outgoing
+}
+
+/// \[Generic\] Decompose a flow into paths from `source` to `destination`.
+///
+/// `flows` holds the flow on every edge, indexed by `EdgeIndexable::to_index`,
+/// as returned by [`ford_fulkerson`] or
+/// [`min_cost_max_flow`](super::min_cost_max_flow). Edges are directed from
+/// their source to their target.
+///
+/// Returns the paths as lists of edges, each with the amount of flow it
+/// carries. The amounts add up to the value of the flow. Flow that circulates
+/// on cycles does not reach `destination` and is left out.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::{flow_decomposition, ford_fulkerson};
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), u32>::from_edges(&[(0, 1, 2), (0, 2, 1), (1, 3, 2), (2, 3, 3)]);
+/// let (source, destination) = (NodeIndex::new(0), NodeIndex::new(3));
+/// let (max_flow, flows) = ford_fulkerson(&graph, source, destination);
+///
+/// let paths = flow_decomposition(&graph, source, destination, &flows);
+/// assert_eq!(paths.len(), 2);
+/// assert_eq!(paths.iter().map(|(_, amount)| amount).sum::<u32>(), max_flow);
+/// ```
+pub fn flow_decomposition<N, K>(
+    network: N,
+    source: N::NodeId,
+    destination: N::NodeId,
+    flows: &[K],
+) -> Vec<(Vec<N::EdgeRef>, K)>
+where
+    N: IntoEdgeReferences + NodeIndexable + EdgeIndexable,
+    K: Measure + Copy + Sub<Output = K>,
+{
+    let zero = K::default();
+    let outgoing = outgoing_edges(network);
+    let mut remaining = flows.to_vec();
+    // The next outgoing edge to try at every node.
+    let mut next = vec![0; outgoing.len()];
+    // The position of every node on the current walk.
+    let mut on_path = vec![None; outgoing.len()];
+    let mut paths = Vec::new();
+
+    let s = NodeIndexable::to_index(&network, source);
+    let t = NodeIndexable::to_index(&network, destination);
+    if s == t {
+        return paths;
+    }
+    // The edges of the current walk, as (node, position in `outgoing`).
+    let mut walk: Vec<(usize, usize)> = Vec::new();
+    let mut u = s;
+    on_path[s] = Some(0);
+    loop {
+        if u == t {
+            let amount = bottleneck(&walk, &outgoing, &remaining);
+            let mut path = Vec::with_capacity(walk.len());
+            for &(v, i) in &walk {
+                let (edge, index, _) = outgoing[v][i];
+                remaining[index] = remaining[index] - amount;
+                on_path[v] = None;
+                path.push(edge);
+            }
+            paths.push((path, amount));
+            on_path[t] = None;
+            walk.clear();
+            u = s;
+            on_path[s] = Some(0);
+            continue;
+        }
+
+        let edges = &outgoing[u];
+        while next[u] < edges.len() && remaining[edges[next[u]].1] <= zero {
+            next[u] += 1;
+        }
+        if next[u] == edges.len() {
+            // Only the source runs out of flow when the flow is conserved.
+            break;
+        }
+        let (_, _, v) = edges[next[u]];
+        walk.push((u, next[u]));
+        match on_path[v] {
+            Some(position) => {
+                // Cancel the flow around the cycle and continue from `v`.
+                let cycle = walk.split_off(position);
+                let amount = bottleneck(&cycle, &outgoing, &remaining);
+                for &(w, i) in &cycle {
+                    let index = outgoing[w][i].1;
+                    remaining[index] = remaining[index] - amount;
+                    on_path[w] = None;
+                }
+                on_path[v] = Some(position);
+            }
+            None => on_path[v] = Some(walk.len()),
+        }
+        u = v;
+    }
+    paths
+}
+
+fn bottleneck<E, K>(walk: &[(usize, usize)], outgoing: &[Vec<(E, usize, usize)>], flows: &[K]) -> K
+where
+    K: Measure + Copy,
+{
+    let mut amounts = walk.iter().map(|&(v, i)| flows[outgoing[v][i].1]);
+    let first = amounts.next().unwrap();
+    amounts.fold(first, |min, f| if f < min { f } else { min })
+}
+
+/// \[Generic\] Return a minimum cut between `source` and the destination of a
+/// maximum flow, as a partition of the nodes.
+///
+/// `flows` holds the flow on every edge, indexed by `EdgeIndexable::to_index`,
+/// and `capacity` the capacity of every edge. For the flows returned by
+/// [`ford_fulkerson`] the capacity is the edge weight.
+///
+/// The first set holds the nodes that can be reached from `source` in the
+/// residual network, the second set all other nodes. If the flow is maximum,
+/// the edges from the first set to the second set form a cut of minimum
+/// capacity, and they are all saturated.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::{ford_fulkerson, min_cut};
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), u32>::from_edges(&[(0, 1, 5), (1, 2, 1), (2, 3, 5)]);
+/// let (source, destination) = (NodeIndex::new(0), NodeIndex::new(3));
+/// let (_, flows) = ford_fulkerson(&graph, source, destination);
+///
+/// let (reachable, rest) = min_cut(&graph, source, &flows, |edge| *edge.weight());
+/// assert_eq!(reachable, [NodeIndex::new(0), NodeIndex::new(1)]);
+/// assert_eq!(rest, [NodeIndex::new(2), NodeIndex::new(3)]);
+/// ```
+pub fn min_cut<N, F, K>(
+    network: N,
+    source: N::NodeId,
+    flows: &[K],
+    mut capacity: F,
+) -> (Vec<N::NodeId>, Vec<N::NodeId>)
+where
+    N: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + EdgeIndexable + Visitable,
+    F: FnMut(N::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let zero = K::default();
+    // Residual arcs of every node: forward along unsaturated edges and
+    // backward along edges with flow.
+    let mut residual = vec![Vec::new(); network.node_bound()];
+    for edge in network.edge_references() {
+        let flow = flows[EdgeIndexable::to_index(&network, edge.id())];
+        if flow < capacity(edge) {
+            residual[NodeIndexable::to_index(&network, edge.source())].push(edge.target());
+        }
+        if flow > zero {
+            residual[NodeIndexable::to_index(&network, edge.target())].push(edge.source());
+        }
+    }
+
+    let mut visited = network.visit_map();
+    let mut stack = vec![source];
+    visited.visit(source);
+    while let Some(u) = stack.pop() {
+        for &v in &residual[NodeIndexable::to_index(&network, u)] {
+            if visited.visit(v) {
+                stack.push(v);
+            }
+        }
+    }
+    network
+        .node_identifiers()
+        .partition(|n| visited.is_visited(n))
+}
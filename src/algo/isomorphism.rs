@@ -9,8 +9,10 @@ use crate::visit::GraphProp;
 use crate::visit::IntoEdgesDirected;
 use crate::visit::IntoNeighborsDirected;
 use crate::visit::NodeCompactIndexable;
-use crate::{Incoming, Outgoing};
+use crate::{Direction, Incoming, Outgoing};
+use std::cmp::Reverse;
 
+use self::matching::Problem;
 use self::semantic::EdgeMatcher;
 use self::semantic::NoSemanticMatch;
 use self::semantic::NodeMatcher;
@@ -112,38 +114,20 @@ mod state {
             self.generation -= 1;
         }
 
-        /// Find the next (least) node in the Tout set.
-        pub fn next_out_index(&self, from_index: usize) -> Option<usize> {
-            self.out[from_index..]
-                .iter()
-                .enumerate()
-                .find(move |&(index, &elt)| {
-                    elt > 0 && self.mapping[from_index + index] == usize::MAX
-                })
-                .map(|(index, _)| index)
+        /// Return the number of nodes in the mapping.
+        pub fn generation(&self) -> usize {
+            self.generation
         }
 
-        /// Find the next (least) node in the Tin set.
-        pub fn next_in_index(&self, from_index: usize) -> Option<usize> {
-            if !self.graph.is_directed() {
-                return None;
-            }
-            self.ins[from_index..]
-                .iter()
-                .enumerate()
-                .find(move |&(index, &elt)| {
-                    elt > 0 && self.mapping[from_index + index] == usize::MAX
-                })
-                .map(|(index, _)| index)
+        /// Return **true** if the node is in M(s) or Tout(s).
+        pub fn is_out(&self, index: usize) -> bool {
+            self.out[index] > 0
         }
 
-        /// Find the next (least) node in the N - M set.
-        pub fn next_rest_index(&self, from_index: usize) -> Option<usize> {
-            self.mapping[from_index..]
-                .iter()
-                .enumerate()
-                .find(|&(_, &elt)| elt == usize::MAX)
-                .map(|(index, _)| index)
+        /// Return **true** if the node is in M(s) or Tin(s), always
+        /// **false** if the graph is undirected.
+        pub fn is_in(&self, index: usize) -> bool {
+            self.graph.is_directed() && self.ins[index] > 0
         }
     }
 }
@@ -255,11 +239,15 @@ mod semantic {
 mod matching {
     use super::*;
 
+    /// The kind of mapping to find.
     #[derive(Copy, Clone, PartialEq, Debug)]
-    enum OpenList {
-        Out,
-        In,
-        Other,
+    pub enum Problem {
+        /// A bijection that preserves edges and non-edges.
+        Isomorphism,
+        /// An injection that preserves edges and non-edges.
+        InducedSubgraph,
+        /// An injection that preserves edges.
+        Monomorphism,
     }
 
     #[derive(Clone, PartialEq, Debug)]
@@ -271,11 +259,11 @@ mod matching {
         Outer,
         Inner {
             nodes: (G0::NodeId, G1::NodeId),
-            open_list: OpenList,
+            position: usize,
         },
         Unwind {
             nodes: (G0::NodeId, G1::NodeId),
-            open_list: OpenList,
+            position: usize,
         },
     }
 
@@ -284,6 +272,7 @@ mod matching {
         nodes: (G0::NodeId, G1::NodeId),
         node_match: &mut NM,
         edge_match: &mut EM,
+        problem: Problem,
     ) -> bool
     where
         G0: GetAdjacencyMatrix + GraphProp + NodeCompactIndexable + IntoNeighborsDirected,
@@ -306,14 +295,21 @@ mod matching {
             };
         }
 
+        // Edges of g1 between mapped nodes need to be in g0 unless we look
+        // for a monomorphism.
+        let induced = problem != Problem::Monomorphism;
+
         macro_rules! r_succ {
-            ($j:tt) => {{
+            ($j:tt, $check:expr) => {{
                 let mut succ_count = 0;
                 for n_neigh in field!(st, $j)
                     .graph
                     .neighbors_directed(field!(nodes, $j), Outgoing)
                 {
                     succ_count += 1;
+                    if !$check {
+                        continue;
+                    }
                     // handle the self loop case; it's not in the mapping (yet)
                     let m_neigh = if field!(nodes, $j) != n_neigh {
                         field!(st, $j).mapping[field!(st, $j).graph.to_index(n_neigh)]
@@ -337,13 +333,16 @@ mod matching {
         }
 
         macro_rules! r_pred {
-            ($j:tt) => {{
+            ($j:tt, $check:expr) => {{
                 let mut pred_count = 0;
                 for n_neigh in field!(st, $j)
                     .graph
                     .neighbors_directed(field!(nodes, $j), Incoming)
                 {
                     pred_count += 1;
+                    if !$check {
+                        continue;
+                    }
                     // the self loop case is handled in outgoing
                     let m_neigh = field!(st, $j).mapping[field!(st, $j).graph.to_index(n_neigh)];
                     if m_neigh == usize::MAX {
@@ -379,11 +378,11 @@ mod matching {
         // R_new: Equal for G0, G1: Ñ n Pred(G, n); both Succ and Pred,
         //      Ñ is G0 - M - Tin - Tout
         // last attempt to add these did not speed up any of the testcases
-        if r_succ!(0) > r_succ!(1) {
+        if r_succ!(0, true) > r_succ!(1, induced) {
             return false;
         }
         // R_pred
-        if st.0.graph.is_directed() && r_pred!(0) > r_pred!(1) {
+        if st.0.graph.is_directed() && r_pred!(0, true) > r_pred!(1, induced) {
             return false;
         }
 
@@ -455,78 +454,161 @@ mod matching {
             }
 
             edge_feasibility!(0);
-            edge_feasibility!(1);
+            if induced {
+                edge_feasibility!(1);
+            }
         }
         true
     }
 
-    fn next_candidate<G0, G1>(
-        st: &mut (Vf2State<'_, G0>, Vf2State<'_, G1>),
-    ) -> Option<(G0::NodeId, G1::NodeId, OpenList)>
-    where
-        G0: GetAdjacencyMatrix + GraphProp + NodeCompactIndexable + IntoNeighborsDirected,
-        G1: GetAdjacencyMatrix + GraphProp + NodeCompactIndexable + IntoNeighborsDirected,
-    {
-        let mut from_index = None;
-        let mut open_list = OpenList::Out;
-        let mut to_index = st.1.next_out_index(0);
-
-        // Try the out list
-        if to_index.is_some() {
-            from_index = st.0.next_out_index(0);
-            open_list = OpenList::Out;
-        }
-        // Try the in list
-        if to_index.is_none() || from_index.is_none() {
-            to_index = st.1.next_in_index(0);
+    /// The order in which the nodes of `g0` are matched, and the candidates
+    /// for every node in the current state.
+    pub struct SearchOrder {
+        /// The indices of the nodes of `g0`, in the order they are matched.
+        order: Vec<usize>,
+        /// For every node in `order`, a neighbor before it in `order` and the
+        /// direction of the edge from it, if any.
+        parent: Vec<Option<(usize, Direction)>>,
+        /// For every node in `order`, the indices of the nodes of `g1` it can
+        /// be mapped to in the current state.
+        candidates: Vec<Vec<usize>>,
+    }
 
-            if to_index.is_some() {
-                from_index = st.0.next_in_index(0);
-                open_list = OpenList::In;
+    impl SearchOrder {
+        /// Order the nodes of `g0` as in VF2++: in breadth first order from a
+        /// node of maximum degree, taking the nodes of every level with the
+        /// most neighbors already ordered first, then those of maximum
+        /// degree. Every node after the first of its component has a neighbor
+        /// before it, so its candidates are restricted to the neighbors of
+        /// its image.
+        pub fn new<G0>(g0: &G0) -> Self
+        where
+            G0: GraphProp + NodeCompactIndexable + IntoNeighborsDirected,
+        {
+            let n = g0.node_count();
+            let directions: &[Direction] = if g0.is_directed() {
+                &[Outgoing, Incoming]
+            } else {
+                &[Outgoing]
+            };
+            let neighbors = |v: usize| {
+                let node = g0.from_index(v);
+                directions.iter().flat_map(move |&d| {
+                    g0.neighbors_directed(node, d)
+                        .map(move |w| (g0.to_index(w), d))
+                })
+            };
+            let degree: Vec<usize> = (0..n).map(|v| neighbors(v).count()).collect();
+            let mut connections = vec![0; n];
+            // The first ordered neighbor of every node that has one.
+            let mut parent = vec![None; n];
+            let mut seen = vec![false; n];
+            let mut ordered = vec![false; n];
+            let mut order = Vec::with_capacity(n);
+            let mut level = Vec::new();
+            let mut next_level = Vec::new();
+            while order.len() < n {
+                let root = (0..n)
+                    .filter(|&v| !seen[v])
+                    .max_by_key(|&v| (degree[v], Reverse(v)))
+                    .unwrap();
+                seen[root] = true;
+                level.push(root);
+                while !level.is_empty() {
+                    while !level.is_empty() {
+                        let (i, _) = level
+                            .iter()
+                            .enumerate()
+                            .max_by_key(|&(i, &v)| (connections[v], degree[v], Reverse(i)))
+                            .unwrap();
+                        let v = level.swap_remove(i);
+                        order.push(v);
+                        ordered[v] = true;
+                        for (w, d) in neighbors(v) {
+                            connections[w] += 1;
+                            if parent[w].is_none() && !ordered[w] {
+                                parent[w] = Some((v, d));
+                            }
+                            if !seen[w] {
+                                seen[w] = true;
+                                next_level.push(w);
+                            }
+                        }
+                    }
+                    std::mem::swap(&mut level, &mut next_level);
+                }
             }
-        }
-        // Try the other list -- disconnected graph
-        if to_index.is_none() || from_index.is_none() {
-            to_index = st.1.next_rest_index(0);
-            if to_index.is_some() {
-                from_index = st.0.next_rest_index(0);
-                open_list = OpenList::Other;
+            let parent = order.iter().map(|&v| parent[v]).collect();
+            SearchOrder {
+                order,
+                parent,
+                candidates: vec![Vec::new(); n],
             }
         }
-        match (from_index, to_index) {
-            (Some(n), Some(m)) => Some((
-                st.0.graph.from_index(n),
-                st.1.graph.from_index(m),
-                open_list,
-            )),
-            // No more candidates
-            _ => None,
-        }
-    }
 
-    fn next_from_ix<G0, G1>(
-        st: &mut (Vf2State<'_, G0>, Vf2State<'_, G1>),
-        nx: G1::NodeId,
-        open_list: OpenList,
-    ) -> Option<G1::NodeId>
-    where
-        G0: GetAdjacencyMatrix + GraphProp + NodeCompactIndexable + IntoNeighborsDirected,
-        G1: GetAdjacencyMatrix + GraphProp + NodeCompactIndexable + IntoNeighborsDirected,
-    {
-        // Find the next node index to try on the `to` side of the mapping
-        let start = st.1.graph.to_index(nx) + 1;
-        let cand1 = match open_list {
-            OpenList::Out => st.1.next_out_index(start),
-            OpenList::In => st.1.next_in_index(start),
-            OpenList::Other => st.1.next_rest_index(start),
-        }
-        .map(|c| c + start); // compensate for start offset.
-        match cand1 {
-            None => None, // no more candidates
-            Some(ix) => {
-                debug_assert!(ix >= start);
-                Some(st.1.graph.from_index(ix))
+        /// Collect the candidates for the node at `depth` in the current
+        /// state. Returns the index of the node in `g0`.
+        fn collect_candidates<G0, G1>(
+            &mut self,
+            st: &(Vf2State<'_, G0>, Vf2State<'_, G1>),
+            depth: usize,
+        ) -> usize
+        where
+            G0: GetAdjacencyMatrix + GraphProp + NodeCompactIndexable + IntoNeighborsDirected,
+            G1: GetAdjacencyMatrix + GraphProp + NodeCompactIndexable + IntoNeighborsDirected,
+        {
+            let candidates = &mut self.candidates[depth];
+            candidates.clear();
+            match self.parent[depth] {
+                Some((parent, direction)) => {
+                    let image = st.1.graph.from_index(st.0.mapping[parent]);
+                    candidates.extend(
+                        st.1.graph
+                            .neighbors_directed(image, direction)
+                            .map(|m| st.1.graph.to_index(m)),
+                    );
+                    candidates.sort_unstable();
+                    candidates.dedup();
+                }
+                None => candidates.extend(0..st.1.mapping.len()),
             }
+            self.order[depth]
+        }
+
+        /// Find the next candidate at or after position `from` in the
+        /// candidates of the node at `depth`, that is not excluded by its
+        /// adjacency to the mapped nodes.
+        fn next_candidate<G0, G1>(
+            &self,
+            st: &(Vf2State<'_, G0>, Vf2State<'_, G1>),
+            depth: usize,
+            from: usize,
+            problem: Problem,
+        ) -> Option<(G1::NodeId, usize)>
+        where
+            G0: GetAdjacencyMatrix + GraphProp + NodeCompactIndexable + IntoNeighborsDirected,
+            G1: GetAdjacencyMatrix + GraphProp + NodeCompactIndexable + IntoNeighborsDirected,
+        {
+            let n = self.order[depth];
+            let out0 = st.0.is_out(n);
+            let in0 = st.0.is_in(n);
+            let candidates = &self.candidates[depth];
+            (from..candidates.len())
+                .find(|&i| {
+                    let m = candidates[i];
+                    if st.1.mapping[m] != usize::MAX {
+                        return false;
+                    }
+                    let (out1, in1) = (st.1.is_out(m), st.1.is_in(m));
+                    match problem {
+                        // A node next to a mapped node needs to be mapped next
+                        // to its image, and the other way around if non-edges
+                        // are preserved.
+                        Problem::Monomorphism => (!out0 || out1) && (!in0 || in1),
+                        _ => out0 == out1 && in0 == in1,
+                    }
+                })
+                .map(|i| (st.1.graph.from_index(candidates[i]), i))
         }
     }
 
@@ -557,7 +639,7 @@ mod matching {
         st: &mut (Vf2State<'_, G0>, Vf2State<'_, G1>),
         node_match: &mut NM,
         edge_match: &mut EM,
-        match_subgraph: bool,
+        problem: Problem,
     ) -> Option<bool>
     where
         G0: NodeCompactIndexable
@@ -573,21 +655,23 @@ mod matching {
         NM: NodeMatcher<G0, G1>,
         EM: EdgeMatcher<G0, G1>,
     {
+        let mut order = SearchOrder::new(st.0.graph);
         let mut stack = vec![Frame::Outer];
-        if isomorphisms(st, node_match, edge_match, match_subgraph, &mut stack).is_some() {
+        if next_match(st, &mut order, node_match, edge_match, problem, &mut stack) {
             Some(true)
         } else {
             None
         }
     }
 
-    fn isomorphisms<G0, G1, NM, EM>(
+    /// Count the mappings, stopping at `limit`.
+    pub fn count_matches<G0, G1, NM, EM>(
         st: &mut (Vf2State<'_, G0>, Vf2State<'_, G1>),
         node_match: &mut NM,
         edge_match: &mut EM,
-        match_subgraph: bool,
-        stack: &mut Vec<Frame<G0, G1>>,
-    ) -> Option<Vec<usize>>
+        problem: Problem,
+        limit: Option<usize>,
+    ) -> usize
     where
         G0: NodeCompactIndexable
             + EdgeCount
@@ -602,78 +686,100 @@ mod matching {
         NM: NodeMatcher<G0, G1>,
         EM: EdgeMatcher<G0, G1>,
     {
-        if st.0.is_complete() {
-            return Some(st.0.mapping.clone());
+        let mut order = SearchOrder::new(st.0.graph);
+        let mut stack = vec![Frame::Outer];
+        let mut count = 0;
+        while limit.map_or(true, |limit| count < limit)
+            && next_match(st, &mut order, node_match, edge_match, problem, &mut stack)
+        {
+            count += 1;
         }
+        count
+    }
 
+    /// Search for the next complete mapping, and return `true` if there is
+    /// one, with the mapping in the state.
+    fn next_match<G0, G1, NM, EM>(
+        st: &mut (Vf2State<'_, G0>, Vf2State<'_, G1>),
+        order: &mut SearchOrder,
+        node_match: &mut NM,
+        edge_match: &mut EM,
+        problem: Problem,
+        stack: &mut Vec<Frame<G0, G1>>,
+    ) -> bool
+    where
+        G0: NodeCompactIndexable
+            + EdgeCount
+            + GetAdjacencyMatrix
+            + GraphProp
+            + IntoNeighborsDirected,
+        G1: NodeCompactIndexable
+            + EdgeCount
+            + GetAdjacencyMatrix
+            + GraphProp
+            + IntoNeighborsDirected,
+        NM: NodeMatcher<G0, G1>,
+        EM: EdgeMatcher<G0, G1>,
+    {
         // A "depth first" search of a valid mapping from graph 1 to graph 2
         // F(s, n, m) -- evaluate state s and add mapping n <-> m
-        // Find least T1out node (in st.out[1] but not in M[1])
-        let mut result = None;
+        // The nodes of graph 1 are mapped in the search order, the depth of
+        // the search is the number of mapped nodes.
         while let Some(frame) = stack.pop() {
-            match frame {
-                Frame::Unwind { nodes, open_list } => {
-                    pop_state(st, nodes);
-
-                    match next_from_ix(st, nodes.1, open_list) {
-                        None => continue,
-                        Some(nx) => {
-                            let f = Frame::Inner {
-                                nodes: (nodes.0, nx),
-                                open_list,
-                            };
-                            stack.push(f);
+            let (nodes, next) = match frame {
+                Frame::Outer => {
+                    let depth = st.0.generation();
+                    if depth == order.order.len() {
+                        if depth == 0 && stack.is_empty() {
+                            // The empty graph has a single, empty, mapping.
+                            return true;
                         }
+                        continue;
                     }
-                }
-                Frame::Outer => match next_candidate(st) {
-                    None => continue,
-                    Some((nx, mx, open_list)) => {
-                        let f = Frame::Inner {
-                            nodes: (nx, mx),
-                            open_list,
-                        };
-                        stack.push(f);
+                    let n = order.collect_candidates(st, depth);
+                    if let Some((mx, position)) = order.next_candidate(st, depth, 0, problem) {
+                        stack.push(Frame::Inner {
+                            nodes: (st.0.graph.from_index(n), mx),
+                            position,
+                        });
                     }
-                },
-                Frame::Inner { nodes, open_list } => {
-                    if is_feasible(st, nodes, node_match, edge_match) {
+                    continue;
+                }
+                Frame::Unwind { nodes, position } => {
+                    pop_state(st, nodes);
+                    (nodes, position + 1)
+                }
+                Frame::Inner { nodes, position } => {
+                    if is_feasible(st, nodes, node_match, edge_match, problem) {
                         push_state(st, nodes);
-                        if st.0.is_complete() {
-                            result = Some(st.0.mapping.clone());
-                        }
                         // Check cardinalities of Tin, Tout sets
-                        if (!match_subgraph
-                            && st.0.out_size == st.1.out_size
-                            && st.0.ins_size == st.1.ins_size)
-                            || (match_subgraph
-                                && st.0.out_size <= st.1.out_size
-                                && st.0.ins_size <= st.1.ins_size)
-                        {
-                            let f0 = Frame::Unwind { nodes, open_list };
-                            stack.push(f0);
+                        let cardinalities = if problem == Problem::Isomorphism {
+                            st.0.out_size == st.1.out_size && st.0.ins_size == st.1.ins_size
+                        } else {
+                            st.0.out_size <= st.1.out_size && st.0.ins_size <= st.1.ins_size
+                        };
+                        if cardinalities {
+                            stack.push(Frame::Unwind { nodes, position });
                             stack.push(Frame::Outer);
+                            if st.0.is_complete() {
+                                return true;
+                            }
                             continue;
                         }
                         pop_state(st, nodes);
                     }
-                    match next_from_ix(st, nodes.1, open_list) {
-                        None => continue,
-                        Some(nx) => {
-                            let f = Frame::Inner {
-                                nodes: (nodes.0, nx),
-                                open_list,
-                            };
-                            stack.push(f);
-                        }
-                    }
+                    (nodes, position + 1)
                 }
-            }
-            if result.is_some() {
-                return result;
+            };
+            let depth = st.0.generation();
+            if let Some((mx, position)) = order.next_candidate(st, depth, next, problem) {
+                stack.push(Frame::Inner {
+                    nodes: (nodes.0, mx),
+                    position,
+                });
             }
         }
-        result
+        false
     }
 
     pub struct GraphMatcher<'a, 'b, 'c, G0, G1, NM, EM>
@@ -692,9 +798,10 @@ mod matching {
         EM: EdgeMatcher<G0, G1>,
     {
         st: (Vf2State<'a, G0>, Vf2State<'b, G1>),
+        order: SearchOrder,
         node_match: &'c mut NM,
         edge_match: &'c mut EM,
-        match_subgraph: bool,
+        problem: Problem,
         stack: Vec<Frame<G0, G1>>,
     }
 
@@ -718,14 +825,15 @@ mod matching {
             g1: &'b G1,
             node_match: &'c mut NM,
             edge_match: &'c mut EM,
-            match_subgraph: bool,
+            problem: Problem,
         ) -> Self {
             let stack = vec![Frame::Outer];
             Self {
                 st: (Vf2State::new(g0), Vf2State::new(g1)),
+                order: SearchOrder::new(g0),
                 node_match,
                 edge_match,
-                match_subgraph,
+                problem,
                 stack,
             }
         }
@@ -749,13 +857,18 @@ mod matching {
         type Item = Vec<usize>;
 
         fn next(&mut self) -> Option<Self::Item> {
-            isomorphisms(
+            if next_match(
                 &mut self.st,
+                &mut self.order,
                 self.node_match,
                 self.edge_match,
-                self.match_subgraph,
+                self.problem,
                 &mut self.stack,
-            )
+            ) {
+                Some(self.st.0.mapping.clone())
+            } else {
+                None
+            }
         }
 
         fn size_hint(&self) -> (usize, Option<usize>) {
@@ -827,8 +940,13 @@ where
     }
 
     let mut st = (Vf2State::new(&g0), Vf2State::new(&g1));
-    self::matching::try_match(&mut st, &mut NoSemanticMatch, &mut NoSemanticMatch, false)
-        .unwrap_or(false)
+    self::matching::try_match(
+        &mut st,
+        &mut NoSemanticMatch,
+        &mut NoSemanticMatch,
+        Problem::Isomorphism,
+    )
+    .unwrap_or(false)
 }
 
 /// \[Generic\] Return `true` if the graphs `g0` and `g1` are isomorphic.
@@ -864,7 +982,13 @@ where
     }
 
     let mut st = (Vf2State::new(&g0), Vf2State::new(&g1));
-    self::matching::try_match(&mut st, &mut node_match, &mut edge_match, false).unwrap_or(false)
+    self::matching::try_match(
+        &mut st,
+        &mut node_match,
+        &mut edge_match,
+        Problem::Isomorphism,
+    )
+    .unwrap_or(false)
 }
 
 /// \[Generic\] Return `true` if `g0` is isomorphic to a subgraph of `g1`.
@@ -894,7 +1018,8 @@ where
 /// Finally, the term ‘subgraph’ can have multiple meanings. In this context,
 /// ‘subgraph’ always means a ‘node-induced subgraph’. Edge-induced subgraph
 /// isomorphisms are not directly supported. For subgraphs which are not
-/// induced, the term ‘monomorphism’ is preferred over ‘isomorphism’.
+/// induced, the term ‘monomorphism’ is preferred over ‘isomorphism’, see
+/// [`is_subgraph_monomorphic`].
 ///
 /// **Reference**
 ///
@@ -914,8 +1039,13 @@ where
     }
 
     let mut st = (Vf2State::new(&g0), Vf2State::new(&g1));
-    self::matching::try_match(&mut st, &mut NoSemanticMatch, &mut NoSemanticMatch, true)
-        .unwrap_or(false)
+    self::matching::try_match(
+        &mut st,
+        &mut NoSemanticMatch,
+        &mut NoSemanticMatch,
+        Problem::InducedSubgraph,
+    )
+    .unwrap_or(false)
 }
 
 /// \[Generic\] Return `true` if `g0` is isomorphic to a subgraph of `g1`.
@@ -951,7 +1081,13 @@ where
     }
 
     let mut st = (Vf2State::new(&g0), Vf2State::new(&g1));
-    self::matching::try_match(&mut st, &mut node_match, &mut edge_match, true).unwrap_or(false)
+    self::matching::try_match(
+        &mut st,
+        &mut node_match,
+        &mut edge_match,
+        Problem::InducedSubgraph,
+    )
+    .unwrap_or(false)
 }
 
 /// Using the VF2 algorithm, examine both syntactic and semantic graph
@@ -989,6 +1125,249 @@ where
     }
 
     Some(self::matching::GraphMatcher::new(
-        g0, g1, node_match, edge_match, true,
+        g0,
+        g1,
+        node_match,
+        edge_match,
+        Problem::InducedSubgraph,
     ))
 }
+
+/// \[Generic\] Return `true` if `g0` is isomorphic to a not necessarily
+/// induced subgraph of `g1`.
+///
+/// Unlike [`is_isomorphic_subgraph`], the mapping only needs to preserve the
+/// edges of `g0`: two nodes of `g1` that the mapping reaches may be adjacent
+/// even if their preimages are not. Such a mapping is a *monomorphism*.
+///
+/// Using the VF2 algorithm with the VF2++ node ordering, only matching graph
+/// syntactically (graph structure).
+///
+/// The graphs should not be multigraphs.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::{is_isomorphic_subgraph, is_subgraph_monomorphic};
+/// use petgraph::graph::UnGraph;
+///
+/// let path = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// let triangle = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+///
+/// assert!(is_subgraph_monomorphic(&path, &triangle));
+/// assert!(!is_isomorphic_subgraph(&path, &triangle));
+/// ```
+pub fn is_subgraph_monomorphic<G0, G1>(g0: G0, g1: G1) -> bool
+where
+    G0: NodeCompactIndexable + EdgeCount + GetAdjacencyMatrix + GraphProp + IntoNeighborsDirected,
+    G1: NodeCompactIndexable
+        + EdgeCount
+        + GetAdjacencyMatrix
+        + GraphProp<EdgeType = G0::EdgeType>
+        + IntoNeighborsDirected,
+{
+    if g0.node_count() > g1.node_count() || g0.edge_count() > g1.edge_count() {
+        return false;
+    }
+
+    let mut st = (Vf2State::new(&g0), Vf2State::new(&g1));
+    self::matching::try_match(
+        &mut st,
+        &mut NoSemanticMatch,
+        &mut NoSemanticMatch,
+        Problem::Monomorphism,
+    )
+    .unwrap_or(false)
+}
+
+/// \[Generic\] Return `true` if `g0` is isomorphic to a not necessarily
+/// induced subgraph of `g1`.
+///
+/// Like [`is_subgraph_monomorphic`], examining both syntactic and semantic
+/// graph isomorphism (graph structure and matching node and edge weights).
+///
+/// The graphs should not be multigraphs.
+pub fn is_subgraph_monomorphic_matching<G0, G1, NM, EM>(
+    g0: G0,
+    g1: G1,
+    mut node_match: NM,
+    mut edge_match: EM,
+) -> bool
+where
+    G0: NodeCompactIndexable
+        + EdgeCount
+        + DataMap
+        + GetAdjacencyMatrix
+        + GraphProp
+        + IntoEdgesDirected,
+    G1: NodeCompactIndexable
+        + EdgeCount
+        + DataMap
+        + GetAdjacencyMatrix
+        + GraphProp<EdgeType = G0::EdgeType>
+        + IntoEdgesDirected,
+    NM: FnMut(&G0::NodeWeight, &G1::NodeWeight) -> bool,
+    EM: FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
+{
+    if g0.node_count() > g1.node_count() || g0.edge_count() > g1.edge_count() {
+        return false;
+    }
+
+    let mut st = (Vf2State::new(&g0), Vf2State::new(&g1));
+    self::matching::try_match(
+        &mut st,
+        &mut node_match,
+        &mut edge_match,
+        Problem::Monomorphism,
+    )
+    .unwrap_or(false)
+}
+
+/// Using the VF2 algorithm, examine both syntactic and semantic graph
+/// isomorphism (graph structure and matching node and edge weights) and,
+/// if `g0` is isomorphic to a not necessarily induced subgraph of `g1`,
+/// return the mappings between them.
+///
+/// See [`is_subgraph_monomorphic`] for the difference to
+/// [`subgraph_isomorphisms_iter`].
+///
+/// The graphs should not be multigraphs.
+pub fn subgraph_monomorphisms_iter<'a, G0, G1, NM, EM>(
+    g0: &'a G0,
+    g1: &'a G1,
+    node_match: &'a mut NM,
+    edge_match: &'a mut EM,
+) -> Option<impl Iterator<Item = Vec<usize>> + 'a>
+where
+    G0: 'a
+        + NodeCompactIndexable
+        + EdgeCount
+        + DataMap
+        + GetAdjacencyMatrix
+        + GraphProp
+        + IntoEdgesDirected,
+    G1: 'a
+        + NodeCompactIndexable
+        + EdgeCount
+        + DataMap
+        + GetAdjacencyMatrix
+        + GraphProp<EdgeType = G0::EdgeType>
+        + IntoEdgesDirected,
+    NM: 'a + FnMut(&G0::NodeWeight, &G1::NodeWeight) -> bool,
+    EM: 'a + FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
+{
+    if g0.node_count() > g1.node_count() || g0.edge_count() > g1.edge_count() {
+        return None;
+    }
+
+    Some(self::matching::GraphMatcher::new(
+        g0,
+        g1,
+        node_match,
+        edge_match,
+        Problem::Monomorphism,
+    ))
+}
+
+/// \[Generic\] Count the mappings of `g0` to induced subgraphs of `g1`, the
+/// mappings that [`subgraph_isomorphisms_iter`] returns, stopping early once
+/// `limit` mappings are found.
+///
+/// Faster than counting the iterator, as the mappings are not collected.
+///
+/// The graphs should not be multigraphs.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::isomorphism::{count_subgraph_isomorphisms, count_subgraph_monomorphisms};
+/// use petgraph::graph::UnGraph;
+///
+/// let path = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// // A square with a diagonal.
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0), (0, 2)]);
+///
+/// // The paths 1 - 0 - 3 and 1 - 2 - 3, each in both directions.
+/// assert_eq!(count_subgraph_isomorphisms(&path, &graph, |_, _| true, |_, _| true, None), 4);
+/// // Every path of length two, in both directions.
+/// assert_eq!(count_subgraph_monomorphisms(&path, &graph, |_, _| true, |_, _| true, None), 16);
+/// assert_eq!(count_subgraph_monomorphisms(&path, &graph, |_, _| true, |_, _| true, Some(3)), 3);
+/// ```
+pub fn count_subgraph_isomorphisms<G0, G1, NM, EM>(
+    g0: G0,
+    g1: G1,
+    mut node_match: NM,
+    mut edge_match: EM,
+    limit: Option<usize>,
+) -> usize
+where
+    G0: NodeCompactIndexable
+        + EdgeCount
+        + DataMap
+        + GetAdjacencyMatrix
+        + GraphProp
+        + IntoEdgesDirected,
+    G1: NodeCompactIndexable
+        + EdgeCount
+        + DataMap
+        + GetAdjacencyMatrix
+        + GraphProp<EdgeType = G0::EdgeType>
+        + IntoEdgesDirected,
+    NM: FnMut(&G0::NodeWeight, &G1::NodeWeight) -> bool,
+    EM: FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
+{
+    if g0.node_count() > g1.node_count() || g0.edge_count() > g1.edge_count() {
+        return 0;
+    }
+
+    let mut st = (Vf2State::new(&g0), Vf2State::new(&g1));
+    self::matching::count_matches(
+        &mut st,
+        &mut node_match,
+        &mut edge_match,
+        Problem::InducedSubgraph,
+        limit,
+    )
+}
+
+/// \[Generic\] Count the mappings of `g0` to not necessarily induced
+/// subgraphs of `g1`, the mappings that [`subgraph_monomorphisms_iter`]
+/// returns, stopping early once `limit` mappings are found.
+///
+/// Faster than counting the iterator, as the mappings are not collected.
+///
+/// The graphs should not be multigraphs.
+pub fn count_subgraph_monomorphisms<G0, G1, NM, EM>(
+    g0: G0,
+    g1: G1,
+    mut node_match: NM,
+    mut edge_match: EM,
+    limit: Option<usize>,
+) -> usize
+where
+    G0: NodeCompactIndexable
+        + EdgeCount
+        + DataMap
+        + GetAdjacencyMatrix
+        + GraphProp
+        + IntoEdgesDirected,
+    G1: NodeCompactIndexable
+        + EdgeCount
+        + DataMap
+        + GetAdjacencyMatrix
+        + GraphProp<EdgeType = G0::EdgeType>
+        + IntoEdgesDirected,
+    NM: FnMut(&G0::NodeWeight, &G1::NodeWeight) -> bool,
+    EM: FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
+{
+    if g0.node_count() > g1.node_count() || g0.edge_count() > g1.edge_count() {
+        return 0;
+    }
+
+    let mut st = (Vf2State::new(&g0), Vf2State::new(&g1));
+    self::matching::count_matches(
+        &mut st,
+        &mut node_match,
+        &mut edge_match,
+        Problem::Monomorphism,
+        limit,
+    )
+}
@@ -0,0 +1,244 @@
+//! Johnson's algorithm for all pairs shortest paths in sparse graphs.
+
+use std::hash::Hash;
+use std::ops::Sub;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::algo::{
+    bellman_ford, AllPairsPaths, FloatMeasure, NegativeCycle, ShortestPathWorkspace,
+};
+use crate::graph::{DiGraph, NodeIndex};
+use crate::scored::MinScored;
+use crate::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoEdges, IntoNodeIdentifiers, NodeIndexable,
+};
+use crate::Direction::Incoming;
+
+/// \[Generic\] [Johnson's algorithm][johnson] for the shortest paths between
+/// all pairs of nodes.
+///
+/// Compute the shortest paths in a weighted graph with positive or negative
+/// edge costs, but no negative cycles. Negative edge costs are first
+/// removed by reweighting every edge with node potentials from
+/// [`bellman_ford`](fn@bellman_ford), without changing which paths are
+/// shortest, and then [Dijkstra's algorithm](fn@crate::algo::dijkstra) runs
+/// from every node. If no edge cost is negative, the reweighting is skipped.
+///
+/// This is much faster than [`floyd_warshall`](fn@crate::algo::floyd_warshall)
+/// on sparse graphs. With the crate feature `rayon`, [`parallel_johnson`]
+/// runs the searches from the nodes in parallel.
+///
+/// The function `edge_cost` should return the cost for a particular edge.
+/// Edges of undirected graphs can be traversed both ways, so an undirected
+/// edge with a negative cost is a negative cycle.
+///
+/// Returns the distances and paths between all pairs of nodes (see
+/// [`AllPairsPaths`]), or
+/// `Err(NegativeCycle)` if the graph has a negative cycle.
+///
+/// [johnson]: https://en.wikipedia.org/wiki/Johnson%27s_algorithm
+///
+/// # Complexity
+/// * Time complexity: **O(|V|·|E| log |V|)**.
+/// * Auxiliary space: **O(|V|²)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::johnson;
+/// use petgraph::prelude::*;
+///
+/// let mut graph = DiGraph::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// let d = graph.add_node(());
+/// graph.extend_with_edges(&[(a, b, 3.), (b, c, -2.), (a, c, 2.), (c, d, 1.), (d, b, 4.)]);
+///
+/// let paths = johnson(&graph, |e| *e.weight()).unwrap();
+/// assert_eq!(paths.distance(a, d), Some(2.));
+/// let (nodes, edges) = paths.path(a, d).unwrap();
+/// assert_eq!(nodes, [a, b, c, d]);
+/// assert_eq!(edges, [EdgeIndex::new(0), EdgeIndex::new(1), EdgeIndex::new(3)]);
+/// assert_eq!(paths.distance(d, c), Some(2.));
+/// assert_eq!(paths.distance(b, a), None);
+///
+/// graph.add_edge(c, a, -2.);
+/// assert!(johnson(&graph, |e| *e.weight()).is_err());
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn johnson<G, F, K>(
+    graph: G,
+    mut edge_cost: F,
+) -> Result<AllPairsPaths<G::NodeId, G::EdgeId, K>, NegativeCycle>
+where
+    G: IntoEdges + IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: FloatMeasure + Sub<Output = K>,
+{
+    let potentials = potentials(graph, &mut edge_cost)?;
+    let mut paths = AllPairsPaths::new(graph, Incoming);
+    let node_bound = paths.node_bound();
+    let mut workspace = ShortestPathWorkspace::new(graph);
+    for source in graph.node_identifiers() {
+        let row = graph.to_index(source) * node_bound;
+        search_from(
+            graph,
+            source,
+            &mut edge_cost,
+            &potentials,
+            &mut workspace,
+            &mut paths.distances[row..row + node_bound],
+            &mut paths.hops[row..row + node_bound],
+        );
+    }
+    Ok(paths)
+}
+
+/// \[Generic\] Parallel version of [`johnson`], which runs the searches from
+/// the nodes on the [rayon] thread pool.
+///
+/// [rayon]: https://docs.rs/rayon
+#[cfg(feature = "rayon")]
+#[allow(clippy::type_complexity)]
+pub fn parallel_johnson<G, F, K>(
+    graph: G,
+    edge_cost: F,
+) -> Result<AllPairsPaths<G::NodeId, G::EdgeId, K>, NegativeCycle>
+where
+    G: IntoEdges + IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp + Sync,
+    G::NodeId: Eq + Hash + Send,
+    G::EdgeId: Send,
+    F: Fn(G::EdgeRef) -> K + Sync,
+    K: FloatMeasure + Sub<Output = K> + Send + Sync,
+{
+    let potentials = potentials(graph, &mut |edge| edge_cost(edge))?;
+    let mut paths = AllPairsPaths::new(graph, Incoming);
+    let node_bound = paths.node_bound();
+    let sources: Vec<_> = graph.node_identifiers().collect();
+    let mut rows: Vec<_> = paths
+        .distances
+        .chunks_mut(node_bound.max(1))
+        .zip(paths.hops.chunks_mut(node_bound.max(1)))
+        .map(Some)
+        .collect();
+    // Hand every search the rows of its source.
+    let mut work = Vec::with_capacity(sources.len());
+    for source in sources {
+        if let Some(row) = rows[graph.to_index(source)].take() {
+            work.push((source, row));
+        }
+    }
+    work.into_par_iter().for_each_init(
+        || ShortestPathWorkspace::new(graph),
+        |workspace, (source, (distances, hops))| {
+            search_from(
+                graph,
+                source,
+                &mut |edge| edge_cost(edge),
+                &potentials,
+                workspace,
+                distances,
+                hops,
+            );
+        },
+    );
+    Ok(paths)
+}
+
+/// Node potentials that make every edge cost non-negative, from the distances
+/// of an extra node with an edge of cost zero to every node.
+fn potentials<G, F, K>(graph: G, edge_cost: &mut F) -> Result<Vec<K>, NegativeCycle>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> K,
+    K: FloatMeasure,
+{
+    let node_bound = graph.node_bound();
+    let mut edges = Vec::new();
+    let mut negative = false;
+    for edge in graph.edge_references() {
+        let a = graph.to_index(edge.source());
+        let b = graph.to_index(edge.target());
+        let cost = edge_cost(edge);
+        negative |= cost < K::zero();
+        edges.push((a, b, cost));
+        if !graph.is_directed() {
+            edges.push((b, a, cost));
+        }
+    }
+    if !negative {
+        return Ok(vec![K::zero(); node_bound]);
+    }
+
+    let mut extended = DiGraph::<(), K, usize>::with_capacity(node_bound + 1, 0);
+    for _ in 0..=node_bound {
+        extended.add_node(());
+    }
+    let extra = NodeIndex::new(node_bound);
+    for node in graph.node_identifiers() {
+        extended.add_edge(extra, NodeIndex::new(graph.to_index(node)), K::zero());
+    }
+    for (a, b, cost) in edges {
+        extended.add_edge(NodeIndex::new(a), NodeIndex::new(b), cost);
+    }
+    let mut distances = bellman_ford(&extended, extra)?.distances;
+    distances.truncate(node_bound);
+    Ok(distances)
+}
+
+/// Fill the rows of `source` with the shortest paths from it, searching with
+/// edge costs reweighted by `potentials`.
+fn search_from<G, F, K>(
+    graph: G,
+    source: G::NodeId,
+    edge_cost: &mut F,
+    potentials: &[K],
+    workspace: &mut ShortestPathWorkspace<K>,
+    distances: &mut [Option<K>],
+    hops: &mut [Option<(usize, G::EdgeId)>],
+) where
+    G: IntoEdges + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: FloatMeasure + Sub<Output = K>,
+{
+    // Dijkstra's algorithm, which also records the edge to every node.
+    workspace.start(graph.node_bound());
+    let start = graph.to_index(source);
+    let zero_score = K::zero();
+    workspace.reach(start, zero_score, usize::MAX);
+    workspace
+        .visit_next
+        .push(MinScored(zero_score, (start, zero_score)));
+    while let Some(MinScored(node_score, (node, _))) = workspace.visit_next.pop() {
+        if workspace.is_settled(node) {
+            continue;
+        }
+        workspace.settle(node);
+        distances[node] = Some(node_score - potentials[start] + potentials[node]);
+        for edge in graph.edges(graph.from_index(node)) {
+            let next = graph.to_index(edge.target());
+            if workspace.is_settled(next) {
+                continue;
+            }
+            let mut cost = edge_cost(edge) + potentials[node] - potentials[next];
+            // Rounding can leave tight edges slightly below zero.
+            if cost < K::zero() {
+                cost = K::zero();
+            }
+            let next_score = node_score + cost;
+            if workspace
+                .score(next)
+                .map_or(true, |score| next_score < score)
+            {
+                workspace.reach(next, next_score, node);
+                hops[next] = Some((node, edge.id()));
+                workspace
+                    .visit_next
+                    .push(MinScored(next_score, (next, next_score)));
+            }
+        }
+    }
+}
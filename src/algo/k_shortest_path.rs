@@ -1,10 +1,11 @@
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::hash_map::Entry::{Occupied, Vacant};
+use std::collections::{BinaryHeap, HashMap, HashSet};
 
 use std::hash::Hash;
 
 use crate::algo::Measure;
 use crate::scored::MinScored;
-use crate::visit::{EdgeRef, IntoEdges, NodeCount, NodeIndexable, Visitable};
+use crate::visit::{EdgeRef, GraphBase, IntoEdges, NodeCount, NodeIndexable, Visitable};
 
 /// \[Generic\] k'th shortest path algorithm.
 ///
@@ -20,7 +21,9 @@ use crate::visit::{EdgeRef, IntoEdges, NodeCount, NodeIndexable, Visitable};
 ///
 /// Computes in **O(k * (|E| + |V|*log(|V|)))** time (average).
 ///
-/// Returns a `HashMap` that maps `NodeId` to path cost.
+/// Returns a `HashMap` that maps `NodeId` to path cost. The paths are walks
+/// which may repeat nodes; see [`k_shortest_simple_paths`] for the k shortest
+/// paths without repeated nodes themselves.
 /// # Example
 /// ```rust
 /// use petgraph::Graph;
@@ -113,3 +116,262 @@ where
     }
     scores
 }
+
+/// \[Generic\] The shortest simple paths from `start` to `goal`, in order of
+/// their cost, with [Yen's algorithm][yen].
+///
+/// Returns an iterator over the loopless paths from `start` to `goal`, from
+/// the shortest to the longest. Every item is the cost of a path, its nodes
+/// from `start` to `goal` and the edges between them. Paths that differ only
+/// in the parallel edges they take are distinct. Paths of equal cost are
+/// returned in no particular order.
+///
+/// The paths are computed lazily: each one after the first takes a shortest
+/// path search from every node of the previous one, so use `take(k)` for the
+/// k shortest paths.
+///
+/// The graph should implement `IntoEdges`. The function `edge_cost` should
+/// return the cost for a particular edge, which is used to compute path
+/// costs. Edge costs must be non-negative.
+///
+/// [yen]: https://en.wikipedia.org/wiki/Yen%27s_algorithm
+///
+/// # Complexity
+/// * Time complexity: **O(k·|V|·(|E| + |V| log |V|))** for the first k paths.
+/// * Auxiliary space: **O(k·|V|²)**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::k_shortest_simple_paths;
+/// use petgraph::prelude::*;
+///
+/// let mut graph = DiGraph::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// let d = graph.add_node(());
+/// let ab = graph.add_edge(a, b, 1);
+/// let bd = graph.add_edge(b, d, 1);
+/// let ac = graph.add_edge(a, c, 3);
+/// let cd = graph.add_edge(c, d, 1);
+/// let bc = graph.add_edge(b, c, 1);
+/// graph.add_edge(d, a, 1);
+///
+/// let mut paths = k_shortest_simple_paths(&graph, a, d, |e| *e.weight());
+/// assert_eq!(paths.next(), Some((2, vec![a, b, d], vec![ab, bd])));
+/// assert_eq!(paths.next(), Some((3, vec![a, b, c, d], vec![ab, bc, cd])));
+/// assert_eq!(paths.next(), Some((4, vec![a, c, d], vec![ac, cd])));
+/// assert_eq!(paths.next(), None);
+/// ```
+pub fn k_shortest_simple_paths<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: G::NodeId,
+    edge_cost: F,
+) -> KShortestSimplePaths<G, F, K>
+where
+    G: IntoEdges,
+    G::NodeId: Eq + Hash,
+    G::EdgeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    KShortestSimplePaths {
+        graph,
+        start,
+        goal,
+        edge_cost,
+        found: Vec::new(),
+        candidates: BinaryHeap::new(),
+        seen: HashSet::new(),
+        finished: false,
+    }
+}
+
+/// A path found by [`KShortestSimplePaths`].
+struct SimplePath<N, E, K> {
+    nodes: Vec<N>,
+    edges: Vec<E>,
+    // The cost of the path up to every node.
+    costs: Vec<K>,
+}
+
+/// An iterator over the shortest simple paths between two nodes.
+///
+/// See [`k_shortest_simple_paths`].
+#[allow(clippy::type_complexity)]
+pub struct KShortestSimplePaths<G, F, K>
+where
+    G: GraphBase,
+{
+    graph: G,
+    start: G::NodeId,
+    goal: G::NodeId,
+    edge_cost: F,
+    // The paths returned so far, the list `A` of Yen's algorithm.
+    found: Vec<SimplePath<G::NodeId, G::EdgeId, K>>,
+    // Paths that are not returned yet, the list `B` of Yen's algorithm.
+    candidates: BinaryHeap<MinScored<K, SimplePath<G::NodeId, G::EdgeId, K>>>,
+    // The edges of every path in `found` or `candidates`.
+    seen: HashSet<Vec<G::EdgeId>>,
+    finished: bool,
+}
+
+impl<G, F, K> KShortestSimplePaths<G, F, K>
+where
+    G: IntoEdges,
+    G::NodeId: Eq + Hash,
+    G::EdgeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    /// Add the deviations of the last path found to the candidates: for every
+    /// node of the path, the shortest path that follows it up to that node
+    /// and then leaves it by an edge that no path found so far with the same
+    /// beginning takes.
+    fn push_deviations(&mut self) {
+        let last = self.found.len() - 1;
+        for spur in 0..self.found[last].edges.len() {
+            let previous = &self.found[last];
+            let root = &previous.edges[..spur];
+            let banned_nodes: HashSet<_> = previous.nodes[..spur].iter().cloned().collect();
+            let banned_edges: HashSet<_> = self
+                .found
+                .iter()
+                .filter(|path| path.edges.len() > spur && path.edges[..spur] == *root)
+                .map(|path| path.edges[spur])
+                .collect();
+            let spur_node = previous.nodes[spur];
+            let deviation = match self.search(spur_node, &banned_nodes, &banned_edges) {
+                Some(deviation) => deviation,
+                None => continue,
+            };
+
+            let previous = &self.found[last];
+            let mut edges = previous.edges[..spur].to_vec();
+            edges.extend_from_slice(&deviation.edges);
+            if self.seen.contains(&edges) {
+                continue;
+            }
+            let mut nodes = previous.nodes[..spur].to_vec();
+            nodes.extend_from_slice(&deviation.nodes);
+            let root_cost = previous.costs[spur];
+            let mut costs = previous.costs[..spur].to_vec();
+            costs.extend(deviation.costs.iter().map(|&cost| root_cost + cost));
+            self.seen.insert(edges.clone());
+            let cost = *costs.last().unwrap();
+            self.candidates.push(MinScored(
+                cost,
+                SimplePath {
+                    nodes,
+                    edges,
+                    costs,
+                },
+            ));
+        }
+    }
+
+    /// Dijkstra's algorithm from `from` to the goal, avoiding the given nodes
+    /// and edges.
+    fn search(
+        &mut self,
+        from: G::NodeId,
+        banned_nodes: &HashSet<G::NodeId>,
+        banned_edges: &HashSet<G::EdgeId>,
+    ) -> Option<SimplePath<G::NodeId, G::EdgeId, K>> {
+        let mut settled = HashSet::new();
+        let mut scores = HashMap::new();
+        let mut predecessors = HashMap::new();
+        let mut visit_next = BinaryHeap::new();
+        let zero_score = K::default();
+        scores.insert(from, zero_score);
+        visit_next.push(MinScored(zero_score, from));
+        while let Some(MinScored(node_score, node)) = visit_next.pop() {
+            if !settled.insert(node) {
+                continue;
+            }
+            if node == self.goal {
+                let mut nodes = vec![node];
+                let mut edges = Vec::new();
+                let mut costs = vec![node_score];
+                let mut node = node;
+                while let Some(&(previous, edge)) = predecessors.get(&node) {
+                    nodes.push(previous);
+                    edges.push(edge);
+                    costs.push(scores[&previous]);
+                    node = previous;
+                }
+                nodes.reverse();
+                edges.reverse();
+                costs.reverse();
+                return Some(SimplePath {
+                    nodes,
+                    edges,
+                    costs,
+                });
+            }
+            for edge in self.graph.edges(node) {
+                let next = edge.target();
+                if settled.contains(&next)
+                    || banned_nodes.contains(&next)
+                    || banned_edges.contains(&edge.id())
+                {
+                    continue;
+                }
+                let next_score = node_score + (self.edge_cost)(edge);
+                match scores.entry(next) {
+                    Occupied(ent) => {
+                        if next_score < *ent.get() {
+                            *ent.into_mut() = next_score;
+                            visit_next.push(MinScored(next_score, next));
+                            predecessors.insert(next, (node, edge.id()));
+                        }
+                    }
+                    Vacant(ent) => {
+                        ent.insert(next_score);
+                        visit_next.push(MinScored(next_score, next));
+                        predecessors.insert(next, (node, edge.id()));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<G, F, K> Iterator for KShortestSimplePaths<G, F, K>
+where
+    G: IntoEdges,
+    G::NodeId: Eq + Hash,
+    G::EdgeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    type Item = (K, Vec<G::NodeId>, Vec<G::EdgeId>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let path = if self.found.is_empty() {
+            self.search(self.start, &HashSet::new(), &HashSet::new())
+        } else {
+            self.push_deviations();
+            self.candidates.pop().map(|MinScored(_, path)| path)
+        };
+        let path = match path {
+            Some(path) => path,
+            None => {
+                self.finished = true;
+                return None;
+            }
+        };
+        let item = (
+            *path.costs.last().unwrap(),
+            path.nodes.clone(),
+            path.edges.clone(),
+        );
+        self.found.push(path);
+        Some(item)
+    }
+}
@@ -1,9 +1,10 @@
 use std::collections::VecDeque;
 use std::hash::Hash;
 
+use super::{BoundedMeasure, Measure};
 use crate::visit::{
-    EdgeRef, GraphBase, IntoEdges, IntoNeighbors, IntoNodeIdentifiers, NodeCount, NodeIndexable,
-    VisitMap, Visitable,
+    EdgeRef, GraphBase, IntoEdgeReferences, IntoEdges, IntoNeighbors, IntoNodeIdentifiers,
+    NodeCount, NodeIndexable, VisitMap, Visitable,
 };
 
 /// Computed
@@ -592,3 +593,343 @@ fn augment_path<G>(
         panic!("Unexpected label when augmenting path");
     }
 }
+
+/// \[Generic\] Compute a maximum weight matching of a bipartite graph with the
+/// [Hungarian algorithm][1].
+///
+/// [1]: https://en.wikipedia.org/wiki/Hungarian_algorithm
+///
+/// The nodes in `left` form one side of the bipartition, all other nodes the
+/// other side. Edges between two nodes on the same side are ignored, and the
+/// input graph is treated as if undirected. The weight of every edge is given
+/// by `edge_weight`, only edges of positive weight are matched.
+///
+/// Returns the matching with the largest total weight, which is not
+/// necessarily a maximum or perfect matching, and its total weight.
+///
+/// The algorithm runs in *O(|L|² |R| + |E|)* time and uses *O(|L| |R|)*
+/// memory, for `|L|` nodes in `left` and `|R|` other nodes.
+///
+/// # Examples
+///
+/// ```
+/// use petgraph::prelude::*;
+/// use petgraph::algo::maximum_weight_bipartite_matching;
+///
+/// // Workers a and b, jobs x and y, weighted by the profit of the assignment.
+/// let mut graph: UnGraph<(), u32> = UnGraph::new_undirected();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let x = graph.add_node(());
+/// let y = graph.add_node(());
+/// graph.extend_with_edges(&[(a, x, 8), (a, y, 6), (b, x, 7)]);
+///
+/// let (matching, weight) = maximum_weight_bipartite_matching(&graph, [a, b], |e| *e.weight());
+/// assert_eq!(weight, 13);
+/// assert!(matching.contains_edge(a, y));
+/// assert!(matching.contains_edge(b, x));
+/// ```
+pub fn maximum_weight_bipartite_matching<G, I, F, K>(
+    graph: G,
+    left: I,
+    mut edge_weight: F,
+) -> (Matching<G>, K)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    I: IntoIterator<Item = G::NodeId>,
+    F: FnMut(G::EdgeRef) -> K,
+    K: BoundedMeasure + Copy,
+{
+    let zero = K::default();
+    let sides = BipartiteSides::new(&graph, left);
+    let weights = sides.edge_matrix(
+        &graph,
+        |edge| {
+            let weight = edge_weight(edge);
+            (weight > zero).then_some(weight)
+        },
+        |new, old| new > old,
+    );
+
+    // Maximize the weight by minimizing `max - weight`, where leaving a node
+    // unmatched costs `max`.
+    let max = weights
+        .iter()
+        .flatten()
+        .fold(zero, |max, &w| if w > max { w } else { max });
+    let costs: Vec<_> = weights.iter().map(|w| w.map(|w| max - w)).collect();
+    let assignment = min_cost_assignment(sides.left.len(), sides.right.len(), &costs, Some(max))
+        .expect("every node can stay unmatched");
+    sides.into_matching(graph, &assignment, &weights)
+}
+
+/// \[Generic\] Compute a minimum cost matching of a bipartite graph that
+/// matches every node in `left`, with the [Hungarian algorithm][1].
+///
+/// [1]: https://en.wikipedia.org/wiki/Hungarian_algorithm
+///
+/// The nodes in `left` form one side of the bipartition, all other nodes the
+/// other side. Edges between two nodes on the same side are ignored, and the
+/// input graph is treated as if undirected. The cost of every edge is given
+/// by `edge_cost`, and may be negative.
+///
+/// Returns the matching and its total cost, or `None` if there is no matching
+/// that covers `left`. If both sides have the same size, the matching is
+/// perfect. Use it for assignment problems, where every item of `left` has to
+/// be assigned to a different node on the other side.
+///
+/// The algorithm runs in *O(|L|² |R| + |E|)* time and uses *O(|L| |R|)*
+/// memory, for `|L|` nodes in `left` and `|R|` other nodes.
+///
+/// # Examples
+///
+/// ```
+/// use petgraph::prelude::*;
+/// use petgraph::algo::min_cost_bipartite_matching;
+///
+/// // Jobs a and b, workers x, y and z, weighted by the cost of the assignment.
+/// let mut graph: UnGraph<(), u32> = UnGraph::new_undirected();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let x = graph.add_node(());
+/// let y = graph.add_node(());
+/// let z = graph.add_node(());
+/// graph.extend_with_edges(&[(a, x, 3), (a, y, 1), (b, y, 2), (b, z, 5)]);
+///
+/// let (matching, cost) = min_cost_bipartite_matching(&graph, [a, b], |e| *e.weight()).unwrap();
+/// assert_eq!(cost, 5);
+/// assert_eq!(matching.mate(a), Some(x));
+/// assert_eq!(matching.mate(b), Some(y));
+///
+/// // No matching covers x, y and z.
+/// assert!(min_cost_bipartite_matching(&graph, [x, y, z], |e| *e.weight()).is_none());
+/// ```
+pub fn min_cost_bipartite_matching<G, I, F, K>(
+    graph: G,
+    left: I,
+    mut edge_cost: F,
+) -> Option<(Matching<G>, K)>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    I: IntoIterator<Item = G::NodeId>,
+    F: FnMut(G::EdgeRef) -> K,
+    K: BoundedMeasure + Copy,
+{
+    let zero = K::default();
+    let sides = BipartiteSides::new(&graph, left);
+    let costs = sides.edge_matrix(&graph, |edge| Some(edge_cost(edge)), |new, old| new < old);
+
+    // Every node in `left` is matched, so shifting all costs by the same
+    // amount keeps the optimum and makes them non-negative.
+    let min = costs
+        .iter()
+        .flatten()
+        .fold(zero, |min, &c| if c < min { c } else { min });
+    let shifted: Vec<_> = costs.iter().map(|c| c.map(|c| c - min)).collect();
+    let assignment = min_cost_assignment(sides.left.len(), sides.right.len(), &shifted, None)?;
+    Some(sides.into_matching(graph, &assignment, &costs))
+}
+
+// The two sides of a bipartite graph, numbered densely.
+struct BipartiteSides<N> {
+    left: Vec<N>,
+    right: Vec<N>,
+    // The row or column of every node, by node index.
+    position: Vec<Option<(bool, usize)>>,
+}
+
+impl<N: Copy> BipartiteSides<N> {
+    fn new<G, I>(graph: &G, left: I) -> Self
+    where
+        G: IntoNodeIdentifiers + NodeIndexable + GraphBase<NodeId = N>,
+        I: IntoIterator<Item = N>,
+    {
+        let mut position = vec![None; graph.node_bound()];
+        let mut sides = BipartiteSides {
+            left: Vec::new(),
+            right: Vec::new(),
+            position: Vec::new(),
+        };
+        for node in left {
+            let index = graph.to_index(node);
+            if position[index].is_none() {
+                position[index] = Some((true, sides.left.len()));
+                sides.left.push(node);
+            }
+        }
+        for node in graph.node_identifiers() {
+            let index = graph.to_index(node);
+            if position[index].is_none() {
+                position[index] = Some((false, sides.right.len()));
+                sides.right.push(node);
+            }
+        }
+        sides.position = position;
+        sides
+    }
+
+    // The weight of the edges between the sides as a dense matrix, rows for
+    // `left` and columns for `right`. Of parallel edges the one `better` than
+    // the others is kept.
+    fn edge_matrix<G, F, B, K>(&self, graph: &G, mut weight: F, better: B) -> Vec<Option<K>>
+    where
+        G: IntoEdgeReferences + NodeIndexable + GraphBase<NodeId = N>,
+        F: FnMut(G::EdgeRef) -> Option<K>,
+        B: Fn(K, K) -> bool,
+        K: Copy,
+    {
+        let columns = self.right.len();
+        let mut matrix = vec![None; self.left.len() * columns];
+        for edge in graph.edge_references() {
+            let a = self.position[graph.to_index(edge.source())];
+            let b = self.position[graph.to_index(edge.target())];
+            let (row, column) = match (a, b) {
+                (Some((true, row)), Some((false, column)))
+                | (Some((false, column)), Some((true, row))) => (row, column),
+                _ => continue,
+            };
+            if let Some(w) = weight(edge) {
+                let entry = &mut matrix[row * columns + column];
+                if entry.map_or(true, |old| better(w, old)) {
+                    *entry = Some(w);
+                }
+            }
+        }
+        matrix
+    }
+
+    fn into_matching<G, K>(
+        self,
+        graph: G,
+        assignment: &[Option<usize>],
+        weights: &[Option<K>],
+    ) -> (Matching<G>, K)
+    where
+        G: NodeIndexable + GraphBase<NodeId = N>,
+        K: Measure + Copy,
+    {
+        let columns = self.right.len();
+        let mut mate = vec![None; graph.node_bound()];
+        let mut n_edges = 0;
+        let mut total = K::default();
+        for (row, column) in assignment.iter().enumerate() {
+            if let Some(column) = *column {
+                let (a, b) = (self.left[row], self.right[column]);
+                mate[graph.to_index(a)] = Some(b);
+                mate[graph.to_index(b)] = Some(a);
+                n_edges += 1;
+                total = total + weights[row * columns + column].unwrap();
+            }
+        }
+        (Matching::new(graph, mate, n_edges), total)
+    }
+}
+
+// Solve the assignment problem for a `rows` by `columns` matrix of
+// non-negative costs, where `None` forbids a pair, by successive shortest
+// paths with potentials. If `unmatched` is given, a row may also stay
+// unassigned at that cost. Returns the column of every row, or `None` if
+// not every row can be assigned.
+fn min_cost_assignment<K>(
+    rows: usize,
+    columns: usize,
+    cost: &[Option<K>],
+    unmatched: Option<K>,
+) -> Option<Vec<Option<usize>>>
+where
+    K: BoundedMeasure + Copy,
+{
+    let zero = K::default();
+    // Reduced costs `cost + row_potential - column_potential` stay
+    // non-negative. Column `columns` stands for staying unassigned, it is
+    // never taken.
+    let mut row_potential = vec![zero; rows];
+    let mut column_potential = vec![zero; columns + 1];
+    let mut row_match: Vec<Option<usize>> = vec![None; rows];
+    let mut column_match: Vec<Option<usize>> = vec![None; columns];
+
+    let mut distance: Vec<Option<K>> = vec![None; columns + 1];
+    let mut done = vec![false; columns + 1];
+    // The row a column is reached from.
+    let mut from = vec![0; columns + 1];
+    let mut row_distance = vec![zero; rows];
+    let mut tree_rows = Vec::new();
+
+    for start in 0..rows {
+        distance.iter_mut().for_each(|d| *d = None);
+        done.iter_mut().for_each(|d| *d = false);
+        tree_rows.clear();
+
+        let mut row = start;
+        let mut row_dist = zero;
+        let (end, end_distance) = loop {
+            row_distance[row] = row_dist;
+            tree_rows.push(row);
+            let row_costs = &cost[row * columns..(row + 1) * columns];
+            let candidates = row_costs.iter().copied().chain(Some(unmatched)).enumerate();
+            for (column, c) in candidates {
+                if let Some(c) = c {
+                    if done[column] {
+                        continue;
+                    }
+                    let d = row_dist + (c + row_potential[row]) - column_potential[column];
+                    if distance[column].map_or(true, |old| d < old) {
+                        distance[column] = Some(d);
+                        from[column] = row;
+                    }
+                }
+            }
+
+            // Settle the closest column.
+            let mut closest: Option<(usize, K)> = None;
+            for (column, d) in distance.iter().enumerate() {
+                if let Some(d) = *d {
+                    if !done[column] && closest.map_or(true, |(_, best)| d < best) {
+                        closest = Some((column, d));
+                    }
+                }
+            }
+            let (column, d) = closest?;
+            done[column] = true;
+            match column_match.get(column).copied().flatten() {
+                Some(next) => {
+                    row = next;
+                    row_dist = d;
+                }
+                None => break (column, d),
+            }
+        };
+
+        // Keep the reduced costs non-negative: nodes in the search tree move
+        // by their distance, all others by the distance of the free column.
+        for potential in row_potential.iter_mut() {
+            *potential = *potential + end_distance;
+        }
+        for &row in &tree_rows {
+            row_potential[row] = row_potential[row] - (end_distance - row_distance[row]);
+        }
+        for (column, potential) in column_potential.iter_mut().enumerate() {
+            let d = match distance[column] {
+                Some(d) if done[column] => d,
+                _ => end_distance,
+            };
+            *potential = *potential + d;
+        }
+
+        // Augment along the alternating path.
+        let mut column = end;
+        loop {
+            let row = from[column];
+            let previous = row_match[row];
+            row_match[row] = (column < columns).then_some(column);
+            if column < columns {
+                column_match[column] = Some(row);
+            }
+            if row == start {
+                break;
+            }
+            column = previous.expect("rows on the path are matched");
+        }
+    }
+    Some(row_match)
+}
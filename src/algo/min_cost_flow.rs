@@ -0,0 +1,249 @@
+//! Minimum cost maximum flow.
+
+use std::collections::{BinaryHeap, VecDeque};
+use std::ops::Mul;
+
+use super::{BoundedMeasure, NegativeCycle};
+use crate::scored::MinScored;
+use crate::visit::{EdgeIndexable, EdgeRef, IntoEdgeReferences, NodeIndexable};
+
+/// The result of [`min_cost_max_flow`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinCostFlow<K> {
+    /// The value of the maximum flow.
+    pub flow: K,
+    /// The total cost of the flow, the sum of flow times cost over all edges.
+    pub cost: K,
+    /// The flow on every edge, indexed by `EdgeIndexable::to_index`.
+    pub edge_flows: Vec<K>,
+}
+
+// An arc of the residual network. Arc `2 * i` runs along edge `i`, arc
+// `2 * i + 1` is its reverse. Both store the cost of the edge, the reverse
+// arc costs its negation, so that unsigned costs can be used.
+#[derive(Clone, Debug)]
+struct Arc<K> {
+    target: usize,
+    residual: K,
+    cost: K,
+}
+
+/// \[Generic\] Compute a maximum flow of least total cost from `source` to
+/// `sink`, using successive shortest paths with node potentials.
+///
+/// Every edge is directed from its source to its target and can carry up to
+/// `capacity(edge)` units of flow, each costing `cost(edge)`. Edges with a
+/// capacity of zero or less and self loops carry no flow.
+///
+/// Costs may be negative, but if a cycle of negative total cost is reachable
+/// from `source` along edges with positive capacity, the function returns
+/// `Err(NegativeCycle)`.
+///
+/// With integer capacities the running time is **O(F (|E| + |V|) log |V|)**,
+/// where **F** is the value of the flow, plus **O(|V| |E|)** to find the
+/// initial potentials.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::min_cost_max_flow;
+/// use petgraph::Graph;
+///
+/// // Edges carry (capacity, cost per unit).
+/// let mut graph = Graph::<(), (u32, u32)>::new();
+/// let s = graph.add_node(());
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let t = graph.add_node(());
+/// graph.extend_with_edges(&[
+///     (s, a, (2, 1)),
+///     (s, b, (1, 5)),
+///     (a, b, (1, 1)),
+///     (a, t, (1, 3)),
+///     (b, t, (2, 1)),
+/// ]);
+///
+/// let result = min_cost_max_flow(&graph, s, t, |e| e.weight().0, |e| e.weight().1).unwrap();
+/// assert_eq!(result.flow, 3);
+/// assert_eq!(result.cost, 2 * 1 + 5 + 1 + 3 + 2 * 1);
+/// ```
+pub fn min_cost_max_flow<G, F, C, K>(
+    network: G,
+    source: G::NodeId,
+    sink: G::NodeId,
+    mut capacity: F,
+    mut cost: C,
+) -> Result<MinCostFlow<K>, NegativeCycle>
+where
+    G: IntoEdgeReferences + NodeIndexable + EdgeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    C: FnMut(G::EdgeRef) -> K,
+    K: BoundedMeasure + Copy + Mul<Output = K>,
+{
+    let zero = K::default();
+    let node_count = network.node_bound();
+    let edge_count = network.edge_bound();
+
+    let mut arcs = Vec::with_capacity(2 * edge_count);
+    let mut adjacency = vec![Vec::new(); node_count];
+    // The graph edge of every pair of arcs.
+    let mut edges = Vec::with_capacity(edge_count);
+    for edge in network.edge_references() {
+        let a = NodeIndexable::to_index(&network, edge.source());
+        let b = NodeIndexable::to_index(&network, edge.target());
+        let cap = capacity(edge);
+        if a == b || cap <= zero {
+            continue;
+        }
+        let c = cost(edge);
+        adjacency[a].push(arcs.len());
+        arcs.push(Arc {
+            target: b,
+            residual: cap,
+            cost: c,
+        });
+        adjacency[b].push(arcs.len());
+        arcs.push(Arc {
+            target: a,
+            residual: zero,
+            cost: c,
+        });
+        edges.push((EdgeIndexable::to_index(&network, edge.id()), c));
+    }
+
+    let s = NodeIndexable::to_index(&network, source);
+    let t = NodeIndexable::to_index(&network, sink);
+    let mut edge_flows = vec![zero; edge_count];
+    let mut flow = zero;
+    if s == t {
+        return Ok(MinCostFlow {
+            flow,
+            cost: zero,
+            edge_flows,
+        });
+    }
+
+    let mut potential = initial_potentials(&arcs, &adjacency, s)?;
+    let mut distance: Vec<Option<K>> = vec![None; node_count];
+    let mut pred_arc = vec![usize::MAX; node_count];
+    let mut heap = BinaryHeap::new();
+    loop {
+        // Dijkstra on reduced costs, which are non-negative for every arc
+        // with residual capacity.
+        distance.iter_mut().for_each(|d| *d = None);
+        distance[s] = Some(zero);
+        heap.push(MinScored(zero, s));
+        while let Some(MinScored(d, u)) = heap.pop() {
+            if distance[u].map_or(false, |best| d > best) {
+                continue;
+            }
+            for &i in &adjacency[u] {
+                let arc = &arcs[i];
+                if arc.residual <= zero {
+                    continue;
+                }
+                let v = arc.target;
+                // The reduced cost is non-negative, keep intermediate values
+                // non-negative as well.
+                let next = if i % 2 == 0 {
+                    d + potential[u] + arc.cost - potential[v]
+                } else {
+                    d + potential[u] - (potential[v] + arc.cost)
+                };
+                if distance[v].map_or(true, |best| next < best) {
+                    distance[v] = Some(next);
+                    pred_arc[v] = i;
+                    heap.push(MinScored(next, v));
+                }
+            }
+        }
+        if distance[t].is_none() {
+            break;
+        }
+        for (p, d) in potential.iter_mut().zip(&distance) {
+            if let Some(d) = *d {
+                *p = *p + d;
+            }
+        }
+
+        // Push the bottleneck capacity along the shortest path.
+        let mut bottleneck = K::max();
+        let mut v = t;
+        while v != s {
+            let i = pred_arc[v];
+            if arcs[i].residual < bottleneck {
+                bottleneck = arcs[i].residual;
+            }
+            v = arcs[i ^ 1].target;
+        }
+        let mut v = t;
+        while v != s {
+            let i = pred_arc[v];
+            arcs[i].residual = arcs[i].residual - bottleneck;
+            arcs[i ^ 1].residual = arcs[i ^ 1].residual + bottleneck;
+            v = arcs[i ^ 1].target;
+        }
+        flow = flow + bottleneck;
+    }
+
+    let mut total_cost = zero;
+    for (pair, &(index, c)) in edges.iter().enumerate() {
+        // The residual capacity of the reverse arc is the flow on the edge.
+        let f = arcs[2 * pair + 1].residual;
+        edge_flows[index] = f;
+        total_cost = total_cost + f * c;
+    }
+    Ok(MinCostFlow {
+        flow,
+        cost: total_cost,
+        edge_flows,
+    })
+}
+
+// Shortest distances from `s` along arcs with residual capacity, found with
+// the queue based Bellman-Ford algorithm. Nodes that can not be reached keep
+// a potential of zero, they stay unreachable while the flow is augmented.
+fn initial_potentials<K>(
+    arcs: &[Arc<K>],
+    adjacency: &[Vec<usize>],
+    s: usize,
+) -> Result<Vec<K>, NegativeCycle>
+where
+    K: BoundedMeasure + Copy,
+{
+    let n = adjacency.len();
+    let mut distance: Vec<Option<K>> = vec![None; n];
+    let mut in_queue = vec![false; n];
+    // Number of arcs on the shortest path found so far.
+    let mut length = vec![0; n];
+    let mut queue = VecDeque::new();
+    distance[s] = Some(K::default());
+    queue.push_back(s);
+    in_queue[s] = true;
+    while let Some(u) = queue.pop_front() {
+        in_queue[u] = false;
+        let du = distance[u].unwrap();
+        for &i in &adjacency[u] {
+            let arc = &arcs[i];
+            if arc.residual <= K::default() {
+                continue;
+            }
+            let v = arc.target;
+            let next = du + arc.cost;
+            if distance[v].map_or(true, |best| next < best) {
+                distance[v] = Some(next);
+                length[v] = length[u] + 1;
+                if length[v] >= n {
+                    return Err(NegativeCycle(()));
+                }
+                if !in_queue[v] {
+                    in_queue[v] = true;
+                    queue.push_back(v);
+                }
+            }
+        }
+    }
+    Ok(distance
+        .into_iter()
+        .map(|d| d.unwrap_or_default())
+        .collect())
+}
@@ -7,17 +7,34 @@
 pub mod articulation_points;
 pub mod astar;
 pub mod bellman_ford;
+#[cfg(feature = "rayon")]
+pub mod bfs;
+pub mod bidirectional;
+pub mod canonical;
+pub mod centrality;
 pub mod coloring;
+pub mod community;
+#[cfg(feature = "rayon")]
+pub mod connected_components;
+pub mod contraction_hierarchy;
+pub mod cycles;
+#[cfg(feature = "rayon")]
+pub mod delta_stepping;
 pub mod dijkstra;
+pub mod dinic;
 pub mod dominators;
 pub mod feedback_arc_set;
 pub mod floyd_warshall;
 pub mod ford_fulkerson;
 pub mod isomorphism;
+pub mod johnson;
 pub mod k_shortest_path;
 pub mod matching;
+pub mod min_cost_flow;
 pub mod min_spanning_tree;
 pub mod page_rank;
+pub mod push_relabel;
+pub mod shortest_paths;
 pub mod simple_paths;
 pub mod tred;
 
@@ -34,21 +51,62 @@ use super::visit::{
 use super::EdgeType;
 use crate::visit::Walker;
 
-pub use astar::astar;
-pub use bellman_ford::{bellman_ford, find_negative_cycle};
+pub use astar::{astar, astar_with_workspace};
+pub use bellman_ford::{
+    bellman_ford, bellman_ford_paths, bellman_ford_with_workspace, find_negative_cycle,
+};
+#[cfg(feature = "rayon")]
+pub use bfs::parallel_bfs;
+pub use bidirectional::{bidirectional_astar, bidirectional_dijkstra};
+pub use canonical::{
+    automorphism_group, automorphism_group_matching, canonical_graph6, canonical_labeling,
+    weisfeiler_lehman_hash, AutomorphismGroup,
+};
+pub use centrality::{
+    betweenness_centrality, closeness_centrality, eigenvector_centrality, harmonic_centrality,
+    katz_centrality, weighted_betweenness_centrality,
+};
+#[cfg(feature = "rayon")]
+pub use centrality::{
+    parallel_betweenness_centrality, parallel_closeness_centrality, parallel_harmonic_centrality,
+    parallel_weighted_betweenness_centrality,
+};
 pub use coloring::dsatur_coloring;
-pub use dijkstra::dijkstra;
+pub use community::{label_propagation, leiden, louvain, modularity};
+#[cfg(feature = "rayon")]
+pub use connected_components::{
+    parallel_connected_component_labels, parallel_connected_components,
+};
+pub use contraction_hierarchy::{contraction_hierarchy, ContractionHierarchy};
+pub use cycles::{cycle_basis, elementary_cycles, ElementaryCycles};
+#[cfg(feature = "rayon")]
+pub use delta_stepping::parallel_delta_stepping;
+pub use dijkstra::{
+    dijkstra, dijkstra_with_predecessors, dijkstra_with_workspace, ShortestPathWorkspace,
+};
+pub use dinic::dinic;
 pub use feedback_arc_set::greedy_feedback_arc_set;
-pub use floyd_warshall::floyd_warshall;
-pub use ford_fulkerson::ford_fulkerson;
+pub use floyd_warshall::{floyd_warshall, floyd_warshall_paths};
+pub use ford_fulkerson::{flow_decomposition, ford_fulkerson, min_cut};
 pub use isomorphism::{
-    is_isomorphic, is_isomorphic_matching, is_isomorphic_subgraph, is_isomorphic_subgraph_matching,
-    subgraph_isomorphisms_iter,
+    count_subgraph_isomorphisms, count_subgraph_monomorphisms, is_isomorphic,
+    is_isomorphic_matching, is_isomorphic_subgraph, is_isomorphic_subgraph_matching,
+    is_subgraph_monomorphic, is_subgraph_monomorphic_matching, subgraph_isomorphisms_iter,
+    subgraph_monomorphisms_iter,
+};
+pub use johnson::johnson;
+#[cfg(feature = "rayon")]
+pub use johnson::parallel_johnson;
+pub use k_shortest_path::{k_shortest_path, k_shortest_simple_paths, KShortestSimplePaths};
+pub use matching::{
+    greedy_matching, maximum_matching, maximum_weight_bipartite_matching,
+    min_cost_bipartite_matching, Matching,
 };
-pub use k_shortest_path::k_shortest_path;
-pub use matching::{greedy_matching, maximum_matching, Matching};
+pub use min_cost_flow::{min_cost_max_flow, MinCostFlow};
 pub use min_spanning_tree::{min_spanning_tree, min_spanning_tree_prim};
 pub use page_rank::page_rank;
+pub use push_relabel::push_relabel;
+pub use shortest_paths::{AllPairsPaths, ShortestPaths};
 pub use simple_paths::all_simple_paths;
 
 /// \[Generic\] Return the number of connected components of the graph.
@@ -0,0 +1,196 @@
+use std::{collections::VecDeque, ops::Sub};
+
+use crate::{
+    data::DataMap,
+    visit::{EdgeCount, EdgeIndexable, IntoEdgesDirected, NodeCount, NodeIndexable, Visitable},
+};
+
+use super::ford_fulkerson::ResidualNetwork;
+use super::PositiveMeasure;
+
+/// \[Generic\] Push-relabel algorithm.
+///
+/// Computes the [maximum flow][pr] of a weighted directed graph, discharging
+/// the active node with the highest label first and relabeling every node
+/// above an empty label at once (the gap heuristic).
+///
+/// It takes the same arguments and returns the same result as
+/// [`ford_fulkerson`](fn@super::ford_fulkerson): the maximum flow and the
+/// computed edge flows, indexed by `EdgeIndexable::to_index`.
+///
+/// Runs in **O(|V|² √|E|)** time.
+///
+/// [pr]: https://en.wikipedia.org/wiki/Push%E2%80%93relabel_maximum_flow_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::push_relabel;
+/// // Example from CLRS book
+/// let mut graph = Graph::<u8, u8>::new();
+/// let source = graph.add_node(0);
+/// let _ = graph.add_node(1);
+/// let _ = graph.add_node(2);
+/// let _ = graph.add_node(3);
+/// let _ = graph.add_node(4);
+/// let destination = graph.add_node(5);
+/// graph.extend_with_edges(&[
+///    (0, 1, 16),
+///    (0, 2, 13),
+///    (1, 2, 10),
+///    (1, 3, 12),
+///    (2, 1, 4),
+///    (2, 4, 14),
+///    (3, 2, 9),
+///    (3, 5, 20),
+///    (4, 3, 7),
+///    (4, 5, 4),
+/// ]);
+/// let (max_flow, _) = push_relabel(&graph, source, destination);
+/// assert_eq!(23, max_flow);
+/// ```
+pub fn push_relabel<N>(
+    network: N,
+    source: N::NodeId,
+    destination: N::NodeId,
+) -> (N::EdgeWeight, Vec<N::EdgeWeight>)
+where
+    N: NodeCount
+        + EdgeCount
+        + IntoEdgesDirected
+        + EdgeIndexable
+        + NodeIndexable
+        + DataMap
+        + Visitable,
+    N::EdgeWeight: Sub<Output = N::EdgeWeight> + PositiveMeasure,
+{
+    let zero = N::EdgeWeight::zero();
+    let mut residual_network = ResidualNetwork::new(network);
+    let s = NodeIndexable::to_index(&network, source);
+    let t = NodeIndexable::to_index(&network, destination);
+    if s == t {
+        return (zero, residual_network.edge_flows());
+    }
+
+    let ResidualNetwork {
+        ref target,
+        ref mut residual,
+        ref adjacency,
+        ..
+    } = residual_network;
+    let n = adjacency.len();
+
+    // Start with the exact distances to the destination. Nodes that can not
+    // reach it are labeled `n`, like the source, and only return flow to it.
+    let mut label = vec![n; n];
+    let mut queue = VecDeque::new();
+    label[t] = 0;
+    queue.push_back(t);
+    while let Some(v) = queue.pop_front() {
+        for &arc in &adjacency[v] {
+            let u = target[arc];
+            if label[u] == n && u != s && residual[arc ^ 1] > zero {
+                label[u] = label[v] + 1;
+                queue.push_back(u);
+            }
+        }
+    }
+    label[s] = n;
+
+    // Number of nodes with each label, for the gap heuristic.
+    let mut count = vec![0; 2 * n];
+    for &l in &label {
+        count[l] += 1;
+    }
+    let mut excess = vec![zero; n];
+    // Active nodes by label. Entries are checked when they are taken out,
+    // nodes may have been relabeled since they were added.
+    let mut active: Vec<Vec<usize>> = vec![Vec::new(); 2 * n];
+    let mut highest = 0;
+    // The next arc to push along at every node.
+    let mut next = vec![0; n];
+
+    for &arc in &adjacency[s] {
+        let v = target[arc];
+        let delta = residual[arc];
+        if delta > zero {
+            residual[arc] = zero;
+            residual[arc ^ 1] = residual[arc ^ 1] + delta;
+            if v != t && v != s && excess[v] <= zero {
+                active[label[v]].push(v);
+                highest = highest.max(label[v]);
+            }
+            excess[v] = excess[v] + delta;
+        }
+    }
+
+    loop {
+        let u = match active[highest].pop() {
+            Some(u) => u,
+            None if highest == 0 => break,
+            None => {
+                highest -= 1;
+                continue;
+            }
+        };
+        if label[u] != highest || excess[u] <= zero {
+            continue;
+        }
+
+        // Discharge `u`: push its excess downhill, relabel when stuck.
+        while excess[u] > zero {
+            let arcs = &adjacency[u];
+            if next[u] == arcs.len() {
+                let old = label[u];
+                let mut new = 2 * n - 1;
+                for &arc in arcs {
+                    if residual[arc] > zero {
+                        new = new.min(label[target[arc]] + 1);
+                    }
+                }
+                count[old] -= 1;
+                next[u] = 0;
+                if count[old] == 0 && old < n {
+                    // No node is left at label `old`, so the nodes above it
+                    // can not reach the destination anymore.
+                    for v in 0..n {
+                        if label[v] > old && label[v] < n {
+                            count[label[v]] -= 1;
+                            label[v] = n + 1;
+                            count[n + 1] += 1;
+                            next[v] = 0;
+                            if excess[v] > zero && v != s && v != t {
+                                active[n + 1].push(v);
+                            }
+                        }
+                    }
+                    new = new.max(n + 1);
+                }
+                label[u] = new;
+                count[new] += 1;
+                highest = highest.max(new);
+                continue;
+            }
+
+            let arc = arcs[next[u]];
+            let v = target[arc];
+            if residual[arc] > zero && label[u] == label[v] + 1 {
+                let delta = if excess[u] < residual[arc] {
+                    excess[u]
+                } else {
+                    residual[arc]
+                };
+                residual[arc] = residual[arc] - delta;
+                residual[arc ^ 1] = residual[arc ^ 1] + delta;
+                excess[u] = excess[u] - delta;
+                if v != s && v != t && excess[v] <= zero {
+                    active[label[v]].push(v);
+                }
+                excess[v] = excess[v] + delta;
+            } else {
+                next[u] += 1;
+            }
+        }
+    }
+    (excess[t], residual_network.edge_flows())
+}
@@ -0,0 +1,203 @@
+//! Shortest path results that reconstruct the paths they describe.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::visit::{IntoNodeIdentifiers, NodeIndexable};
+use crate::Direction::{self, Incoming, Outgoing};
+
+/// The shortest paths from one node to every node reachable from it,
+/// returned by [`bellman_ford_paths`](fn@crate::algo::bellman_ford_paths).
+///
+/// Paths are returned as the sequence of their nodes, from the source to the
+/// target, together with the sequence of the edges between them.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::bellman_ford_paths;
+/// use petgraph::prelude::*;
+///
+/// let mut graph = DiGraph::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// let ab = graph.add_edge(a, b, 2.);
+/// let bc = graph.add_edge(b, c, -1.);
+/// graph.add_edge(a, c, 3.);
+///
+/// let paths = bellman_ford_paths(&graph, a).unwrap();
+/// assert_eq!(paths.distance(c), Some(1.));
+/// assert_eq!(paths.predecessor(c), Some(b));
+/// assert_eq!(paths.path_to(c), Some((vec![a, b, c], vec![ab, bc])));
+/// assert_eq!(paths.path_to(a), Some((vec![a], vec![])));
+/// assert_eq!(paths.path_to(NodeIndex::new(3)), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<N, E, K>
+where
+    N: Eq + Hash,
+{
+    source: N,
+    distances: HashMap<N, K>,
+    /// The node and edge before every node other than the source on its path.
+    predecessors: HashMap<N, (N, E)>,
+}
+
+impl<N, E, K> ShortestPaths<N, E, K>
+where
+    N: Copy + Eq + Hash,
+    E: Copy,
+    K: Copy,
+{
+    pub(crate) fn new(
+        source: N,
+        distances: HashMap<N, K>,
+        predecessors: HashMap<N, (N, E)>,
+    ) -> Self {
+        ShortestPaths {
+            source,
+            distances,
+            predecessors,
+        }
+    }
+
+    /// Return the node the paths start from.
+    pub fn source(&self) -> N {
+        self.source
+    }
+
+    /// Return the cost of the shortest path to `target`, if it is reachable.
+    pub fn distance(&self, target: N) -> Option<K> {
+        self.distances.get(&target).copied()
+    }
+
+    /// Return the costs of the shortest paths to all reachable nodes.
+    pub fn distances(&self) -> &HashMap<N, K> {
+        &self.distances
+    }
+
+    /// Return the node before `target` on its shortest path, if `target` is
+    /// reachable and is not the source.
+    pub fn predecessor(&self, target: N) -> Option<N> {
+        self.predecessors.get(&target).map(|&(node, _)| node)
+    }
+
+    /// Return the nodes and edges of the shortest path to `target`, if it is
+    /// reachable.
+    pub fn path_to(&self, target: N) -> Option<(Vec<N>, Vec<E>)> {
+        self.distances.get(&target)?;
+        let mut nodes = vec![target];
+        let mut edges = Vec::new();
+        let mut node = target;
+        while let Some(&(previous, edge)) = self.predecessors.get(&node) {
+            nodes.push(previous);
+            edges.push(edge);
+            node = previous;
+        }
+        nodes.reverse();
+        edges.reverse();
+        Some((nodes, edges))
+    }
+}
+
+/// The shortest paths between all pairs of nodes of a graph, returned by
+/// [`floyd_warshall_paths`](fn@crate::algo::floyd_warshall_paths) and
+/// [`johnson`](fn@crate::algo::johnson).
+///
+/// The distances and paths are kept in dense matrices indexed by the
+/// graph's node indices, so this takes **O(|V|²)** space. Paths are returned
+/// as the sequence of their nodes, from the first node to the second,
+/// together with the sequence of the edges between them.
+#[derive(Clone, Debug)]
+pub struct AllPairsPaths<N, E, K>
+where
+    N: Eq + Hash,
+{
+    /// The node of every index, `None` for indices without a node.
+    nodes: Vec<Option<N>>,
+    indices: HashMap<N, usize>,
+    /// The cost of the shortest path from the row node to the column node,
+    /// `None` if there is no path.
+    pub(crate) distances: Vec<Option<K>>,
+    /// For `Outgoing`, the index of the node after the row node on the
+    /// shortest path to the column node, and the edge to it. For `Incoming`,
+    /// the node before the column node on the shortest path from the row node,
+    /// and the edge from it.
+    pub(crate) hops: Vec<Option<(usize, E)>>,
+    direction: Direction,
+}
+
+impl<N, E, K> AllPairsPaths<N, E, K>
+where
+    N: Copy + Eq + Hash,
+    E: Copy,
+    K: Copy,
+{
+    pub(crate) fn new<G>(graph: G, direction: Direction) -> Self
+    where
+        G: IntoNodeIdentifiers<NodeId = N> + NodeIndexable,
+    {
+        let node_bound = graph.node_bound();
+        let mut nodes = vec![None; node_bound];
+        let mut indices = HashMap::new();
+        for node in graph.node_identifiers() {
+            let index = graph.to_index(node);
+            nodes[index] = Some(node);
+            indices.insert(node, index);
+        }
+        AllPairsPaths {
+            nodes,
+            indices,
+            distances: vec![None; node_bound * node_bound],
+            hops: vec![None; node_bound * node_bound],
+            direction,
+        }
+    }
+
+    /// The number of columns of the matrices.
+    pub(crate) fn node_bound(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return the cost of the shortest path from `a` to `b`, if `b` is
+    /// reachable from `a`.
+    pub fn distance(&self, a: N, b: N) -> Option<K> {
+        let (a, b) = (*self.indices.get(&a)?, *self.indices.get(&b)?);
+        self.distances[a * self.node_bound() + b]
+    }
+
+    /// Return the nodes and edges of the shortest path from `a` to `b`, if
+    /// `b` is reachable from `a`.
+    pub fn path(&self, a: N, b: N) -> Option<(Vec<N>, Vec<E>)> {
+        let (a, b) = (*self.indices.get(&a)?, *self.indices.get(&b)?);
+        let node_bound = self.node_bound();
+        self.distances[a * node_bound + b]?;
+        let node = |index: usize| self.nodes[index].unwrap();
+        let mut edges = Vec::new();
+        let nodes = match self.direction {
+            Outgoing => {
+                let mut nodes = vec![node(a)];
+                let mut index = a;
+                while let Some((next, edge)) = self.hops[index * node_bound + b] {
+                    nodes.push(node(next));
+                    edges.push(edge);
+                    index = next;
+                }
+                nodes
+            }
+            Incoming => {
+                let mut nodes = vec![node(b)];
+                let mut index = b;
+                while let Some((previous, edge)) = self.hops[a * node_bound + index] {
+                    nodes.push(node(previous));
+                    edges.push(edge);
+                    index = previous;
+                }
+                nodes.reverse();
+                edges.reverse();
+                nodes
+            }
+        };
+        Some((nodes, edges))
+    }
+}
@@ -1,11 +1,17 @@
 //! Simple graphviz dot file format output.
+//!
+//! Use the [`parse`] module to read dot files back into graphs.
 
 use std::fmt::{self, Display, Write};
 
+use indexmap::IndexMap;
+
 use crate::visit::{
     EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef,
 };
 
+pub mod parse;
+
 /// `Dot` implements output to graphviz .dot format for a graph.
 ///
 /// Formatting and options are rather simple, this is mostly intended
@@ -47,6 +53,45 @@ use crate::visit::{
 ///
 /// // If you need multiple config options, just list them all in the slice.
 /// ```
+///
+/// For readable diagrams, the output can be styled with graph attributes,
+/// default node and edge attributes, per element attributes and clusters:
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::dot::{Config, Dot, Id};
+///
+/// let mut graph = Graph::<_, ()>::new();
+/// let core = graph.add_node("core");
+/// let cli = graph.add_node("cli");
+/// let web = graph.add_node("web");
+/// graph.extend_with_edges(&[(cli, core), (web, core)]);
+///
+/// let dot = Dot::with_config(&graph, &[Config::EdgeNoLabel, Config::NodeNoLabel])
+///     .graph_attribute("rankdir", "LR")
+///     .default_node_attribute("shape", "box")
+///     .node_attributes(|_, (_, name)| {
+///         vec![("label", Id::html(format!("<b>{}</b>", name)))]
+///     })
+///     .clusters(|_, (_, name)| (*name != "core").then(|| "frontends".to_string()));
+///
+/// assert_eq!(
+///     format!("{:?}", dot),
+///     r#"digraph {
+///     graph [ rankdir = "LR" ]
+///     node [ shape = "box" ]
+///     0 [ label = <<b>core</b>> ]
+///     subgraph "cluster_frontends" {
+///         label = "frontends"
+///         1 [ label = <<b>cli</b>> ]
+///         2 [ label = <<b>web</b>> ]
+///     }
+///     1 -> 0 [ ]
+///     2 -> 0 [ ]
+/// }
+/// "#
+/// );
+/// ```
 pub struct Dot<'a, G>
 where
     G: IntoEdgeReferences + IntoNodeReferences,
@@ -55,8 +100,17 @@ where
     get_edge_attributes: &'a dyn Fn(G, G::EdgeRef) -> String,
     get_node_attributes: &'a dyn Fn(G, G::NodeRef) -> String,
     config: Configs,
+    graph_attributes: Vec<(String, Id)>,
+    node_defaults: Vec<(String, Id)>,
+    edge_defaults: Vec<(String, Id)>,
+    node_attributes: Option<AttributesFn<'a, G, G::NodeRef>>,
+    edge_attributes: Option<AttributesFn<'a, G, G::EdgeRef>>,
+    cluster: Option<ClusterFn<'a, G>>,
 }
 
+type AttributesFn<'a, G, R> = Box<dyn Fn(G, R) -> Vec<(&'static str, Id)> + 'a>;
+type ClusterFn<'a, G> = Box<dyn Fn(G, <G as IntoNodeReferences>::NodeRef) -> Option<String> + 'a>;
+
 static TYPE: [&str; 2] = ["graph", "digraph"];
 static EDGE: [&str; 2] = ["--", "->"];
 static INDENT: &str = "    ";
@@ -90,8 +144,167 @@ where
             get_edge_attributes,
             get_node_attributes,
             config,
+            graph_attributes: Vec::new(),
+            node_defaults: Vec::new(),
+            edge_defaults: Vec::new(),
+            node_attributes: None,
+            edge_attributes: None,
+            cluster: None,
+        }
+    }
+
+    /// Set the graph attribute `name`, like `rankdir`, `splines` or `fontname`.
+    pub fn graph_attribute(mut self, name: &str, value: impl Into<Id>) -> Self {
+        self.graph_attributes.push((name.to_string(), value.into()));
+        self
+    }
+
+    /// Set the attribute `name` for all nodes that do not override it.
+    pub fn default_node_attribute(mut self, name: &str, value: impl Into<Id>) -> Self {
+        self.node_defaults.push((name.to_string(), value.into()));
+        self
+    }
+
+    /// Set the attribute `name` for all edges that do not override it.
+    pub fn default_edge_attribute(mut self, name: &str, value: impl Into<Id>) -> Self {
+        self.edge_defaults.push((name.to_string(), value.into()));
+        self
+    }
+
+    /// Compute the attributes of each node with `attributes`.
+    ///
+    /// They are written after the label, so combine a `label` attribute
+    /// with [`Config::NodeNoLabel`].
+    pub fn node_attributes<F>(mut self, attributes: F) -> Self
+    where
+        F: Fn(G, G::NodeRef) -> Vec<(&'static str, Id)> + 'a,
+    {
+        self.node_attributes = Some(Box::new(attributes));
+        self
+    }
+
+    /// Compute the attributes of each edge with `attributes`.
+    ///
+    /// They are written after the label, so combine a `label` attribute
+    /// with [`Config::EdgeNoLabel`].
+    pub fn edge_attributes<F>(mut self, attributes: F) -> Self
+    where
+        F: Fn(G, G::EdgeRef) -> Vec<(&'static str, Id)> + 'a,
+    {
+        self.edge_attributes = Some(Box::new(attributes));
+        self
+    }
+
+    /// Group nodes into clusters, drawn as boxes by most Graphviz layouts.
+    ///
+    /// `cluster` returns the name of the cluster of each node, or `None` to
+    /// leave it outside of any cluster. Each cluster becomes a
+    /// `subgraph cluster_<name>` labelled with its name.
+    pub fn clusters<F>(mut self, cluster: F) -> Self
+    where
+        F: Fn(G, G::NodeRef) -> Option<String> + 'a,
+    {
+        self.cluster = Some(Box::new(cluster));
+        self
+    }
+}
+
+/// A Graphviz attribute value.
+///
+/// Strings are quoted, HTML-like labels are enclosed in angle brackets.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Id {
+    /// A string, written as a quoted string.
+    ///
+    /// Quotes are escaped and line breaks become `\n`. Backslashes are
+    /// kept, so escape sequences like `\l` work as in Graphviz.
+    String(String),
+    /// An HTML-like label, written verbatim between `<` and `>`.
+    ///
+    /// Text in the markup must be escaped, see [`escape_html`].
+    Html(String),
+}
+
+impl Id {
+    /// Create an HTML-like label from `markup`.
+    pub fn html(markup: impl Into<String>) -> Self {
+        Id::Html(markup.into())
+    }
+}
+
+impl From<&str> for Id {
+    fn from(s: &str) -> Self {
+        Id::String(s.to_string())
+    }
+}
+
+impl From<String> for Id {
+    fn from(s: String) -> Self {
+        Id::String(s)
+    }
+}
+
+macro_rules! impl_id_from_display {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Id {
+                fn from(value: $ty) -> Self {
+                    Id::String(value.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_id_from_display!(bool, i32, i64, u32, u64, usize, f32, f64);
+
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Id::String(s) => {
+                f.write_char('"')?;
+                let mut chars = s.chars().peekable();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => f.write_str("\\\"")?,
+                        '\n' => f.write_str("\\n")?,
+                        // A backslash at the end would escape the closing quote.
+                        '\\' if chars.peek().is_none() => f.write_str("\\\\")?,
+                        '\\' => {
+                            f.write_char(c)?;
+                            if let Some(next) = chars.next() {
+                                f.write_char(next)?;
+                            }
+                        }
+                        c => f.write_char(c)?,
+                    }
+                }
+                f.write_char('"')
+            }
+            Id::Html(markup) => write!(f, "<{}>", markup),
+        }
+    }
+}
+
+/// Escape `text` for use in the markup of an HTML-like label.
+///
+/// ```
+/// use petgraph::dot::escape_html;
+///
+/// assert_eq!(escape_html("Vec<&str>"), "Vec&lt;&amp;str&gt;");
+/// ```
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
         }
     }
+    escaped
 }
 
 /// `Dot` configuration.
@@ -158,19 +371,35 @@ where
             writeln!(f, "{} {{", TYPE[g.is_directed() as usize])?;
         }
 
-        // output all labels
+        // output graph attributes and defaults
+        for (kind, attributes) in [
+            ("graph", &self.graph_attributes),
+            ("node", &self.node_defaults),
+            ("edge", &self.edge_defaults),
+        ] {
+            if !attributes.is_empty() {
+                write!(f, "{}{} [ ", INDENT, kind)?;
+                write_attributes(f, attributes.iter().map(|(k, v)| (&k[..], v)))?;
+                writeln!(f, "]")?;
+            }
+        }
+
+        // output all labels, grouping clustered nodes
+        let mut clusters = IndexMap::<String, Vec<G::NodeRef>>::new();
         for node in g.node_references() {
-            write!(f, "{}{} [ ", INDENT, g.to_index(node.id()),)?;
-            if !self.config.NodeNoLabel {
-                write!(f, "label = \"")?;
-                if self.config.NodeIndexLabel {
-                    write!(f, "{}", g.to_index(node.id()))?;
-                } else {
-                    Escaped(FnFmt(node.weight(), &node_fmt)).fmt(f)?;
-                }
-                write!(f, "\" ")?;
+            match self.cluster.as_ref().and_then(|cluster| cluster(g, node)) {
+                Some(name) => clusters.entry(name).or_default().push(node),
+                None => self.node_fmt(f, node, INDENT, &node_fmt)?,
             }
-            writeln!(f, "{}]", (self.get_node_attributes)(g, node))?;
+        }
+        for (name, nodes) in clusters {
+            let id = Id::String(format!("cluster_{}", name));
+            writeln!(f, "{}subgraph {} {{", INDENT, id)?;
+            writeln!(f, "{0}{0}label = {1}", INDENT, Id::String(name))?;
+            for node in nodes {
+                self.node_fmt(f, node, &INDENT.repeat(2), &node_fmt)?;
+            }
+            writeln!(f, "{}}}", INDENT)?;
         }
         // output all edges
         for (i, edge) in g.edge_references().enumerate() {
@@ -191,6 +420,9 @@ where
                 }
                 write!(f, "\" ")?;
             }
+            if let Some(attributes) = &self.edge_attributes {
+                write_attributes(f, attributes(g, edge).iter().map(|(k, v)| (*k, v)))?;
+            }
             writeln!(f, "{}]", (self.get_edge_attributes)(g, edge))?;
         }
 
@@ -199,6 +431,43 @@ where
         }
         Ok(())
     }
+
+    fn node_fmt<NF>(
+        &self,
+        f: &mut fmt::Formatter,
+        node: G::NodeRef,
+        indent: &str,
+        node_fmt: NF,
+    ) -> fmt::Result
+    where
+        NF: Fn(&G::NodeWeight, &mut fmt::Formatter) -> fmt::Result,
+    {
+        let g = self.graph;
+        write!(f, "{}{} [ ", indent, g.to_index(node.id()),)?;
+        if !self.config.NodeNoLabel {
+            write!(f, "label = \"")?;
+            if self.config.NodeIndexLabel {
+                write!(f, "{}", g.to_index(node.id()))?;
+            } else {
+                Escaped(FnFmt(node.weight(), &node_fmt)).fmt(f)?;
+            }
+            write!(f, "\" ")?;
+        }
+        if let Some(attributes) = &self.node_attributes {
+            write_attributes(f, attributes(g, node).iter().map(|(k, v)| (*k, v)))?;
+        }
+        writeln!(f, "{}]", (self.get_node_attributes)(g, node))
+    }
+}
+
+fn write_attributes<'a, I>(f: &mut fmt::Formatter, attributes: I) -> fmt::Result
+where
+    I: IntoIterator<Item = (&'a str, &'a Id)>,
+{
+    for (name, value) in attributes {
+        write!(f, "{} = {} ", name, value)?;
+    }
+    Ok(())
 }
 
 impl<G> fmt::Display for Dot<'_, G>
@@ -300,7 +569,7 @@ where
 
 #[cfg(test)]
 mod test {
-    use super::{Config, Dot, Escaper};
+    use super::{escape_html, Config, Dot, Escaper, Id};
     use crate::prelude::Graph;
     use crate::visit::NodeRef;
     use std::fmt::Write;
@@ -368,4 +637,55 @@ mod test {
         );
         assert_eq!(dot, "digraph {\n    0 [ label = \"a\"]\n    1 [ label = \"b\"]\n    0 -> 1 [ label = \"EDGE_LABEL\"]\n}\n");
     }
+
+    #[test]
+    fn test_styling() {
+        let graph = simple_graph();
+        let dot = format!(
+            "{:?}",
+            Dot::with_config(
+                &graph,
+                &[
+                    Config::NodeIndexLabel,
+                    Config::EdgeNoLabel,
+                    Config::GraphContentOnly
+                ]
+            )
+            .graph_attribute("splines", "ortho")
+            .graph_attribute("fontname", "Fira \"Sans\"")
+            .default_edge_attribute("penwidth", 2)
+            .edge_attributes(|_, er| vec![("label", Id::html(escape_html(er.weight())))])
+            .node_attributes(|_, nr| match *nr.weight() {
+                "A" => vec![("tooltip", "first\nnode\\l".into())],
+                _ => vec![],
+            })
+            .clusters(|_, nr| Some(format!("\"{}\"", nr.weight())))
+        );
+        assert_eq!(
+            dot,
+            r#"    graph [ splines = "ortho" fontname = "Fira \"Sans\"" ]
+    edge [ penwidth = "2" ]
+    subgraph "cluster_\"A\"" {
+        label = "\"A\""
+        0 [ label = "0" tooltip = "first\nnode\l" ]
+    }
+    subgraph "cluster_\"B\"" {
+        label = "\"B\""
+        1 [ label = "1" ]
+    }
+    0 -> 1 [ label = <edge_label> ]
+"#
+        );
+    }
+
+    #[test]
+    fn test_id_escape() {
+        assert_eq!(Id::from("a\\").to_string(), r#""a\\""#);
+        assert_eq!(Id::from(r#"\"q"#).to_string(), r#""\"q""#);
+        assert_eq!(Id::html("<b>&amp;</b>").to_string(), "<<b>&amp;</b>>");
+        assert_eq!(
+            escape_html("a<b> & \"c\""),
+            "a&lt;b&gt; &amp; &quot;c&quot;"
+        );
+    }
 }
@@ -0,0 +1,1024 @@
+//! Parse graphviz dot files back into graphs.
+//!
+//! The parser understands the full [DOT language] grammar: `graph` and
+//! `digraph`, the `strict` modifier, node, edge and attribute statements,
+//! default attribute blocks, (nested and anonymous) subgraphs, edge chains
+//! like `a -> b -> c`, ports and comments.
+//!
+//! Nodes and edges are built with [`DotNode`] and [`DotEdge`] weights that
+//! hold their attributes. Any graph implementing [`Create`] and [`DataMapMut`]
+//! can be used as the target, e.g. [`Graph`](crate::Graph) or
+//! [`StableGraph`](crate::stable_graph::StableGraph).
+//!
+//! [DOT language]: https://graphviz.org/doc/info/lang.html
+//!
+//! # Example
+//!
+//! ```
+//! use petgraph::dot::parse::{from_dot, DotEdge, DotNode};
+//! use petgraph::graph::DiGraph;
+//!
+//! let dot = from_dot::<DiGraph<DotNode, DotEdge>>(
+//!     r#"digraph deps {
+//!         rankdir = LR;
+//!         node [shape = box];
+//!         a -> b -> c [weight = 2];
+//!         a [label = "first"];
+//!     }"#,
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(dot.name.as_deref(), Some("deps"));
+//! assert_eq!(dot.attributes["rankdir"], "LR");
+//! assert_eq!(dot.graph.node_count(), 3);
+//! assert_eq!(dot.graph.edge_count(), 2);
+//!
+//! let a = dot.node_id("a").unwrap();
+//! assert_eq!(dot.graph[a].attributes["label"], "first");
+//! assert_eq!(dot.graph[a].attributes["shape"], "box");
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use indexmap::IndexMap;
+
+use crate::data::{Create, DataMapMut};
+use crate::visit::{GraphBase, GraphProp};
+
+/// Attributes of a graph, node or edge, in the order of their first appearance.
+///
+/// Quoted strings are stored unquoted and unescaped. HTML-like strings keep
+/// their enclosing angle brackets so they can be told apart from plain ones.
+pub type Attributes = IndexMap<String, String>;
+
+/// The node weight produced by the parser.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DotNode {
+    /// The node identifier as written in the dot source.
+    pub id: String,
+    /// The node attributes, including inherited defaults.
+    pub attributes: Attributes,
+}
+
+/// The edge weight produced by the parser.
+///
+/// Ports on the edge endpoints are recorded as the `tailport` and `headport`
+/// attributes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DotEdge {
+    /// The edge attributes, including inherited defaults.
+    pub attributes: Attributes,
+}
+
+/// A subgraph statement of the dot source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Subgraph<N> {
+    /// The subgraph identifier, `None` for anonymous subgraphs.
+    pub name: Option<String>,
+    /// The graph attributes set inside the subgraph.
+    pub attributes: Attributes,
+    /// All nodes that appear in the subgraph or any of its own subgraphs.
+    pub nodes: Vec<N>,
+    /// The index of the enclosing subgraph in [`DotGraph::subgraphs`], if any.
+    pub parent: Option<usize>,
+}
+
+/// The result of parsing a dot file: the graph and everything that is not
+/// attached to a single node or edge.
+#[derive(Clone, Debug)]
+pub struct DotGraph<G: GraphBase> {
+    /// The parsed graph.
+    pub graph: G,
+    /// The graph identifier, if any.
+    pub name: Option<String>,
+    /// Whether the graph was declared `strict`.
+    ///
+    /// Edges of strict graphs are added with [`Build::update_edge`](crate::data::Build::update_edge), so
+    /// repeated edges are merged into one.
+    pub strict: bool,
+    /// Graph level attributes.
+    pub attributes: Attributes,
+    /// All subgraphs, in order of appearance.
+    pub subgraphs: Vec<Subgraph<G::NodeId>>,
+    node_ids: HashMap<String, G::NodeId>,
+}
+
+impl<G: GraphBase> DotGraph<G> {
+    /// Return the node with dot identifier `id`, if it exists.
+    pub fn node_id(&self, id: &str) -> Option<G::NodeId> {
+        self.node_ids.get(id).copied()
+    }
+}
+
+/// An error encountered while parsing a dot file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// The column of the error in characters, starting at 1.
+    pub column: usize,
+    /// What went wrong.
+    pub kind: ParseErrorKind,
+}
+
+/// The kind of a [`ParseError`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A character that cannot start any token.
+    UnexpectedCharacter(char),
+    /// A quoted string is missing its closing quote.
+    UnterminatedString,
+    /// An HTML-like string is missing its closing angle bracket.
+    UnterminatedHtml,
+    /// A `/* ... */` comment is missing its end.
+    UnterminatedComment,
+    /// The input ended in the middle of the graph.
+    UnexpectedEof,
+    /// A token that is not allowed at this position.
+    UnexpectedToken {
+        /// A description of what the parser expected.
+        expected: &'static str,
+        /// The token that was found instead.
+        found: String,
+    },
+    /// An edge operator that does not match the graph kind, like `->` in an
+    /// undirected `graph`.
+    EdgeOperatorMismatch,
+    /// The dot source and the target graph type disagree on directedness.
+    GraphKindMismatch {
+        /// Whether the dot source declares a `digraph`.
+        directed: bool,
+    },
+    /// There is input left after the closing brace of the graph.
+    TrailingInput,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        match &self.kind {
+            ParseErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character {c:?}"),
+            ParseErrorKind::UnterminatedString => write!(f, "unterminated quoted string"),
+            ParseErrorKind::UnterminatedHtml => write!(f, "unterminated HTML string"),
+            ParseErrorKind::UnterminatedComment => write!(f, "unterminated comment"),
+            ParseErrorKind::UnexpectedEof => write!(f, "unexpected end of input"),
+            ParseErrorKind::UnexpectedToken { expected, found } => {
+                write!(f, "expected {expected}, found {found}")
+            }
+            ParseErrorKind::EdgeOperatorMismatch => {
+                write!(f, "edge operator does not match the graph kind")
+            }
+            ParseErrorKind::GraphKindMismatch { directed: true } => {
+                write!(f, "cannot read a digraph into an undirected graph")
+            }
+            ParseErrorKind::GraphKindMismatch { directed: false } => {
+                write!(f, "cannot read an undirected graph into a directed graph")
+            }
+            ParseErrorKind::TrailingInput => write!(f, "unexpected input after the graph"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse the dot source `input` into a graph of type `G`.
+///
+/// `G` must agree with the source on directedness, otherwise a
+/// [`ParseErrorKind::GraphKindMismatch`] error is returned.
+///
+/// Nodes are added in order of their first appearance. A node or edge gets
+/// the `node [...]` or `edge [...]` defaults that are in scope when it is
+/// created, later node statements for the same identifier add to its
+/// attributes. An edge statement with a subgraph endpoint connects every node
+/// of that subgraph.
+pub fn from_dot<G>(input: &str) -> Result<DotGraph<G>, ParseError>
+where
+    G: Create<NodeWeight = DotNode, EdgeWeight = DotEdge> + DataMapMut + GraphProp,
+{
+    let tokens = Lexer::new(input).tokenize()?;
+    Parser::new(tokens).parse()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum IdKind {
+    Plain,
+    Quoted,
+    Html,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum TokenKind {
+    Id(String, IdKind),
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Equals,
+    Semicolon,
+    Comma,
+    Colon,
+    Plus,
+    Arrow,
+    Line,
+    Eof,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Id(id, IdKind::Plain) => write!(f, "`{id}`"),
+            TokenKind::Id(id, IdKind::Quoted) => write!(f, "{id:?}"),
+            TokenKind::Id(id, IdKind::Html) => write!(f, "`{id}`"),
+            TokenKind::LBrace => f.write_str("`{`"),
+            TokenKind::RBrace => f.write_str("`}`"),
+            TokenKind::LBracket => f.write_str("`[`"),
+            TokenKind::RBracket => f.write_str("`]`"),
+            TokenKind::Equals => f.write_str("`=`"),
+            TokenKind::Semicolon => f.write_str("`;`"),
+            TokenKind::Comma => f.write_str("`,`"),
+            TokenKind::Colon => f.write_str("`:`"),
+            TokenKind::Plus => f.write_str("`+`"),
+            TokenKind::Arrow => f.write_str("`->`"),
+            TokenKind::Line => f.write_str("`--`"),
+            TokenKind::Eof => f.write_str("end of input"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+    column: usize,
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Lexer {
+    fn new(input: &str) -> Self {
+        Lexer {
+            chars: input.chars().collect(),
+            pos: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek(0)?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn error(&self, line: usize, column: usize, kind: ParseErrorKind) -> ParseError {
+        ParseError { line, column, kind }
+    }
+
+    fn tokenize(mut self) -> Result<Vec<Token>, ParseError> {
+        let mut tokens = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            let (line, column) = (self.line, self.column);
+            let c = match self.peek(0) {
+                None => {
+                    tokens.push(Token {
+                        kind: TokenKind::Eof,
+                        line,
+                        column,
+                    });
+                    return Ok(tokens);
+                }
+                Some(c) => c,
+            };
+            let kind = match c {
+                '{' | '}' | '[' | ']' | '=' | ';' | ',' | ':' | '+' => {
+                    self.bump();
+                    match c {
+                        '{' => TokenKind::LBrace,
+                        '}' => TokenKind::RBrace,
+                        '[' => TokenKind::LBracket,
+                        ']' => TokenKind::RBracket,
+                        '=' => TokenKind::Equals,
+                        ';' => TokenKind::Semicolon,
+                        ',' => TokenKind::Comma,
+                        ':' => TokenKind::Colon,
+                        _ => TokenKind::Plus,
+                    }
+                }
+                '-' if self.peek(1) == Some('>') => {
+                    self.bump();
+                    self.bump();
+                    TokenKind::Arrow
+                }
+                '-' if self.peek(1) == Some('-') => {
+                    self.bump();
+                    self.bump();
+                    TokenKind::Line
+                }
+                '"' => self.quoted(line, column)?,
+                '<' => self.html(line, column)?,
+                c if c == '-' || c == '.' || c.is_ascii_digit() => self.numeral(line, column)?,
+                c if is_id_start(c) => {
+                    let mut id = String::new();
+                    while let Some(c) = self.peek(0).filter(|&c| is_id_continue(c)) {
+                        id.push(c);
+                        self.bump();
+                    }
+                    TokenKind::Id(id, IdKind::Plain)
+                }
+                c => return Err(self.error(line, column, ParseErrorKind::UnexpectedCharacter(c))),
+            };
+            tokens.push(Token { kind, line, column });
+        }
+    }
+
+    /// Skip whitespace, comments and preprocessor output lines.
+    fn skip_trivia(&mut self) -> Result<(), ParseError> {
+        loop {
+            match (self.peek(0), self.peek(1)) {
+                (Some(c), _) if c.is_whitespace() => {
+                    self.bump();
+                }
+                (Some('#'), _) if self.at_line_start() => self.skip_line(),
+                (Some('/'), Some('/')) => self.skip_line(),
+                (Some('/'), Some('*')) => {
+                    let (line, column) = (self.line, self.column);
+                    self.bump();
+                    self.bump();
+                    loop {
+                        match (self.peek(0), self.peek(1)) {
+                            (Some('*'), Some('/')) => {
+                                self.bump();
+                                self.bump();
+                                break;
+                            }
+                            (Some(_), _) => {
+                                self.bump();
+                            }
+                            (None, _) => {
+                                return Err(self.error(
+                                    line,
+                                    column,
+                                    ParseErrorKind::UnterminatedComment,
+                                ))
+                            }
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Whether only whitespace precedes the current position on its line.
+    fn at_line_start(&self) -> bool {
+        self.chars[..self.pos]
+            .iter()
+            .rev()
+            .take_while(|&&c| c != '\n')
+            .all(|c| c.is_whitespace())
+    }
+
+    fn skip_line(&mut self) {
+        while let Some(c) = self.bump() {
+            if c == '\n' {
+                break;
+            }
+        }
+    }
+
+    fn quoted(&mut self, line: usize, column: usize) -> Result<TokenKind, ParseError> {
+        self.bump();
+        let mut s = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(TokenKind::Id(s, IdKind::Quoted)),
+                Some('\\') => match self.peek(0) {
+                    Some(c @ ('"' | '\\')) => {
+                        self.bump();
+                        s.push(c);
+                    }
+                    // An escaped line break continues the string on the next line.
+                    Some('\n') => {
+                        self.bump();
+                    }
+                    Some('\r') if self.peek(1) == Some('\n') => {
+                        self.bump();
+                        self.bump();
+                    }
+                    // Other escapes like `\l` are meaningful to graphviz, keep them.
+                    _ => s.push('\\'),
+                },
+                Some(c) => s.push(c),
+                None => return Err(self.error(line, column, ParseErrorKind::UnterminatedString)),
+            }
+        }
+    }
+
+    fn html(&mut self, line: usize, column: usize) -> Result<TokenKind, ParseError> {
+        let mut s = String::new();
+        let mut depth = 0usize;
+        loop {
+            match self.bump() {
+                Some(c) => {
+                    s.push(c);
+                    match c {
+                        '<' => depth += 1,
+                        '>' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                return Ok(TokenKind::Id(s, IdKind::Html));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                None => return Err(self.error(line, column, ParseErrorKind::UnterminatedHtml)),
+            }
+        }
+    }
+
+    fn numeral(&mut self, line: usize, column: usize) -> Result<TokenKind, ParseError> {
+        let mut s = String::new();
+        if self.peek(0) == Some('-') {
+            s.push('-');
+            self.bump();
+        }
+        let mut seen_dot = false;
+        let mut seen_digit = false;
+        while let Some(c) = self.peek(0) {
+            if c.is_ascii_digit() {
+                seen_digit = true;
+            } else if c == '.' && !seen_dot {
+                seen_dot = true;
+            } else {
+                break;
+            }
+            s.push(c);
+            self.bump();
+        }
+        if !seen_digit {
+            let c = s.chars().next().unwrap_or('-');
+            return Err(self.error(line, column, ParseErrorKind::UnexpectedCharacter(c)));
+        }
+        Ok(TokenKind::Id(s, IdKind::Plain))
+    }
+}
+
+fn is_id_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || !c.is_ascii()
+}
+
+fn is_id_continue(c: char) -> bool {
+    is_id_start(c) || c.is_ascii_digit()
+}
+
+/// The node defaults, edge defaults and subgraph of one `{ ... }` block.
+#[derive(Clone, Default)]
+struct Scope {
+    node_defaults: Attributes,
+    edge_defaults: Attributes,
+    subgraph: Option<usize>,
+}
+
+/// The nodes on one side of an edge operator, with their optional ports.
+type Endpoint<N> = Vec<(N, Option<String>)>;
+
+struct Parser<G: GraphBase> {
+    tokens: Vec<Token>,
+    pos: usize,
+    directed: bool,
+    scopes: Vec<Scope>,
+    out: DotGraph<G>,
+}
+
+impl<G> Parser<G>
+where
+    G: Create<NodeWeight = DotNode, EdgeWeight = DotEdge> + DataMapMut + GraphProp,
+{
+    fn new(tokens: Vec<Token>) -> Self {
+        Parser {
+            tokens,
+            pos: 0,
+            directed: false,
+            scopes: vec![Scope::default()],
+            out: DotGraph {
+                graph: G::default(),
+                name: None,
+                strict: false,
+                attributes: Attributes::new(),
+                subgraphs: Vec::new(),
+                node_ids: HashMap::new(),
+            },
+        }
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos]
+    }
+
+    fn peek_kind(&self, offset: usize) -> &TokenKind {
+        let index = (self.pos + offset).min(self.tokens.len() - 1);
+        &self.tokens[index].kind
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.pos].clone();
+        if token.kind != TokenKind::Eof {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn error_at(token: &Token, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            line: token.line,
+            column: token.column,
+            kind,
+        }
+    }
+
+    fn unexpected(&self, expected: &'static str) -> ParseError {
+        let token = self.peek();
+        let kind = match token.kind {
+            TokenKind::Eof => ParseErrorKind::UnexpectedEof,
+            ref found => ParseErrorKind::UnexpectedToken {
+                expected,
+                found: found.to_string(),
+            },
+        };
+        Self::error_at(token, kind)
+    }
+
+    fn expect(&mut self, kind: TokenKind, expected: &'static str) -> Result<(), ParseError> {
+        if *self.peek_kind(0) == kind {
+            self.next();
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn eat(&mut self, kind: TokenKind) -> bool {
+        if *self.peek_kind(0) == kind {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn is_keyword(&self, offset: usize, keyword: &str) -> bool {
+        matches!(self.peek_kind(offset), TokenKind::Id(id, IdKind::Plain) if id.eq_ignore_ascii_case(keyword))
+    }
+
+    fn parse(mut self) -> Result<DotGraph<G>, ParseError> {
+        if self.is_keyword(0, "strict") {
+            self.next();
+            self.out.strict = true;
+        }
+        let start = self.peek().clone();
+        if self.is_keyword(0, "digraph") {
+            self.directed = true;
+        } else if !self.is_keyword(0, "graph") {
+            return Err(self.unexpected("`graph` or `digraph`"));
+        }
+        self.next();
+        if self.directed != self.out.graph.is_directed() {
+            return Err(Self::error_at(
+                &start,
+                ParseErrorKind::GraphKindMismatch {
+                    directed: self.directed,
+                },
+            ));
+        }
+        if let TokenKind::Id(..) = self.peek_kind(0) {
+            self.out.name = Some(self.id("graph name")?);
+        }
+        self.expect(TokenKind::LBrace, "`{`")?;
+        self.stmt_list()?;
+        self.expect(TokenKind::RBrace, "`}`")?;
+        if *self.peek_kind(0) != TokenKind::Eof {
+            return Err(Self::error_at(self.peek(), ParseErrorKind::TrailingInput));
+        }
+        Ok(self.out)
+    }
+
+    /// Parse an identifier, joining `"a" + "b"` concatenations.
+    fn id(&mut self, expected: &'static str) -> Result<String, ParseError> {
+        let mut id = match self.peek_kind(0) {
+            TokenKind::Id(..) => match self.next().kind {
+                TokenKind::Id(id, _) => id,
+                _ => unreachable!(),
+            },
+            _ => return Err(self.unexpected(expected)),
+        };
+        while *self.peek_kind(0) == TokenKind::Plus {
+            self.next();
+            match self.peek_kind(0) {
+                TokenKind::Id(_, IdKind::Quoted) => match self.next().kind {
+                    TokenKind::Id(tail, _) => id.push_str(&tail),
+                    _ => unreachable!(),
+                },
+                _ => return Err(self.unexpected("quoted string")),
+            }
+        }
+        Ok(id)
+    }
+
+    fn stmt_list(&mut self) -> Result<(), ParseError> {
+        while !matches!(self.peek_kind(0), TokenKind::RBrace | TokenKind::Eof) {
+            self.stmt()?;
+            self.eat(TokenKind::Semicolon);
+        }
+        Ok(())
+    }
+
+    fn stmt(&mut self) -> Result<(), ParseError> {
+        for keyword in ["graph", "node", "edge"] {
+            if self.is_keyword(0, keyword) {
+                self.next();
+                let attributes = self.attr_list()?;
+                let scope = self.scopes.last_mut().unwrap();
+                let target = match keyword {
+                    "node" => &mut scope.node_defaults,
+                    "edge" => &mut scope.edge_defaults,
+                    _ => match scope.subgraph {
+                        Some(index) => &mut self.out.subgraphs[index].attributes,
+                        None => &mut self.out.attributes,
+                    },
+                };
+                target.extend(attributes);
+                return Ok(());
+            }
+        }
+        if let (TokenKind::Id(..), TokenKind::Equals) = (self.peek_kind(0), self.peek_kind(1)) {
+            let key = self.id("attribute name")?;
+            self.next();
+            let value = self.id("attribute value")?;
+            let attributes = match self.scopes.last().unwrap().subgraph {
+                Some(index) => &mut self.out.subgraphs[index].attributes,
+                None => &mut self.out.attributes,
+            };
+            attributes.insert(key, value);
+            return Ok(());
+        }
+        let is_subgraph = self.is_keyword(0, "subgraph") || *self.peek_kind(0) == TokenKind::LBrace;
+        let first = if is_subgraph {
+            self.subgraph()?
+        } else {
+            let (id, port) = self.node_id()?;
+            if !self.is_edge_op() {
+                let attributes = if *self.peek_kind(0) == TokenKind::LBracket {
+                    self.attr_list()?
+                } else {
+                    Attributes::new()
+                };
+                let node = self.node(id);
+                if let Some(weight) = self.out.graph.node_weight_mut(node) {
+                    weight.attributes.extend(attributes);
+                }
+                return Ok(());
+            }
+            let node = self.node(id);
+            vec![(node, port)]
+        };
+        if self.is_edge_op() {
+            self.edge_stmt(first)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn is_edge_op(&self) -> bool {
+        matches!(self.peek_kind(0), TokenKind::Arrow | TokenKind::Line)
+    }
+
+    fn edge_stmt(&mut self, first: Endpoint<G::NodeId>) -> Result<(), ParseError> {
+        let mut endpoints = vec![first];
+        while self.is_edge_op() {
+            let op = self.next();
+            if (op.kind == TokenKind::Arrow) != self.directed {
+                return Err(Self::error_at(&op, ParseErrorKind::EdgeOperatorMismatch));
+            }
+            if self.is_keyword(0, "subgraph") || *self.peek_kind(0) == TokenKind::LBrace {
+                endpoints.push(self.subgraph()?);
+            } else {
+                let (id, port) = self.node_id()?;
+                let node = self.node(id);
+                endpoints.push(vec![(node, port)]);
+            }
+        }
+        let mut attributes = self.scopes.last().unwrap().edge_defaults.clone();
+        if *self.peek_kind(0) == TokenKind::LBracket {
+            attributes.extend(self.attr_list()?);
+        }
+        for pair in endpoints.windows(2) {
+            for (source, tail_port) in &pair[0] {
+                for (target, head_port) in &pair[1] {
+                    let mut weight = DotEdge {
+                        attributes: attributes.clone(),
+                    };
+                    if let Some(port) = tail_port {
+                        weight.attributes.insert("tailport".into(), port.clone());
+                    }
+                    if let Some(port) = head_port {
+                        weight.attributes.insert("headport".into(), port.clone());
+                    }
+                    if self.out.strict {
+                        self.out.graph.update_edge(*source, *target, weight);
+                    } else {
+                        self.out.graph.add_edge(*source, *target, weight);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn subgraph(&mut self) -> Result<Endpoint<G::NodeId>, ParseError> {
+        let mut name = None;
+        if self.is_keyword(0, "subgraph") {
+            self.next();
+            if let TokenKind::Id(..) = self.peek_kind(0) {
+                name = Some(self.id("subgraph name")?);
+            }
+        }
+        // A named subgraph that was seen before is reopened, not duplicated.
+        let existing = name.as_ref().and_then(|name| {
+            self.out
+                .subgraphs
+                .iter()
+                .position(|sub| sub.name.as_ref() == Some(name))
+        });
+        let index = match existing {
+            Some(index) => index,
+            None => {
+                self.out.subgraphs.push(Subgraph {
+                    name,
+                    attributes: Attributes::new(),
+                    nodes: Vec::new(),
+                    parent: self.scopes.last().unwrap().subgraph,
+                });
+                self.out.subgraphs.len() - 1
+            }
+        };
+        // `subgraph name` without a body refers to the subgraph's nodes.
+        if existing.is_none() || *self.peek_kind(0) == TokenKind::LBrace {
+            let mut scope = self.scopes.last().unwrap().clone();
+            scope.subgraph = Some(index);
+            self.scopes.push(scope);
+            self.expect(TokenKind::LBrace, "`{`")?;
+            self.stmt_list()?;
+            self.expect(TokenKind::RBrace, "`}`")?;
+            self.scopes.pop();
+        }
+        Ok(self.out.subgraphs[index]
+            .nodes
+            .iter()
+            .map(|&node| (node, None))
+            .collect())
+    }
+
+    fn node_id(&mut self) -> Result<(String, Option<String>), ParseError> {
+        let id = self.id("node identifier")?;
+        let mut port = None;
+        if self.eat(TokenKind::Colon) {
+            let mut p = self.id("port")?;
+            if self.eat(TokenKind::Colon) {
+                p.push(':');
+                p.push_str(&self.id("compass point")?);
+            }
+            port = Some(p);
+        }
+        Ok((id, port))
+    }
+
+    /// Return the node named `id`, creating it with the current defaults if
+    /// needed, and record it in all enclosing subgraphs.
+    fn node(&mut self, id: String) -> G::NodeId {
+        let scope = self.scopes.last().unwrap();
+        let node = match self.out.node_ids.get(&id) {
+            Some(&node) => node,
+            None => {
+                let weight = DotNode {
+                    id: id.clone(),
+                    attributes: scope.node_defaults.clone(),
+                };
+                let node = self.out.graph.add_node(weight);
+                self.out.node_ids.insert(id, node);
+                node
+            }
+        };
+        let mut subgraph = scope.subgraph;
+        while let Some(index) = subgraph {
+            let sub = &mut self.out.subgraphs[index];
+            if !sub.nodes.contains(&node) {
+                sub.nodes.push(node);
+            }
+            subgraph = sub.parent;
+        }
+        node
+    }
+
+    fn attr_list(&mut self) -> Result<Attributes, ParseError> {
+        let mut attributes = Attributes::new();
+        self.expect(TokenKind::LBracket, "`[`")?;
+        loop {
+            if self.eat(TokenKind::RBracket) {
+                // `[a = b][c = d]` is the same as `[a = b, c = d]`
+                if !self.eat(TokenKind::LBracket) {
+                    return Ok(attributes);
+                }
+                continue;
+            }
+            let key = self.id("attribute name or `]`")?;
+            self.expect(TokenKind::Equals, "`=`")?;
+            let value = self.id("attribute value")?;
+            attributes.insert(key, value);
+            if !self.eat(TokenKind::Comma) {
+                self.eat(TokenKind::Semicolon);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{from_dot, DotEdge, DotGraph, DotNode, ParseError, ParseErrorKind};
+    use crate::dot::Dot;
+    use crate::graph::{DiGraph, UnGraph};
+    #[cfg(feature = "stable_graph")]
+    use crate::stable_graph::StableUnGraph;
+
+    fn digraph(input: &str) -> Result<DotGraph<DiGraph<DotNode, DotEdge>>, ParseError> {
+        from_dot(input)
+    }
+
+    #[test]
+    fn test_graph_example() {
+        let dot = digraph(include_str!("../../graph-example.dot")).unwrap();
+        assert_eq!(dot.attributes["rankdir"], "LR");
+        assert_eq!(dot.attributes["splines"], "true");
+        assert_eq!(dot.graph.node_count(), 5);
+        assert_eq!(dot.graph.edge_count(), 5);
+        let petgraph = dot.node_id("0").unwrap();
+        assert_eq!(dot.graph[petgraph].attributes["label"], "petgraph");
+        let libc = dot.node_id("4").unwrap();
+        assert_eq!(
+            dot.graph
+                .neighbors_directed(libc, crate::Incoming)
+                .map(|n| &dot.graph[n].id[..])
+                .collect::<Vec<_>>(),
+            ["2", "3"]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "stable_graph")]
+    fn test_undirected_stable_graph() {
+        let dot = from_dot::<StableUnGraph<DotNode, DotEdge>>(
+            "strict graph { a -- b; b -- a [color = red]; a -- a }",
+        )
+        .unwrap();
+        assert!(dot.strict);
+        assert_eq!(dot.graph.node_count(), 2);
+        assert_eq!(dot.graph.edge_count(), 2);
+        let (a, b) = (dot.node_id("a").unwrap(), dot.node_id("b").unwrap());
+        let ab = dot.graph.find_edge(a, b).unwrap();
+        assert_eq!(dot.graph[ab].attributes["color"], "red");
+    }
+
+    #[test]
+    fn test_chains_and_subgraphs() {
+        let dot = digraph(
+            r#"
+            /* a block comment */
+            digraph G {
+                edge [color = blue]
+                a -> { b c } -> d // trailing comment
+                subgraph cluster_x {
+                    label = "X";
+                    node [shape = box]
+                    e; f
+                    subgraph inner { g }
+                }
+                # preprocessor line
+                h -> subgraph cluster_x
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(dot.graph.node_count(), 8);
+        // a -> b, a -> c, b -> d, c -> d, h -> e, h -> f, h -> g
+        assert_eq!(dot.graph.edge_count(), 7);
+        assert!(dot
+            .graph
+            .edge_weights()
+            .all(|e| e.attributes["color"] == "blue"));
+
+        assert_eq!(dot.subgraphs.len(), 3);
+        let cluster = &dot.subgraphs[1];
+        assert_eq!(cluster.name.as_deref(), Some("cluster_x"));
+        assert_eq!(cluster.attributes["label"], "X");
+        assert_eq!(cluster.nodes.len(), 3);
+        assert_eq!(dot.subgraphs[2].parent, Some(1));
+        assert!(dot.subgraphs[0].name.is_none());
+
+        let g = dot.node_id("g").unwrap();
+        assert_eq!(dot.graph[g].attributes["shape"], "box");
+        let h = dot.node_id("h").unwrap();
+        assert!(dot.graph[h].attributes.is_empty());
+    }
+
+    #[test]
+    fn test_ids() {
+        let dot = digraph(
+            "digraph { \"multi\\\nline\" + \" id\" [label = <<b>bold</b>>]; \
+             -1.5 -> .5:p:n [weight = 3, style = \"a\\\"b\"] }",
+        )
+        .unwrap();
+        let a = dot.node_id("multiline id").unwrap();
+        assert_eq!(dot.graph[a].attributes["label"], "<<b>bold</b>>");
+        assert!(dot.node_id("-1.5").is_some());
+        let edge = dot.graph.edge_weights().next().unwrap();
+        assert_eq!(edge.attributes["headport"], "p:n");
+        assert_eq!(edge.attributes["style"], "a\"b");
+        assert_eq!(edge.attributes["weight"], "3");
+    }
+
+    #[test]
+    fn test_dot_round_trip() {
+        let mut graph = DiGraph::<&str, &str>::new();
+        let a = graph.add_node(r"C:\dir\");
+        let b = graph.add_node(r#"say "hi""#);
+        let c = graph.add_node(r#"\"\\"#);
+        graph.add_edge(a, b, r"a\b");
+        graph.add_edge(b, c, r#""\"#);
+
+        let dot = digraph(&Dot::new(&graph).to_string()).unwrap();
+        assert_eq!(dot.graph.node_count(), 3);
+        for node in graph.node_indices() {
+            let id = dot.node_id(&node.index().to_string()).unwrap();
+            assert_eq!(dot.graph[id].attributes["label"], graph[node]);
+        }
+        let labels: Vec<_> = dot
+            .graph
+            .edge_weights()
+            .map(|e| &e.attributes["label"][..])
+            .collect();
+        assert_eq!(labels, [r"a\b", r#""\"#]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = digraph("digraph {\n  a -> b\n  c -- d\n}").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 5));
+        assert_eq!(error.kind, ParseErrorKind::EdgeOperatorMismatch);
+        assert_eq!(
+            error.to_string(),
+            "3:5: edge operator does not match the graph kind"
+        );
+
+        let error = digraph("digraph {\n  a [label = ]\n}").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 14));
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::UnexpectedToken {
+                expected: "attribute value",
+                found: "`]`".into(),
+            }
+        );
+
+        let error = digraph("digraph { a -> \"b }").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 16));
+        assert_eq!(error.kind, ParseErrorKind::UnterminatedString);
+
+        let error = digraph("digraph { a -> ").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::UnexpectedEof);
+
+        let error = digraph("digraph { } }").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::TrailingInput);
+
+        let error = from_dot::<UnGraph<DotNode, DotEdge>>("digraph { }").unwrap_err();
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::GraphKindMismatch { directed: true }
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! Six-bit packing shared by the graph6, digraph6 and sparse6 formats.
+
+use super::Graph6Error;
+use crate::graph::IndexType;
+
+/// Offset of the printable characters that carry six bits each.
+pub(super) const BIAS: u8 = 63;
+
+/// Packs bits, most significant first, into printable characters.
+pub(super) struct BitWriter {
+    out: String,
+    byte: u8,
+    len: u8,
+}
+
+impl BitWriter {
+    pub(super) fn new(prefix: &str) -> Self {
+        BitWriter {
+            out: String::from(prefix),
+            byte: 0,
+            len: 0,
+        }
+    }
+
+    pub(super) fn push(&mut self, bit: bool) {
+        self.byte = (self.byte << 1) | bit as u8;
+        self.len += 1;
+        if self.len == 6 {
+            self.out.push(char::from(BIAS + self.byte));
+            self.byte = 0;
+            self.len = 0;
+        }
+    }
+
+    /// Push the `count` lowest bits of `value`.
+    pub(super) fn push_bits(&mut self, value: u64, count: u32) {
+        for i in (0..count).rev() {
+            self.push((value >> i) & 1 == 1);
+        }
+    }
+
+    /// Number of bits needed to complete the current character.
+    pub(super) fn padding(&self) -> u8 {
+        (6 - self.len) % 6
+    }
+
+    /// Write the graph order `n` in the `N(n)` encoding.
+    pub(super) fn push_order(&mut self, n: usize) {
+        let n = n as u64;
+        if n < 63 {
+            self.push_bits(n, 6);
+        } else if n < 258048 {
+            self.push_bits(63, 6);
+            self.push_bits(n, 18);
+        } else {
+            assert!(n < 1 << 36, "Graph order not supported.");
+            self.push_bits(0xfff, 12);
+            self.push_bits(n, 36);
+        }
+    }
+
+    /// Complete the last character with `0` bits and return the encoding.
+    pub(super) fn finish(mut self) -> String {
+        while self.len != 0 {
+            self.push(false);
+        }
+        self.out
+    }
+}
+
+/// Reads bits, most significant first, from printable characters.
+pub(super) struct BitReader<'a> {
+    bytes: &'a [u8],
+    bit: usize,
+}
+
+impl<'a> BitReader<'a> {
+    /// Check that `data` only holds six-bit characters and wrap it.
+    ///
+    /// `offset` is the position of `data` in the original input, used for
+    /// error reporting.
+    pub(super) fn new(data: &'a str, offset: usize) -> Result<Self, Graph6Error> {
+        if let Some((position, character)) = data
+            .char_indices()
+            .find(|&(_, c)| !(BIAS as char..=(BIAS + 63) as char).contains(&c))
+        {
+            return Err(Graph6Error::InvalidCharacter {
+                position: offset + position,
+                character,
+            });
+        }
+        Ok(BitReader {
+            bytes: data.as_bytes(),
+            bit: 0,
+        })
+    }
+
+    pub(super) fn remaining(&self) -> usize {
+        self.bytes.len() * 6 - self.bit
+    }
+
+    /// Read `count` bits as a number, if that many are left.
+    pub(super) fn read(&mut self, count: u32) -> Option<u64> {
+        if self.remaining() < count as usize {
+            return None;
+        }
+        let mut value = 0;
+        for _ in 0..count {
+            let byte = self.bytes[self.bit / 6] - BIAS;
+            let bit = (byte >> (5 - self.bit % 6)) & 1;
+            value = (value << 1) | u64::from(bit);
+            self.bit += 1;
+        }
+        Some(value)
+    }
+
+    /// Read a graph order in the `N(n)` encoding, which must leave every node
+    /// an index of type `Ix`.
+    pub(super) fn read_order<Ix: IndexType>(&mut self) -> Result<usize, Graph6Error> {
+        let n = match self.read(6).ok_or(Graph6Error::Truncated)? {
+            63 => match self.read(6).ok_or(Graph6Error::Truncated)? {
+                63 => self.read(36).ok_or(Graph6Error::Truncated)?,
+                high => (high << 12) | self.read(12).ok_or(Graph6Error::Truncated)?,
+            },
+            n => n,
+        };
+        // The largest index is reserved by the graphs as an end marker.
+        match usize::try_from(n) {
+            Ok(order) if order <= <Ix as IndexType>::max().index() => Ok(order),
+            _ => Err(Graph6Error::OrderTooLarge { order: n }),
+        }
+    }
+}
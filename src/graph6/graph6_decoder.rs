@@ -1,6 +1,9 @@
-//! [graph6 format](https://users.cecs.anu.edu.au/~bdm/data/formats.txt) decoder for undirected graphs.
+//! [graph6 format](https://users.cecs.anu.edu.au/~bdm/data/formats.txt) decoder for undirected graphs,
+//! and its digraph6 sibling for directed graphs.
 
-use crate::{csr::Csr, graph::IndexType, Graph, Undirected};
+use super::bits::BitReader;
+use super::{Graph6Error, Graph6Format};
+use crate::{csr::Csr, graph::IndexType, Directed, Graph, Undirected};
 
 #[cfg(feature = "graphmap")]
 use crate::graphmap::GraphMap;
@@ -12,144 +15,231 @@ use std::hash::BuildHasher;
 use crate::matrix_graph::{MatrixGraph, Nullable};
 
 #[cfg(feature = "stable_graph")]
-use crate::stable_graph::{StableGraph, StableUnGraph};
-
-const N: usize = 63;
+use crate::stable_graph::{StableDiGraph, StableGraph, StableUnGraph};
 
 /// A graph that can be converted from graph6 format string.
+///
+/// **Panics** if the string is not valid graph6.
+#[deprecated(note = "use TryFromGraph6, which returns an error for invalid strings")]
 pub trait FromGraph6 {
     fn from_graph6_string(graph6_string: String) -> Self;
 }
 
+/// A graph that can be converted from a graph6 format string.
+pub trait TryFromGraph6: Sized {
+    fn try_from_graph6_string(graph6_string: &str) -> Result<Self, Graph6Error>;
+}
+
+/// A directed graph that can be converted from a digraph6 format string.
+pub trait FromDigraph6: Sized {
+    fn from_digraph6_string(digraph6_string: &str) -> Result<Self, Graph6Error>;
+}
+
 /// Converts a graph6 format string into data can be used to construct an undirected graph.
 /// Returns a tuple containing the graph order and its edges.
+///
+/// **Panics** if the string is not valid graph6, see
+/// [`try_from_graph6_representation`] for a fallible version.
+#[deprecated(note = "use try_from_graph6_representation")]
 pub fn from_graph6_representation<Ix>(graph6_representation: String) -> (usize, Vec<(Ix, Ix)>)
 where
     Ix: IndexType,
 {
-    let (order_bytes, adj_matrix_bytes) =
-        get_order_bytes_and_adj_matrix_bytes(graph6_representation);
+    match try_from_graph6_representation(&graph6_representation) {
+        Ok(decoded) => decoded,
+        Err(error) => panic!("Invalid graph6 string: {}", error),
+    }
+}
 
-    let order_bits = bytes_vector_to_bits_vector(order_bytes);
-    let adj_matrix_bits = bytes_vector_to_bits_vector(adj_matrix_bytes);
+/// Converts a graph6 format string into the graph order and its edges.
+///
+/// An optional `>>graph6<<` header and a trailing line break are skipped.
+pub fn try_from_graph6_representation<Ix>(
+    graph6_representation: &str,
+) -> Result<(usize, Vec<(Ix, Ix)>), Graph6Error>
+where
+    Ix: IndexType,
+{
+    let (data, offset) = Graph6Format::Graph6.strip(graph6_representation)?;
+    let mut bits = BitReader::new(data, offset)?;
+    let order = bits.read_order::<Ix>()?;
 
-    let graph_order = get_bits_as_decimal(order_bits);
-    let edges = get_edges(graph_order, adj_matrix_bits);
+    let mut edges = vec![];
+    for col in 1..order {
+        for lin in 0..col {
+            if bits.read(1).ok_or(Graph6Error::Truncated)? == 1 {
+                edges.push((Ix::new(lin), Ix::new(col)));
+            }
+        }
+    }
+    // Only the padding of the last character may be left.
+    if bits.remaining() >= 6 {
+        return Err(Graph6Error::TrailingData);
+    }
 
-    (graph_order, edges)
+    Ok((order, edges))
 }
 
-// Converts a graph6 format string into a vector of bytes, converted from ASCII characters,
-// split into two parts, the first representing the graph order, and the second its adjacency matrix.
-fn get_order_bytes_and_adj_matrix_bytes(graph6_representation: String) -> (Vec<usize>, Vec<usize>) {
-    let bytes: Vec<usize> = graph6_representation
-        .chars()
-        .map(|c| (c as usize) - N)
-        .collect();
-
-    let mut order_bytes = vec![];
-    let mut adj_matrix_bytes = vec![];
+/// Converts a digraph6 format string into the graph order and its directed edges.
+///
+/// An optional `>>digraph6<<` header and a trailing line break are skipped.
+pub fn from_digraph6_representation<Ix>(
+    digraph6_representation: &str,
+) -> Result<(usize, Vec<(Ix, Ix)>), Graph6Error>
+where
+    Ix: IndexType,
+{
+    let (data, offset) = Graph6Format::Digraph6.strip(digraph6_representation)?;
+    let mut bits = BitReader::new(data, offset)?;
+    let order = bits.read_order::<Ix>()?;
 
-    let first_byte = *bytes.first().unwrap();
-    if first_byte == N {
-        order_bytes.extend_from_slice(&bytes[1..=3]);
-        adj_matrix_bytes.extend_from_slice(&bytes[4..]);
-    } else {
-        order_bytes.push(first_byte);
-        adj_matrix_bytes.extend_from_slice(&bytes[1..]);
-    };
+    let mut edges = vec![];
+    for source in 0..order {
+        for target in 0..order {
+            if bits.read(1).ok_or(Graph6Error::Truncated)? == 1 {
+                edges.push((Ix::new(source), Ix::new(target)));
+            }
+        }
+    }
+    if bits.remaining() >= 6 {
+        return Err(Graph6Error::TrailingData);
+    }
 
-    (order_bytes, adj_matrix_bytes)
+    Ok((order, edges))
 }
 
-// Converts a bytes vector into a bits vector.
-fn bytes_vector_to_bits_vector(bytes: Vec<usize>) -> Vec<u8> {
-    bytes
-        .iter()
-        .flat_map(|&byte| get_number_as_bits(byte, 6))
-        .collect()
+impl<Ix: IndexType> TryFromGraph6 for Graph<(), (), Undirected, Ix> {
+    fn try_from_graph6_string(graph6_string: &str) -> Result<Self, Graph6Error> {
+        let (order, edges): (usize, Vec<(Ix, Ix)>) = try_from_graph6_representation(graph6_string)?;
+
+        let mut graph: Graph<(), (), Undirected, Ix> = Graph::with_capacity(order, edges.len());
+        for _ in 0..order {
+            graph.add_node(());
+        }
+        graph.extend_with_edges(edges);
+
+        Ok(graph)
+    }
 }
 
-// Get binary representation of `n` as a vector of bits with `bits_length` length.
-fn get_number_as_bits(n: usize, bits_length: usize) -> Vec<u8> {
-    let mut bits = Vec::new();
-    for i in (0..bits_length).rev() {
-        bits.push(((n >> i) & 1) as u8);
+#[cfg(feature = "stable_graph")]
+impl<Ix: IndexType> TryFromGraph6 for StableGraph<(), (), Undirected, Ix> {
+    fn try_from_graph6_string(graph6_string: &str) -> Result<Self, Graph6Error> {
+        let (order, edges): (usize, Vec<(Ix, Ix)>) = try_from_graph6_representation(graph6_string)?;
+
+        let mut graph: StableGraph<(), (), Undirected, Ix> =
+            StableUnGraph::with_capacity(order, edges.len());
+        for _ in 0..order {
+            graph.add_node(());
+        }
+        graph.extend_with_edges(edges);
+
+        Ok(graph)
     }
-    bits
 }
 
-// Convert a bits vector into its decimal representation.
-fn get_bits_as_decimal(bits: Vec<u8>) -> usize {
-    let bits_str = bits
-        .iter()
-        .map(|bit| bit.to_string())
-        .collect::<Vec<String>>()
-        .join("");
+#[cfg(feature = "graphmap")]
+impl<Ix: IndexType, S: BuildHasher + Default> TryFromGraph6 for GraphMap<Ix, (), Undirected, S> {
+    fn try_from_graph6_string(graph6_string: &str) -> Result<Self, Graph6Error> {
+        let (order, edges): (usize, Vec<(Ix, Ix)>) = try_from_graph6_representation(graph6_string)?;
+
+        let mut graph: GraphMap<Ix, (), Undirected, S> =
+            GraphMap::with_capacity(order, edges.len());
+        for i in 0..order {
+            graph.add_node(Ix::new(i));
+        }
+        for (a, b) in edges {
+            graph.add_edge(a, b, ());
+        }
 
-    usize::from_str_radix(&bits_str, 2).unwrap()
+        Ok(graph)
+    }
 }
 
-// Get graph edges from its order and bits vector representation of its adjacency matrix.
-fn get_edges<Ix>(order: usize, adj_matrix_bits: Vec<u8>) -> Vec<(Ix, Ix)>
+#[cfg(feature = "matrix_graph")]
+impl<Null, Ix> TryFromGraph6 for MatrixGraph<(), (), Undirected, Null, Ix>
 where
+    Null: Nullable<Wrapped = ()>,
     Ix: IndexType,
 {
-    let mut edges = vec![];
+    fn try_from_graph6_string(graph6_string: &str) -> Result<Self, Graph6Error> {
+        let (order, edges): (usize, Vec<(Ix, Ix)>) = try_from_graph6_representation(graph6_string)?;
 
-    let mut i = 0;
-    for col in 1..order {
-        for lin in 0..col {
-            let is_adjacent = adj_matrix_bits[i] == 1;
+        let mut graph: MatrixGraph<(), (), Undirected, Null, Ix> =
+            MatrixGraph::with_capacity(order);
+        for _ in 0..order {
+            graph.add_node(());
+        }
+        graph.extend_with_edges(edges.iter());
 
-            if is_adjacent {
-                edges.push((Ix::new(lin), Ix::new(col)));
-            };
+        Ok(graph)
+    }
+}
+
+impl<Ix: IndexType> TryFromGraph6 for Csr<(), (), Undirected, Ix> {
+    fn try_from_graph6_string(graph6_string: &str) -> Result<Self, Graph6Error> {
+        let (order, edges): (usize, Vec<(Ix, Ix)>) = try_from_graph6_representation(graph6_string)?;
 
-            i += 1;
+        let mut graph: Csr<(), (), Undirected, Ix> = Csr::new();
+        let mut nodes = Vec::new();
+        for _ in 0..order {
+            let i = graph.add_node(());
+            nodes.push(i);
+        }
+        for (a, b) in edges {
+            graph.add_edge(a, b, ());
         }
-    }
 
-    edges
+        Ok(graph)
+    }
 }
 
-impl<Ix: IndexType> FromGraph6 for Graph<(), (), Undirected, Ix> {
+#[allow(deprecated)]
+impl<G: TryFromGraph6> FromGraph6 for G {
     fn from_graph6_string(graph6_string: String) -> Self {
-        let (order, edges): (usize, Vec<(Ix, Ix)>) = from_graph6_representation(graph6_string);
+        match G::try_from_graph6_string(&graph6_string) {
+            Ok(graph) => graph,
+            Err(error) => panic!("Invalid graph6 string: {}", error),
+        }
+    }
+}
 
-        let mut graph: Graph<(), (), Undirected, Ix> = Graph::with_capacity(order, edges.len());
+impl<Ix: IndexType> FromDigraph6 for Graph<(), (), Directed, Ix> {
+    fn from_digraph6_string(digraph6_string: &str) -> Result<Self, Graph6Error> {
+        let (order, edges): (usize, Vec<(Ix, Ix)>) = from_digraph6_representation(digraph6_string)?;
+
+        let mut graph: Graph<(), (), Directed, Ix> = Graph::with_capacity(order, edges.len());
         for _ in 0..order {
             graph.add_node(());
         }
         graph.extend_with_edges(edges);
 
-        graph
+        Ok(graph)
     }
 }
 
 #[cfg(feature = "stable_graph")]
-impl<Ix: IndexType> FromGraph6 for StableGraph<(), (), Undirected, Ix> {
-    fn from_graph6_string(graph6_string: String) -> Self {
-        let (order, edges): (usize, Vec<(Ix, Ix)>) = from_graph6_representation(graph6_string);
+impl<Ix: IndexType> FromDigraph6 for StableGraph<(), (), Directed, Ix> {
+    fn from_digraph6_string(digraph6_string: &str) -> Result<Self, Graph6Error> {
+        let (order, edges): (usize, Vec<(Ix, Ix)>) = from_digraph6_representation(digraph6_string)?;
 
-        let mut graph: StableGraph<(), (), Undirected, Ix> =
-            StableUnGraph::with_capacity(order, edges.len());
+        let mut graph: StableGraph<(), (), Directed, Ix> =
+            StableDiGraph::with_capacity(order, edges.len());
         for _ in 0..order {
             graph.add_node(());
         }
         graph.extend_with_edges(edges);
 
-        graph
+        Ok(graph)
     }
 }
 
 #[cfg(feature = "graphmap")]
-impl<Ix: IndexType, S: BuildHasher + Default> FromGraph6 for GraphMap<Ix, (), Undirected, S> {
-    fn from_graph6_string(graph6_string: String) -> Self {
-        let (order, edges): (usize, Vec<(Ix, Ix)>) = from_graph6_representation(graph6_string);
+impl<Ix: IndexType, S: BuildHasher + Default> FromDigraph6 for GraphMap<Ix, (), Directed, S> {
+    fn from_digraph6_string(digraph6_string: &str) -> Result<Self, Graph6Error> {
+        let (order, edges): (usize, Vec<(Ix, Ix)>) = from_digraph6_representation(digraph6_string)?;
 
-        let mut graph: GraphMap<Ix, (), Undirected, S> =
-            GraphMap::with_capacity(order, edges.len());
+        let mut graph: GraphMap<Ix, (), Directed, S> = GraphMap::with_capacity(order, edges.len());
         for i in 0..order {
             graph.add_node(Ix::new(i));
         }
@@ -157,44 +247,41 @@ impl<Ix: IndexType, S: BuildHasher + Default> FromGraph6 for GraphMap<Ix, (), Un
             graph.add_edge(a, b, ());
         }
 
-        graph
+        Ok(graph)
     }
 }
 
 #[cfg(feature = "matrix_graph")]
-impl<Null, Ix> FromGraph6 for MatrixGraph<(), (), Undirected, Null, Ix>
+impl<Null, Ix> FromDigraph6 for MatrixGraph<(), (), Directed, Null, Ix>
 where
     Null: Nullable<Wrapped = ()>,
     Ix: IndexType,
 {
-    fn from_graph6_string(graph6_string: String) -> Self {
-        let (order, edges): (usize, Vec<(Ix, Ix)>) = from_graph6_representation(graph6_string);
+    fn from_digraph6_string(digraph6_string: &str) -> Result<Self, Graph6Error> {
+        let (order, edges): (usize, Vec<(Ix, Ix)>) = from_digraph6_representation(digraph6_string)?;
 
-        let mut graph: MatrixGraph<(), (), Undirected, Null, Ix> =
-            MatrixGraph::with_capacity(order);
+        let mut graph: MatrixGraph<(), (), Directed, Null, Ix> = MatrixGraph::with_capacity(order);
         for _ in 0..order {
             graph.add_node(());
         }
         graph.extend_with_edges(edges.iter());
 
-        graph
+        Ok(graph)
     }
 }
 
-impl<Ix: IndexType> FromGraph6 for Csr<(), (), Undirected, Ix> {
-    fn from_graph6_string(graph6_string: String) -> Self {
-        let (order, edges): (usize, Vec<(Ix, Ix)>) = from_graph6_representation(graph6_string);
+impl<Ix: IndexType> FromDigraph6 for Csr<(), (), Directed, Ix> {
+    fn from_digraph6_string(digraph6_string: &str) -> Result<Self, Graph6Error> {
+        let (order, edges): (usize, Vec<(Ix, Ix)>) = from_digraph6_representation(digraph6_string)?;
 
-        let mut graph: Csr<(), (), Undirected, Ix> = Csr::new();
-        let mut nodes = Vec::new();
+        let mut graph: Csr<(), (), Directed, Ix> = Csr::new();
         for _ in 0..order {
-            let i = graph.add_node(());
-            nodes.push(i);
+            graph.add_node(());
         }
         for (a, b) in edges {
             graph.add_edge(a, b, ());
         }
 
-        graph
+        Ok(graph)
     }
 }
@@ -1,11 +1,14 @@
-//! [graph6 format](https://users.cecs.anu.edu.au/~bdm/data/formats.txt) encoder for undirected graphs.
+//! [graph6 format](https://users.cecs.anu.edu.au/~bdm/data/formats.txt) encoder for undirected graphs,
+//! and its digraph6 sibling for directed graphs.
 
+use super::bits::BitWriter;
 use crate::{
     csr::Csr,
     graph::IndexType,
     visit::{GetAdjacencyMatrix, IntoNodeIdentifiers},
-    Graph, Undirected,
+    Directed, Graph, Undirected,
 };
+use fixedbitset::FixedBitSet;
 
 #[cfg(feature = "graphmap")]
 use crate::graphmap::{GraphMap, NodeTrait};
@@ -19,100 +22,96 @@ use crate::matrix_graph::{MatrixGraph, Nullable};
 #[cfg(feature = "stable_graph")]
 use crate::stable_graph::StableGraph;
 
-const N: usize = 63;
-
 /// A graph that can be converted to graph6 format string.
 pub trait ToGraph6 {
     fn graph6_string(&self) -> String;
 }
 
+/// A directed graph that can be converted to a digraph6 format string.
+pub trait ToDigraph6 {
+    fn digraph6_string(&self) -> String;
+}
+
 /// Converts a graph that implements GetAdjacencyMatrix and IntoNodeIdentifers
 /// into a graph6 format string.
+///
+/// **Panics** if the graph has 2<sup>36</sup> nodes or more.
 pub fn get_graph6_representation<G>(graph: G) -> String
 where
     G: GetAdjacencyMatrix + IntoNodeIdentifiers,
 {
-    let (graph_order, mut upper_diagonal_as_bits) = get_adj_matrix_upper_diagonal_as_bits(graph);
-    let mut graph_order_as_bits = get_graph_order_as_bits(graph_order);
-
-    let mut graph_as_bits = vec![];
-    graph_as_bits.append(&mut graph_order_as_bits);
-    graph_as_bits.append(&mut upper_diagonal_as_bits);
+    let node_ids: Vec<_> = graph.node_identifiers().collect();
+    let adj_matrix = graph.adjacency_matrix();
 
-    bits_to_ascii(graph_as_bits)
+    let mut bits = BitWriter::new("");
+    bits.push_order(node_ids.len());
+    // The upper triangle of the adjacency matrix, column by column.
+    for (col, &b) in node_ids.iter().enumerate() {
+        for &a in &node_ids[..col] {
+            bits.push(graph.is_adjacent(&adj_matrix, a, b));
+        }
+    }
+    bits.finish()
 }
 
-// Traverse graph nodes and construct the upper diagonal of its adjacency matrix as a vector of bits.
-// Returns a tuple containing:
-// - `n`: graph order (number of nodes in graph)
-// - `bits`: a vector of 0s and 1s encoding the upper diagonal of the graphs adjacency matrix.
-fn get_adj_matrix_upper_diagonal_as_bits<G>(graph: G) -> (usize, Vec<usize>)
+/// Converts a directed graph that implements GetAdjacencyMatrix and
+/// IntoNodeIdentifers into a digraph6 format string.
+///
+/// **Panics** if the graph has 2<sup>36</sup> nodes or more.
+pub fn get_digraph6_representation<G>(graph: G) -> String
 where
     G: GetAdjacencyMatrix + IntoNodeIdentifiers,
 {
-    let node_ids_iter = graph.node_identifiers();
-    let mut node_ids_vec = vec![];
-
+    let node_ids: Vec<_> = graph.node_identifiers().collect();
     let adj_matrix = graph.adjacency_matrix();
-    let mut bits = vec![];
-    let mut n = 0;
-    for node_id in node_ids_iter {
-        node_ids_vec.push(node_id);
-
-        for i in 1..=n {
-            let is_adjacent: bool =
-                graph.is_adjacent(&adj_matrix, node_ids_vec[i - 1], node_ids_vec[n]);
-            bits.push(if is_adjacent { 1 } else { 0 });
-        }
 
-        n += 1;
+    let mut bits = BitWriter::new("&");
+    bits.push_order(node_ids.len());
+    // The whole adjacency matrix, row by row.
+    for &a in &node_ids {
+        for &b in &node_ids {
+            bits.push(graph.is_adjacent(&adj_matrix, a, b));
+        }
     }
-
-    (n, bits)
-}
-
-// Converts graph order to a bits vector.
-fn get_graph_order_as_bits(order: usize) -> Vec<usize> {
-    let to_convert_to_bits = if order < N {
-        vec![(order, 6)]
-    } else if order <= 258047 {
-        vec![(N, 6), (order, 18)]
-    } else {
-        panic!("Graph order not supported.")
-    };
-
-    to_convert_to_bits
-        .iter()
-        .flat_map(|&(n, n_of_bits)| get_number_as_bits(n, n_of_bits))
-        .collect()
+    bits.finish()
 }
 
-// Get binary representation of `n` as a vector of bits with `bits_length` length.
-fn get_number_as_bits(n: usize, bits_length: usize) -> Vec<usize> {
-    let mut bits = Vec::new();
-    for i in (0..bits_length).rev() {
-        bits.push((n >> i) & 1);
+// Encode a graph given as an edge list. Loops cannot be written and are skipped.
+pub(crate) fn encode_graph6<I>(order: usize, edges: I) -> String
+where
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    // Bit `b * (b - 1) / 2 + a` stands for the edge {a, b} with a < b.
+    let mut matrix = FixedBitSet::with_capacity(order * order.saturating_sub(1) / 2);
+    for (a, b) in edges {
+        let (a, b) = (a.min(b), a.max(b));
+        if a != b {
+            matrix.insert(b * (b - 1) / 2 + a);
+        }
+    }
+    let mut bits = BitWriter::new("");
+    bits.push_order(order);
+    for i in 0..matrix.len() {
+        bits.push(matrix[i]);
     }
-    bits
+    bits.finish()
 }
 
-// Convert a vector of bits to a String using ASCII encoding.
-// Each 6 bits will be converted to a single ASCII character.
-fn bits_to_ascii(mut bits: Vec<usize>) -> String {
-    while bits.len() % 6 != 0 {
-        bits.push(0);
+// Encode a directed graph given as an edge list.
+pub(crate) fn encode_digraph6<I>(order: usize, edges: I) -> String
+where
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    let mut matrix = FixedBitSet::with_capacity(order * order);
+    for (a, b) in edges {
+        matrix.insert(a * order + b);
     }
-
-    let bits_strs = bits.iter().map(|bit| bit.to_string()).collect::<Vec<_>>();
-
-    let bytes = bits_strs
-        .chunks(6)
-        .map(|bits_chunk| bits_chunk.join(""))
-        .map(|bits_str| usize::from_str_radix(&bits_str, 2));
-
-    bytes
-        .map(|byte| char::from((N + byte.unwrap()) as u8))
-        .collect()
+    let mut bits = BitWriter::new("&");
+    bits.push_order(order);
+    for i in 0..matrix.len() {
+        bits.push(matrix[i]);
+    }
+    bits.finish()
 }
 
 impl<N, E, Ix: IndexType> ToGraph6 for Graph<N, E, Undirected, Ix> {
@@ -152,3 +151,41 @@ impl<N, E, Ix: IndexType> ToGraph6 for Csr<N, E, Undirected, Ix> {
         get_graph6_representation(self)
     }
 }
+
+impl<N, E, Ix: IndexType> ToDigraph6 for Graph<N, E, Directed, Ix> {
+    fn digraph6_string(&self) -> String {
+        get_digraph6_representation(self)
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+impl<N, E, Ix: IndexType> ToDigraph6 for StableGraph<N, E, Directed, Ix> {
+    fn digraph6_string(&self) -> String {
+        get_digraph6_representation(self)
+    }
+}
+
+#[cfg(feature = "graphmap")]
+impl<N: NodeTrait, E, S: BuildHasher> ToDigraph6 for GraphMap<N, E, Directed, S> {
+    fn digraph6_string(&self) -> String {
+        get_digraph6_representation(self)
+    }
+}
+
+#[cfg(feature = "matrix_graph")]
+impl<N, E, Null, Ix> ToDigraph6 for MatrixGraph<N, E, Directed, Null, Ix>
+where
+    N: NodeTrait,
+    Null: Nullable<Wrapped = E>,
+    Ix: IndexType,
+{
+    fn digraph6_string(&self) -> String {
+        get_digraph6_representation(self)
+    }
+}
+
+impl<N, E, Ix: IndexType> ToDigraph6 for Csr<N, E, Directed, Ix> {
+    fn digraph6_string(&self) -> String {
+        get_digraph6_representation(self)
+    }
+}
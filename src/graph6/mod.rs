@@ -1,7 +1,170 @@
 //! Traits related to [graph6 format](https://users.cecs.anu.edu.au/~bdm/data/formats.txt) for undirected graphs.
+//!
+//! Besides graph6, this module supports its siblings digraph6 (prefix `&`)
+//! for directed graphs and sparse6 (prefix `:`) for large sparse undirected
+//! graphs, which may also contain loops and parallel edges. [`Graph6Reader`]
+//! and [`Graph6Writer`] stream files with one graph per line, as distributed
+//! by nauty and the House of Graphs.
+
+use std::fmt;
+
+use crate::visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
 
 pub use self::graph6_decoder::*;
 pub use self::graph6_encoder::*;
+pub use self::sparse6::*;
+pub use self::stream::*;
 
+mod bits;
 mod graph6_decoder;
 mod graph6_encoder;
+mod sparse6;
+mod stream;
+
+/// The members of the graph6 family of formats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Graph6Format {
+    /// Undirected simple graphs, stored as the upper triangle of the
+    /// adjacency matrix.
+    Graph6,
+    /// Directed graphs, stored as the full adjacency matrix.
+    Digraph6,
+    /// Undirected graphs, stored as an edge list.
+    Sparse6,
+}
+
+impl Graph6Format {
+    /// The optional header at the start of a file, like `>>graph6<<`.
+    pub fn header(self) -> &'static str {
+        match self {
+            Graph6Format::Graph6 => ">>graph6<<",
+            Graph6Format::Digraph6 => ">>digraph6<<",
+            Graph6Format::Sparse6 => ">>sparse6<<",
+        }
+    }
+
+    /// The character that starts every graph of this format, if any.
+    pub fn prefix(self) -> Option<char> {
+        match self {
+            Graph6Format::Graph6 => None,
+            Graph6Format::Digraph6 => Some('&'),
+            Graph6Format::Sparse6 => Some(':'),
+        }
+    }
+
+    /// Detect the format of an encoded graph, skipping any header.
+    fn detect(encoded: &str) -> (Self, &str) {
+        let mut format = None;
+        let mut rest = encoded;
+        for f in [
+            Graph6Format::Graph6,
+            Graph6Format::Digraph6,
+            Graph6Format::Sparse6,
+        ] {
+            if let Some(stripped) = rest.strip_prefix(f.header()) {
+                format = Some(f);
+                rest = stripped;
+            }
+        }
+        let format = match rest.chars().next() {
+            Some('&') => Graph6Format::Digraph6,
+            Some(':') => Graph6Format::Sparse6,
+            _ => format.unwrap_or(Graph6Format::Graph6),
+        };
+        (format, rest)
+    }
+
+    /// Strip header, prefix and line break from `encoded`, checking that it
+    /// is of this format. Return the data and its offset in `encoded`.
+    fn strip(self, encoded: &str) -> Result<(&str, usize), Graph6Error> {
+        let (found, rest) = Self::detect(encoded);
+        if found != self {
+            return Err(Graph6Error::WrongFormat {
+                expected: self,
+                found,
+            });
+        }
+        let rest = match self.prefix() {
+            Some(prefix) => &rest[prefix.len_utf8()..],
+            None => rest,
+        };
+        let data = rest.trim_end_matches(['\n', '\r']);
+        if data.is_empty() {
+            return Err(Graph6Error::Truncated);
+        }
+        Ok((data, encoded.len() - rest.len()))
+    }
+}
+
+// Number the nodes of `graph` in the order of `node_identifiers` and list its
+// edges by those numbers. Return the graph order and the edges.
+fn numbered_edges<G>(graph: G) -> (usize, impl Iterator<Item = (usize, usize)>)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+{
+    let mut position = vec![usize::MAX; graph.node_bound()];
+    let mut order = 0;
+    for node in graph.node_identifiers() {
+        position[graph.to_index(node)] = order;
+        order += 1;
+    }
+    let edges = graph.edge_references().map(move |edge| {
+        (
+            position[graph.to_index(edge.source())],
+            position[graph.to_index(edge.target())],
+        )
+    });
+    (order, edges)
+}
+
+/// An error encountered while decoding a graph6, digraph6 or sparse6 string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Graph6Error {
+    /// A character that cannot appear in the encoding.
+    InvalidCharacter {
+        /// The byte offset of the character in the input.
+        position: usize,
+        /// The offending character.
+        character: char,
+    },
+    /// The input ends before the graph is complete.
+    Truncated,
+    /// The input holds more data than the graph order allows.
+    TrailingData,
+    /// The graph has more nodes than the node index type can hold.
+    OrderTooLarge {
+        /// The order declared by the input.
+        order: u64,
+    },
+    /// The input is in a different member of the graph6 family.
+    WrongFormat {
+        /// The format that was asked for.
+        expected: Graph6Format,
+        /// The format of the input.
+        found: Graph6Format,
+    },
+}
+
+impl fmt::Display for Graph6Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Graph6Error::InvalidCharacter {
+                position,
+                character,
+            } => write!(f, "invalid character {character:?} at position {position}"),
+            Graph6Error::Truncated => write!(f, "unexpected end of input"),
+            Graph6Error::TrailingData => write!(f, "unexpected data after the graph"),
+            Graph6Error::OrderTooLarge { order } => {
+                write!(
+                    f,
+                    "graph order {order} is too large for the node index type"
+                )
+            }
+            Graph6Error::WrongFormat { expected, found } => {
+                write!(f, "expected {expected:?} input, found {found:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Graph6Error {}
@@ -0,0 +1,204 @@
+//! [sparse6 format](https://users.cecs.anu.edu.au/~bdm/data/formats.txt) encoder and decoder
+//! for undirected graphs, which may have loops and parallel edges.
+
+use super::bits::{BitReader, BitWriter};
+use super::{numbered_edges, Graph6Error, Graph6Format};
+use crate::{
+    csr::Csr,
+    graph::IndexType,
+    visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable},
+    Graph, Undirected,
+};
+
+#[cfg(feature = "graphmap")]
+use crate::graphmap::{GraphMap, NodeTrait};
+
+#[cfg(feature = "graphmap")]
+use std::hash::BuildHasher;
+
+#[cfg(feature = "matrix_graph")]
+use crate::matrix_graph::{MatrixGraph, Nullable};
+
+#[cfg(feature = "stable_graph")]
+use crate::stable_graph::{StableGraph, StableUnGraph};
+
+/// A graph that can be converted to a sparse6 format string.
+pub trait ToSparse6 {
+    fn sparse6_string(&self) -> String;
+}
+
+/// A graph that can be converted from a sparse6 format string.
+///
+/// Only implemented for graphs that can hold parallel edges.
+pub trait FromSparse6: Sized {
+    fn from_sparse6_string(sparse6_string: &str) -> Result<Self, Graph6Error>;
+}
+
+// Number of bits needed to write the largest vertex, `n - 1`.
+fn vertex_bits(order: usize) -> u32 {
+    usize::BITS - order.saturating_sub(1).leading_zeros()
+}
+
+/// Converts a graph that implements IntoEdgeReferences, IntoNodeIdentifiers
+/// and NodeIndexable into a sparse6 format string.
+///
+/// Vertices are numbered in the order of `node_identifiers`. Loops and
+/// parallel edges are kept.
+///
+/// **Panics** if the graph has 2<sup>36</sup> nodes or more.
+pub fn get_sparse6_representation<G>(graph: G) -> String
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+{
+    let (order, edges) = numbered_edges(graph);
+    encode_sparse6(order, edges)
+}
+
+pub(super) fn encode_sparse6<I>(order: usize, edges: I) -> String
+where
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    // Edges are written sorted by their larger end.
+    let mut edges: Vec<(usize, usize)> = edges
+        .into_iter()
+        .map(|(a, b)| (a.max(b), a.min(b)))
+        .collect();
+    edges.sort_unstable();
+
+    let k = vertex_bits(order);
+    let mut bits = BitWriter::new(":");
+    bits.push_order(order);
+    let mut v = 0;
+    for (y, x) in edges {
+        if y == v {
+            bits.push(false);
+            bits.push_bits(x as u64, k);
+        } else if y == v + 1 {
+            bits.push(true);
+            bits.push_bits(x as u64, k);
+        } else {
+            bits.push(true);
+            bits.push_bits(y as u64, k);
+            bits.push(false);
+            bits.push_bits(x as u64, k);
+        }
+        v = y;
+    }
+
+    // Padding with 1s could be read as an extra loop on `n - 1` in this case.
+    let padding = bits.padding();
+    if order == 1 << k && order >= 2 && v == order - 2 && u32::from(padding) > k {
+        bits.push(false);
+        bits.push_bits(u64::MAX, u32::from(padding) - 1);
+    } else {
+        bits.push_bits(u64::MAX, u32::from(padding));
+    }
+    bits.finish()
+}
+
+/// Converts a sparse6 format string into the graph order and its edges.
+///
+/// An optional `>>sparse6<<` header and a trailing line break are skipped.
+pub fn from_sparse6_representation<Ix>(
+    sparse6_representation: &str,
+) -> Result<(usize, Vec<(Ix, Ix)>), Graph6Error>
+where
+    Ix: IndexType,
+{
+    let (data, offset) = Graph6Format::Sparse6.strip(sparse6_representation)?;
+    let mut bits = BitReader::new(data, offset)?;
+    let order = bits.read_order::<Ix>()?;
+    let k = vertex_bits(order);
+
+    let mut edges = vec![];
+    let mut v = 0;
+    // An incomplete pair at the end is padding.
+    while bits.remaining() > k as usize {
+        if bits.read(1) == Some(1) {
+            v += 1;
+        }
+        let x = bits.read(k).unwrap() as usize;
+        if v >= order {
+            break;
+        }
+        if x > v {
+            v = x;
+        } else {
+            edges.push((Ix::new(x), Ix::new(v)));
+        }
+    }
+
+    Ok((order, edges))
+}
+
+impl<Ix: IndexType> FromSparse6 for Graph<(), (), Undirected, Ix> {
+    fn from_sparse6_string(sparse6_string: &str) -> Result<Self, Graph6Error> {
+        let (order, edges): (usize, Vec<(Ix, Ix)>) = from_sparse6_representation(sparse6_string)?;
+
+        let mut graph: Graph<(), (), Undirected, Ix> = Graph::with_capacity(order, edges.len());
+        for _ in 0..order {
+            graph.add_node(());
+        }
+        graph.extend_with_edges(edges);
+
+        Ok(graph)
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+impl<Ix: IndexType> FromSparse6 for StableGraph<(), (), Undirected, Ix> {
+    fn from_sparse6_string(sparse6_string: &str) -> Result<Self, Graph6Error> {
+        let (order, edges): (usize, Vec<(Ix, Ix)>) = from_sparse6_representation(sparse6_string)?;
+
+        let mut graph: StableGraph<(), (), Undirected, Ix> =
+            StableUnGraph::with_capacity(order, edges.len());
+        for _ in 0..order {
+            graph.add_node(());
+        }
+        graph.extend_with_edges(edges);
+
+        Ok(graph)
+    }
+}
+
+impl<N, E, Ix: IndexType> ToSparse6 for Graph<N, E, Undirected, Ix> {
+    fn sparse6_string(&self) -> String {
+        get_sparse6_representation(self)
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+impl<N, E, Ix: IndexType> ToSparse6 for StableGraph<N, E, Undirected, Ix> {
+    fn sparse6_string(&self) -> String {
+        get_sparse6_representation(self)
+    }
+}
+
+#[cfg(feature = "graphmap")]
+impl<N: NodeTrait, E, S: BuildHasher> ToSparse6 for GraphMap<N, E, Undirected, S> {
+    fn sparse6_string(&self) -> String {
+        get_sparse6_representation(self)
+    }
+}
+
+#[cfg(feature = "matrix_graph")]
+impl<N, E, Null, Ix> ToSparse6 for MatrixGraph<N, E, Undirected, Null, Ix>
+where
+    Null: Nullable<Wrapped = E>,
+    Ix: IndexType,
+{
+    fn sparse6_string(&self) -> String {
+        get_sparse6_representation(self)
+    }
+}
+
+impl<N, E, Ix: IndexType> ToSparse6 for Csr<N, E, Undirected, Ix> {
+    fn sparse6_string(&self) -> String {
+        // `Csr` stores both directions of an undirected edge.
+        let edges = self
+            .edge_references()
+            .filter(|edge| edge.source() <= edge.target())
+            .map(|edge| (edge.source().index(), edge.target().index()));
+        encode_sparse6(self.node_count(), edges)
+    }
+}
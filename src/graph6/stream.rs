@@ -0,0 +1,202 @@
+//! Reading and writing files with one graph6, digraph6 or sparse6 graph per line.
+
+use std::io::{self, BufRead, Write};
+
+use super::graph6_encoder::{encode_digraph6, encode_graph6};
+use super::sparse6::{encode_sparse6, from_sparse6_representation};
+use super::{
+    from_digraph6_representation, numbered_edges, try_from_graph6_representation, Graph6Error,
+    Graph6Format,
+};
+use crate::{
+    graph::{Graph, IndexType},
+    visit::{IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable},
+    EdgeType,
+};
+
+/// A graph decoded from any member of the graph6 family.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedGraph {
+    /// The format the graph was encoded in.
+    pub format: Graph6Format,
+    /// The number of nodes.
+    pub order: usize,
+    /// The edges as pairs of node indices, directed for digraph6.
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl DecodedGraph {
+    /// Decode a graph6, digraph6 or sparse6 string, detecting its format from
+    /// the prefix or header.
+    pub fn parse(encoded: &str) -> Result<Self, Graph6Error> {
+        let (format, _) = Graph6Format::detect(encoded);
+        let (order, edges) = match format {
+            Graph6Format::Graph6 => try_from_graph6_representation::<usize>(encoded)?,
+            Graph6Format::Digraph6 => from_digraph6_representation::<usize>(encoded)?,
+            Graph6Format::Sparse6 => from_sparse6_representation::<usize>(encoded)?,
+        };
+        Ok(DecodedGraph {
+            format,
+            order,
+            edges,
+        })
+    }
+
+    /// Build a `Graph` with `order` nodes and the decoded edges.
+    ///
+    /// The edge type is up to the caller: a digraph6 graph read into an
+    /// undirected graph loses its edge directions.
+    ///
+    /// **Panics** if the order does not fit in the node index type `Ix`.
+    pub fn into_graph<Ty: EdgeType, Ix: IndexType>(self) -> Graph<(), (), Ty, Ix> {
+        let mut graph = Graph::with_capacity(self.order, self.edges.len());
+        for _ in 0..self.order {
+            graph.add_node(());
+        }
+        graph.extend_with_edges(
+            self.edges
+                .into_iter()
+                .map(|(a, b)| (Ix::new(a), Ix::new(b))),
+        );
+        graph
+    }
+}
+
+/// `Graph6Reader` reads graphs from a file with one encoded graph per line,
+/// like those produced by nauty's `geng` or `directg`.
+///
+/// The formats may be mixed, each line is detected on its own. Headers like
+/// `>>graph6<<` and empty lines are skipped. A line that cannot be decoded is
+/// returned as an [`io::ErrorKind::InvalidData`] error wrapping the
+/// [`Graph6Error`].
+///
+/// # Example
+///
+/// ```
+/// use petgraph::graph6::{Graph6Format, Graph6Reader};
+///
+/// let input = ">>graph6<<A_\n:Fa@x^\n&BG?\n";
+/// let graphs = Graph6Reader::new(input.as_bytes())
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(graphs.len(), 3);
+/// assert_eq!(graphs[0].edges, [(0, 1)]);
+/// assert_eq!(graphs[1].format, Graph6Format::Sparse6);
+/// assert_eq!(graphs[1].edges, [(0, 1), (0, 2), (1, 2), (5, 6)]);
+/// assert_eq!(graphs[2].edges, [(0, 2)]);
+/// ```
+pub struct Graph6Reader<R> {
+    reader: R,
+    line: String,
+}
+
+impl<R: BufRead> Graph6Reader<R> {
+    /// Create a `Graph6Reader` reading lines from `reader`.
+    pub fn new(reader: R) -> Self {
+        Graph6Reader {
+            reader,
+            line: String::new(),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Graph6Reader<R> {
+    type Item = io::Result<DecodedGraph>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(error) => return Some(Err(error)),
+            }
+            let mut line = self.line.trim_end_matches(['\n', '\r']);
+            for format in [
+                Graph6Format::Graph6,
+                Graph6Format::Digraph6,
+                Graph6Format::Sparse6,
+            ] {
+                line = line.strip_prefix(format.header()).unwrap_or(line);
+            }
+            if line.is_empty() {
+                continue;
+            }
+            return Some(
+                DecodedGraph::parse(line)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            );
+        }
+    }
+}
+
+/// `Graph6Writer` writes graphs in one of the graph6 formats, one per line.
+///
+/// # Example
+///
+/// ```
+/// use petgraph::graph::UnGraph;
+/// use petgraph::graph6::{Graph6Format, Graph6Writer};
+///
+/// let mut writer = Graph6Writer::new(Vec::new(), Graph6Format::Graph6).with_header();
+/// writer.write_graph(&UnGraph::<(), ()>::from_edges(&[(0, 1)])).unwrap();
+/// writer.write_graph(&UnGraph::<(), ()>::from_edges(&[(0, 2)])).unwrap();
+///
+/// assert_eq!(writer.into_inner(), b">>graph6<<A_\nBO\n");
+/// ```
+pub struct Graph6Writer<W> {
+    writer: W,
+    format: Graph6Format,
+    header: bool,
+}
+
+impl<W: Write> Graph6Writer<W> {
+    /// Create a `Graph6Writer` writing graphs to `writer` in `format`.
+    pub fn new(writer: W, format: Graph6Format) -> Self {
+        Graph6Writer {
+            writer,
+            format,
+            header: false,
+        }
+    }
+
+    /// Start the output with the header of the format, like `>>graph6<<`.
+    pub fn with_header(mut self) -> Self {
+        self.header = true;
+        self
+    }
+
+    /// Write `graph` as one line.
+    ///
+    /// Nodes are numbered in the order of `node_identifiers`. Each edge
+    /// reference is written as one edge: graph6 drops loops and parallel
+    /// edges, and digraph6 drops parallel edges.
+    pub fn write_graph<G>(&mut self, graph: G) -> io::Result<()>
+    where
+        G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    {
+        let (order, edges) = numbered_edges(graph);
+        let encoded = match self.format {
+            Graph6Format::Graph6 => encode_graph6(order, edges),
+            Graph6Format::Digraph6 => encode_digraph6(order, edges),
+            Graph6Format::Sparse6 => encode_sparse6(order, edges),
+        };
+
+        if self.header {
+            self.writer.write_all(self.format.header().as_bytes())?;
+            self.header = false;
+        }
+        writeln!(self.writer, "{}", encoded)
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
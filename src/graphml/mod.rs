@@ -0,0 +1,321 @@
+//! [GraphML](http://graphml.graphdrawing.org/) import and export.
+//!
+//! GraphML is the XML based exchange format understood by Gephi, yEd, NetworkX
+//! and many other graph tools. [`GraphMl`] writes any graph, mapping node and
+//! edge weights to typed `<key>` attributes with user supplied closures.
+//! [`from_graphml`] reads a GraphML document back into any graph that
+//! implements [`Create`](crate::data::Create), like `Graph`, `StableGraph` or
+//! `GraphMap`.
+//!
+//! Hyperedges, nested graphs and ports are not supported.
+//!
+//! # Example
+//!
+//! ```
+//! use petgraph::graph::DiGraph;
+//! use petgraph::graphml::{from_graphml, AttrValue, GraphMl, GraphMlEdge, GraphMlNode};
+//!
+//! let mut graph = DiGraph::<&str, f64>::new();
+//! let a = graph.add_node("a");
+//! let b = graph.add_node("b");
+//! graph.add_edge(a, b, 2.5);
+//!
+//! let text = GraphMl::new(&graph)
+//!     .node_attribute("name", |name: &&str| name.to_string())
+//!     .edge_attribute("weight", |weight: &f64| *weight)
+//!     .to_string();
+//!
+//! let parsed = from_graphml::<DiGraph<GraphMlNode, GraphMlEdge>, _, _>(&text, |n| n, |e| e)
+//!     .unwrap();
+//! let b = parsed.node_id("n1").unwrap();
+//! assert_eq!(parsed.graph[b].attributes["name"], AttrValue::String("b".into()));
+//! assert_eq!(parsed.graph.edge_weights().next().unwrap().attributes["weight"].as_f64(), Some(2.5));
+//! ```
+
+use std::fmt;
+
+pub use self::reader::*;
+pub use self::writer::*;
+
+mod reader;
+mod writer;
+mod xml;
+
+/// The type of a GraphML attribute, as declared by `attr.type` on its `<key>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AttrType {
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    String,
+}
+
+impl AttrType {
+    /// The `attr.type` name of this type.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AttrType::Boolean => "boolean",
+            AttrType::Int => "int",
+            AttrType::Long => "long",
+            AttrType::Float => "float",
+            AttrType::Double => "double",
+            AttrType::String => "string",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "boolean" => AttrType::Boolean,
+            "int" => AttrType::Int,
+            "long" => AttrType::Long,
+            "float" => AttrType::Float,
+            "double" => AttrType::Double,
+            "string" => AttrType::String,
+            _ => return None,
+        })
+    }
+
+    /// Decode the text of a `<data>` or `<default>` element.
+    fn parse(self, text: &str) -> Option<AttrValue> {
+        let text = text.trim();
+        Some(match self {
+            AttrType::Boolean => match text {
+                "true" | "1" => AttrValue::Boolean(true),
+                "false" | "0" => AttrValue::Boolean(false),
+                _ => return None,
+            },
+            AttrType::Int => AttrValue::Int(text.parse().ok()?),
+            AttrType::Long => AttrValue::Long(text.parse().ok()?),
+            AttrType::Float => AttrValue::Float(parse_float(text)? as f32),
+            AttrType::Double => AttrValue::Double(parse_float(text)?),
+            AttrType::String => unreachable!("strings are not trimmed or parsed"),
+        })
+    }
+}
+
+// Accept the XML Schema spellings of the special values too.
+fn parse_float(text: &str) -> Option<f64> {
+    match text {
+        "INF" => Some(f64::INFINITY),
+        "-INF" => Some(f64::NEG_INFINITY),
+        _ => text.parse().ok(),
+    }
+}
+
+/// A typed GraphML attribute value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttrValue {
+    Boolean(bool),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+}
+
+impl AttrValue {
+    /// The type of this value.
+    pub fn attr_type(&self) -> AttrType {
+        match self {
+            AttrValue::Boolean(_) => AttrType::Boolean,
+            AttrValue::Int(_) => AttrType::Int,
+            AttrValue::Long(_) => AttrType::Long,
+            AttrValue::Float(_) => AttrType::Float,
+            AttrValue::Double(_) => AttrType::Double,
+            AttrValue::String(_) => AttrType::String,
+        }
+    }
+
+    /// Return the value of a `Boolean`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            AttrValue::Boolean(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// Return the value of an `Int` or `Long`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            AttrValue::Int(i) => Some(i.into()),
+            AttrValue::Long(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Return the value of any numeric variant as a `f64`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            AttrValue::Int(i) => Some(i.into()),
+            AttrValue::Long(i) => Some(i as f64),
+            AttrValue::Float(x) => Some(x.into()),
+            AttrValue::Double(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Return the value of a `String`.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AttrValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AttrValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttrValue::Boolean(b) => write!(f, "{b}"),
+            AttrValue::Int(i) => write!(f, "{i}"),
+            AttrValue::Long(i) => write!(f, "{i}"),
+            // Use the XML Schema spelling of infinity.
+            AttrValue::Float(x) if x.is_infinite() => f.write_str(infinity(*x > 0.)),
+            AttrValue::Double(x) if x.is_infinite() => f.write_str(infinity(*x > 0.)),
+            AttrValue::Float(x) => write!(f, "{x}"),
+            AttrValue::Double(x) => write!(f, "{x}"),
+            AttrValue::String(s) => f.write_str(s),
+        }
+    }
+}
+
+fn infinity(positive: bool) -> &'static str {
+    if positive {
+        "INF"
+    } else {
+        "-INF"
+    }
+}
+
+/// A Rust type that can be written as a typed GraphML attribute.
+///
+/// `Option<T>` leaves the attribute out for elements where it is `None`.
+pub trait GraphMlValue {
+    /// The declared `attr.type` of the attribute.
+    fn attr_type() -> AttrType;
+    /// Convert into the value to write, if any.
+    fn into_attr_value(self) -> Option<AttrValue>;
+}
+
+macro_rules! impl_graphml_value {
+    ($($ty:ty => $variant:ident,)*) => {
+        $(
+            impl GraphMlValue for $ty {
+                fn attr_type() -> AttrType {
+                    AttrType::$variant
+                }
+                fn into_attr_value(self) -> Option<AttrValue> {
+                    Some(AttrValue::$variant(self.into()))
+                }
+            }
+        )*
+    };
+}
+
+impl_graphml_value!(
+    bool => Boolean,
+    i32 => Int,
+    i64 => Long,
+    f32 => Float,
+    f64 => Double,
+    String => String,
+);
+
+impl<T: GraphMlValue> GraphMlValue for Option<T> {
+    fn attr_type() -> AttrType {
+        T::attr_type()
+    }
+    fn into_attr_value(self) -> Option<AttrValue> {
+        self.and_then(T::into_attr_value)
+    }
+}
+
+/// An error encountered while reading a GraphML document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GraphMlError {
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// The column of the error in characters, starting at 1.
+    pub column: usize,
+    /// What went wrong.
+    pub kind: GraphMlErrorKind,
+}
+
+/// The kind of a [`GraphMlError`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GraphMlErrorKind {
+    /// The document is not well-formed XML.
+    Xml(&'static str),
+    /// An element is closed by a different end tag.
+    MismatchedTag {
+        /// The open element.
+        expected: String,
+        /// The end tag that was found.
+        found: String,
+    },
+    /// An element lacks an attribute that GraphML requires.
+    MissingAttribute {
+        /// The element name.
+        element: &'static str,
+        /// The missing attribute.
+        attribute: &'static str,
+    },
+    /// A `<key>` declares an `attr.type` that GraphML does not define.
+    UnknownAttrType(String),
+    /// A `<data>` element refers to an undeclared key.
+    UnknownKey(String),
+    /// A `<data>` or `<default>` value does not match the key's type.
+    InvalidValue {
+        /// The key id.
+        key: String,
+        /// The text that could not be decoded.
+        value: String,
+    },
+    /// Two nodes share the same id.
+    DuplicateNode(String),
+    /// An edge refers to a node that does not exist.
+    UnknownNode(String),
+    /// The document does not contain a `<graph>` element.
+    MissingGraph,
+    /// The document and the target graph type disagree on directedness.
+    GraphKindMismatch {
+        /// Whether the document's edges are directed.
+        directed: bool,
+    },
+    /// A GraphML construct that cannot be represented, like a hyperedge.
+    Unsupported(&'static str),
+}
+
+impl fmt::Display for GraphMlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        match &self.kind {
+            GraphMlErrorKind::Xml(msg) => write!(f, "malformed XML: {msg}"),
+            GraphMlErrorKind::MismatchedTag { expected, found } => {
+                write!(f, "expected `</{expected}>`, found `</{found}>`")
+            }
+            GraphMlErrorKind::MissingAttribute { element, attribute } => {
+                write!(f, "`<{element}>` is missing the `{attribute}` attribute")
+            }
+            GraphMlErrorKind::UnknownAttrType(ty) => write!(f, "unknown attribute type {ty:?}"),
+            GraphMlErrorKind::UnknownKey(key) => write!(f, "undeclared key {key:?}"),
+            GraphMlErrorKind::InvalidValue { key, value } => {
+                write!(f, "invalid value {value:?} for key {key:?}")
+            }
+            GraphMlErrorKind::DuplicateNode(id) => write!(f, "duplicate node id {id:?}"),
+            GraphMlErrorKind::UnknownNode(id) => write!(f, "edge refers to unknown node {id:?}"),
+            GraphMlErrorKind::MissingGraph => write!(f, "no `<graph>` element found"),
+            GraphMlErrorKind::GraphKindMismatch { directed: true } => {
+                write!(f, "cannot read directed edges into an undirected graph")
+            }
+            GraphMlErrorKind::GraphKindMismatch { directed: false } => {
+                write!(f, "cannot read undirected edges into a directed graph")
+            }
+            GraphMlErrorKind::Unsupported(what) => write!(f, "unsupported GraphML: {what}"),
+        }
+    }
+}
+
+impl std::error::Error for GraphMlError {}
@@ -0,0 +1,536 @@
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+
+use super::xml::{Event, Reader};
+use super::{AttrType, AttrValue, GraphMlError, GraphMlErrorKind};
+use crate::data::Create;
+use crate::visit::{GraphBase, GraphProp};
+
+/// Attributes of a graph, node or edge, keyed by their `attr.name`.
+pub type GraphMlAttributes = IndexMap<String, AttrValue>;
+
+/// A node as read from a GraphML document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphMlNode {
+    /// The node id.
+    pub id: String,
+    /// The node attributes, including key defaults.
+    pub attributes: GraphMlAttributes,
+}
+
+/// An edge as read from a GraphML document.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GraphMlEdge {
+    /// The edge id, if the document has one.
+    pub id: Option<String>,
+    /// The edge attributes, including key defaults.
+    pub attributes: GraphMlAttributes,
+}
+
+/// The result of reading a GraphML document.
+#[derive(Clone, Debug)]
+pub struct GraphMlGraph<G: GraphBase> {
+    /// The graph that was read.
+    pub graph: G,
+    /// The id of the `<graph>` element, if any.
+    pub id: Option<String>,
+    /// Graph level attributes.
+    pub attributes: GraphMlAttributes,
+    node_ids: HashMap<String, G::NodeId>,
+}
+
+impl<G: GraphBase> GraphMlGraph<G> {
+    /// Return the node with GraphML id `id`, if it exists.
+    pub fn node_id(&self, id: &str) -> Option<G::NodeId> {
+        self.node_ids.get(id).copied()
+    }
+}
+
+/// Read the GraphML document `input` into a graph of type `G`.
+///
+/// The nodes and edges of the `<graph>` element are decoded into
+/// [`GraphMlNode`] and [`GraphMlEdge`] values with typed attributes, and then
+/// converted to the weights of `G` by `node_weight` and `edge_weight`. Nodes
+/// are added in document order, edges after all nodes.
+///
+/// For `GraphMap`, nodes that `node_weight` maps to the same weight are
+/// merged.
+///
+/// Data of keys that have no `attr.type` but a `yfiles.type` (yEd's graphics
+/// and other extensions) is skipped, as are unknown elements.
+///
+/// **Errors** for malformed XML, undeclared keys, values that do not match
+/// their key's type, edges to unknown nodes, edges that disagree with `G` on
+/// directedness, documents with more than one `<graph>` element, and the
+/// unsupported hyperedges, ports and nested graphs.
+pub fn from_graphml<G, FN, FE>(
+    input: &str,
+    mut node_weight: FN,
+    mut edge_weight: FE,
+) -> Result<GraphMlGraph<G>, GraphMlError>
+where
+    G: Create + GraphProp,
+    FN: FnMut(GraphMlNode) -> G::NodeWeight,
+    FE: FnMut(GraphMlEdge) -> G::EdgeWeight,
+{
+    let doc = Parser::new(input).document()?;
+    let mut graph = G::with_capacity(doc.nodes.len(), doc.edges.len());
+    let mut node_ids = HashMap::with_capacity(doc.nodes.len());
+    for (node, position) in doc.nodes {
+        if node_ids.contains_key(&node.id) {
+            return Err(error_at(position, GraphMlErrorKind::DuplicateNode(node.id)));
+        }
+        let id = node.id.clone();
+        node_ids.insert(id, graph.add_node(node_weight(node)));
+    }
+    for pending in doc.edges {
+        if pending.directed != graph.is_directed() {
+            return Err(error_at(
+                pending.position,
+                GraphMlErrorKind::GraphKindMismatch {
+                    directed: pending.directed,
+                },
+            ));
+        }
+        let endpoint = |id: String| match node_ids.get(&id) {
+            Some(&node) => Ok(node),
+            None => Err(error_at(
+                pending.position,
+                GraphMlErrorKind::UnknownNode(id),
+            )),
+        };
+        let source = endpoint(pending.source)?;
+        let target = endpoint(pending.target)?;
+        graph.add_edge(source, target, edge_weight(pending.edge));
+    }
+    Ok(GraphMlGraph {
+        graph,
+        id: doc.id,
+        attributes: doc.attributes,
+        node_ids,
+    })
+}
+
+fn error_at((line, column): (usize, usize), kind: GraphMlErrorKind) -> GraphMlError {
+    GraphMlError { line, column, kind }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Domain {
+    Graph,
+    Node,
+    Edge,
+    All,
+}
+
+struct KeyDecl {
+    name: String,
+    domain: Domain,
+    /// `None` for extension keys whose data is skipped.
+    attr_type: Option<AttrType>,
+    default: Option<AttrValue>,
+}
+
+struct PendingEdge {
+    source: String,
+    target: String,
+    directed: bool,
+    edge: GraphMlEdge,
+    position: (usize, usize),
+}
+
+#[derive(Default)]
+struct Document {
+    id: Option<String>,
+    attributes: GraphMlAttributes,
+    nodes: Vec<(GraphMlNode, (usize, usize))>,
+    edges: Vec<PendingEdge>,
+}
+
+struct Parser<'a> {
+    reader: Reader<'a>,
+    /// Key declarations in document order.
+    keys: IndexMap<String, KeyDecl>,
+}
+
+fn attribute<'b>(attributes: &'b [(String, String)], name: &str) -> Option<&'b str> {
+    attributes
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| &value[..])
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Parser {
+            reader: Reader::new(input),
+            keys: IndexMap::new(),
+        }
+    }
+
+    fn position(&self) -> (usize, usize) {
+        self.reader.event_start()
+    }
+
+    fn eof(&self) -> GraphMlError {
+        self.reader
+            .error(GraphMlErrorKind::Xml("unexpected end of input"))
+    }
+
+    fn required<'b>(
+        &self,
+        attributes: &'b [(String, String)],
+        element: &'static str,
+        name: &'static str,
+    ) -> Result<&'b str, GraphMlError> {
+        attribute(attributes, name).ok_or_else(|| {
+            self.reader.error(GraphMlErrorKind::MissingAttribute {
+                element,
+                attribute: name,
+            })
+        })
+    }
+
+    fn end(&self, expected: &str, found: String) -> Result<(), GraphMlError> {
+        if expected == found {
+            Ok(())
+        } else {
+            Err(self.reader.error(GraphMlErrorKind::MismatchedTag {
+                expected: expected.to_string(),
+                found,
+            }))
+        }
+    }
+
+    /// Skip the content of an element whose start tag was just read.
+    fn skip(&mut self, name: &str) -> Result<(), GraphMlError> {
+        loop {
+            match self.reader.next_event()? {
+                Event::Start {
+                    name: child,
+                    empty: false,
+                    ..
+                } => self.skip(&child)?,
+                Event::End { name: found } => return self.end(name, found),
+                Event::Eof => return Err(self.eof()),
+                _ => {}
+            }
+        }
+    }
+
+    /// Collect the text of an element whose start tag was just read, skipping
+    /// any child elements.
+    fn text(&mut self, name: &str) -> Result<String, GraphMlError> {
+        let mut text = String::new();
+        loop {
+            match self.reader.next_event()? {
+                Event::Start {
+                    name: child,
+                    empty: false,
+                    ..
+                } => self.skip(&child)?,
+                Event::Text(t) => text.push_str(&t),
+                Event::End { name: found } => {
+                    self.end(name, found)?;
+                    return Ok(text);
+                }
+                Event::Eof => return Err(self.eof()),
+                _ => {}
+            }
+        }
+    }
+
+    fn document(mut self) -> Result<Document, GraphMlError> {
+        let mut doc = None;
+        loop {
+            match self.reader.next_event()? {
+                Event::Start {
+                    name, empty: false, ..
+                } if name == "graphml" => doc = Some(self.graphml()?),
+                Event::Start {
+                    name, empty: false, ..
+                } => self.skip(&name)?,
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        doc.ok_or_else(|| self.reader.error(GraphMlErrorKind::MissingGraph))
+    }
+
+    fn graphml(&mut self) -> Result<Document, GraphMlError> {
+        let mut doc = None;
+        loop {
+            match self.reader.next_event()? {
+                Event::Start {
+                    name,
+                    attributes,
+                    empty,
+                } => match &name[..] {
+                    "key" => self.key(&attributes, empty)?,
+                    "graph" if doc.is_some() => {
+                        return Err(self
+                            .reader
+                            .error(GraphMlErrorKind::Unsupported("multiple graphs")))
+                    }
+                    "graph" => doc = Some(self.graph(&attributes, empty)?),
+                    _ if empty => {}
+                    _ => self.skip(&name)?,
+                },
+                Event::End { name } => {
+                    self.end("graphml", name)?;
+                    return doc.ok_or_else(|| self.reader.error(GraphMlErrorKind::MissingGraph));
+                }
+                Event::Eof => return Err(self.eof()),
+                Event::Text(_) => {}
+            }
+        }
+    }
+
+    fn key(&mut self, attributes: &[(String, String)], empty: bool) -> Result<(), GraphMlError> {
+        let id = self.required(attributes, "key", "id")?.to_string();
+        let domain = match attribute(attributes, "for").unwrap_or("all") {
+            "graph" => Domain::Graph,
+            "node" => Domain::Node,
+            "edge" => Domain::Edge,
+            "all" => Domain::All,
+            // Keys for ports, hyperedges etc. can never be used.
+            _ => {
+                if !empty {
+                    self.skip("key")?;
+                }
+                return Ok(());
+            }
+        };
+        let name = attribute(attributes, "attr.name")
+            .unwrap_or(&id)
+            .to_string();
+        let attr_type = match attribute(attributes, "attr.type") {
+            Some(ty) => Some(AttrType::from_name(ty).ok_or_else(|| {
+                self.reader
+                    .error(GraphMlErrorKind::UnknownAttrType(ty.to_string()))
+            })?),
+            None if attribute(attributes, "yfiles.type").is_some() => None,
+            None => Some(AttrType::String),
+        };
+        let mut default = None;
+        if !empty {
+            loop {
+                match self.reader.next_event()? {
+                    Event::Start {
+                        name, empty: false, ..
+                    } if name == "default" => {
+                        let position = self.position();
+                        let text = self.text("default")?;
+                        if let Some(ty) = attr_type {
+                            default = Some(Self::decode(&id, ty, text, position)?);
+                        }
+                    }
+                    Event::Start {
+                        name, empty: false, ..
+                    } => self.skip(&name)?,
+                    Event::End { name } => {
+                        self.end("key", name)?;
+                        break;
+                    }
+                    Event::Eof => return Err(self.eof()),
+                    _ => {}
+                }
+            }
+        }
+        self.keys.insert(
+            id,
+            KeyDecl {
+                name,
+                domain,
+                attr_type,
+                default,
+            },
+        );
+        Ok(())
+    }
+
+    /// Decode `text` as a value of `ty`, reporting errors at `position`.
+    fn decode(
+        key: &str,
+        ty: AttrType,
+        text: String,
+        position: (usize, usize),
+    ) -> Result<AttrValue, GraphMlError> {
+        if ty == AttrType::String {
+            return Ok(AttrValue::String(text));
+        }
+        ty.parse(&text).ok_or_else(|| {
+            error_at(
+                position,
+                GraphMlErrorKind::InvalidValue {
+                    key: key.to_string(),
+                    value: text,
+                },
+            )
+        })
+    }
+
+    /// The defaults of all keys that apply to `domain`.
+    fn defaults(&self, domain: Domain) -> GraphMlAttributes {
+        self.keys
+            .values()
+            .filter(|key| key.domain == domain || key.domain == Domain::All)
+            .filter_map(|key| Some((key.name.clone(), key.default.clone()?)))
+            .collect()
+    }
+
+    /// Read a `<data>` element into `attributes`.
+    fn data(
+        &mut self,
+        element: &[(String, String)],
+        empty: bool,
+        attributes: &mut GraphMlAttributes,
+    ) -> Result<(), GraphMlError> {
+        let position = self.position();
+        let key = self.required(element, "data", "key")?;
+        let decl = self.keys.get(key).ok_or_else(|| {
+            self.reader
+                .error(GraphMlErrorKind::UnknownKey(key.to_string()))
+        })?;
+        let (name, attr_type) = (decl.name.clone(), decl.attr_type);
+        let text = if empty {
+            String::new()
+        } else {
+            self.text("data")?
+        };
+        if let Some(ty) = attr_type {
+            attributes.insert(name, Self::decode(key, ty, text, position)?);
+        }
+        Ok(())
+    }
+
+    fn graph(
+        &mut self,
+        attributes: &[(String, String)],
+        empty: bool,
+    ) -> Result<Document, GraphMlError> {
+        let mut doc = Document {
+            id: attribute(attributes, "id").map(str::to_string),
+            attributes: self.defaults(Domain::Graph),
+            ..Document::default()
+        };
+        let directed = attribute(attributes, "edgedefault") != Some("undirected");
+        if empty {
+            return Ok(doc);
+        }
+        loop {
+            match self.reader.next_event()? {
+                Event::Start {
+                    name,
+                    attributes,
+                    empty,
+                } => match &name[..] {
+                    "data" => self.data(&attributes, empty, &mut doc.attributes)?,
+                    "node" => {
+                        let position = self.position();
+                        let node = self.node(&attributes, empty)?;
+                        doc.nodes.push((node, position));
+                    }
+                    "edge" => {
+                        let edge = self.edge(&attributes, empty, directed)?;
+                        doc.edges.push(edge);
+                    }
+                    "hyperedge" => {
+                        return Err(self
+                            .reader
+                            .error(GraphMlErrorKind::Unsupported("hyperedges")))
+                    }
+                    _ if empty => {}
+                    _ => self.skip(&name)?,
+                },
+                Event::End { name } => {
+                    self.end("graph", name)?;
+                    return Ok(doc);
+                }
+                Event::Eof => return Err(self.eof()),
+                Event::Text(_) => {}
+            }
+        }
+    }
+
+    fn node(
+        &mut self,
+        attributes: &[(String, String)],
+        empty: bool,
+    ) -> Result<GraphMlNode, GraphMlError> {
+        let mut node = GraphMlNode {
+            id: self.required(attributes, "node", "id")?.to_string(),
+            attributes: self.defaults(Domain::Node),
+        };
+        if !empty {
+            self.children("node", &mut node.attributes)?;
+        }
+        Ok(node)
+    }
+
+    fn edge(
+        &mut self,
+        attributes: &[(String, String)],
+        empty: bool,
+        edgedefault: bool,
+    ) -> Result<PendingEdge, GraphMlError> {
+        let position = self.position();
+        let directed = match attribute(attributes, "directed") {
+            Some("true") => true,
+            Some("false") => false,
+            _ => edgedefault,
+        };
+        if attribute(attributes, "sourceport").is_some()
+            || attribute(attributes, "targetport").is_some()
+        {
+            return Err(self.reader.error(GraphMlErrorKind::Unsupported("ports")));
+        }
+        let mut pending = PendingEdge {
+            source: self.required(attributes, "edge", "source")?.to_string(),
+            target: self.required(attributes, "edge", "target")?.to_string(),
+            directed,
+            edge: GraphMlEdge {
+                id: attribute(attributes, "id").map(str::to_string),
+                attributes: self.defaults(Domain::Edge),
+            },
+            position,
+        };
+        if !empty {
+            self.children("edge", &mut pending.edge.attributes)?;
+        }
+        Ok(pending)
+    }
+
+    /// Read the `<data>` children of a node or edge.
+    fn children(
+        &mut self,
+        element: &'static str,
+        attributes: &mut GraphMlAttributes,
+    ) -> Result<(), GraphMlError> {
+        loop {
+            match self.reader.next_event()? {
+                Event::Start {
+                    name,
+                    attributes: data,
+                    empty,
+                } => match &name[..] {
+                    "data" => self.data(&data, empty, attributes)?,
+                    "graph" => {
+                        return Err(self
+                            .reader
+                            .error(GraphMlErrorKind::Unsupported("nested graphs")))
+                    }
+                    "port" => {
+                        return Err(self.reader.error(GraphMlErrorKind::Unsupported("ports")))
+                    }
+                    _ if empty => {}
+                    _ => self.skip(&name)?,
+                },
+                Event::End { name } => return self.end(element, name),
+                Event::Eof => return Err(self.eof()),
+                Event::Text(_) => {}
+            }
+        }
+    }
+}
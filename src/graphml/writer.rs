@@ -0,0 +1,199 @@
+use std::fmt;
+use std::io;
+
+use super::xml::escape;
+use super::{AttrType, AttrValue, GraphMlValue};
+use crate::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef,
+};
+
+static INDENT: &str = "  ";
+
+type ValueFn<'a, W> = Box<dyn Fn(&W) -> Option<AttrValue> + 'a>;
+
+/// A `<key>` declaration together with the closure producing its values.
+struct Key<'a, W> {
+    name: String,
+    attr_type: AttrType,
+    value: ValueFn<'a, W>,
+}
+
+/// `GraphMl` writes a graph in GraphML format.
+///
+/// Nodes get the ids `n0`, `n1`, ... after their [`NodeIndexable`] index, and
+/// edges the ids `e0`, `e1`, ... in iteration order. Weights are only written
+/// through attributes registered with [`node_attribute`](Self::node_attribute)
+/// and [`edge_attribute`](Self::edge_attribute).
+///
+/// The output is produced by the `Display` implementation or by
+/// [`to_writer`](Self::to_writer).
+///
+/// # Example
+///
+/// ```
+/// use petgraph::graph::UnGraph;
+/// use petgraph::graphml::GraphMl;
+///
+/// let graph = UnGraph::<u32, ()>::from_edges(&[(0, 1)]);
+/// let graphml = GraphMl::new(&graph)
+///     .node_attribute("weight", |w: &u32| i64::from(*w))
+///     .to_string();
+///
+/// assert!(graphml.contains(r#"<graph edgedefault="undirected">"#));
+/// assert!(graphml.contains(
+///     r#"<key id="d0" for="node" attr.name="weight" attr.type="long"/>"#
+/// ));
+/// assert!(graphml.contains(r#"<edge id="e0" source="n0" target="n1"/>"#));
+/// ```
+pub struct GraphMl<'a, G>
+where
+    G: IntoNodeReferences + IntoEdgeReferences,
+{
+    graph: G,
+    node_keys: Vec<Key<'a, G::NodeWeight>>,
+    edge_keys: Vec<Key<'a, G::EdgeWeight>>,
+}
+
+impl<'a, G> GraphMl<'a, G>
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+{
+    /// Create a `GraphMl` writer for `graph` without any attributes.
+    pub fn new(graph: G) -> Self {
+        GraphMl {
+            graph,
+            node_keys: Vec::new(),
+            edge_keys: Vec::new(),
+        }
+    }
+
+    /// Add the node attribute `name`, computed from each node weight by `value`.
+    ///
+    /// The attribute type follows from the return type of `value`, see
+    /// [`GraphMlValue`].
+    pub fn node_attribute<T, F>(mut self, name: &str, value: F) -> Self
+    where
+        T: GraphMlValue,
+        F: Fn(&G::NodeWeight) -> T + 'a,
+    {
+        self.node_keys.push(Key {
+            name: name.to_string(),
+            attr_type: T::attr_type(),
+            value: Box::new(move |w| value(w).into_attr_value()),
+        });
+        self
+    }
+
+    /// Add the edge attribute `name`, computed from each edge weight by `value`.
+    ///
+    /// The attribute type follows from the return type of `value`, see
+    /// [`GraphMlValue`].
+    pub fn edge_attribute<T, F>(mut self, name: &str, value: F) -> Self
+    where
+        T: GraphMlValue,
+        F: Fn(&G::EdgeWeight) -> T + 'a,
+    {
+        self.edge_keys.push(Key {
+            name: name.to_string(),
+            attr_type: T::attr_type(),
+            value: Box::new(move |w| value(w).into_attr_value()),
+        });
+        self
+    }
+
+    /// Write the GraphML document to `writer`.
+    pub fn to_writer<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "{}", self)
+    }
+
+    fn write_keys<W>(
+        f: &mut fmt::Formatter,
+        kind: &str,
+        offset: usize,
+        keys: &[Key<W>],
+    ) -> fmt::Result {
+        for (i, key) in keys.iter().enumerate() {
+            write!(
+                f,
+                "{}<key id=\"d{}\" for=\"{}\" attr.name=\"",
+                INDENT,
+                offset + i,
+                kind
+            )?;
+            escape(f, &key.name)?;
+            writeln!(f, "\" attr.type=\"{}\"/>", key.attr_type.as_str())?;
+        }
+        Ok(())
+    }
+
+    fn write_data<W>(
+        f: &mut fmt::Formatter,
+        offset: usize,
+        keys: &[Key<W>],
+        weight: &W,
+    ) -> fmt::Result {
+        for (i, key) in keys.iter().enumerate() {
+            if let Some(value) = (key.value)(weight) {
+                write!(f, "{0}{0}{0}<data key=\"d{1}\">", INDENT, offset + i)?;
+                escape(f, &value.to_string())?;
+                writeln!(f, "</data>")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<G> fmt::Display for GraphMl<'_, G>
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let g = self.graph;
+        writeln!(f, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(
+            f,
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" \
+             xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+             xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns \
+             http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">"
+        )?;
+        Self::write_keys(f, "node", 0, &self.node_keys)?;
+        Self::write_keys(f, "edge", self.node_keys.len(), &self.edge_keys)?;
+
+        let edgedefault = if g.is_directed() {
+            "directed"
+        } else {
+            "undirected"
+        };
+        writeln!(f, "{}<graph edgedefault=\"{}\">", INDENT, edgedefault)?;
+        for node in g.node_references() {
+            write!(f, "{0}{0}<node id=\"n{1}\"", INDENT, g.to_index(node.id()))?;
+            if self.node_keys.is_empty() {
+                writeln!(f, "/>")?;
+                continue;
+            }
+            writeln!(f, ">")?;
+            Self::write_data(f, 0, &self.node_keys, node.weight())?;
+            writeln!(f, "{0}{0}</node>", INDENT)?;
+        }
+        for (i, edge) in g.edge_references().enumerate() {
+            write!(
+                f,
+                "{0}{0}<edge id=\"e{1}\" source=\"n{2}\" target=\"n{3}\"",
+                INDENT,
+                i,
+                g.to_index(edge.source()),
+                g.to_index(edge.target()),
+            )?;
+            if self.edge_keys.is_empty() {
+                writeln!(f, "/>")?;
+                continue;
+            }
+            writeln!(f, ">")?;
+            Self::write_data(f, self.node_keys.len(), &self.edge_keys, edge.weight())?;
+            writeln!(f, "{0}{0}</edge>", INDENT)?;
+        }
+        writeln!(f, "{}</graph>", INDENT)?;
+        writeln!(f, "</graphml>")
+    }
+}
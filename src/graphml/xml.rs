@@ -0,0 +1,250 @@
+//! A minimal pull parser for the subset of XML used by GraphML files.
+//!
+//! Namespaces are not resolved: element and attribute names are reduced to
+//! their local part. Comments, processing instructions and the document type
+//! declaration are skipped.
+
+use super::{GraphMlError, GraphMlErrorKind};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum Event {
+    Start {
+        name: String,
+        attributes: Vec<(String, String)>,
+        empty: bool,
+    },
+    End {
+        name: String,
+    },
+    Text(String),
+    Eof,
+}
+
+pub(super) struct Reader<'a> {
+    input: &'a str,
+    pos: usize,
+    line: usize,
+    column: usize,
+    event_start: (usize, usize),
+}
+
+impl<'a> Reader<'a> {
+    pub(super) fn new(input: &'a str) -> Self {
+        Reader {
+            input,
+            pos: 0,
+            line: 1,
+            column: 1,
+            event_start: (1, 1),
+        }
+    }
+
+    /// The line and column where the last event started.
+    pub(super) fn event_start(&self) -> (usize, usize) {
+        self.event_start
+    }
+
+    /// Return an error located at the start of the last event.
+    pub(super) fn error(&self, kind: GraphMlErrorKind) -> GraphMlError {
+        let (line, column) = self.event_start;
+        GraphMlError { line, column, kind }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn advance(&mut self, bytes: usize) -> &'a str {
+        let s = &self.input[self.pos..self.pos + bytes];
+        for c in s.chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.pos += bytes;
+        s
+    }
+
+    /// Advance past `end`, returning everything before it.
+    fn take_until(&mut self, end: &str) -> Result<&'a str, GraphMlError> {
+        match self.rest().find(end) {
+            Some(offset) => {
+                let s = self.advance(offset);
+                self.advance(end.len());
+                Ok(s)
+            }
+            None => Err(self.error(GraphMlErrorKind::Xml("unexpected end of input"))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let n = self.rest().len() - self.rest().trim_start().len();
+        self.advance(n);
+    }
+
+    fn name(&mut self) -> Result<String, GraphMlError> {
+        let n = self
+            .rest()
+            .find(|c: char| c.is_whitespace() || matches!(c, '>' | '/' | '='))
+            .unwrap_or(self.rest().len());
+        if n == 0 {
+            return Err(self.error(GraphMlErrorKind::Xml("expected a name")));
+        }
+        let name = self.advance(n);
+        Ok(local_name(name).to_string())
+    }
+
+    /// Return the next element or non-blank text event.
+    pub(super) fn next_event(&mut self) -> Result<Event, GraphMlError> {
+        loop {
+            self.event_start = (self.line, self.column);
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Ok(Event::Eof);
+            }
+            if rest.starts_with("<!--") {
+                self.advance(4);
+                self.take_until("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.advance(9);
+                let text = self.take_until("]]>")?;
+                return Ok(Event::Text(text.to_string()));
+            } else if rest.starts_with("<?") {
+                self.take_until("?>")?;
+            } else if rest.starts_with("<!") {
+                self.skip_declaration()?;
+            } else if rest.starts_with("</") {
+                self.advance(2);
+                let name = self.name()?;
+                self.skip_whitespace();
+                if !self.rest().starts_with('>') {
+                    return Err(self.error(GraphMlErrorKind::Xml("expected `>`")));
+                }
+                self.advance(1);
+                return Ok(Event::End { name });
+            } else if rest.starts_with('<') {
+                self.advance(1);
+                return self.start_tag();
+            } else {
+                let n = rest.find('<').unwrap_or(rest.len());
+                let raw = self.advance(n);
+                if !raw.trim().is_empty() {
+                    return Ok(Event::Text(self.unescape(raw)?));
+                }
+            }
+        }
+    }
+
+    /// Skip a `<!DOCTYPE ...>` declaration, including an internal subset.
+    fn skip_declaration(&mut self) -> Result<(), GraphMlError> {
+        let mut depth = 0usize;
+        for (offset, c) in self.rest().char_indices() {
+            match c {
+                '<' => depth += 1,
+                '>' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.advance(offset + 1);
+                        return Ok(());
+                    }
+                }
+                _ => {}
+            }
+        }
+        Err(self.error(GraphMlErrorKind::Xml("unexpected end of input")))
+    }
+
+    fn start_tag(&mut self) -> Result<Event, GraphMlError> {
+        let name = self.name()?;
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.advance(2);
+                return Ok(Event::Start {
+                    name,
+                    attributes,
+                    empty: true,
+                });
+            } else if rest.starts_with('>') {
+                self.advance(1);
+                return Ok(Event::Start {
+                    name,
+                    attributes,
+                    empty: false,
+                });
+            }
+            let key = self.name()?;
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return Err(self.error(GraphMlErrorKind::Xml("expected `=`")));
+            }
+            self.advance(1);
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(q @ ('"' | '\'')) => q,
+                _ => return Err(self.error(GraphMlErrorKind::Xml("expected a quoted value"))),
+            };
+            self.advance(1);
+            let raw = self.take_until(if quote == '"' { "\"" } else { "'" })?;
+            attributes.push((key, self.unescape(raw)?));
+        }
+    }
+
+    fn unescape(&self, raw: &str) -> Result<String, GraphMlError> {
+        if !raw.contains('&') {
+            return Ok(raw.to_string());
+        }
+        let mut s = String::with_capacity(raw.len());
+        let mut rest = raw;
+        while let Some(start) = rest.find('&') {
+            s.push_str(&rest[..start]);
+            rest = &rest[start + 1..];
+            let end = rest
+                .find(';')
+                .ok_or_else(|| self.error(GraphMlErrorKind::Xml("unterminated entity")))?;
+            let c = match &rest[..end] {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                entity => match entity.strip_prefix('#') {
+                    Some(code) => match code.strip_prefix('x') {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => code.parse().ok(),
+                    }
+                    .and_then(char::from_u32),
+                    None => None,
+                },
+            };
+            s.push(c.ok_or_else(|| self.error(GraphMlErrorKind::Xml("unknown entity")))?);
+            rest = &rest[end + 1..];
+        }
+        s.push_str(rest);
+        Ok(s)
+    }
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Write `s` with the XML special characters escaped.
+pub(super) fn escape<W: std::fmt::Write>(f: &mut W, s: &str) -> std::fmt::Result {
+    for c in s.chars() {
+        match c {
+            '<' => f.write_str("&lt;")?,
+            '>' => f.write_str("&gt;")?,
+            '&' => f.write_str("&amp;")?,
+            '"' => f.write_str("&quot;")?,
+            '\'' => f.write_str("&apos;")?,
+            c => f.write_char(c)?,
+        }
+    }
+    Ok(())
+}
@@ -144,6 +144,7 @@ pub mod graph6;
 mod graph_impl;
 #[cfg(feature = "graphmap")]
 pub mod graphmap;
+pub mod graphml;
 mod iter_format;
 mod iter_utils;
 #[cfg(feature = "matrix_graph")]
@@ -1,10 +1,20 @@
 use petgraph::{
-    algo::articulation_points::articulation_points,
+    algo::articulation_points::{
+        articulation_points, biconnected_components, block_cut_tree, bridges, BlockCutNode,
+    },
+    algo::connected_components,
     graph::{NodeIndex, UnGraph},
+    visit::EdgeRef,
 };
+use rand::{ChaChaRng, SeedableRng};
 
 use std::collections::HashSet;
 
+#[cfg(feature = "stable_graph")]
+use petgraph::stable_graph::StableUnGraph;
+
+mod utils;
+
 #[test]
 fn art_single_node() {
     let mut gr = UnGraph::<&str, ()>::new_undirected();
@@ -180,3 +190,145 @@ fn art_simple2() {
 
     assert_eq!(articulation_points(&gr), set);
 }
+
+fn random_graph(rng: &mut ChaChaRng) -> UnGraph<(), ()> {
+    utils::random_graph(rng, 1..12, 2, true, |_| (), |_, _, _| Some(()))
+}
+
+// The number of connected components without some nodes and edges.
+fn components_without(graph: &UnGraph<(), ()>, node: Option<usize>, edge: Option<usize>) -> usize {
+    let mut g = graph.clone();
+    if let Some(e) = edge {
+        g.remove_edge(petgraph::graph::EdgeIndex::new(e));
+    }
+    match node {
+        Some(n) => {
+            g.remove_node(NodeIndex::new(n));
+            connected_components(&g)
+        }
+        None => connected_components(&g),
+    }
+}
+
+#[test]
+fn biconnected_random() {
+    let mut rng = ChaChaRng::from_seed([14; 32]);
+    for _ in 0..300 {
+        let graph = random_graph(&mut rng);
+        let n = graph.node_count();
+        let components = connected_components(&graph);
+
+        // Removing an articulation point splits its connected component.
+        let cuts = articulation_points(&graph);
+        for v in 0..n {
+            let isolated = graph.neighbors(NodeIndex::new(v)).all(|u| u.index() == v);
+            let expected = components_without(&graph, Some(v), None) + isolated as usize;
+            assert_eq!(cuts.contains(&NodeIndex::new(v)), expected > components);
+        }
+
+        let bridge_ids: HashSet<_> = bridges(&graph).iter().map(|e| e.id()).collect();
+        for edge in graph.edge_references() {
+            let expected = components_without(&graph, None, Some(edge.id().index())) > components;
+            assert_eq!(bridge_ids.contains(&edge.id()), expected);
+        }
+
+        // Every edge that is not a self loop is in exactly one block.
+        let blocks = biconnected_components(&graph);
+        let mut block_of = vec![None; graph.edge_count()];
+        for (i, block) in blocks.iter().enumerate() {
+            assert!(!block.is_empty());
+            for edge in block {
+                assert!(block_of[edge.id().index()].replace(i).is_none());
+            }
+            if block.len() == 1 && block[0].source() != block[0].target() {
+                // Parallel edges are a block together.
+                assert!(bridge_ids.contains(&block[0].id()));
+            }
+            // Removing a node leaves the other edges of a block connected.
+            let nodes: HashSet<_> = block
+                .iter()
+                .flat_map(|e| vec![e.source(), e.target()])
+                .collect();
+            for &removed in &nodes {
+                let mut sub = UnGraph::<(), ()>::with_capacity(n, 0);
+                for _ in 0..n {
+                    sub.add_node(());
+                }
+                for edge in block {
+                    if edge.source() != removed && edge.target() != removed {
+                        sub.add_edge(edge.source(), edge.target(), ());
+                    }
+                }
+                let remaining: HashSet<_> = nodes.iter().filter(|&&v| v != removed).collect();
+                assert!(
+                    connected_components(&sub) == n - remaining.len() + 1 || remaining.len() == 1
+                );
+            }
+        }
+        for edge in graph.edge_references() {
+            assert_eq!(
+                block_of[edge.id().index()].is_some(),
+                edge.source() != edge.target()
+            );
+        }
+
+        // The block-cut tree is a forest with a tree for every connected
+        // component with edges.
+        let tree = block_cut_tree(&graph);
+        assert_eq!(tree.node_count(), blocks.len() + cuts.len());
+        let with_edges = (0..n)
+            .filter(|&v| graph.neighbors(NodeIndex::new(v)).any(|u| u.index() != v))
+            .count();
+        let trees = components - (n - with_edges);
+        assert_eq!(tree.edge_count() + trees, tree.node_count());
+        assert_eq!(connected_components(&tree), trees);
+        for node in tree.node_indices() {
+            if let BlockCutNode::Cut(v) = tree[node] {
+                assert!(cuts.contains(&v));
+                assert!(tree.neighbors(node).count() >= 2);
+            }
+        }
+    }
+}
+
+#[test]
+fn bridges_parallel_edges() {
+    // The parallel edges 1 - 2 are no bridge.
+    let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 2), (2, 3), (3, 3)]);
+    let bridges: Vec<_> = bridges(&graph).iter().map(|e| e.id().index()).collect();
+    assert_eq!(bridges, vec![3, 0]);
+    let mut blocks: Vec<Vec<_>> = biconnected_components(&graph)
+        .iter()
+        .map(|b| {
+            let mut ids: Vec<_> = b.iter().map(|e| e.id().index()).collect();
+            ids.sort();
+            ids
+        })
+        .collect();
+    blocks.sort();
+    assert_eq!(blocks, vec![vec![0], vec![1, 2], vec![3]]);
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn biconnected_stable_graph() {
+    let mut graph = StableUnGraph::<(), ()>::default();
+    let removed = graph.add_node(());
+    let a = graph.add_node(());
+    let b = graph.add_node(());
+    let c = graph.add_node(());
+    graph.add_edge(a, b, ());
+    let bc = graph.add_edge(b, c, ());
+    graph.add_edge(removed, c, ());
+    graph.remove_node(removed);
+
+    let cuts: Vec<_> = articulation_points(&graph).into_iter().collect();
+    assert_eq!(cuts, vec![b]);
+    assert_eq!(bridges(&graph).len(), 2);
+    assert_eq!(biconnected_components(&graph).len(), 2);
+    let tree = block_cut_tree(&graph);
+    assert_eq!(tree.node_count(), 3);
+    assert!(tree
+        .node_weights()
+        .any(|n| matches!(n, BlockCutNode::Block(edges) if edges[0].id() == bc)));
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "rayon")]
+
+use petgraph::algo::bfs::parallel_bfs;
+use petgraph::csr::Csr;
+use petgraph::prelude::*;
+use petgraph::visit::{Bfs, GraphProp, IntoNeighbors, NodeCompactIndexable};
+use petgraph::EdgeType;
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+mod utils;
+
+// A random graph, sometimes dense enough for the search to take bottom-up
+// steps.
+fn random_graph<Ty: EdgeType>(rng: &mut ChaChaRng) -> Graph<(), (), Ty> {
+    utils::random_graph(rng, 1..300, 6, true, |_| (), |_, _, _| Some(()))
+}
+
+fn to_csr<Ty: EdgeType>(graph: &Graph<(), (), Ty>) -> Csr<(), (), Ty> {
+    let mut csr = Csr::with_nodes(graph.node_count());
+    for edge in graph.edge_references() {
+        let (a, b) = (edge.source().index() as u32, edge.target().index() as u32);
+        csr.add_edge(a, b, ());
+    }
+    csr
+}
+
+// The depths of the nodes, by a sequential search.
+fn bfs_depths<G>(graph: G, start: G::NodeId) -> Vec<Option<usize>>
+where
+    G: IntoNeighbors + NodeCompactIndexable + GraphProp + petgraph::visit::Visitable,
+{
+    let mut depths = vec![None; graph.node_bound()];
+    depths[graph.to_index(start)] = Some(0);
+    let mut bfs = Bfs::new(graph, start);
+    while let Some(u) = bfs.next(graph) {
+        let depth = depths[graph.to_index(u)].unwrap();
+        for v in graph.neighbors(u) {
+            let v = graph.to_index(v);
+            if depths[v].is_none() {
+                depths[v] = Some(depth + 1);
+            }
+        }
+    }
+    depths
+}
+
+fn with_threads(f: impl FnOnce() + Send) {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap();
+    pool.install(f);
+}
+
+#[test]
+fn parallel_bfs_random() {
+    with_threads(|| {
+        let mut rng = ChaChaRng::from_seed([60; 32]);
+        for _ in 0..100 {
+            let graph = random_graph::<Directed>(&mut rng);
+            let start = NodeIndex::new(rng.gen_range(0, graph.node_count()));
+            let expected = bfs_depths(&graph, start);
+            assert_eq!(parallel_bfs(&graph, start), expected);
+            assert_eq!(
+                parallel_bfs(&to_csr(&graph), start.index() as u32),
+                expected
+            );
+
+            let graph = random_graph::<Undirected>(&mut rng);
+            let start = NodeIndex::new(rng.gen_range(0, graph.node_count()));
+            let expected = bfs_depths(&graph, start);
+            assert_eq!(parallel_bfs(&graph, start), expected);
+            assert_eq!(
+                parallel_bfs(&to_csr(&graph), start.index() as u32),
+                expected
+            );
+        }
+    });
+}
+
+#[test]
+fn parallel_bfs_path() {
+    // A long path, with a dense part in its middle.
+    let mut graph = DiGraph::<(), ()>::new();
+    let nodes: Vec<_> = (0..1000).map(|_| graph.add_node(())).collect();
+    for pair in nodes.windows(2) {
+        graph.add_edge(pair[0], pair[1], ());
+    }
+    for &a in &nodes[400..450] {
+        for &b in &nodes[400..450] {
+            graph.add_edge(a, b, ());
+        }
+    }
+    let depths = parallel_bfs(&graph, nodes[0]);
+    assert_eq!(depths, bfs_depths(&graph, nodes[0]));
+    assert_eq!(depths[999], Some(400 + 1 + 550));
+    assert_eq!(parallel_bfs(&graph, nodes[999])[..999], [None; 999]);
+}
@@ -0,0 +1,430 @@
+use petgraph::algo::canonical::{
+    automorphism_group, automorphism_group_matching, canonical_graph6, canonical_labeling,
+    canonical_labeling_by_key, weisfeiler_lehman_hash,
+};
+use petgraph::algo::{is_isomorphic, is_isomorphic_matching};
+use petgraph::prelude::*;
+use petgraph::EdgeType;
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+use std::collections::HashSet;
+
+#[cfg(feature = "stable_graph")]
+use petgraph::stable_graph::StableUnGraph;
+
+mod utils;
+
+fn random_graph<Ty: EdgeType>(rng: &mut ChaChaRng, max_nodes: usize) -> Graph<u8, u8, Ty> {
+    utils::random_graph(
+        rng,
+        0..max_nodes + 1,
+        2,
+        true,
+        |rng| rng.gen_range(0, 2),
+        |rng, a, b| {
+            if a != b || rng.gen_range(0, 4) == 0 {
+                Some(rng.gen_range(0, 2))
+            } else {
+                None
+            }
+        },
+    )
+}
+
+// The same graph with its nodes and edges in random order.
+fn shuffled<Ty: EdgeType>(rng: &mut ChaChaRng, graph: &Graph<u8, u8, Ty>) -> Graph<u8, u8, Ty> {
+    let n = graph.node_count();
+    let mut permutation: Vec<usize> = (0..n).collect();
+    rng.shuffle(&mut permutation);
+    let mut nodes = vec![0; n];
+    for (v, &p) in permutation.iter().enumerate() {
+        nodes[p] = graph[NodeIndex::new(v)];
+    }
+    let mut edges: Vec<_> = graph
+        .edge_references()
+        .map(|e| {
+            (
+                permutation[e.source().index()],
+                permutation[e.target().index()],
+                *e.weight(),
+            )
+        })
+        .collect();
+    rng.shuffle(&mut edges);
+    let mut result = Graph::with_capacity(n, edges.len());
+    for weight in nodes {
+        result.add_node(weight);
+    }
+    for (a, b, weight) in edges {
+        if Ty::is_directed() || rng.gen() {
+            result.add_edge(NodeIndex::new(a), NodeIndex::new(b), weight);
+        } else {
+            result.add_edge(NodeIndex::new(b), NodeIndex::new(a), weight);
+        }
+    }
+    result
+}
+
+// The edges of the graph with its nodes renumbered by `order`, and the node
+// weights in that order.
+fn relabeled<Ty: EdgeType>(
+    graph: &Graph<u8, u8, Ty>,
+    order: &[NodeIndex],
+) -> (Vec<u8>, Vec<(usize, usize)>) {
+    let mut label = vec![0; graph.node_count()];
+    for (i, n) in order.iter().enumerate() {
+        label[n.index()] = i;
+    }
+    let mut edges: Vec<_> = graph
+        .edge_references()
+        .map(|e| {
+            let (a, b) = (label[e.source().index()], label[e.target().index()]);
+            if Ty::is_directed() {
+                (a, b)
+            } else {
+                (a.min(b), a.max(b))
+            }
+        })
+        .collect();
+    edges.sort();
+    edges.dedup();
+    (order.iter().map(|&n| graph[n]).collect(), edges)
+}
+
+fn check_canonical<Ty: EdgeType>(seed: u8) {
+    let mut rng = ChaChaRng::from_seed([seed; 32]);
+    for _ in 0..300 {
+        let graph = random_graph::<Ty>(&mut rng, 9);
+        let other = shuffled(&mut rng, &graph);
+
+        let order = canonical_labeling(&graph);
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(sorted, graph.node_indices().collect::<Vec<_>>());
+        assert_eq!(
+            relabeled(&graph, &order).1,
+            relabeled(&other, &canonical_labeling(&other)).1
+        );
+        assert_eq!(canonical_graph6(&graph), canonical_graph6(&other));
+
+        let order = canonical_labeling_by_key(&graph, |n| graph[n]);
+        assert_eq!(
+            relabeled(&graph, &order),
+            relabeled(&other, &canonical_labeling_by_key(&other, |n| other[n]))
+        );
+
+        let hash = |g: &Graph<u8, u8, Ty>| weisfeiler_lehman_hash(g, |n| g[n], |e| *e.weight(), 3);
+        assert_eq!(hash(&graph), hash(&other));
+    }
+}
+
+#[test]
+fn canonical_isomorphic_undirected() {
+    check_canonical::<Undirected>(20);
+}
+
+#[test]
+fn canonical_isomorphic_directed() {
+    check_canonical::<Directed>(21);
+}
+
+// Without loops, graphs have the same canonical form exactly if they are
+// isomorphic.
+fn check_classes<Ty: EdgeType>(seed: u8) {
+    let mut rng = ChaChaRng::from_seed([seed; 32]);
+    let graphs: Vec<_> = (0..150)
+        .map(|_| {
+            let mut graph = random_graph::<Ty>(&mut rng, 6);
+            graph.retain_edges(|g, e| {
+                let (a, b) = g.edge_endpoints(e).unwrap();
+                a != b
+            });
+            graph
+        })
+        .collect();
+    let forms: Vec<_> = graphs.iter().map(canonical_graph6).collect();
+    let keyed: Vec<_> = graphs
+        .iter()
+        .map(|g| relabeled(g, &canonical_labeling_by_key(g, |n| g[n])))
+        .collect();
+    for i in 0..graphs.len() {
+        for j in 0..i {
+            let (a, b) = (&graphs[i], &graphs[j]);
+            let mut simple_a = a.map(|_, &w| w, |_, _| ());
+            let mut simple_b = b.map(|_, &w| w, |_, _| ());
+            for g in [&mut simple_a, &mut simple_b] {
+                let mut edges: Vec<_> = g
+                    .edge_references()
+                    .map(|e| {
+                        let (a, b) = (e.source(), e.target());
+                        if Ty::is_directed() {
+                            (a, b)
+                        } else {
+                            (a.min(b), a.max(b))
+                        }
+                    })
+                    .collect();
+                edges.sort();
+                edges.dedup();
+                g.clear_edges();
+                g.extend_with_edges(edges);
+            }
+            assert_eq!(forms[i] == forms[j], is_isomorphic(&simple_a, &simple_b));
+            assert_eq!(
+                keyed[i] == keyed[j],
+                is_isomorphic_matching(&simple_a, &simple_b, |x, y| x == y, |_, _| true)
+            );
+        }
+    }
+}
+
+#[test]
+fn canonical_classes_undirected() {
+    check_classes::<Undirected>(22);
+}
+
+#[test]
+fn canonical_classes_directed() {
+    check_classes::<Directed>(23);
+}
+
+#[test]
+fn canonical_symmetric_graphs() {
+    // Graphs with large automorphism groups need the pruning to finish.
+    let empty = UnGraph::<(), ()>::from_edges(std::iter::empty::<(u32, u32)>());
+    assert_eq!(canonical_labeling(&empty), vec![]);
+    assert_eq!(canonical_graph6(&empty), "?");
+
+    let mut independent = UnGraph::<(), ()>::default();
+    for _ in 0..40 {
+        independent.add_node(());
+    }
+    assert_eq!(canonical_labeling(&independent).len(), 40);
+
+    let mut complete = UnGraph::<(), ()>::default();
+    let nodes: Vec<_> = (0..20).map(|_| complete.add_node(())).collect();
+    for (i, &a) in nodes.iter().enumerate() {
+        for &b in &nodes[..i] {
+            complete.add_edge(a, b, ());
+        }
+    }
+    assert_eq!(
+        canonical_graph6(&complete),
+        petgraph::graph6::get_graph6_representation(&complete)
+    );
+
+    // The hypercube of dimension 6.
+    let cube = UnGraph::<(), ()>::from_edges((0..64u32).flat_map(|v| {
+        (0..6)
+            .map(move |b| (v, v ^ (1 << b)))
+            .filter(|(a, b)| a < b)
+    }));
+    // Numbered by 5 v mod 64 instead of v.
+    let shuffled = UnGraph::<(), ()>::from_edges(cube.edge_references().map(|e| {
+        (
+            e.source().index() as u32 * 5 % 64,
+            e.target().index() as u32 * 5 % 64,
+        )
+    }));
+    assert_eq!(canonical_graph6(&cube), canonical_graph6(&shuffled));
+
+    // Two triangles and a hexagon are both 2-regular on six nodes.
+    let triangles = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)]);
+    let hexagon = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]);
+    assert_ne!(canonical_graph6(&triangles), canonical_graph6(&hexagon));
+    let hash = |g: &UnGraph<(), ()>| weisfeiler_lehman_hash(g, |_| (), |_| (), 3);
+    assert_eq!(hash(&triangles), hash(&hexagon));
+}
+
+#[test]
+fn weisfeiler_lehman_labels() {
+    let path = DiGraph::<u8, u8>::from_edges([(0, 1, 0), (1, 2, 0)]);
+    let mut reversed = path.clone();
+    reversed.reverse();
+    let mut relabeled = path.clone();
+    relabeled[NodeIndex::new(2)] = 1;
+    let mut reweighted = path.clone();
+    reweighted[EdgeIndex::new(1)] = 1;
+
+    let hash = |g: &DiGraph<u8, u8>| weisfeiler_lehman_hash(g, |n| g[n], |e| *e.weight(), 2);
+    // A directed path is isomorphic to its reverse.
+    assert_eq!(hash(&path), hash(&reversed));
+    assert_ne!(hash(&path), hash(&relabeled));
+    assert_ne!(hash(&path), hash(&reweighted));
+    assert_ne!(
+        hash(&DiGraph::from_edges([(0, 1, 0), (0, 2, 0)])),
+        hash(&DiGraph::from_edges([(0, 1, 0), (2, 1, 0)]))
+    );
+    // More iterations tell more graphs apart.
+    let path = UnGraph::<(), ()>::from_edges((0..7).map(|i| (i, i + 1)));
+    let mut split = UnGraph::<(), ()>::from_edges((0..5).map(|i| (i, i + 1)));
+    split.extend_with_edges([(6, 7)]);
+    let hash = |g: &UnGraph<(), ()>, k| weisfeiler_lehman_hash(g, |_| (), |_| (), k);
+    assert_eq!(hash(&path, 0), hash(&split, 0));
+    assert_ne!(hash(&path, 1), hash(&split, 1));
+}
+
+// All permutations of `0..n`.
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![vec![]];
+    }
+    let mut result = Vec::new();
+    for p in permutations(n - 1) {
+        for i in 0..n {
+            let mut q = p.clone();
+            q.insert(i, n - 1);
+            result.push(q);
+        }
+    }
+    result
+}
+
+// Whether `p` maps the graph to itself, and the weights too if `weights`.
+fn is_automorphism<Ty: EdgeType>(graph: &Graph<u8, u8, Ty>, p: &[usize], weights: bool) -> bool {
+    let edge = |a: usize, b: usize| {
+        graph
+            .find_edge(NodeIndex::new(a), NodeIndex::new(b))
+            .map(|e| graph[e])
+    };
+    let n = graph.node_count();
+    (0..n).all(|a| !weights || graph[NodeIndex::new(a)] == graph[NodeIndex::new(p[a])])
+        && (0..n).all(|a| {
+            (0..n).all(|b| match (edge(a, b), edge(p[a], p[b])) {
+                (Some(x), Some(y)) => !weights || x == y,
+                (None, None) => true,
+                _ => false,
+            })
+        })
+}
+
+// The group generated by `generators`.
+fn closure(n: usize, generators: &[Vec<usize>]) -> HashSet<Vec<usize>> {
+    let identity: Vec<usize> = (0..n).collect();
+    let mut group = HashSet::new();
+    group.insert(identity.clone());
+    let mut stack = vec![identity];
+    while let Some(p) = stack.pop() {
+        for g in generators {
+            let q: Vec<usize> = p.iter().map(|&v| g[v]).collect();
+            if group.insert(q.clone()) {
+                stack.push(q);
+            }
+        }
+    }
+    group
+}
+
+fn check_automorphisms<Ty: EdgeType>(seed: u8) {
+    let mut rng = ChaChaRng::from_seed([seed; 32]);
+    for _ in 0..200 {
+        let mut graph = random_graph::<Ty>(&mut rng, 6);
+        // Without parallel edges, the weight of an edge is well defined.
+        let mut seen = HashSet::new();
+        graph.retain_edges(|g, e| {
+            let (a, b) = g.edge_endpoints(e).unwrap();
+            seen.insert(if Ty::is_directed() {
+                (a, b)
+            } else {
+                (a.min(b), a.max(b))
+            })
+        });
+        let n = graph.node_count();
+        for &weights in &[false, true] {
+            let group = if weights {
+                automorphism_group_matching(&graph, |a, b| a == b, |a, b| a == b)
+            } else {
+                automorphism_group(&graph)
+            };
+            let expected: HashSet<_> = permutations(n)
+                .into_iter()
+                .filter(|p| is_automorphism(&graph, p, weights))
+                .collect();
+            for g in group.generators() {
+                assert!(expected.contains(g));
+            }
+            assert_eq!(closure(n, group.generators()), expected);
+            assert_eq!(group.order(), Some(expected.len() as u128));
+            for v in 0..n {
+                let smallest = expected.iter().map(|p| p[v]).min().unwrap();
+                assert_eq!(group.orbits()[v], smallest);
+            }
+        }
+    }
+}
+
+#[test]
+fn automorphisms_undirected() {
+    check_automorphisms::<Undirected>(24);
+}
+
+#[test]
+fn automorphisms_directed() {
+    check_automorphisms::<Directed>(25);
+}
+
+#[test]
+fn automorphisms_large_groups() {
+    let mut independent = UnGraph::<(), ()>::default();
+    for _ in 0..30 {
+        independent.add_node(());
+    }
+    let group = automorphism_group(&independent);
+    assert_eq!(group.order(), Some((1..=30u128).product()));
+    assert_eq!(group.orbits(), vec![0; 30]);
+    for _ in 0..5 {
+        independent.add_node(());
+    }
+    assert_eq!(automorphism_group(&independent).order(), None);
+
+    // The hypercube of dimension 6 has 2^6 6! automorphisms.
+    let cube = UnGraph::<(), ()>::from_edges((0..64u32).flat_map(|v| {
+        (0..6)
+            .map(move |b| (v, v ^ (1 << b)))
+            .filter(|(a, b)| a < b)
+    }));
+    assert_eq!(automorphism_group(&cube).order(), Some(64 * 720));
+
+    // The Petersen graph has 120 automorphisms.
+    let petersen = UnGraph::<(), ()>::from_edges([
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 4),
+        (4, 0),
+        (0, 5),
+        (1, 6),
+        (2, 7),
+        (3, 8),
+        (4, 9),
+        (5, 7),
+        (7, 9),
+        (9, 6),
+        (6, 8),
+        (8, 5),
+    ]);
+    let group = automorphism_group(&petersen);
+    assert_eq!(group.order(), Some(120));
+    assert!(group.generators().len() <= 10);
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn canonical_stable_graph() {
+    let mut graph = StableUnGraph::<(), ()>::default();
+    let removed = graph.add_node(());
+    let nodes: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+    graph.add_edge(nodes[0], nodes[1], ());
+    graph.add_edge(nodes[0], nodes[2], ());
+    graph.add_edge(nodes[0], nodes[3], ());
+    graph.remove_node(removed);
+
+    let order = canonical_labeling(&graph);
+    assert_eq!(order.len(), 4);
+    let star = UnGraph::<(), ()>::from_edges([(3, 0), (3, 1), (3, 2)]);
+    assert_eq!(canonical_graph6(&graph), canonical_graph6(&star));
+    assert_eq!(
+        weisfeiler_lehman_hash(&graph, |_| (), |_| (), 3),
+        weisfeiler_lehman_hash(&star, |_| (), |_| (), 3)
+    );
+}
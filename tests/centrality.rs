@@ -0,0 +1,305 @@
+use petgraph::algo::{
+    betweenness_centrality, closeness_centrality, eigenvector_centrality, harmonic_centrality,
+    katz_centrality, weighted_betweenness_centrality,
+};
+use petgraph::prelude::*;
+use petgraph::EdgeType;
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+#[cfg(feature = "rayon")]
+use petgraph::algo::centrality::{
+    parallel_betweenness_centrality, parallel_closeness_centrality, parallel_harmonic_centrality,
+    parallel_weighted_betweenness_centrality,
+};
+
+#[cfg(feature = "stable_graph")]
+use petgraph::{stable_graph::StableUnGraph, visit::NodeIndexable};
+
+mod utils;
+
+fn random_graph<Ty: EdgeType>(rng: &mut ChaChaRng) -> Graph<(), u32, Ty> {
+    utils::random_graph(
+        rng,
+        1..9,
+        3,
+        false,
+        |_| (),
+        |rng, a, b| {
+            if a != b {
+                Some(rng.gen_range(1, 4))
+            } else {
+                None
+            }
+        },
+    )
+}
+
+// Distances and numbers of shortest paths between all pairs of nodes.
+fn all_pairs<Ty: EdgeType>(
+    graph: &Graph<(), u32, Ty>,
+    weighted: bool,
+) -> (Vec<Vec<Option<u32>>>, Vec<Vec<f64>>) {
+    let n = graph.node_count();
+    let mut distance = vec![vec![None; n]; n];
+    for (i, row) in distance.iter_mut().enumerate() {
+        row[i] = Some(0);
+    }
+    for edge in graph.edge_references() {
+        let w = if weighted { *edge.weight() } else { 1 };
+        let (a, b) = (edge.source().index(), edge.target().index());
+        distance[a][b] = Some(w);
+        if !graph.is_directed() {
+            distance[b][a] = Some(w);
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            for j in 0..n {
+                if let (Some(a), Some(b)) = (distance[i][k], distance[k][j]) {
+                    if distance[i][j].map_or(true, |d| a + b < d) {
+                        distance[i][j] = Some(a + b);
+                    }
+                }
+            }
+        }
+    }
+
+    // Count the paths in order of distance, every edge on a shortest path
+    // extends the paths to its source.
+    let mut count = vec![vec![0.; n]; n];
+    for s in 0..n {
+        let mut order: Vec<_> = (0..n).filter(|&v| distance[s][v].is_some()).collect();
+        order.sort_by_key(|&v| distance[s][v]);
+        count[s][s] = 1.;
+        for &v in &order[1..] {
+            for edge in graph.edges_directed(NodeIndex::new(v), Incoming) {
+                let u = if edge.target().index() == v {
+                    edge.source().index()
+                } else {
+                    edge.target().index()
+                };
+                let w = if weighted { *edge.weight() } else { 1 };
+                if distance[s][u].map(|d| d + w) == distance[s][v] {
+                    count[s][v] += count[s][u];
+                }
+            }
+        }
+    }
+    (distance, count)
+}
+
+fn brute_force_betweenness<Ty: EdgeType>(
+    graph: &Graph<(), u32, Ty>,
+    weighted: bool,
+    endpoints: bool,
+) -> Vec<f64> {
+    let n = graph.node_count();
+    let (distance, count) = all_pairs(graph, weighted);
+    let mut betweenness = vec![0.; n];
+    for s in 0..n {
+        for t in 0..n {
+            let d = match distance[s][t] {
+                Some(d) if s != t => d,
+                _ => continue,
+            };
+            for v in 0..n {
+                if v == s || v == t {
+                    if endpoints {
+                        betweenness[v] += 1.;
+                    }
+                } else if let (Some(a), Some(b)) = (distance[s][v], distance[v][t]) {
+                    if a + b == d {
+                        betweenness[v] += count[s][v] * count[v][t] / count[s][t];
+                    }
+                }
+            }
+        }
+    }
+    if !graph.is_directed() {
+        for b in &mut betweenness {
+            *b /= 2.;
+        }
+    }
+    betweenness
+}
+
+fn assert_close(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+    }
+}
+
+fn check_betweenness<Ty: EdgeType + Sync>(graph: &Graph<(), u32, Ty>) {
+    let n = graph.node_count();
+    for endpoints in [false, true] {
+        let unweighted = brute_force_betweenness(graph, false, endpoints);
+        let weighted = brute_force_betweenness(graph, true, endpoints);
+        assert_close(
+            &betweenness_centrality(graph, endpoints, false),
+            &unweighted,
+        );
+        assert_close(
+            &weighted_betweenness_centrality(graph, |e| *e.weight(), endpoints, false),
+            &weighted,
+        );
+
+        let pairs = if endpoints {
+            n * (n - 1)
+        } else {
+            (n - 1) * n.saturating_sub(2)
+        };
+        let scale = if pairs == 0 {
+            1.
+        } else if graph.is_directed() {
+            1. / pairs as f64
+        } else {
+            2. / pairs as f64
+        };
+        let normalized: Vec<_> = unweighted.iter().map(|b| b * scale).collect();
+        assert_close(&betweenness_centrality(graph, endpoints, true), &normalized);
+
+        #[cfg(feature = "rayon")]
+        {
+            assert_close(
+                &parallel_betweenness_centrality(graph, endpoints, false),
+                &unweighted,
+            );
+            assert_close(
+                &parallel_weighted_betweenness_centrality(graph, |e| *e.weight(), endpoints, false),
+                &weighted,
+            );
+        }
+    }
+}
+
+#[test]
+fn betweenness_random() {
+    let mut rng = ChaChaRng::from_seed([9; 32]);
+    for _ in 0..200 {
+        check_betweenness(&random_graph::<Directed>(&mut rng));
+        check_betweenness(&random_graph::<Undirected>(&mut rng));
+    }
+}
+
+#[test]
+fn betweenness_star() {
+    let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3), (0, 4)]);
+    let centrality: Vec<f64> = betweenness_centrality(&graph, false, false);
+    assert_eq!(centrality, vec![6., 0., 0., 0., 0.]);
+    let centrality: Vec<f64> = betweenness_centrality(&graph, false, true);
+    assert_eq!(centrality, vec![1., 0., 0., 0., 0.]);
+    let centrality: Vec<f32> = betweenness_centrality(&graph, true, false);
+    assert_eq!(centrality, vec![10., 4., 4., 4., 4.]);
+}
+
+fn check_distances<Ty: EdgeType + Sync>(graph: &Graph<(), u32, Ty>) {
+    let n = graph.node_count();
+    let (distance, _) = all_pairs(graph, true);
+    let mut closeness = vec![0.; n];
+    let mut harmonic = vec![0.; n];
+    for s in 0..n {
+        let reached: Vec<f64> = distance[s]
+            .iter()
+            .flatten()
+            .filter(|&&d| d > 0)
+            .map(|&d| d as f64)
+            .collect();
+        let total: f64 = reached.iter().sum();
+        if total > 0. {
+            let r = reached.len() as f64;
+            closeness[s] = r / total * r / (n - 1) as f64;
+        }
+        harmonic[s] = reached.iter().map(|d| 1. / d).sum();
+    }
+    let cost = |e: petgraph::graph::EdgeReference<u32>| *e.weight() as f64;
+    assert_close(&closeness_centrality(graph, cost), &closeness);
+    assert_close(&harmonic_centrality(graph, cost), &harmonic);
+
+    #[cfg(feature = "rayon")]
+    {
+        assert_close(&parallel_closeness_centrality(graph, cost), &closeness);
+        assert_close(&parallel_harmonic_centrality(graph, cost), &harmonic);
+    }
+}
+
+#[test]
+fn closeness_and_harmonic_random() {
+    let mut rng = ChaChaRng::from_seed([10; 32]);
+    for _ in 0..200 {
+        check_distances(&random_graph::<Directed>(&mut rng));
+        check_distances(&random_graph::<Undirected>(&mut rng));
+    }
+}
+
+#[test]
+fn eigenvector_satisfies_eigen_equation() {
+    // Two triangles joined by a path.
+    let graph = UnGraph::<(), f64>::from_edges([
+        (0, 1, 1.),
+        (1, 2, 1.),
+        (2, 0, 1.),
+        (2, 3, 2.),
+        (3, 4, 1.),
+        (4, 5, 1.),
+        (5, 6, 1.),
+        (6, 4, 1.),
+    ]);
+    let centrality = eigenvector_centrality(&graph, |e| *e.weight(), 1000, Some(1e-12));
+    assert!((centrality.iter().sum::<f64>() - 1.).abs() < 1e-9);
+
+    let mut product = vec![0.; graph.node_count()];
+    for edge in graph.edge_references() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        product[a] += edge.weight() * centrality[b];
+        product[b] += edge.weight() * centrality[a];
+    }
+    let eigenvalue = product[0] / centrality[0];
+    for (p, c) in product.iter().zip(&centrality) {
+        assert!((p - eigenvalue * c).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn katz_satisfies_fixed_point() {
+    let mut rng = ChaChaRng::from_seed([11; 32]);
+    for _ in 0..50 {
+        let graph = random_graph::<Directed>(&mut rng);
+        let alpha = 0.1;
+        let centrality = katz_centrality(&graph, |_| 1.0, alpha, 1.0, 1000, Some(1e-14));
+        assert!((centrality.iter().sum::<f64>() - 1.).abs() < 1e-9);
+
+        // `x = alpha A^T x + beta c` for some scaling factor `c`.
+        let mut incoming = vec![0.; graph.node_count()];
+        for edge in graph.edge_references() {
+            incoming[edge.target().index()] += centrality[edge.source().index()];
+        }
+        let rest: Vec<f64> = centrality
+            .iter()
+            .zip(&incoming)
+            .map(|(x, i)| x - alpha * i)
+            .collect();
+        for r in &rest {
+            assert!((r - rest[0]).abs() < 1e-9);
+        }
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn centrality_stable_graph() {
+    let mut graph = StableUnGraph::<(), ()>::default();
+    let a = graph.add_node(());
+    let removed = graph.add_node(());
+    let b = graph.add_node(());
+    let c = graph.add_node(());
+    graph.add_edge(a, b, ());
+    graph.add_edge(b, c, ());
+    graph.remove_node(removed);
+
+    let centrality: Vec<f64> = betweenness_centrality(&graph, false, false);
+    assert_eq!(centrality.len(), graph.node_bound());
+    assert_eq!(centrality, vec![0., 0., 1., 0.]);
+    let closeness = closeness_centrality(&graph, |_| 1.0);
+    assert_eq!(closeness, vec![2. / 3., 0., 1., 2. / 3.]);
+}
@@ -0,0 +1,178 @@
+use petgraph::algo::{label_propagation, leiden, louvain, modularity};
+use petgraph::prelude::*;
+use petgraph::unionfind::UnionFind;
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+#[cfg(feature = "stable_graph")]
+use petgraph::{stable_graph::StableUnGraph, visit::NodeIndexable};
+
+fn random_graph(rng: &mut ChaChaRng) -> UnGraph<(), f64> {
+    let nodes = rng.gen_range(1, 30);
+    let mut graph = Graph::with_capacity(nodes, 0);
+    for _ in 0..nodes {
+        graph.add_node(());
+    }
+    // Denser within blocks of ten nodes.
+    for _ in 0..rng.gen_range(0, 4 * nodes) {
+        let a = rng.gen_range(0, nodes);
+        let b = if rng.gen_range(0, 4) == 0 {
+            rng.gen_range(0, nodes)
+        } else {
+            (a / 10 * 10 + rng.gen_range(0, 10)).min(nodes - 1)
+        };
+        graph.add_edge(
+            NodeIndex::new(a),
+            NodeIndex::new(b),
+            rng.gen_range(1, 4) as f64,
+        );
+    }
+    graph
+}
+
+// Every label is the smallest index of its community.
+fn assert_labeling(labels: &[usize]) {
+    for (i, &l) in labels.iter().enumerate() {
+        assert!(l <= i);
+        assert_eq!(labels[l], l);
+    }
+}
+
+fn brute_force_modularity(graph: &UnGraph<(), f64>, labels: &[usize], resolution: f64) -> f64 {
+    let n = graph.node_count();
+    let mut adjacency = vec![vec![0.; n]; n];
+    let mut degree = vec![0.; n];
+    for edge in graph.edge_references() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        adjacency[a][b] += edge.weight();
+        adjacency[b][a] += edge.weight();
+        degree[a] += edge.weight();
+        degree[b] += edge.weight();
+    }
+    let m2: f64 = degree.iter().sum();
+    if m2 == 0. {
+        return 0.;
+    }
+    let mut q = 0.;
+    for i in 0..n {
+        for j in 0..n {
+            if labels[i] == labels[j] {
+                q += adjacency[i][j] - resolution * degree[i] * degree[j] / m2;
+            }
+        }
+    }
+    q / m2
+}
+
+// Whether every community induces a connected subgraph.
+fn communities_connected(graph: &UnGraph<(), f64>, labels: &[usize]) -> bool {
+    let mut sets = UnionFind::new(graph.node_count());
+    for edge in graph.edge_references() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        if labels[a] == labels[b] {
+            sets.union(a, b);
+        }
+    }
+    (0..graph.node_count()).all(|v| sets.equiv(v, labels[v]))
+}
+
+#[test]
+fn modularity_matches_definition() {
+    let mut rng = ChaChaRng::from_seed([12; 32]);
+    for _ in 0..100 {
+        let graph = random_graph(&mut rng);
+        let n = graph.node_count();
+        let mut labels: Vec<usize> = (0..n).map(|_| rng.gen_range(0, 3)).collect();
+        for resolution in [0.5, 1., 2.] {
+            let q = modularity(&graph, &labels, |e| *e.weight(), resolution);
+            let expected = brute_force_modularity(&graph, &labels, resolution);
+            assert!((q - expected).abs() < 1e-9, "{} != {}", q, expected);
+        }
+        labels.iter_mut().for_each(|l| *l = 0);
+        let q = modularity(&graph, &labels, |e| *e.weight(), 1.);
+        assert!(q.abs() < 1e-9);
+    }
+}
+
+#[test]
+fn communities_random() {
+    let mut rng = ChaChaRng::from_seed([13; 32]);
+    for _ in 0..200 {
+        let graph = random_graph(&mut rng);
+        let weight = |e: petgraph::graph::EdgeReference<f64>| *e.weight();
+        let singletons: Vec<usize> = (0..graph.node_count()).collect();
+        let lower = modularity(&graph, &singletons, weight, 1.);
+
+        let propagated = label_propagation(&graph, weight);
+        assert_labeling(&propagated);
+
+        let louvain = louvain(&graph, weight, 1.);
+        assert_labeling(&louvain);
+        let louvain_q = modularity(&graph, &louvain, weight, 1.);
+        assert!(louvain_q >= lower - 1e-9);
+
+        let leiden = leiden(&graph, weight, 1.);
+        assert_labeling(&leiden);
+        let leiden_q = modularity(&graph, &leiden, weight, 1.);
+        assert!(leiden_q >= lower - 1e-9);
+        assert!(communities_connected(&graph, &leiden));
+    }
+}
+
+#[test]
+fn communities_cliques() {
+    // Three cliques of four nodes in a row.
+    let mut edges = Vec::new();
+    for clique in 0..3 {
+        let base = clique * 4;
+        for a in 0..4 {
+            for b in a + 1..4 {
+                edges.push((base + a, base + b));
+            }
+        }
+        if clique < 2 {
+            edges.push((base + 3, base + 4));
+        }
+    }
+    let graph = UnGraph::<(), ()>::from_edges(edges);
+    let expected = vec![0, 0, 0, 0, 4, 4, 4, 4, 8, 8, 8, 8];
+    assert_eq!(label_propagation(&graph, |_| 1), expected);
+    assert_eq!(louvain(&graph, |_| 1., 1.), expected);
+    assert_eq!(leiden(&graph, |_| 1., 1.), expected);
+
+    // A low resolution prefers a single community.
+    assert_eq!(leiden(&graph, |_| 1., 0.01), vec![0; 12]);
+    // A high resolution prefers single nodes.
+    let singletons: Vec<usize> = (0..12).collect();
+    assert_eq!(louvain(&graph, |_| 1., 100.), singletons);
+}
+
+#[test]
+fn communities_without_edges() {
+    let mut graph = UnGraph::<(), f32>::default();
+    assert_eq!(louvain(&graph, |e| *e.weight(), 1.), vec![]);
+    graph.add_node(());
+    graph.add_node(());
+    assert_eq!(label_propagation(&graph, |e| *e.weight()), vec![0, 1]);
+    assert_eq!(louvain(&graph, |e| *e.weight(), 1.), vec![0, 1]);
+    assert_eq!(leiden(&graph, |e| *e.weight(), 1.), vec![0, 1]);
+    assert_eq!(modularity(&graph, &[0, 1], |e| *e.weight(), 1.), 0.);
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn communities_stable_graph() {
+    let mut graph = StableUnGraph::<(), f64>::default();
+    let removed = graph.add_node(());
+    let nodes: Vec<_> = (0..6).map(|_| graph.add_node(())).collect();
+    for &(a, b) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+        graph.add_edge(nodes[a], nodes[b], 1.);
+    }
+    graph.remove_node(removed);
+
+    let labels = leiden(&graph, |e| *e.weight(), 1.);
+    assert_eq!(labels.len(), graph.node_bound());
+    assert_eq!(labels, vec![0, 1, 1, 1, 4, 4, 4]);
+    assert_eq!(louvain(&graph, |e| *e.weight(), 1.), labels);
+    let q = modularity(&graph, &labels, |e| *e.weight(), 1.);
+    assert!((q - 5. / 14.).abs() < 1e-9);
+}
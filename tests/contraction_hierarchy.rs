@@ -0,0 +1,174 @@
+use petgraph::algo::{contraction_hierarchy, dijkstra};
+use petgraph::prelude::*;
+use petgraph::EdgeType;
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+mod utils;
+
+use utils::path_cost;
+
+fn random_graph<Ty: EdgeType>(rng: &mut ChaChaRng) -> Graph<(), u32, Ty> {
+    utils::random_graph(
+        rng,
+        1..40,
+        4,
+        true,
+        |_| (),
+        |rng, _, _| Some(rng.gen_range(0, 10)),
+    )
+}
+
+fn check_all_pairs<Ty: EdgeType>(graph: &Graph<(), u32, Ty>) {
+    let hierarchy = contraction_hierarchy(graph, |e| *e.weight());
+    for start in graph.node_indices() {
+        let expected = dijkstra(graph, start, None, |e| *e.weight());
+        for goal in graph.node_indices() {
+            let cost = expected.get(&goal).copied();
+            assert_eq!(hierarchy.distance(graph, start, goal), cost);
+            let found = hierarchy.shortest_path(graph, start, goal);
+            assert_eq!(found.as_ref().map(|(cost, _)| *cost), cost);
+            if let Some((cost, path)) = found {
+                assert_eq!(path[0], start);
+                assert_eq!(*path.last().unwrap(), goal);
+                assert_eq!(path_cost(graph, &path), Some(cost));
+            }
+        }
+    }
+}
+
+#[test]
+fn contraction_hierarchy_random_directed() {
+    let mut rng = ChaChaRng::from_seed([40; 32]);
+    for _ in 0..50 {
+        check_all_pairs(&random_graph::<Directed>(&mut rng));
+    }
+}
+
+#[test]
+fn contraction_hierarchy_random_undirected() {
+    let mut rng = ChaChaRng::from_seed([41; 32]);
+    for _ in 0..50 {
+        check_all_pairs(&random_graph::<Undirected>(&mut rng));
+    }
+}
+
+#[test]
+fn contraction_hierarchy_grid() {
+    let mut rng = ChaChaRng::from_seed([42; 32]);
+    let size = 30;
+    let mut graph = DiGraph::<(), u32>::new();
+    let nodes: Vec<_> = (0..size * size).map(|_| graph.add_node(())).collect();
+    for i in 0..size * size {
+        if i % size + 1 < size {
+            graph.add_edge(nodes[i], nodes[i + 1], rng.gen_range(1, 20));
+            graph.add_edge(nodes[i + 1], nodes[i], rng.gen_range(1, 20));
+        }
+        if i + size < size * size {
+            graph.add_edge(nodes[i], nodes[i + size], rng.gen_range(1, 20));
+            graph.add_edge(nodes[i + size], nodes[i], rng.gen_range(1, 20));
+        }
+    }
+
+    let hierarchy = contraction_hierarchy(&graph, |e| *e.weight());
+    for _ in 0..50 {
+        let start = nodes[rng.gen_range(0, nodes.len())];
+        let goal = nodes[rng.gen_range(0, nodes.len())];
+        let expected = dijkstra(&graph, start, Some(goal), |e| *e.weight());
+        let (cost, path) = hierarchy.shortest_path(&graph, start, goal).unwrap();
+        assert_eq!(cost, expected[&goal]);
+        assert_eq!(path_cost(&graph, &path), Some(cost));
+    }
+}
+
+#[test]
+fn contraction_hierarchy_float_costs() {
+    let mut graph = DiGraph::<(), f64>::new();
+    let a = graph.add_node(());
+    let b = graph.add_node(());
+    let c = graph.add_node(());
+    let d = graph.add_node(());
+    graph.extend_with_edges([(a, b, 0.5), (b, c, 0.25), (a, c, 1.0), (c, d, 2.0)]);
+
+    let hierarchy = contraction_hierarchy(&graph, |e| *e.weight());
+    assert_eq!(
+        hierarchy.shortest_path(&graph, a, d),
+        Some((2.75, vec![a, b, c, d]))
+    );
+    assert_eq!(hierarchy.shortest_path(&graph, d, a), None);
+    assert_eq!(hierarchy.shortest_path(&graph, c, c), Some((0.0, vec![c])));
+}
+
+#[cfg(feature = "serde-1")]
+#[test]
+fn contraction_hierarchy_serialize() {
+    use petgraph::algo::ContractionHierarchy;
+
+    let mut rng = ChaChaRng::from_seed([43; 32]);
+    let graph = random_graph::<Directed>(&mut rng);
+    let hierarchy = contraction_hierarchy(&graph, |e| *e.weight());
+    let bytes = bincode::serialize(&hierarchy).unwrap();
+    let deserialized: ContractionHierarchy<u32> = bincode::deserialize(&bytes).unwrap();
+    for start in graph.node_indices() {
+        for goal in graph.node_indices() {
+            assert_eq!(
+                deserialized.shortest_path(&graph, start, goal),
+                hierarchy.shortest_path(&graph, start, goal)
+            );
+        }
+    }
+}
+
+#[cfg(feature = "serde-1")]
+#[test]
+fn contraction_hierarchy_deserialize_invalid() {
+    use petgraph::algo::ContractionHierarchy;
+
+    // The rank of every node, and the upward and downward edges with their
+    // cost and the node they bypass.
+    type Edges = Vec<(u32, u32, (u32, Option<u32>))>;
+    let deserialize = |rank: Vec<usize>, upward: Edges, downward: Edges| {
+        let bytes = bincode::serialize(&(rank, upward, downward)).unwrap();
+        bincode::deserialize::<ContractionHierarchy<u32>>(&bytes)
+    };
+
+    // 2 -> 0 -> 1, with a shortcut from 2 to 1 bypassing 0.
+    let graph = DiGraph::<(), u32>::from_edges([(2, 0, 1), (0, 1, 2)]);
+    let n = NodeIndex::new;
+    let hierarchy = deserialize(
+        vec![0, 2, 1],
+        vec![(0, 1, (2, None)), (2, 1, (3, Some(0)))],
+        vec![(0, 2, (1, None))],
+    )
+    .unwrap();
+    assert_eq!(
+        hierarchy.shortest_path(&graph, n(2), n(1)),
+        Some((3, vec![n(2), n(0), n(1)]))
+    );
+
+    let invalid = [
+        // Ranks that are not a permutation.
+        (vec![0, 0, 1], vec![], vec![]),
+        (vec![0, 1, 3], vec![], vec![]),
+        // An edge to a node that does not exist.
+        (vec![0, 2, 1], vec![(0, 3, (2, None))], vec![]),
+        // An upward edge to a node of lower rank.
+        (vec![0, 2, 1], vec![(1, 0, (2, None))], vec![]),
+        // The same edge twice.
+        (
+            vec![0, 2, 1],
+            vec![(0, 1, (2, None)), (0, 1, (3, None))],
+            vec![],
+        ),
+        // A shortcut without the edges it bypasses.
+        (vec![0, 2, 1], vec![(2, 1, (3, Some(0)))], vec![]),
+        // A shortcut bypassing a node of higher rank.
+        (
+            vec![0, 2, 1],
+            vec![(0, 1, (2, Some(2)))],
+            vec![(0, 2, (1, None))],
+        ),
+    ];
+    for (rank, upward, downward) in invalid {
+        assert!(deserialize(rank, upward, downward).is_err());
+    }
+}
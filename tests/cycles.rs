@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+
+use petgraph::algo::{connected_components, cycle_basis, elementary_cycles};
+use petgraph::graph::{DiGraph, NodeIndex, UnGraph};
+use rand::{ChaChaRng, SeedableRng};
+
+#[cfg(feature = "graphmap")]
+use petgraph::graphmap::DiGraphMap;
+
+mod utils;
+
+// All cycles whose smallest node is `path[0]`, by plain depth first search.
+fn brute_force_cycles(graph: &DiGraph<(), ()>, path: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+    let last = *path.last().unwrap();
+    let mut neighbors: Vec<_> = graph.neighbors(NodeIndex::new(last)).collect();
+    neighbors.sort();
+    neighbors.dedup();
+    for n in neighbors {
+        let n = n.index();
+        if n == path[0] {
+            out.push(path.clone());
+        } else if n > path[0] && !path.contains(&n) {
+            path.push(n);
+            brute_force_cycles(graph, path, out);
+            path.pop();
+        }
+    }
+}
+
+fn cycles_of(graph: &DiGraph<(), ()>, max_length: Option<usize>) -> HashSet<Vec<usize>> {
+    let cycles: Vec<Vec<usize>> = elementary_cycles(graph, max_length)
+        .map(|cycle| cycle.into_iter().map(|n| n.index()).collect())
+        .collect();
+    let set: HashSet<_> = cycles.iter().cloned().collect();
+    assert_eq!(set.len(), cycles.len(), "cycles are produced once");
+    set
+}
+
+#[test]
+fn elementary_cycles_small() {
+    let graph = DiGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (2, 3), (3, 2), (3, 3)]);
+    let expected: HashSet<Vec<usize>> = [vec![0, 1, 2], vec![2, 3], vec![3]].into_iter().collect();
+    assert_eq!(cycles_of(&graph, None), expected);
+
+    let expected: HashSet<Vec<usize>> = [vec![2, 3], vec![3]].into_iter().collect();
+    assert_eq!(cycles_of(&graph, Some(2)), expected);
+    assert_eq!(cycles_of(&graph, Some(0)), HashSet::new());
+
+    let acyclic = DiGraph::<(), ()>::from_edges([(0, 1), (1, 2), (0, 2)]);
+    assert_eq!(elementary_cycles(&acyclic, None).next(), None);
+}
+
+#[test]
+fn elementary_cycles_complete() {
+    // A complete digraph on n nodes has sum over k of C(n, k) (k - 1)! cycles.
+    let mut graph = DiGraph::<(), ()>::new();
+    let nodes: Vec<_> = (0..6).map(|_| graph.add_node(())).collect();
+    for &a in &nodes {
+        for &b in &nodes {
+            if a != b {
+                graph.add_edge(a, b, ());
+            }
+        }
+    }
+    assert_eq!(
+        elementary_cycles(&graph, None).count(),
+        15 + 40 + 90 + 144 + 120
+    );
+    assert_eq!(elementary_cycles(&graph, Some(3)).count(), 15 + 40);
+    // The iterator is lazy.
+    assert_eq!(elementary_cycles(&graph, None).take(7).count(), 7);
+}
+
+#[test]
+fn elementary_cycles_random() {
+    let mut rng = ChaChaRng::from_seed([7; 32]);
+    for _ in 0..200 {
+        let graph: DiGraph<(), ()> =
+            utils::random_graph(&mut rng, 1..9, 3, true, |_| (), |_, _, _| Some(()));
+        let nodes = graph.node_count();
+
+        let mut expected = Vec::new();
+        for start in 0..nodes {
+            brute_force_cycles(&graph, &mut vec![start], &mut expected);
+        }
+        let expected: HashSet<_> = expected.into_iter().collect();
+        assert_eq!(cycles_of(&graph, None), expected);
+
+        for bound in 1..5 {
+            let bounded: HashSet<_> = expected
+                .iter()
+                .filter(|cycle| cycle.len() <= bound)
+                .cloned()
+                .collect();
+            assert_eq!(cycles_of(&graph, Some(bound)), bounded);
+        }
+    }
+}
+
+#[cfg(feature = "graphmap")]
+#[test]
+fn elementary_cycles_graphmap() {
+    let graph = DiGraphMap::<&str, ()>::from_edges([
+        ("app", "lib"),
+        ("lib", "util"),
+        ("util", "lib"),
+        ("util", "app"),
+    ]);
+    let mut cycles: Vec<_> = elementary_cycles(&graph, None).collect();
+    cycles.sort();
+    assert_eq!(
+        cycles,
+        vec![vec!["app", "lib", "util"], vec!["lib", "util"]]
+    );
+}
+
+#[test]
+fn cycle_basis_random() {
+    let mut rng = ChaChaRng::from_seed([3; 32]);
+    for _ in 0..200 {
+        let graph: UnGraph<(), ()> =
+            utils::random_graph(&mut rng, 1..12, 3, false, |_| (), |_, _, _| Some(()));
+        let nodes = graph.node_count();
+
+        let basis = cycle_basis(&graph);
+        let expected = graph.edge_count() + connected_components(&graph) - nodes;
+        assert_eq!(basis.len(), expected);
+        for cycle in &basis {
+            let distinct: HashSet<_> = cycle.iter().collect();
+            assert_eq!(distinct.len(), cycle.len());
+            for (i, &a) in cycle.iter().enumerate() {
+                let b = cycle[(i + 1) % cycle.len()];
+                assert!(graph.find_edge(a, b).is_some());
+            }
+        }
+    }
+}
+
+#[test]
+fn cycle_basis_loops_and_parallel_edges() {
+    let graph = UnGraph::<(), ()>::from_edges([(0, 0), (0, 1), (0, 1), (1, 2), (2, 3), (3, 1)]);
+    let mut basis = cycle_basis(&graph);
+    for cycle in &mut basis {
+        cycle.sort();
+    }
+    basis.sort();
+    assert_eq!(
+        basis,
+        vec![
+            vec![NodeIndex::new(0)],
+            vec![NodeIndex::new(1), NodeIndex::new(2), NodeIndex::new(3)]
+        ]
+    );
+}
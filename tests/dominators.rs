@@ -0,0 +1,172 @@
+use petgraph::algo::dominators::{
+    dominance_frontiers, lengauer_tarjan, post_dominance_frontiers, post_dominators, simple_fast,
+    DominatorTree,
+};
+use petgraph::prelude::*;
+use petgraph::visit::{Dfs, Reversed};
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+use std::collections::HashSet;
+
+mod utils;
+
+fn random_graph(rng: &mut ChaChaRng) -> DiGraph<(), ()> {
+    utils::random_graph(rng, 1..12, 3, true, |_| (), |_, _, _| Some(()))
+}
+
+// The nodes reachable from `start` without passing through `removed`.
+fn reachable(graph: &DiGraph<(), ()>, start: NodeIndex, removed: Option<NodeIndex>) -> Vec<bool> {
+    let mut seen = vec![false; graph.node_count()];
+    if Some(start) == removed {
+        return seen;
+    }
+    let mut stack = vec![start];
+    seen[start.index()] = true;
+    while let Some(v) = stack.pop() {
+        for w in graph.neighbors(v) {
+            if Some(w) != removed && !seen[w.index()] {
+                seen[w.index()] = true;
+                stack.push(w);
+            }
+        }
+    }
+    seen
+}
+
+// Whether `a` dominates `b`, by definition.
+fn brute_force_dominates(
+    graph: &DiGraph<(), ()>,
+    root: NodeIndex,
+    a: NodeIndex,
+    b: NodeIndex,
+) -> bool {
+    reachable(graph, root, None)[b.index()]
+        && (a == b || !reachable(graph, root, Some(a))[b.index()])
+}
+
+#[test]
+fn dominators_random() {
+    let mut rng = ChaChaRng::from_seed([15; 32]);
+    for _ in 0..300 {
+        let graph = random_graph(&mut rng);
+        let root = NodeIndex::new(rng.gen_range(0, graph.node_count()));
+        let simple = simple_fast(&graph, root);
+        let fast = lengauer_tarjan(&graph, root);
+        assert_eq!(fast.root(), root);
+        for v in graph.node_indices() {
+            assert_eq!(fast.immediate_dominator(v), simple.immediate_dominator(v));
+            assert_eq!(
+                fast.dominators(v).map(|d| d.collect::<Vec<_>>()),
+                simple.dominators(v).map(|d| d.collect::<Vec<_>>())
+            );
+        }
+
+        let tree = DominatorTree::new(fast);
+        for a in graph.node_indices() {
+            for b in graph.node_indices() {
+                let expected = brute_force_dominates(&graph, root, a, b);
+                assert_eq!(tree.dominates(a, b), expected);
+                assert_eq!(tree.strictly_dominates(a, b), expected && a != b);
+            }
+            for &child in tree.children(a) {
+                assert_eq!(tree.immediate_dominator(child), Some(a));
+            }
+        }
+
+        // `b` is in the frontier of `a` iff `a` dominates a predecessor of
+        // `b` but does not strictly dominate `b`.
+        let frontiers = dominance_frontiers(&graph, tree.dominators());
+        for a in graph.node_indices() {
+            let frontier: HashSet<_> = frontiers.frontier(a).iter().copied().collect();
+            assert_eq!(frontier.len(), frontiers.frontier(a).len());
+            let expected: HashSet<_> = graph
+                .node_indices()
+                .filter(|&b| {
+                    !tree.strictly_dominates(a, b)
+                        && graph
+                            .neighbors_directed(b, Incoming)
+                            .any(|p| tree.dominates(a, p))
+                })
+                .collect();
+            assert_eq!(frontier, expected);
+        }
+
+        // The iterated frontier is closed under taking frontiers.
+        let start: Vec<_> = graph
+            .node_indices()
+            .filter(|_| rng.gen_range(0, 3) == 0)
+            .collect();
+        let iterated = frontiers.iterated_frontier(start.iter().copied());
+        let set: HashSet<_> = iterated.iter().copied().collect();
+        assert_eq!(set.len(), iterated.len());
+        let mut expected = HashSet::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &v in start.iter().chain(expected.clone().iter()) {
+                for &f in frontiers.frontier(v) {
+                    changed |= expected.insert(f);
+                }
+            }
+        }
+        assert_eq!(set, expected);
+    }
+}
+
+#[test]
+fn post_dominators_random() {
+    let mut rng = ChaChaRng::from_seed([16; 32]);
+    for _ in 0..300 {
+        let graph = random_graph(&mut rng);
+        let exits: Vec<_> = graph
+            .node_indices()
+            .filter(|&v| graph.neighbors(v).next().is_none())
+            .collect();
+        let reaches_exit = |removed: Option<NodeIndex>, v: NodeIndex| {
+            let seen = reachable(&graph, v, removed);
+            exits.iter().any(|e| seen[e.index()])
+        };
+
+        let post = post_dominators(&graph);
+        let frontiers = post_dominance_frontiers(&graph, &post);
+        let tree = DominatorTree::new(post);
+        assert_eq!(tree.root(), None);
+        for b in graph.node_indices() {
+            let can_exit = reaches_exit(None, b);
+            assert_eq!(tree.dominates(None, Some(b)), can_exit);
+            for a in graph.node_indices() {
+                let expected = can_exit && (a == b || !reaches_exit(Some(a), b));
+                assert_eq!(tree.dominates(Some(a), Some(b)), expected);
+            }
+        }
+
+        // `b` is in the post-dominance frontier of `a` iff `a` post-dominates
+        // a successor of `b` but does not strictly post-dominate `b`.
+        for a in graph.node_indices() {
+            let frontier: HashSet<_> = frontiers.frontier(a).iter().copied().collect();
+            assert_eq!(frontier.len(), frontiers.frontier(a).len());
+            let expected: HashSet<_> = graph
+                .node_indices()
+                .filter(|&b| {
+                    !tree.strictly_dominates(Some(a), Some(b))
+                        && graph.neighbors(b).any(|s| tree.dominates(Some(a), Some(s)))
+                })
+                .collect();
+            assert_eq!(frontier, expected);
+        }
+
+        // Dominators of the reversed graph from a single exit.
+        if let [exit] = exits[..] {
+            let reversed = simple_fast(Reversed(&graph), exit);
+            let mut dfs = Dfs::new(Reversed(&graph), exit);
+            while let Some(v) = dfs.next(Reversed(&graph)) {
+                if v != exit {
+                    assert_eq!(
+                        tree.immediate_dominator(Some(v)),
+                        reversed.immediate_dominator(v).map(Some)
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,10 @@
-use petgraph::algo::floyd_warshall;
-use petgraph::{prelude::*, Directed, Graph, Undirected};
+use petgraph::algo::{bellman_ford_paths, floyd_warshall, floyd_warshall_paths};
+use petgraph::{prelude::*, Directed, EdgeType, Graph, Undirected};
+use rand::{ChaChaRng, Rng, SeedableRng};
 use std::collections::HashMap;
 
+mod utils;
+
 #[test]
 fn floyd_warshall_uniform_weight() {
     let mut graph: Graph<(), (), Directed> = Graph::new();
@@ -339,3 +342,92 @@ fn floyd_warshall_multiple_edges() {
         }
     }
 }
+
+#[test]
+fn floyd_warshall_paths_multiple_edges() {
+    let mut graph: Graph<(), i32, Directed> = Graph::new();
+    let a = graph.add_node(());
+    let b = graph.add_node(());
+    let c = graph.add_node(());
+    graph.add_edge(a, b, 10);
+    let ab = graph.add_edge(a, b, 1);
+    let bc = graph.add_edge(b, c, 2);
+    graph.add_edge(a, c, 4);
+    graph.add_edge(c, c, 5);
+
+    let paths = floyd_warshall_paths(&graph, |edge| *edge.weight()).unwrap();
+    assert_eq!(paths.distance(a, c), Some(3));
+    assert_eq!(paths.path(a, c), Some((vec![a, b, c], vec![ab, bc])));
+    assert_eq!(paths.path(c, c), Some((vec![c], vec![])));
+    assert_eq!(paths.distance(c, a), None);
+    assert_eq!(paths.path(c, a), None);
+}
+
+// A graph with negative edge costs but no negative cycles if it is directed:
+// every cost is a non-negative base cost adjusted by node potentials.
+fn random_graph<Ty: EdgeType>(rng: &mut ChaChaRng) -> Graph<(), f64, Ty> {
+    let potentials: Vec<i32> = (0..25)
+        .map(|_| {
+            if Ty::is_directed() {
+                rng.gen_range(-20, 20)
+            } else {
+                0
+            }
+        })
+        .collect();
+    utils::random_graph(
+        rng,
+        1..25,
+        3,
+        true,
+        |_| (),
+        |rng, a, b| {
+            let cost = rng.gen_range(0, 10) + potentials[a.index()] - potentials[b.index()];
+            Some(cost as f64)
+        },
+    )
+}
+
+fn check_random_paths<Ty: EdgeType>(seed: u8) {
+    let mut rng = ChaChaRng::from_seed([seed; 32]);
+    for _ in 0..100 {
+        let graph = random_graph::<Ty>(&mut rng);
+        let paths = floyd_warshall_paths(&graph, |e| *e.weight()).unwrap();
+        for a in graph.node_indices() {
+            let single = bellman_ford_paths(&graph, a).unwrap();
+            assert_eq!(single.source(), a);
+            for b in graph.node_indices() {
+                let distance = paths.distance(a, b);
+                assert_eq!(distance, single.distance(b));
+
+                let path = paths.path(a, b);
+                assert_eq!(path.is_some(), distance.is_some());
+                assert_eq!(single.path_to(b).is_some(), distance.is_some());
+                for (nodes, edges) in path.into_iter().chain(single.path_to(b)) {
+                    assert_eq!(nodes[0], a);
+                    assert_eq!(*nodes.last().unwrap(), b);
+                    assert_eq!(edges.len() + 1, nodes.len());
+                    for (pair, &edge) in nodes.windows(2).zip(&edges) {
+                        let (source, target) = graph.edge_endpoints(edge).unwrap();
+                        assert!(
+                            (source, target) == (pair[0], pair[1])
+                                || !Ty::is_directed() && (target, source) == (pair[0], pair[1])
+                        );
+                    }
+                    let cost: f64 = edges.iter().map(|&e| graph[e]).sum();
+                    assert_eq!(Some(cost), distance);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn floyd_warshall_paths_random() {
+    check_random_paths::<Directed>(60);
+}
+
+#[test]
+fn floyd_warshall_paths_random_undirected() {
+    check_random_paths::<Undirected>(61);
+}
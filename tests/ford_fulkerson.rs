@@ -1,5 +1,13 @@
-use petgraph::algo::ford_fulkerson;
-use petgraph::prelude::Graph;
+use petgraph::algo::{dinic, ford_fulkerson, push_relabel};
+use petgraph::prelude::{DiGraph, Graph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::Direction::{Incoming, Outgoing};
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+#[cfg(feature = "stable_graph")]
+use petgraph::stable_graph::StableDiGraph;
+
+mod utils;
 
 #[test]
 fn test_ford_fulkerson() {
@@ -119,3 +127,110 @@ fn test_ford_fulkerson() {
     let (max_flow, _) = ford_fulkerson(&graph, source, destination);
     assert_eq!(19, max_flow);
 }
+
+// Check that `flows` respects the capacities and is conserved at every node
+// but the source and the destination.
+fn assert_valid_flow(
+    graph: &DiGraph<(), u32>,
+    source: NodeIndex,
+    destination: NodeIndex,
+    value: u32,
+    flows: &[u32],
+) {
+    for edge in graph.edge_references() {
+        assert!(flows[edge.id().index()] <= *edge.weight());
+    }
+    for node in graph.node_indices() {
+        let inflow: u32 = graph
+            .edges_directed(node, Incoming)
+            .map(|e| flows[e.id().index()])
+            .sum();
+        let outflow: u32 = graph
+            .edges_directed(node, Outgoing)
+            .map(|e| flows[e.id().index()])
+            .sum();
+        if node == destination {
+            assert_eq!(inflow - outflow, value);
+        } else if node != source {
+            assert_eq!(inflow, outflow);
+        }
+    }
+}
+
+#[test]
+fn test_dinic_and_push_relabel() {
+    let mut graph = Graph::<u8, f32>::new();
+    let source = graph.add_node(0);
+    let _ = graph.add_node(1);
+    let _ = graph.add_node(2);
+    let _ = graph.add_node(3);
+    let _ = graph.add_node(4);
+    let destination = graph.add_node(5);
+    graph.extend_with_edges([
+        (0, 1, 7.),
+        (0, 2, 4.),
+        (1, 3, 5.),
+        (1, 4, 3.),
+        (2, 1, 3.),
+        (2, 4, 2.),
+        (3, 5, 8.),
+        (4, 3, 3.),
+        (4, 5, 5.),
+    ]);
+    assert_eq!(10.0, dinic(&graph, source, destination).0);
+    assert_eq!(10.0, push_relabel(&graph, source, destination).0);
+    assert_eq!(0.0, dinic(&graph, source, source).0);
+    assert_eq!(0.0, push_relabel(&graph, source, source).0);
+    assert_eq!(0.0, dinic(&graph, destination, source).0);
+    assert_eq!(0.0, push_relabel(&graph, destination, source).0);
+}
+
+#[test]
+fn test_max_flow_random() {
+    let mut rng = ChaChaRng::from_seed([7; 32]);
+    for _ in 0..300 {
+        let graph: DiGraph<(), u32> = utils::random_graph(
+            &mut rng,
+            2..12,
+            4,
+            true,
+            |_| (),
+            |rng, _, _| Some(rng.gen_range(0, 10)),
+        );
+        let source = NodeIndex::new(0);
+        let destination = NodeIndex::new(graph.node_count() - 1);
+
+        let (expected, _) = ford_fulkerson(&graph, source, destination);
+        for (max_flow, flows) in [
+            dinic(&graph, source, destination),
+            push_relabel(&graph, source, destination),
+        ] {
+            assert_eq!(max_flow, expected);
+            assert_valid_flow(&graph, source, destination, max_flow, &flows);
+        }
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn test_max_flow_stable_graph() {
+    let mut graph = StableDiGraph::<(), u32>::new();
+    let source = graph.add_node(());
+    let removed = graph.add_node(());
+    let a = graph.add_node(());
+    let destination = graph.add_node(());
+    let gone = graph.add_edge(source, removed, 5);
+    let first = graph.add_edge(source, a, 3);
+    let second = graph.add_edge(a, destination, 2);
+    graph.remove_edge(gone);
+    graph.remove_node(removed);
+
+    for (max_flow, flows) in [
+        dinic(&graph, source, destination),
+        push_relabel(&graph, source, destination),
+    ] {
+        assert_eq!(max_flow, 2);
+        assert_eq!(flows[first.index()], 2);
+        assert_eq!(flows[second.index()], 2);
+    }
+}
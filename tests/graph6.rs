@@ -1,8 +1,14 @@
 use petgraph::{
     csr::Csr,
-    graph6::{from_graph6_representation, get_graph6_representation, FromGraph6, ToGraph6},
-    Graph, Undirected,
+    graph::{DiGraph, UnGraph},
+    graph6::{
+        from_digraph6_representation, from_sparse6_representation, get_graph6_representation,
+        try_from_graph6_representation, DecodedGraph, FromDigraph6, FromSparse6, Graph6Error,
+        Graph6Format, Graph6Reader, Graph6Writer, ToDigraph6, ToGraph6, ToSparse6, TryFromGraph6,
+    },
+    Directed, Graph, Undirected,
 };
+use std::io::{BufReader, ErrorKind};
 
 #[cfg(feature = "graphmap")]
 use petgraph::graphmap::GraphMap;
@@ -48,7 +54,7 @@ fn test_graph6_generic_decoder(
 ) {
     type G = (usize, Vec<(u16, u16)>);
 
-    let (order, mut edges): G = from_graph6_representation(graph6_str.to_string());
+    let (order, mut edges): G = try_from_graph6_representation(graph6_str).unwrap();
     assert_eq!(order, expected_order, "order should be the same");
 
     edges.sort();
@@ -79,7 +85,7 @@ fn test_graph6_for_graph(graph6_str: &str, order: usize, edges: Vec<(u16, u16)>)
     assert_eq!(graph6_string, graph6_str);
 
     // Assert decoded graph properties
-    let decoded_graph = G::from_graph6_string(graph6_string);
+    let decoded_graph = G::try_from_graph6_string(&graph6_string).unwrap();
     assert_eq!(decoded_graph.node_count(), order);
     assert_eq!(decoded_graph.edge_count(), size);
 
@@ -112,7 +118,7 @@ fn test_graph6_for_stable_graph(graph6_str: &str, order: usize, edges: Vec<(u16,
     assert_eq!(graph6_string, graph6_str);
 
     // Assert decoded graph properties
-    let decoded_graph = G::from_graph6_string(graph6_string);
+    let decoded_graph = G::try_from_graph6_string(&graph6_string).unwrap();
     assert_eq!(decoded_graph.node_count(), order);
     assert_eq!(decoded_graph.edge_count(), size);
 
@@ -147,7 +153,7 @@ fn test_graph6_for_graph_map(graph6_str: &str, order: usize, edges: Vec<(u16, u1
     assert_eq!(graph6_string, graph6_str);
 
     // Assert decoded graph properties
-    let decoded_graph = G::from_graph6_string(graph6_string);
+    let decoded_graph = G::try_from_graph6_string(&graph6_string).unwrap();
     assert_eq!(decoded_graph.node_count(), order);
     assert_eq!(decoded_graph.edge_count(), size);
 
@@ -180,7 +186,7 @@ fn test_graph6_for_matrix_graph(graph6_str: &str, order: usize, edges: Vec<(u16,
     assert_eq!(graph6_string, graph6_str);
 
     // Assert decoded graph properties
-    let decoded_graph = G::from_graph6_string(graph6_string);
+    let decoded_graph = G::try_from_graph6_string(&graph6_string).unwrap();
     assert_eq!(decoded_graph.node_count(), order);
     assert_eq!(decoded_graph.edge_count(), size);
 
@@ -215,7 +221,7 @@ fn test_graph6_for_csr(graph6_str: &str, order: usize, edges: Vec<(u16, u16)>) {
     assert_eq!(graph6_string, graph6_str);
 
     // Assert decoded graph properties
-    let decoded_graph = G::from_graph6_string(graph6_string);
+    let decoded_graph = G::try_from_graph6_string(&graph6_string).unwrap();
     assert_eq!(decoded_graph.node_count(), order);
     assert_eq!(decoded_graph.edge_count(), size);
 
@@ -223,6 +229,212 @@ fn test_graph6_for_csr(graph6_str: &str, order: usize, edges: Vec<(u16, u16)>) {
     assert_eq!(decoded_graph.graph6_string(), graph6_str);
 }
 
+#[test]
+fn digraph6_encode_decode() {
+    let graph = DiGraph::<(), ()>::from_edges([(0, 2), (0, 4), (3, 1), (3, 4)]);
+    assert_eq!(graph.digraph6_string(), "&DI?AO?");
+
+    let (order, edges) = from_digraph6_representation::<u32>(">>digraph6<<&DI?AO?\n").unwrap();
+    assert_eq!(order, 5);
+    assert_eq!(edges, [(0, 2), (0, 4), (3, 1), (3, 4)]);
+
+    // Loops and opposite edges are kept apart.
+    let graph = DiGraph::<(), ()>::from_edges([(0, 1), (1, 0), (1, 1)]);
+    let decoded = DiGraph::<(), ()>::from_digraph6_string(&graph.digraph6_string()).unwrap();
+    assert_eq!(decoded.edge_count(), 3);
+    assert!(decoded.contains_edge(1.into(), 1.into()));
+
+    let csr = Csr::<(), (), Directed>::from_digraph6_string("&DI?AO?").unwrap();
+    assert_eq!(csr.digraph6_string(), "&DI?AO?");
+}
+
+#[test]
+fn sparse6_encode_decode() {
+    let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 2), (5, 6)]);
+    assert_eq!(graph.sparse6_string(), ":Fa@x^");
+
+    let (order, edges) = from_sparse6_representation::<u32>(">>sparse6<<:Fa@x^\n").unwrap();
+    assert_eq!(order, 7);
+    assert_eq!(edges, [(0, 1), (0, 2), (1, 2), (5, 6)]);
+
+    // Loops and parallel edges survive a round trip.
+    let graph = UnGraph::<(), ()>::from_edges([(3, 3), (0, 3), (3, 0), (1, 2)]);
+    let decoded = UnGraph::<(), ()>::from_sparse6_string(&graph.sparse6_string()).unwrap();
+    assert_eq!(decoded.node_count(), 4);
+    let mut edges: Vec<_> = decoded
+        .raw_edges()
+        .iter()
+        .map(|e| (e.source().index(), e.target().index()))
+        .collect();
+    edges.sort();
+    assert_eq!(edges, [(0, 3), (0, 3), (1, 2), (3, 3)]);
+
+    // The padding must not be read as a loop on the last node.
+    let mut graph = UnGraph::<(), ()>::from_edges([(0, 2), (1, 2)]);
+    graph.add_node(());
+    let (order, edges) = from_sparse6_representation::<u32>(&graph.sparse6_string()).unwrap();
+    assert_eq!(order, 4);
+    assert_eq!(edges, [(0, 2), (1, 2)]);
+    graph.clear_edges();
+    assert_eq!(graph.sparse6_string(), ":C");
+
+    // Csr stores undirected edges twice, but writes them once.
+    let mut csr = Csr::<(), (), Undirected>::with_nodes(7);
+    for (a, b) in [(0, 1), (0, 2), (1, 2), (5, 6)] {
+        csr.add_edge(a, b, ());
+    }
+    assert_eq!(csr.sparse6_string(), ":Fa@x^");
+}
+
+#[test]
+#[allow(deprecated)]
+fn graph6_panicking_decoders() {
+    use petgraph::graph6::{from_graph6_representation, FromGraph6};
+
+    assert_eq!(
+        from_graph6_representation::<u32>("D~{".to_string()),
+        try_from_graph6_representation("D~{").unwrap()
+    );
+    let graph = UnGraph::<(), ()>::from_graph6_string("D~{".to_string());
+    assert_eq!(graph.graph6_string(), "D~{");
+    let result = std::panic::catch_unwind(|| UnGraph::<(), ()>::from_graph6_string("D~".into()));
+    assert!(result.is_err());
+}
+
+#[test]
+fn graph6_decode_errors() {
+    assert_eq!(
+        try_from_graph6_representation::<u32>(">>graph6<<D~{\n"),
+        try_from_graph6_representation("D~{")
+    );
+    assert_eq!(
+        try_from_graph6_representation::<u32>("D~ {"),
+        Err(Graph6Error::InvalidCharacter {
+            position: 2,
+            character: ' '
+        })
+    );
+    assert_eq!(
+        try_from_graph6_representation::<u32>("D~"),
+        Err(Graph6Error::Truncated)
+    );
+    assert_eq!(
+        try_from_graph6_representation::<u32>("D~{?"),
+        Err(Graph6Error::TrailingData)
+    );
+    assert_eq!(
+        try_from_graph6_representation::<u32>(""),
+        Err(Graph6Error::Truncated)
+    );
+    assert_eq!(
+        try_from_graph6_representation::<u32>(":Fa@x^"),
+        Err(Graph6Error::WrongFormat {
+            expected: Graph6Format::Graph6,
+            found: Graph6Format::Sparse6
+        })
+    );
+    assert_eq!(
+        UnGraph::<(), ()>::try_from_graph6_string("D~").unwrap_err(),
+        Graph6Error::Truncated
+    );
+    assert_eq!(
+        DiGraph::<(), ()>::from_digraph6_string("&~").unwrap_err(),
+        Graph6Error::Truncated
+    );
+    assert_eq!(
+        from_sparse6_representation::<u32>(":Fa\u{e9}x"),
+        Err(Graph6Error::InvalidCharacter {
+            position: 3,
+            character: '\u{e9}'
+        })
+    );
+
+    // A few bytes can declare up to 2^36 - 1 nodes.
+    assert_eq!(
+        UnGraph::<(), ()>::from_sparse6_string(":~~~~~~~~").unwrap_err(),
+        Graph6Error::OrderTooLarge {
+            order: (1 << 36) - 1
+        }
+    );
+    assert_eq!(
+        from_sparse6_representation::<u16>(":~O??"),
+        Err(Graph6Error::OrderTooLarge { order: 65536 })
+    );
+    assert_eq!(
+        from_sparse6_representation::<u32>(":~O??").unwrap().0,
+        65536
+    );
+}
+
+#[test]
+fn graph6_order_encoding() {
+    // Orders of 63 and more take 18 bits, from 258048 on 36 bits.
+    let graph = UnGraph::<(), ()>::from_edges([(0, 62)]);
+    assert!(graph.graph6_string().starts_with("~??~"));
+    let mut graph = UnGraph::<(), ()>::with_capacity(258048, 0);
+    for _ in 0..258048 {
+        graph.add_node(());
+    }
+    let sparse6 = graph.sparse6_string();
+    assert_eq!(sparse6, ":~~???~??");
+    assert_eq!(DecodedGraph::parse(&sparse6).unwrap().order, 258048);
+}
+
+#[test]
+fn graph6_stream() {
+    let mut writer = Graph6Writer::new(Vec::new(), Graph6Format::Sparse6).with_header();
+    writer
+        .write_graph(&UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (1, 2),
+            (5, 6),
+        ]))
+        .unwrap();
+    writer
+        .write_graph(&UnGraph::<(), ()>::from_edges([(1, 1)]))
+        .unwrap();
+    let mut output = String::from_utf8(writer.into_inner()).unwrap();
+    assert_eq!(output, ">>sparse6<<:Fa@x^\n:A~\n");
+
+    let mut writer = Graph6Writer::new(Vec::new(), Graph6Format::Digraph6);
+    writer
+        .write_graph(&DiGraph::<(), ()>::from_edges([
+            (0, 2),
+            (0, 4),
+            (3, 1),
+            (3, 4),
+        ]))
+        .unwrap();
+    output += &String::from_utf8(writer.into_inner()).unwrap();
+    output += "\r\n>>graph6<<\nD~{\n";
+
+    let graphs: Vec<_> = Graph6Reader::new(BufReader::new(output.as_bytes()))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let formats: Vec<_> = graphs.iter().map(|g| g.format).collect();
+    assert_eq!(
+        formats,
+        [
+            Graph6Format::Sparse6,
+            Graph6Format::Sparse6,
+            Graph6Format::Digraph6,
+            Graph6Format::Graph6
+        ]
+    );
+    assert_eq!(graphs[1].edges, [(1, 1)]);
+    assert_eq!(graphs[2].edges, [(0, 2), (0, 4), (3, 1), (3, 4)]);
+    let complete = graphs[3].clone().into_graph::<Undirected, u32>();
+    assert_eq!(complete.edge_count(), 10);
+
+    let mut reader = Graph6Reader::new("A_\nA_?\n".as_bytes());
+    assert!(reader.next().unwrap().is_ok());
+    let error = reader.next().unwrap().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(error.to_string(), "unexpected data after the graph");
+    assert!(reader.next().is_none());
+}
+
 // Test cases format: (graph order, expected ghaph6 representation, graph edges)
 #[allow(clippy::type_complexity)]
 #[rustfmt::skip]
@@ -0,0 +1,291 @@
+use petgraph::{
+    graph::{DiGraph, UnGraph},
+    graphml::{
+        from_graphml, AttrValue, GraphMl, GraphMlEdge, GraphMlError, GraphMlErrorKind,
+        GraphMlGraph, GraphMlNode,
+    },
+};
+
+#[cfg(feature = "graphmap")]
+use petgraph::graphmap::UnGraphMap;
+
+#[cfg(feature = "stable_graph")]
+use petgraph::{stable_graph::StableDiGraph, visit::IntoEdgeReferences};
+
+fn read_directed(
+    input: &str,
+) -> Result<GraphMlGraph<DiGraph<GraphMlNode, GraphMlEdge>>, GraphMlError> {
+    from_graphml(input, |n| n, |e| e)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct City {
+    name: String,
+    population: i64,
+    capital: bool,
+}
+
+#[test]
+fn graphml_round_trip() {
+    let mut graph = DiGraph::new();
+    let a = graph.add_node(City {
+        name: "A & <B>".into(),
+        population: 10_000_000_000,
+        capital: true,
+    });
+    let b = graph.add_node(City {
+        name: "\"C\"".into(),
+        population: 42,
+        capital: false,
+    });
+    graph.add_edge(a, b, (1.5f32, Some(3)));
+    graph.add_edge(b, a, (-0.25, None));
+    graph.add_edge(b, b, (f32::INFINITY, Some(-1)));
+
+    let text = GraphMl::new(&graph)
+        .node_attribute("name", |c: &City| c.name.clone())
+        .node_attribute("population", |c: &City| c.population)
+        .node_attribute("capital", |c: &City| c.capital)
+        .edge_attribute("length", |e: &(f32, Option<i32>)| e.0)
+        .edge_attribute("lanes", |e: &(f32, Option<i32>)| e.1)
+        .to_string();
+    assert!(text.contains(r#"<key id="d4" for="edge" attr.name="lanes" attr.type="int"/>"#));
+    assert!(text.contains("<data key=\"d0\">A &amp; &lt;B&gt;</data>"));
+
+    let parsed = read_directed(&text).unwrap();
+    let copy = parsed.graph.map(
+        |_, n| City {
+            name: n.attributes["name"].as_str().unwrap().to_string(),
+            population: n.attributes["population"].as_i64().unwrap(),
+            capital: n.attributes["capital"].as_bool().unwrap(),
+        },
+        |_, e| {
+            (
+                e.attributes["length"].as_f64().unwrap() as f32,
+                e.attributes
+                    .get("lanes")
+                    .map(|l| l.as_i64().unwrap() as i32),
+            )
+        },
+    );
+    assert_eq!(copy.raw_nodes().len(), 2);
+    for (x, y) in graph.raw_nodes().iter().zip(copy.raw_nodes()) {
+        assert_eq!(x.weight, y.weight);
+    }
+    for (x, y) in graph.raw_edges().iter().zip(copy.raw_edges()) {
+        assert_eq!(
+            (x.source(), x.target(), x.weight),
+            (y.source(), y.target(), y.weight)
+        );
+    }
+    assert_eq!(parsed.graph[parsed.node_id("n1").unwrap()].id, "n1");
+
+    let mut buffer = Vec::new();
+    GraphMl::new(&graph).to_writer(&mut buffer).unwrap();
+    assert!(String::from_utf8(buffer)
+        .unwrap()
+        .contains(r#"<edge id="e2" source="n1" target="n1"/>"#));
+}
+
+const NETWORKX: &str = r#"<?xml version='1.0' encoding='utf-8'?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://graphml.graphdrawing.org/xmlns http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd">
+  <key id="d2" for="edge" attr.name="weight" attr.type="double" />
+  <key id="d1" for="node" attr.name="color" attr.type="string">
+    <default>yellow</default>
+  </key>
+  <key id="d0" for="graph" attr.name="name" attr.type="string" />
+  <graph edgedefault="undirected">
+    <data key="d0">cities</data>
+    <!-- edges may come before their nodes -->
+    <edge source="x" target="y">
+      <data key="d2">0.5</data>
+    </edge>
+    <node id="x">
+      <data key="d1"><![CDATA[<green>]]></data>
+    </node>
+    <node id="y" />
+    <node id="z" />
+    <edge source="y" target="z" />
+  </graph>
+</graphml>
+"#;
+
+#[test]
+fn graphml_networkx_document() {
+    let parsed =
+        from_graphml::<UnGraph<GraphMlNode, GraphMlEdge>, _, _>(NETWORKX, |n| n, |e| e).unwrap();
+    assert_eq!(
+        parsed.attributes["name"],
+        AttrValue::String("cities".into())
+    );
+    assert_eq!(parsed.graph.node_count(), 3);
+    assert_eq!(parsed.graph.edge_count(), 2);
+    let x = parsed.node_id("x").unwrap();
+    let y = parsed.node_id("y").unwrap();
+    assert_eq!(
+        parsed.graph[x].attributes["color"].as_str(),
+        Some("<green>")
+    );
+    assert_eq!(parsed.graph[y].attributes["color"].as_str(), Some("yellow"));
+    let xy = parsed.graph.find_edge(y, x).unwrap();
+    assert_eq!(
+        parsed.graph[xy].attributes["weight"],
+        AttrValue::Double(0.5)
+    );
+    assert!(parsed.graph[xy].id.is_none());
+}
+
+#[test]
+#[cfg(feature = "graphmap")]
+fn graphml_into_graphmap() {
+    let ids = ["x", "y", "z"];
+    let parsed = from_graphml::<UnGraphMap<usize, f64>, _, _>(
+        NETWORKX,
+        |n| ids.iter().position(|&id| id == n.id).unwrap(),
+        |e| {
+            e.attributes
+                .get("weight")
+                .and_then(AttrValue::as_f64)
+                .unwrap_or(1.)
+        },
+    )
+    .unwrap();
+    assert_eq!(parsed.node_id("z"), Some(2));
+    let mut edges: Vec<_> = parsed
+        .graph
+        .all_edges()
+        .map(|(a, b, &w)| (a, b, w))
+        .collect();
+    edges.sort_by_key(|&(a, b, _)| (a, b));
+    assert_eq!(edges, [(0, 1, 0.5), (1, 2, 1.)]);
+}
+
+#[test]
+#[cfg(feature = "stable_graph")]
+fn graphml_yed_document() {
+    let input = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:y="http://www.yworks.com/xml/graphml">
+  <key for="node" id="d6" yfiles.type="nodegraphics"/>
+  <key attr.name="description" attr.type="string" for="node" id="d5"/>
+  <key attr.name="count" attr.type="int" for="all" id="d7"><default>7</default></key>
+  <key for="graphml" id="d8" yfiles.type="resources"/>
+  <graph edgedefault="directed" id="G">
+    <node id="n0">
+      <data key="d5">first</data>
+      <data key="d6">
+        <y:ShapeNode>
+          <y:Geometry height="30.0" width="30.0" x="0.0" y="0.0"/>
+          <y:NodeLabel>A</y:NodeLabel>
+        </y:ShapeNode>
+      </data>
+    </node>
+    <node id="n1"/>
+    <edge id="e0" source="n0" target="n1">
+      <data key="d7">3</data>
+    </edge>
+  </graph>
+  <data key="d8">
+    <y:Resources/>
+  </data>
+</graphml>
+"#;
+    let parsed =
+        from_graphml::<StableDiGraph<GraphMlNode, GraphMlEdge>, _, _>(input, |n| n, |e| e).unwrap();
+    assert_eq!(parsed.id.as_deref(), Some("G"));
+    let n0 = parsed.node_id("n0").unwrap();
+    assert_eq!(parsed.graph[n0].attributes.len(), 2);
+    assert_eq!(
+        parsed.graph[n0].attributes["description"].as_str(),
+        Some("first")
+    );
+    assert_eq!(parsed.graph[n0].attributes["count"], AttrValue::Int(7));
+    let edge = (&parsed.graph).edge_references().next().unwrap();
+    assert_eq!(edge.weight().id.as_deref(), Some("e0"));
+    assert_eq!(edge.weight().attributes["count"], AttrValue::Int(3));
+}
+
+fn error_kind(input: &str) -> (usize, usize, GraphMlErrorKind) {
+    let error = read_directed(input).unwrap_err();
+    (error.line, error.column, error.kind)
+}
+
+#[test]
+fn graphml_errors() {
+    assert_eq!(
+        error_kind("<graphml><graph>\n  <hyperedge><endpoint node=\"a\"/></hyperedge>"),
+        (2, 3, GraphMlErrorKind::Unsupported("hyperedges"))
+    );
+    assert_eq!(
+        error_kind("<graphml><graph><node id=\"a\"><graph/></node></graph></graphml>"),
+        (1, 30, GraphMlErrorKind::Unsupported("nested graphs"))
+    );
+    assert_eq!(
+        error_kind("<graphml><graph/><graph/></graphml>"),
+        (1, 18, GraphMlErrorKind::Unsupported("multiple graphs"))
+    );
+    assert_eq!(
+        error_kind(
+            "<graphml><graph><node id=\"a\"><data key=\"k\">1</data></node></graph></graphml>"
+        ),
+        (1, 30, GraphMlErrorKind::UnknownKey("k".into()))
+    );
+    assert_eq!(
+        error_kind(
+            "<graphml><key id=\"k\" attr.type=\"int\"/><graph>\n<node id=\"a\"><data key=\"k\">x</data></node></graph></graphml>"
+        ),
+        (
+            2,
+            14,
+            GraphMlErrorKind::InvalidValue {
+                key: "k".into(),
+                value: "x".into()
+            }
+        )
+    );
+    assert_eq!(
+        error_kind("<graphml><graph>\n<edge source=\"a\" target=\"b\"/></graph></graphml>"),
+        (2, 1, GraphMlErrorKind::UnknownNode("a".into()))
+    );
+    assert_eq!(
+        error_kind(
+            "<graphml><graph edgedefault=\"undirected\"><node id=\"a\"/><edge source=\"a\" target=\"a\"/></graph></graphml>"
+        ),
+        (1, 56, GraphMlErrorKind::GraphKindMismatch { directed: false })
+    );
+    assert_eq!(
+        error_kind("<graphml><graph><node id=\"a\"/></grap></graphml>"),
+        (
+            1,
+            31,
+            GraphMlErrorKind::MismatchedTag {
+                expected: "graph".into(),
+                found: "grap".into()
+            }
+        )
+    );
+    assert_eq!(
+        error_kind("<graphml><graph><node/></graph></graphml>"),
+        (
+            1,
+            17,
+            GraphMlErrorKind::MissingAttribute {
+                element: "node",
+                attribute: "id"
+            }
+        )
+    );
+    assert_eq!(
+        error_kind("<graphml></graphml>"),
+        (1, 10, GraphMlErrorKind::MissingGraph)
+    );
+    assert_eq!(
+        error_kind("<graphml><graph>"),
+        (1, 17, GraphMlErrorKind::Xml("unexpected end of input"))
+    );
+    assert_eq!(
+        read_directed("<graphml><graph><node id=\"a\"/><node id=\"a\"/></graph></graphml>")
+            .unwrap_err()
+            .to_string(),
+        "1:31: duplicate node id \"a\""
+    );
+}
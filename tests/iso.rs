@@ -8,9 +8,14 @@ use petgraph::graph::{edge_index, node_index};
 use petgraph::prelude::*;
 use petgraph::EdgeType;
 
+use petgraph::algo::isomorphism::{count_subgraph_isomorphisms, count_subgraph_monomorphisms};
 use petgraph::algo::{
-    is_isomorphic, is_isomorphic_matching, is_isomorphic_subgraph, subgraph_isomorphisms_iter,
+    is_isomorphic, is_isomorphic_matching, is_isomorphic_subgraph, is_subgraph_monomorphic,
+    is_subgraph_monomorphic_matching, subgraph_isomorphisms_iter, subgraph_monomorphisms_iter,
 };
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+mod utils;
 
 /// Petersen A and B are isomorphic
 ///
@@ -636,3 +641,139 @@ const COXETER_B: &str = "
  0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 0 0 0 1 0 0 1
  0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 0 1 0 0 0 0 0 0 0 0 1 0
 ";
+
+fn random_simple_graph<Ty: EdgeType>(rng: &mut ChaChaRng, max_nodes: usize) -> Graph<u8, (), Ty> {
+    utils::random_graph(
+        rng,
+        0..max_nodes + 1,
+        2,
+        false,
+        |rng| rng.gen_range(0, 2),
+        |_, _, _| Some(()),
+    )
+}
+
+// All injective mappings of `g0` into `g1` preserving edges and node
+// weights, and non-edges if `induced`.
+fn brute_force_mappings<Ty: EdgeType>(
+    g0: &Graph<u8, (), Ty>,
+    g1: &Graph<u8, (), Ty>,
+    induced: bool,
+) -> Vec<Vec<usize>> {
+    fn extend<Ty: EdgeType>(
+        g0: &Graph<u8, (), Ty>,
+        g1: &Graph<u8, (), Ty>,
+        induced: bool,
+        mapping: &mut Vec<usize>,
+        result: &mut Vec<Vec<usize>>,
+    ) {
+        let n = mapping.len();
+        if n == g0.node_count() {
+            result.push(mapping.clone());
+            return;
+        }
+        for m in 0..g1.node_count() {
+            if mapping.contains(&m) || g0[node_index(n)] != g1[node_index(m)] {
+                continue;
+            }
+            mapping.push(m);
+            let consistent = (0..=n).all(|u| {
+                [(u, n), (n, u)].iter().all(|&(a, b)| {
+                    let e0 = g0.contains_edge(node_index(a), node_index(b));
+                    let e1 = g1.contains_edge(node_index(mapping[a]), node_index(mapping[b]));
+                    if induced {
+                        e0 == e1
+                    } else {
+                        !e0 || e1
+                    }
+                })
+            });
+            if consistent {
+                extend(g0, g1, induced, mapping, result);
+            }
+            mapping.pop();
+        }
+    }
+    let mut result = Vec::new();
+    extend(g0, g1, induced, &mut Vec::new(), &mut result);
+    result
+}
+
+fn check_subgraph_matches<Ty: EdgeType>(g0: &Graph<u8, (), Ty>, g1: &Graph<u8, (), Ty>) {
+    let node_match = |a: &u8, b: &u8| a == b;
+    let edge_match = |_: &(), _: &()| true;
+    for induced in [true, false] {
+        let mut expected = brute_force_mappings(g0, g1, induced);
+        expected.sort();
+        let (mut nm, mut em) = (node_match, edge_match);
+        let found = if induced {
+            subgraph_isomorphisms_iter(&g0, &g1, &mut nm, &mut em).map(|i| i.collect())
+        } else {
+            subgraph_monomorphisms_iter(&g0, &g1, &mut nm, &mut em).map(|i| i.collect())
+        };
+        let mut found: Vec<Vec<usize>> = found.unwrap_or_default();
+        found.sort();
+        assert_eq!(found, expected);
+
+        let count = if induced {
+            count_subgraph_isomorphisms(g0, g1, node_match, edge_match, None)
+        } else {
+            count_subgraph_monomorphisms(g0, g1, node_match, edge_match, None)
+        };
+        assert_eq!(count, expected.len());
+        let limited = if induced {
+            count_subgraph_isomorphisms(g0, g1, node_match, edge_match, Some(2))
+        } else {
+            count_subgraph_monomorphisms(g0, g1, node_match, edge_match, Some(2))
+        };
+        assert_eq!(limited, expected.len().min(2));
+    }
+
+    let structural = brute_force_mappings(
+        &g0.map(|_, _| 0, |_, _| ()),
+        &g1.map(|_, _| 0, |_, _| ()),
+        false,
+    );
+    assert_eq!(is_subgraph_monomorphic(g0, g1), !structural.is_empty());
+    assert_eq!(
+        is_subgraph_monomorphic_matching(g0, g1, node_match, edge_match),
+        !brute_force_mappings(g0, g1, false).is_empty()
+    );
+}
+
+#[test]
+fn subgraph_matches_random() {
+    let mut rng = ChaChaRng::from_seed([17; 32]);
+    for _ in 0..300 {
+        let g0 = random_simple_graph::<Undirected>(&mut rng, 4);
+        let g1 = random_simple_graph::<Undirected>(&mut rng, 6);
+        check_subgraph_matches(&g0, &g1);
+        let g0 = random_simple_graph::<Directed>(&mut rng, 4);
+        let g1 = random_simple_graph::<Directed>(&mut rng, 6);
+        check_subgraph_matches(&g0, &g1);
+    }
+}
+
+#[test]
+fn monomorphism_in_cycle() {
+    // A path of length four is in a cycle of five nodes, but not induced.
+    let path = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4)]);
+    let cycle = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+    assert!(!is_isomorphic_subgraph(&path, &cycle));
+    assert!(is_subgraph_monomorphic(&path, &cycle));
+    let any = |_: &(), _: &()| true;
+    assert_eq!(
+        count_subgraph_isomorphisms(&path, &cycle, any, any, None),
+        0
+    );
+    assert_eq!(
+        count_subgraph_monomorphisms(&path, &cycle, any, any, None),
+        10
+    );
+
+    let empty = UnGraph::<(), ()>::default();
+    assert_eq!(
+        count_subgraph_monomorphisms(&empty, &cycle, any, any, None),
+        1
+    );
+}
@@ -0,0 +1,123 @@
+use petgraph::algo::{floyd_warshall, johnson};
+use petgraph::prelude::*;
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+#[cfg(feature = "rayon")]
+use petgraph::algo::johnson::parallel_johnson;
+
+mod utils;
+
+// A graph with negative edge costs but no negative cycles: every cost is a
+// non-negative base cost adjusted by node potentials.
+fn random_graph(rng: &mut ChaChaRng) -> DiGraph<(), f64> {
+    let potentials: Vec<i32> = (0..30).map(|_| rng.gen_range(-20, 20)).collect();
+    utils::random_graph(
+        rng,
+        1..30,
+        4,
+        true,
+        |_| (),
+        |rng, a, b| {
+            let cost = rng.gen_range(0, 10) + potentials[a.index()] - potentials[b.index()];
+            Some(cost as f64)
+        },
+    )
+}
+
+#[test]
+fn johnson_random() {
+    let mut rng = ChaChaRng::from_seed([50; 32]);
+    for _ in 0..200 {
+        let graph = random_graph(&mut rng);
+        let expected = floyd_warshall(&graph, |e| *e.weight()).unwrap();
+        let paths = johnson(&graph, |e| *e.weight()).unwrap();
+        for a in graph.node_indices() {
+            for b in graph.node_indices() {
+                let distance = paths.distance(a, b);
+                let floyd = expected[&(a, b)];
+                assert_eq!(distance, Some(floyd).filter(|&d| d != f64::MAX));
+
+                let path = paths.path(a, b);
+                assert_eq!(path.is_some(), distance.is_some());
+                if let Some((nodes, edges)) = path {
+                    assert_eq!(nodes[0], a);
+                    assert_eq!(*nodes.last().unwrap(), b);
+                    assert_eq!(edges.len() + 1, nodes.len());
+                    for (pair, &edge) in nodes.windows(2).zip(&edges) {
+                        assert_eq!(graph.edge_endpoints(edge), Some((pair[0], pair[1])));
+                    }
+                    let cost: f64 = edges.iter().map(|&e| graph[e]).sum();
+                    assert_eq!(Some(cost), distance);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn johnson_negative_cycle() {
+    let mut graph = DiGraph::<(), f32>::new();
+    let a = graph.add_node(());
+    let b = graph.add_node(());
+    let c = graph.add_node(());
+    graph.extend_with_edges([(a, b, 1.), (b, c, -1.)]);
+    assert!(johnson(&graph, |e| *e.weight()).is_ok());
+    graph.add_edge(c, b, 1.5);
+    assert!(johnson(&graph, |e| *e.weight()).is_ok());
+    graph.add_edge(c, b, 0.5);
+    assert!(johnson(&graph, |e| *e.weight()).is_err());
+
+    // An undirected edge with a negative cost can be traversed back and forth.
+    let undirected = UnGraph::<(), f32>::from_edges([(0, 1, 2.), (1, 2, -1.)]);
+    assert!(johnson(&undirected, |e| *e.weight()).is_err());
+}
+
+#[test]
+fn johnson_undirected() {
+    let graph = UnGraph::<(), f64>::from_edges([(0, 1, 2.), (1, 2, 2.), (0, 2, 5.), (3, 4, 1.)]);
+    let paths = johnson(&graph, |e| *e.weight()).unwrap();
+    let n = NodeIndex::new;
+    assert_eq!(paths.distance(n(2), n(0)), Some(4.));
+    let e = EdgeIndex::new;
+    assert_eq!(
+        paths.path(n(2), n(0)),
+        Some((vec![n(2), n(1), n(0)], vec![e(1), e(0)]))
+    );
+    assert_eq!(paths.path(n(4), n(3)), Some((vec![n(4), n(3)], vec![e(3)])));
+    assert_eq!(paths.path(n(3), n(3)), Some((vec![n(3)], vec![])));
+    assert_eq!(paths.path(n(0), n(3)), None);
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn johnson_stable_graph() {
+    let mut graph = StableDiGraph::<(), f64>::new();
+    let a = graph.add_node(());
+    let removed = graph.add_node(());
+    let b = graph.add_node(());
+    let ab = graph.add_edge(a, b, -1.);
+    graph.add_edge(removed, a, -3.);
+    graph.remove_node(removed);
+
+    let paths = johnson(&graph, |e| *e.weight()).unwrap();
+    assert_eq!(paths.distance(a, b), Some(-1.));
+    assert_eq!(paths.distance(b, a), None);
+    assert_eq!(paths.path(a, b), Some((vec![a, b], vec![ab])));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_johnson_random() {
+    let mut rng = ChaChaRng::from_seed([51; 32]);
+    for _ in 0..50 {
+        let graph = random_graph(&mut rng);
+        let sequential = johnson(&graph, |e| *e.weight()).unwrap();
+        let parallel = parallel_johnson(&graph, |e| *e.weight()).unwrap();
+        for a in graph.node_indices() {
+            for b in graph.node_indices() {
+                assert_eq!(parallel.distance(a, b), sequential.distance(a, b));
+                assert_eq!(parallel.path(a, b), sequential.path(a, b));
+            }
+        }
+    }
+}
@@ -1,7 +1,10 @@
-use petgraph::algo::k_shortest_path;
+use petgraph::algo::{k_shortest_path, k_shortest_simple_paths};
 use petgraph::prelude::*;
-use petgraph::Graph;
-use std::collections::HashMap;
+use petgraph::{EdgeType, Graph};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use std::collections::{HashMap, HashSet};
+
+mod utils;
 
 #[test]
 fn second_shortest_path() {
@@ -61,3 +64,130 @@ fn second_shortest_path() {
 
     assert_eq!(res, expected_res);
 }
+
+fn random_graph<Ty: EdgeType>(rng: &mut ChaChaRng) -> Graph<(), u32, Ty> {
+    utils::random_graph(
+        rng,
+        2..10,
+        3,
+        true,
+        |_| (),
+        |rng, _, _| Some(rng.gen_range(0, 5)),
+    )
+}
+
+// Every simple path from `node` to `goal` that extends `edges`, by brute force.
+fn simple_paths<Ty: EdgeType>(
+    graph: &Graph<(), u32, Ty>,
+    node: NodeIndex,
+    goal: NodeIndex,
+    visited: &mut Vec<NodeIndex>,
+    edges: &mut Vec<EdgeIndex>,
+    paths: &mut Vec<Vec<EdgeIndex>>,
+) {
+    visited.push(node);
+    if node == goal {
+        paths.push(edges.clone());
+    } else {
+        for edge in graph.edges(node) {
+            if !visited.contains(&edge.target()) {
+                edges.push(edge.id());
+                simple_paths(graph, edge.target(), goal, visited, edges, paths);
+                edges.pop();
+            }
+        }
+    }
+    visited.pop();
+}
+
+fn check_k_shortest_simple_paths<Ty: EdgeType>(seed: u8) {
+    let mut rng = ChaChaRng::from_seed([seed; 32]);
+    for _ in 0..300 {
+        let graph = random_graph::<Ty>(&mut rng);
+        let start = NodeIndex::new(rng.gen_range(0, graph.node_count()));
+        let goal = NodeIndex::new(rng.gen_range(0, graph.node_count()));
+
+        let mut expected = Vec::new();
+        simple_paths(&graph, start, goal, &mut vec![], &mut vec![], &mut expected);
+        let cost = |edges: &[EdgeIndex]| edges.iter().map(|&e| graph[e]).sum::<u32>();
+        let mut expected_costs: Vec<_> = expected.iter().map(|edges| cost(edges)).collect();
+        expected_costs.sort_unstable();
+
+        let mut found = HashSet::new();
+        let mut costs = Vec::new();
+        for (path_cost, nodes, edges) in
+            k_shortest_simple_paths(&graph, start, goal, |e| *e.weight())
+        {
+            assert_eq!(nodes[0], start);
+            assert_eq!(*nodes.last().unwrap(), goal);
+            assert_eq!(nodes.len(), edges.len() + 1);
+            assert_eq!(nodes.iter().collect::<HashSet<_>>().len(), nodes.len());
+            for (pair, &edge) in nodes.windows(2).zip(&edges) {
+                let (a, b) = graph.edge_endpoints(edge).unwrap();
+                assert!(
+                    (a, b) == (pair[0], pair[1])
+                        || !Ty::is_directed() && (b, a) == (pair[0], pair[1])
+                );
+            }
+            assert_eq!(path_cost, cost(&edges));
+            costs.push(path_cost);
+            assert!(found.insert(edges));
+        }
+        assert_eq!(costs, expected_costs);
+        assert_eq!(found, expected.into_iter().collect());
+    }
+}
+
+#[test]
+fn k_shortest_simple_paths_random() {
+    check_k_shortest_simple_paths::<Directed>(70);
+}
+
+#[test]
+fn k_shortest_simple_paths_random_undirected() {
+    check_k_shortest_simple_paths::<Undirected>(71);
+}
+
+#[test]
+fn k_shortest_simple_paths_trivial() {
+    let mut graph = DiGraph::<(), u32>::new();
+    let a = graph.add_node(());
+    let b = graph.add_node(());
+    graph.add_edge(b, a, 1);
+    let mut paths = k_shortest_simple_paths(&graph, a, a, |e| *e.weight());
+    assert_eq!(paths.next(), Some((0, vec![a], vec![])));
+    assert_eq!(paths.next(), None);
+    assert_eq!(paths.next(), None);
+    assert_eq!(
+        k_shortest_simple_paths(&graph, a, b, |e| *e.weight()).next(),
+        None
+    );
+}
+
+#[test]
+fn k_shortest_simple_paths_grid() {
+    // The paths through a large grid are far too many to enumerate, but the
+    // iterator only computes the ones that are asked for.
+    let size = 15;
+    let mut graph = UnGraph::<(), u32>::default();
+    let nodes: Vec<_> = (0..size * size).map(|_| graph.add_node(())).collect();
+    for i in 0..size {
+        for j in 0..size {
+            if i + 1 < size {
+                graph.add_edge(nodes[i * size + j], nodes[(i + 1) * size + j], 1);
+            }
+            if j + 1 < size {
+                graph.add_edge(nodes[i * size + j], nodes[i * size + j + 1], 1);
+            }
+        }
+    }
+    let paths: Vec<_> =
+        k_shortest_simple_paths(&graph, nodes[0], nodes[size * size - 1], |e| *e.weight())
+            .take(20)
+            .collect();
+    assert_eq!(paths.len(), 20);
+    let shortest = 2 * (size as u32 - 1);
+    assert!(paths.iter().all(|(cost, _, _)| *cost == shortest));
+    let distinct: HashSet<_> = paths.iter().map(|(_, _, edges)| edges.clone()).collect();
+    assert_eq!(distinct.len(), 20);
+}
@@ -1,8 +1,12 @@
 use std::collections::HashSet;
 use std::hash::Hash;
 
-use petgraph::algo::{greedy_matching, maximum_matching};
+use petgraph::algo::{
+    greedy_matching, maximum_matching, maximum_weight_bipartite_matching,
+    min_cost_bipartite_matching,
+};
 use petgraph::prelude::*;
+use rand::{ChaChaRng, Rng, SeedableRng};
 
 macro_rules! assert_one_of {
     ($actual:expr, [$($expected:expr),+]) => {
@@ -139,3 +143,143 @@ fn is_perfect_in_stable_graph() {
     assert_eq!(m.len(), 1);
     assert!(m.is_perfect());
 }
+
+// The best total weight of a matching between `left` and `right` by trying
+// all of them. `None` weights are missing edges.
+fn brute_force_assignment(
+    weights: &[Vec<Option<i32>>],
+    row: usize,
+    used: &mut Vec<bool>,
+    must_match: bool,
+    better: fn(i32, i32) -> bool,
+) -> Option<i32> {
+    if row == weights.len() {
+        return Some(0);
+    }
+    let mut best = if must_match {
+        None
+    } else {
+        brute_force_assignment(weights, row + 1, used, must_match, better)
+    };
+    for column in 0..used.len() {
+        if let (false, Some(w)) = (used[column], weights[row][column]) {
+            used[column] = true;
+            if let Some(rest) = brute_force_assignment(weights, row + 1, used, must_match, better) {
+                if best.map_or(true, |best| better(w + rest, best)) {
+                    best = Some(w + rest);
+                }
+            }
+            used[column] = false;
+        }
+    }
+    best
+}
+
+// A random bipartite graph, its left side and the weights between the sides.
+type Bipartite = (UnGraph<(), i32>, Vec<NodeIndex>, Vec<Vec<Option<i32>>>);
+
+fn random_bipartite(rng: &mut ChaChaRng) -> Bipartite {
+    let n_left = rng.gen_range(0, 6);
+    let n_right = rng.gen_range(0, 6);
+    let mut graph = UnGraph::new_undirected();
+    let left: Vec<_> = (0..n_left).map(|_| graph.add_node(())).collect();
+    let right: Vec<_> = (0..n_right).map(|_| graph.add_node(())).collect();
+    let mut weights = vec![vec![None; n_right]; n_left];
+    for (i, &a) in left.iter().enumerate() {
+        for (j, &b) in right.iter().enumerate() {
+            if rng.gen_range(0, 3) != 0 {
+                let w = rng.gen_range(-5, 20);
+                weights[i][j] = Some(w);
+                // Add the edges in both directions.
+                if rng.gen() {
+                    graph.add_edge(a, b, w);
+                } else {
+                    graph.add_edge(b, a, w);
+                }
+            }
+        }
+    }
+    // Edges within a side are ignored.
+    if n_left >= 2 {
+        graph.add_edge(left[0], left[1], 100);
+    }
+    (graph, left, weights)
+}
+
+fn assert_valid_matching(
+    graph: &UnGraph<(), i32>,
+    left: &[NodeIndex],
+    matching: &petgraph::algo::Matching<&UnGraph<(), i32>>,
+    weight: i32,
+) {
+    let mut total = 0;
+    for (a, b) in matching.edges() {
+        assert_ne!(left.contains(&a), left.contains(&b));
+        let edge = graph.find_edge(a, b).unwrap();
+        total += graph[edge];
+    }
+    assert_eq!(total, weight);
+}
+
+#[test]
+fn maximum_weight_bipartite_random() {
+    let mut rng = ChaChaRng::from_seed([8; 32]);
+    for _ in 0..500 {
+        let (graph, left, weights) = random_bipartite(&mut rng);
+        let positive: Vec<Vec<_>> = weights
+            .iter()
+            .map(|row| row.iter().map(|w| w.filter(|&w| w > 0)).collect())
+            .collect();
+        let mut used = vec![false; weights.first().map_or(0, |row| row.len())];
+        let expected =
+            brute_force_assignment(&positive, 0, &mut used, false, |a, b| a > b).unwrap();
+
+        let (matching, weight) =
+            maximum_weight_bipartite_matching(&graph, left.iter().cloned(), |e| *e.weight());
+        assert_eq!(weight, expected);
+        assert_valid_matching(&graph, &left, &matching, weight);
+        assert!(matching
+            .edges()
+            .all(|(a, b)| graph[graph.find_edge(a, b).unwrap()] > 0));
+    }
+}
+
+#[test]
+fn min_cost_bipartite_random() {
+    let mut rng = ChaChaRng::from_seed([9; 32]);
+    for _ in 0..500 {
+        let (graph, left, weights) = random_bipartite(&mut rng);
+        let mut used = vec![false; weights.first().map_or(0, |row| row.len())];
+        let expected = if left.is_empty() {
+            Some(0)
+        } else {
+            brute_force_assignment(&weights, 0, &mut used, true, |a, b| a < b)
+        };
+
+        let result = min_cost_bipartite_matching(&graph, left.iter().cloned(), |e| *e.weight());
+        assert_eq!(result.as_ref().map(|(_, cost)| *cost), expected);
+        if let Some((matching, cost)) = result {
+            assert_eq!(matching.len(), left.len());
+            assert_valid_matching(&graph, &left, &matching, cost);
+        }
+    }
+}
+
+#[test]
+fn weighted_bipartite_parallel_edges_and_floats() {
+    let mut graph: UnGraph<(), f64> = UnGraph::new_undirected();
+    let a = graph.add_node(());
+    let b = graph.add_node(());
+    let x = graph.add_node(());
+    let y = graph.add_node(());
+    graph.extend_with_edges([(a, x, 1.5), (a, x, 4.0), (b, x, 3.0), (b, y, 0.5)]);
+
+    let (matching, weight) = maximum_weight_bipartite_matching(&graph, [a, b], |e| *e.weight());
+    assert_eq!(weight, 4.5);
+    assert!(matching.contains_edge(a, x) && matching.contains_edge(b, y));
+
+    let (matching, cost) = min_cost_bipartite_matching(&graph, [a, b], |e| *e.weight()).unwrap();
+    assert_eq!(cost, 2.0);
+    assert!(matching.contains_edge(a, x) && matching.contains_edge(b, y));
+    assert!(matching.is_perfect());
+}
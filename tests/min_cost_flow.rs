@@ -0,0 +1,221 @@
+use petgraph::algo::{
+    flow_decomposition, ford_fulkerson, min_cost_max_flow, min_cut, NegativeCycle,
+};
+use petgraph::prelude::*;
+use petgraph::visit::EdgeRef;
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+#[cfg(feature = "stable_graph")]
+use petgraph::stable_graph::StableDiGraph;
+
+mod utils;
+
+// Edges carry (capacity, cost).
+fn random_network(rng: &mut ChaChaRng, acyclic: bool) -> DiGraph<(), (i64, i64)> {
+    utils::random_graph(
+        rng,
+        2..9,
+        4,
+        true,
+        |_| (),
+        |rng, a, b| {
+            let cost = if !acyclic {
+                rng.gen_range(0, 10)
+            } else if a <= b {
+                rng.gen_range(-5, 10)
+            } else {
+                return None;
+            };
+            Some((rng.gen_range(0, 6), cost))
+        },
+    )
+}
+
+// A flow has minimum cost if the residual network has no negative cycle.
+fn has_negative_residual_cycle(graph: &DiGraph<(), (i64, i64)>, flows: &[i64]) -> bool {
+    let n = graph.node_count();
+    let mut arcs = Vec::new();
+    for edge in graph.edge_references() {
+        let (capacity, cost) = *edge.weight();
+        let flow = flows[edge.id().index()];
+        let (a, b) = (edge.source().index(), edge.target().index());
+        if a == b {
+            continue;
+        }
+        if flow < capacity {
+            arcs.push((a, b, cost));
+        }
+        if flow > 0 {
+            arcs.push((b, a, -cost));
+        }
+    }
+    let mut distance = vec![0; n];
+    for _ in 0..n {
+        let mut changed = false;
+        for &(a, b, cost) in &arcs {
+            if distance[a] + cost < distance[b] {
+                distance[b] = distance[a] + cost;
+                changed = true;
+            }
+        }
+        if !changed {
+            return false;
+        }
+    }
+    true
+}
+
+#[test]
+fn min_cost_max_flow_random() {
+    let mut rng = ChaChaRng::from_seed([6; 32]);
+    for round in 0..300 {
+        let graph = random_network(&mut rng, round % 2 == 0);
+        let source = NodeIndex::new(0);
+        let sink = NodeIndex::new(graph.node_count() - 1);
+        let result =
+            min_cost_max_flow(&graph, source, sink, |e| e.weight().0, |e| e.weight().1).unwrap();
+
+        let capacities = graph.map(|_, _| (), |_, w| w.0.max(0) as u64);
+        let (max_flow, _) = ford_fulkerson(&capacities, source, sink);
+        assert_eq!(result.flow as u64, max_flow);
+        let max_flow = max_flow as i64;
+
+        let mut cost = 0;
+        for edge in graph.edge_references() {
+            let flow = result.edge_flows[edge.id().index()];
+            assert!(flow >= 0 && flow <= edge.weight().0.max(0));
+            cost += flow * edge.weight().1;
+        }
+        assert_eq!(result.cost, cost);
+        for node in graph.node_indices() {
+            if node == source || node == sink {
+                continue;
+            }
+            let inflow: i64 = graph
+                .edges_directed(node, Incoming)
+                .map(|e| result.edge_flows[e.id().index()])
+                .sum();
+            let outflow: i64 = graph
+                .edges(node)
+                .map(|e| result.edge_flows[e.id().index()])
+                .sum();
+            assert_eq!(inflow, outflow);
+        }
+        assert!(!has_negative_residual_cycle(&graph, &result.edge_flows));
+
+        // The decomposition carries the whole flow along source-sink paths.
+        let paths = flow_decomposition(&graph, source, sink, &result.edge_flows);
+        assert_eq!(
+            paths.iter().map(|(_, amount)| amount).sum::<i64>(),
+            max_flow
+        );
+        let mut used = vec![0; graph.edge_count()];
+        for (path, amount) in &paths {
+            assert!(*amount > 0);
+            assert_eq!(path.first().unwrap().source(), source);
+            assert_eq!(path.last().unwrap().target(), sink);
+            for pair in path.windows(2) {
+                assert_eq!(pair[0].target(), pair[1].source());
+            }
+            for edge in path {
+                used[edge.id().index()] += amount;
+            }
+        }
+        for (used, flow) in used.iter().zip(&result.edge_flows) {
+            assert!(used <= flow);
+        }
+
+        // The cut is saturated and its capacity is the maximum flow.
+        let (reachable, rest) = min_cut(&graph, source, &result.edge_flows, |e| e.weight().0);
+        assert_eq!(reachable.len() + rest.len(), graph.node_count());
+        assert!(reachable.contains(&source) && rest.contains(&sink));
+        let cut: i64 = graph
+            .edge_references()
+            .filter(|e| reachable.contains(&e.source()) && rest.contains(&e.target()))
+            .map(|e| e.weight().0.max(0))
+            .sum();
+        assert_eq!(cut, max_flow);
+    }
+}
+
+#[test]
+fn min_cost_max_flow_prefers_cheap_paths() {
+    // Two routes of one unit each, and a shortcut that does not pay off.
+    let mut graph = Graph::<(), (f64, f64)>::new();
+    let s = graph.add_node(());
+    let a = graph.add_node(());
+    let b = graph.add_node(());
+    let t = graph.add_node(());
+    let expensive = graph.add_edge(s, a, (1., 4.));
+    let cheap = graph.add_edge(s, b, (1., 1.5));
+    graph.add_edge(a, t, (1., 0.));
+    graph.add_edge(b, t, (1., 0.));
+    graph.add_edge(a, b, (1., 0.5));
+
+    let result = min_cost_max_flow(&graph, s, t, |e| e.weight().0, |e| e.weight().1).unwrap();
+    assert_eq!(result.flow, 2.);
+    assert_eq!(result.cost, 5.5);
+    assert_eq!(result.edge_flows[expensive.index()], 1.);
+    assert_eq!(result.edge_flows[cheap.index()], 1.);
+
+    assert_eq!(
+        min_cost_max_flow(&graph, s, s, |e| e.weight().0, |e| e.weight().1)
+            .unwrap()
+            .flow,
+        0.
+    );
+}
+
+#[test]
+fn min_cost_max_flow_negative_cycle() {
+    let graph = DiGraph::<(), (i32, i32)>::from_edges([
+        (0, 1, (1, 1)),
+        (1, 2, (1, -3)),
+        (2, 1, (1, 1)),
+        (2, 3, (1, 1)),
+    ]);
+    let result = min_cost_max_flow(
+        &graph,
+        NodeIndex::new(0),
+        NodeIndex::new(3),
+        |e| e.weight().0,
+        |e| e.weight().1,
+    );
+    assert_eq!(result, Err(NegativeCycle(())));
+}
+
+#[test]
+fn flow_decomposition_cancels_cycles() {
+    // The flow circulates on 1 -> 2 -> 1 besides the path 0 -> 1 -> 3.
+    let graph = DiGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 1), (1, 3)]);
+    let paths = flow_decomposition(&graph, NodeIndex::new(0), NodeIndex::new(3), &[2, 1, 1, 2]);
+    assert_eq!(paths.len(), 1);
+    let (path, amount) = &paths[0];
+    assert_eq!(*amount, 2);
+    let nodes: Vec<_> = path.iter().map(|e| e.target().index()).collect();
+    assert_eq!(nodes, [1, 3]);
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn min_cost_max_flow_stable_graph() {
+    let mut graph = StableDiGraph::<(), (u32, u32)>::new();
+    let s = graph.add_node(());
+    let removed = graph.add_node(());
+    let a = graph.add_node(());
+    let t = graph.add_node(());
+    let gone = graph.add_edge(s, removed, (5, 0));
+    graph.add_edge(s, a, (3, 2));
+    graph.add_edge(a, t, (2, 2));
+    graph.add_edge(s, t, (4, 7));
+    graph.remove_edge(gone);
+    graph.remove_node(removed);
+
+    let result = min_cost_max_flow(&graph, s, t, |e| e.weight().0, |e| e.weight().1).unwrap();
+    assert_eq!(result.flow, 6);
+    assert_eq!(result.cost, 2 * 4 + 4 * 7);
+
+    let (reachable, rest) = min_cut(&graph, s, &result.edge_flows, |e| e.weight().0);
+    assert_eq!(reachable, [s, a]);
+    assert_eq!(rest, [t]);
+}
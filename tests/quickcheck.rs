@@ -1473,3 +1473,47 @@ quickcheck! {
         true
     }
 }
+
+#[cfg(feature = "rayon")]
+fn parallel_components_agree<Ty: EdgeType + Sync>(g: &Graph<(), (), Ty>) -> bool {
+    use petgraph::algo::{parallel_connected_component_labels, parallel_connected_components};
+    use petgraph::csr::Csr;
+    use petgraph::unionfind::UnionFind;
+
+    let mut sets = UnionFind::new(g.node_count());
+    for edge in g.edge_references() {
+        sets.union(edge.source().index(), edge.target().index());
+    }
+    let labels = parallel_connected_component_labels(g);
+    for (i, &label) in labels.iter().enumerate() {
+        // The label of a node is the smallest index of its component.
+        let smallest = (0..g.node_count()).find(|&j| sets.equiv(i, j)).unwrap();
+        if label != smallest {
+            return false;
+        }
+    }
+
+    let mut csr = Csr::<(), (), Ty>::with_nodes(g.node_count());
+    for edge in g.edge_references() {
+        csr.add_edge(
+            edge.source().index() as u32,
+            edge.target().index() as u32,
+            (),
+        );
+    }
+    parallel_connected_component_labels(&csr) == labels
+        && parallel_connected_components(g) == connected_components(g)
+}
+
+#[cfg(feature = "rayon")]
+quickcheck! {
+    fn parallel_connected_components_directed(g: Graph<(), (), Directed>) -> bool {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+        pool.install(|| parallel_components_agree(&g))
+    }
+
+    fn parallel_connected_components_undirected(g: Graph<(), (), Undirected>) -> bool {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+        pool.install(|| parallel_components_agree(&g))
+    }
+}
@@ -0,0 +1,382 @@
+use petgraph::algo::{
+    astar, astar_with_workspace, bellman_ford, bellman_ford_with_workspace, bidirectional_astar,
+    bidirectional_dijkstra, dijkstra, dijkstra_with_predecessors, dijkstra_with_workspace,
+    ShortestPathWorkspace,
+};
+use petgraph::prelude::*;
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+#[cfg(feature = "stable_graph")]
+use petgraph::stable_graph::StableDiGraph;
+
+mod utils;
+
+use utils::path_cost;
+
+fn random_graph(rng: &mut ChaChaRng) -> DiGraph<(), u32> {
+    utils::random_graph(
+        rng,
+        1..40,
+        4,
+        true,
+        |_| (),
+        |rng, _, _| Some(rng.gen_range(0, 10)),
+    )
+}
+
+#[test]
+fn dijkstra_predecessors_random() {
+    let mut rng = ChaChaRng::from_seed([30; 32]);
+    for _ in 0..200 {
+        let graph = random_graph(&mut rng);
+        let start = NodeIndex::new(rng.gen_range(0, graph.node_count()));
+        let expected = dijkstra(&graph, start, None, |e| *e.weight());
+        let (scores, predecessors) =
+            dijkstra_with_predecessors(&graph, start, None, |e| *e.weight());
+        assert_eq!(scores, expected);
+        assert!(!predecessors.contains_key(&start));
+        for (&node, &previous) in &predecessors {
+            let edge = graph
+                .edges_connecting(previous, node)
+                .map(|e| *e.weight())
+                .min()
+                .unwrap();
+            assert_eq!(scores[&previous] + edge, scores[&node]);
+        }
+        assert_eq!(predecessors.len() + 1, scores.len());
+    }
+}
+
+#[test]
+fn workspace_random() {
+    let mut rng = ChaChaRng::from_seed([31; 32]);
+    // A single workspace for graphs of all sizes.
+    let mut workspace = ShortestPathWorkspace::default();
+    for _ in 0..200 {
+        let graph = random_graph(&mut rng);
+        let n = graph.node_count();
+        for _ in 0..5 {
+            let start = NodeIndex::new(rng.gen_range(0, n));
+            let expected = dijkstra(&graph, start, None, |e| *e.weight());
+            assert_eq!(
+                dijkstra_with_workspace(&graph, start, None, |e| *e.weight(), &mut workspace),
+                None
+            );
+            for node in graph.node_indices() {
+                let distance = workspace.distance(&graph, node);
+                assert_eq!(distance, expected.get(&node).copied());
+                let path = workspace.path_to(&graph, node);
+                assert_eq!(path.is_some(), distance.is_some());
+                if let Some(path) = path {
+                    assert_eq!(path[0], start);
+                    assert_eq!(*path.last().unwrap(), node);
+                    assert_eq!(path_cost(&graph, &path), distance);
+                    assert_eq!(workspace.predecessor(&graph, node), {
+                        let len = path.len();
+                        (len > 1).then(|| path[len - 2])
+                    });
+                }
+            }
+
+            let goal = NodeIndex::new(rng.gen_range(0, n));
+            let found =
+                dijkstra_with_workspace(&graph, start, Some(goal), |e| *e.weight(), &mut workspace);
+            assert_eq!(found, expected.get(&goal).copied());
+            if let Some(cost) = found {
+                let path = workspace.path_to(&graph, goal).unwrap();
+                assert_eq!(path_cost(&graph, &path), Some(cost));
+            }
+
+            let with_workspace = astar_with_workspace(
+                &graph,
+                start,
+                |n| n == goal,
+                |e| *e.weight(),
+                |_| 0,
+                &mut workspace,
+            );
+            let without = astar(&graph, start, |n| n == goal, |e| *e.weight(), |_| 0);
+            assert_eq!(
+                with_workspace.as_ref().map(|(cost, _)| *cost),
+                without.map(|(cost, _)| cost)
+            );
+            if let Some((cost, path)) = with_workspace {
+                assert_eq!(path_cost(&graph, &path), Some(cost));
+            }
+        }
+    }
+}
+
+#[test]
+fn bellman_ford_workspace_random() {
+    let mut rng = ChaChaRng::from_seed([33; 32]);
+    let mut workspace = ShortestPathWorkspace::default();
+    for _ in 0..200 {
+        // Costs are mostly positive, so that some graphs have no negative
+        // cycle.
+        let graph = random_graph(&mut rng).map(|_, _| (), |_, &w| w as f64 - 1.);
+        let start = NodeIndex::new(rng.gen_range(0, graph.node_count()));
+        let expected = bellman_ford(&graph, start);
+        let result = bellman_ford_with_workspace(&graph, start, &mut workspace);
+        assert_eq!(result.is_ok(), expected.is_ok());
+        let expected = match expected {
+            Ok(expected) => expected,
+            Err(_) => continue,
+        };
+        for node in graph.node_indices() {
+            let distance = expected.distances[node.index()];
+            let distance = Some(distance).filter(|d| d.is_finite());
+            assert_eq!(workspace.distance(&graph, node), distance);
+            assert_eq!(
+                workspace.predecessor(&graph, node),
+                expected.predecessors[node.index()]
+            );
+            if let Some(path) = workspace.path_to(&graph, node) {
+                assert_eq!(path[0], start);
+                assert_eq!(*path.last().unwrap(), node);
+            }
+        }
+    }
+}
+
+#[test]
+fn astar_workspace_inconsistent_heuristic() {
+    let mut g = Graph::new();
+    let a = g.add_node("A");
+    let b = g.add_node("B");
+    let c = g.add_node("C");
+    let d = g.add_node("D");
+    g.add_edge(a, b, 3);
+    g.add_edge(b, c, 3);
+    g.add_edge(c, d, 3);
+    g.add_edge(a, c, 8);
+    g.add_edge(a, d, 10);
+
+    let admissible_inconsistent = |n: NodeIndex| match g[n] {
+        "A" => 9,
+        "B" => 6,
+        "C" => 0,
+        &_ => 0,
+    };
+
+    let mut workspace = ShortestPathWorkspace::new(&g);
+    let optimal = astar_with_workspace(
+        &g,
+        a,
+        |n| n == d,
+        |e| *e.weight(),
+        admissible_inconsistent,
+        &mut workspace,
+    );
+    assert_eq!(optimal, Some((9, vec![a, b, c, d])));
+    assert_eq!(workspace.distance(&g, d), Some(9));
+}
+
+#[test]
+fn bidirectional_dijkstra_random() {
+    let mut rng = ChaChaRng::from_seed([32; 32]);
+    for _ in 0..300 {
+        let graph = random_graph(&mut rng);
+        let n = graph.node_count();
+        let start = NodeIndex::new(rng.gen_range(0, n));
+        let goal = NodeIndex::new(rng.gen_range(0, n));
+        let expected = dijkstra(&graph, start, None, |e| *e.weight());
+
+        let found = bidirectional_dijkstra(&graph, start, goal, |e| *e.weight());
+        assert_eq!(
+            found.as_ref().map(|(cost, _)| *cost),
+            expected.get(&goal).copied()
+        );
+        if let Some((cost, path)) = found {
+            assert_eq!(path[0], start);
+            assert_eq!(*path.last().unwrap(), goal);
+            assert_eq!(path_cost(&graph, &path), Some(cost));
+        }
+
+        // Undirected, where the backward search follows the same edges.
+        let undirected = graph.clone().into_edge_type::<Undirected>();
+        let expected = dijkstra(&undirected, start, Some(goal), |e| *e.weight());
+        let found = bidirectional_dijkstra(&undirected, start, goal, |e| *e.weight());
+        assert_eq!(
+            found.as_ref().map(|(cost, _)| *cost),
+            expected.get(&goal).copied()
+        );
+        if let Some((cost, path)) = found {
+            let mut total = 0;
+            for pair in path.windows(2) {
+                total += undirected
+                    .edges_connecting(pair[0], pair[1])
+                    .map(|e| *e.weight())
+                    .min()
+                    .unwrap();
+            }
+            assert_eq!(total, cost);
+        }
+    }
+}
+
+#[test]
+fn bidirectional_astar_grid() {
+    let mut rng = ChaChaRng::from_seed([33; 32]);
+    // A grid with random edge costs of at least the Manhattan distance, and
+    // some missing edges.
+    let size = 20;
+    let mut graph = DiGraph::<(u32, u32), u32>::new();
+    for i in 0..size * size {
+        graph.add_node((i % size, i / size));
+    }
+    for i in 0..size * size {
+        let (x, y) = (i % size, i / size);
+        let mut neighbors = vec![];
+        if x + 1 < size {
+            neighbors.push(i + 1);
+        }
+        if y + 1 < size {
+            neighbors.push(i + size);
+        }
+        for j in neighbors {
+            for &(a, b) in &[(i, j), (j, i)] {
+                if rng.gen_range(0, 10) > 0 {
+                    let (a, b) = (NodeIndex::new(a as usize), NodeIndex::new(b as usize));
+                    graph.add_edge(a, b, rng.gen_range(1, 5));
+                }
+            }
+        }
+    }
+    let distance = |a: (u32, u32), b: (u32, u32)| {
+        (a.0 as i32 - b.0 as i32).unsigned_abs() + (a.1 as i32 - b.1 as i32).unsigned_abs()
+    };
+
+    for _ in 0..100 {
+        let start = NodeIndex::new(rng.gen_range(0, graph.node_count()));
+        let goal = NodeIndex::new(rng.gen_range(0, graph.node_count()));
+        let expected = astar(&graph, start, |n| n == goal, |e| *e.weight(), |_| 0);
+        let found = bidirectional_astar(
+            &graph,
+            start,
+            goal,
+            |e| *e.weight(),
+            |n| distance(graph[n], graph[goal]),
+            |n| distance(graph[start], graph[n]),
+        );
+        assert_eq!(
+            found.as_ref().map(|(cost, _)| *cost),
+            expected.map(|(cost, _)| cost)
+        );
+        if let Some((cost, path)) = found {
+            assert_eq!(path[0], start);
+            assert_eq!(*path.last().unwrap(), goal);
+            let mut total = 0;
+            for pair in path.windows(2) {
+                total += graph
+                    .edges_connecting(pair[0], pair[1])
+                    .map(|e| *e.weight())
+                    .min()
+                    .unwrap();
+            }
+            assert_eq!(total, cost);
+        }
+    }
+}
+
+#[test]
+fn bidirectional_unreachable() {
+    let mut g = DiGraph::<(), f32>::new();
+    let a = g.add_node(());
+    let b = g.add_node(());
+    let c = g.add_node(());
+    g.add_edge(a, b, 1.);
+    g.add_edge(c, b, 1.);
+    assert_eq!(bidirectional_dijkstra(&g, a, c, |e| *e.weight()), None);
+    assert_eq!(
+        bidirectional_dijkstra(&g, c, c, |e| *e.weight()),
+        Some((0., vec![c]))
+    );
+    assert_eq!(
+        bidirectional_astar(&g, a, b, |e| *e.weight(), |_| 0., |_| 0.),
+        Some((1., vec![a, b]))
+    );
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn workspace_stable_graph() {
+    let mut graph = StableDiGraph::<(), f64>::new();
+    let removed = graph.add_node(());
+    let a = graph.add_node(());
+    let b = graph.add_node(());
+    let c = graph.add_node(());
+    graph.add_edge(a, b, 1.5);
+    graph.add_edge(b, c, 1.5);
+    graph.add_edge(a, c, 4.);
+    graph.remove_node(removed);
+
+    let mut workspace = ShortestPathWorkspace::new(&graph);
+    let cost = dijkstra_with_workspace(&graph, a, Some(c), |e| *e.weight(), &mut workspace);
+    assert_eq!(cost, Some(3.));
+    assert_eq!(workspace.path_to(&graph, c), Some(vec![a, b, c]));
+    assert_eq!(workspace.distance(&graph, removed), None);
+    assert_eq!(
+        dijkstra_with_workspace(&graph, c, Some(a), |e| *e.weight(), &mut workspace),
+        None
+    );
+    assert_eq!(workspace.path_to(&graph, b), None);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_delta_stepping_random() {
+    use petgraph::algo::delta_stepping::parallel_delta_stepping;
+    use petgraph::csr::Csr;
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap();
+    pool.install(|| {
+        let mut rng = ChaChaRng::from_seed([31; 32]);
+        for _ in 0..200 {
+            let graph = random_graph(&mut rng);
+            let start = NodeIndex::new(rng.gen_range(0, graph.node_count()));
+            let scores = dijkstra(&graph, start, None, |e| *e.weight());
+            let expected: Vec<_> = graph
+                .node_indices()
+                .map(|n| scores.get(&n).copied())
+                .collect();
+            let delta = rng.gen_range(1, 12);
+            let distances = parallel_delta_stepping(&graph, start, delta, |e| *e.weight());
+            assert_eq!(distances, expected);
+
+            let undirected = graph.clone().into_edge_type::<Undirected>();
+            let scores = dijkstra(&undirected, start, None, |e| *e.weight());
+            let distances = parallel_delta_stepping(&undirected, start, delta, |e| *e.weight());
+            for n in graph.node_indices() {
+                assert_eq!(distances[n.index()], scores.get(&n).copied());
+            }
+
+            // Csr keeps a single edge between two nodes, the first added.
+            let mut csr = Csr::<(), u32>::with_nodes(graph.node_count());
+            for edge in graph.edge_references() {
+                let (a, b) = (edge.source().index() as u32, edge.target().index() as u32);
+                csr.add_edge(a, b, *edge.weight());
+            }
+            let scores = dijkstra(&csr, start.index() as u32, None, |e| *e.weight());
+            let distances =
+                parallel_delta_stepping(&csr, start.index() as u32, delta, |e| *e.weight());
+            for (n, &distance) in distances.iter().enumerate() {
+                assert_eq!(distance, scores.get(&(n as u32)).copied());
+            }
+        }
+    });
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+#[should_panic(expected = "delta must be positive")]
+fn parallel_delta_stepping_zero_delta() {
+    use petgraph::algo::delta_stepping::parallel_delta_stepping;
+
+    let mut graph = DiGraph::<(), f64>::new();
+    let a = graph.add_node(());
+    parallel_delta_stepping(&graph, a, 0., |e| *e.weight());
+}
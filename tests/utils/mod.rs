@@ -1,3 +1,9 @@
+#![allow(dead_code, unused_imports)]
+
+#[cfg(feature = "quickcheck")]
 mod qc;
+mod random;
 
+#[cfg(feature = "quickcheck")]
 pub use self::qc::*;
+pub use self::random::*;
@@ -0,0 +1,55 @@
+use std::ops::Range;
+
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::EdgeType;
+use rand::{ChaChaRng, Rng};
+
+/// Return a random graph with a node count in `nodes`, and fewer than
+/// `edge_factor` times as many edges, between random endpoints.
+///
+/// `node` returns the weight of every node, and `edge` the weight of an edge
+/// from its endpoints, or `None` to skip it. If `parallel_edges` is false, an
+/// edge between already adjacent nodes replaces the weight of their edge
+/// instead.
+pub fn random_graph<N, E, Ty: EdgeType>(
+    rng: &mut ChaChaRng,
+    nodes: Range<usize>,
+    edge_factor: usize,
+    parallel_edges: bool,
+    mut node: impl FnMut(&mut ChaChaRng) -> N,
+    mut edge: impl FnMut(&mut ChaChaRng, NodeIndex, NodeIndex) -> Option<E>,
+) -> Graph<N, E, Ty> {
+    let node_count = rng.gen_range(nodes.start, nodes.end);
+    let mut graph = Graph::with_capacity(node_count, 0);
+    for _ in 0..node_count {
+        graph.add_node(node(rng));
+    }
+    if node_count == 0 {
+        return graph;
+    }
+    for _ in 0..rng.gen_range(0, edge_factor * node_count) {
+        let a = NodeIndex::new(rng.gen_range(0, node_count));
+        let b = NodeIndex::new(rng.gen_range(0, node_count));
+        if let Some(weight) = edge(rng, a, b) {
+            if parallel_edges {
+                graph.add_edge(a, b, weight);
+            } else {
+                graph.update_edge(a, b, weight);
+            }
+        }
+    }
+    graph
+}
+
+/// Return the total cost of the edges of `path`, the cheapest one between
+/// every two consecutive nodes, or `None` if some of them are not adjacent.
+pub fn path_cost<Ty: EdgeType>(graph: &Graph<(), u32, Ty>, path: &[NodeIndex]) -> Option<u32> {
+    let mut cost = 0;
+    for pair in path.windows(2) {
+        cost += graph
+            .edges_connecting(pair[0], pair[1])
+            .map(|e| *e.weight())
+            .min()?;
+    }
+    Some(cost)
+}